@@ -0,0 +1,166 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{parse_quote, Generics, Ident, Token, WhereClause, WherePredicate};
+
+use crate::types::{Element, Field, Fields, Type};
+
+enum Usage {
+    // read and written as an element
+    Element,
+    // read and written as flattened fields
+    Flatten,
+    // read and written as the text of an attribute or an element
+    Value,
+}
+
+// bounds of the `XmlRead` impl, where `'__input` is the input lifetime
+pub fn read_bounds(element: &Element, generics: &Generics) -> Vec<WherePredicate> {
+    bounds(element, generics, |usage, ty| match usage {
+        Usage::Element => quote! { #ty: hard_xml::XmlRead<'__input> },
+        Usage::Flatten => quote! { #ty: hard_xml::XmlReadFlatten<'__input> },
+        Usage::Value => quote! {
+            #ty: std::str::FromStr,
+            <#ty as std::str::FromStr>::Err: Into<Box<dyn std::error::Error + Send + Sync>>
+        },
+    })
+}
+
+// bounds of the `XmlWrite` impl
+pub fn write_bounds(element: &Element, generics: &Generics) -> Vec<WherePredicate> {
+    bounds(element, generics, |usage, ty| match usage {
+        Usage::Element => quote! { #ty: hard_xml::XmlWrite },
+        Usage::Flatten => quote! { #ty: hard_xml::XmlWriteFlatten },
+        Usage::Value => quote! { #ty: std::fmt::Display },
+    })
+}
+
+// bounds of the `XmlSchemaInfo` impl
+pub fn schema_bounds(element: &Element, generics: &Generics) -> Vec<WherePredicate> {
+    bounds(element, generics, |usage, ty| match usage {
+        Usage::Element | Usage::Flatten => quote! { #ty: hard_xml::XmlSchemaInfo },
+        Usage::Value => quote! {},
+    })
+}
+
+// where clause of the generics with the given predicates added
+pub fn add_bounds(generics: &Generics, bounds: &[WherePredicate]) -> WhereClause {
+    let mut where_clause = generics
+        .where_clause
+        .clone()
+        .unwrap_or_else(|| WhereClause {
+            where_token: Default::default(),
+            predicates: Default::default(),
+        });
+    where_clause.predicates.extend(bounds.iter().cloned());
+    where_clause
+}
+
+// predicates for every type parameter used as the type of a field, or as
+// the item type of an `Option` or `Vec` field, unless it's bounded already
+fn bounds(
+    element: &Element,
+    generics: &Generics,
+    bound: impl Fn(Usage, &syn::Type) -> TokenStream,
+) -> Vec<WherePredicate> {
+    let fields: Vec<&Field> = match element {
+        Element::Struct { fields, .. } => variant_fields(fields),
+        Element::Enum { variants, .. } => variants.iter().flat_map(variant_fields).collect(),
+        Element::Flatten { fields, .. } => fields.iter().collect(),
+        Element::TextEnum { .. } => Vec::new(),
+    };
+
+    let mut usages = match element {
+        Element::Struct { fields, .. } => newtype_usages(std::slice::from_ref(fields)),
+        Element::Enum { variants, .. } => newtype_usages(variants),
+        _ => Vec::new(),
+    };
+    usages.extend(fields.into_iter().flat_map(field_usages));
+
+    let inferred: Vec<&Ident> = generics
+        .type_params()
+        .filter(|param| param.bounds.is_empty() && !has_where_bounds(generics, &param.ident))
+        .map(|param| &param.ident)
+        .collect();
+
+    let mut seen = Vec::new();
+    let mut predicates = Vec::new();
+    for (usage, ty) in usages {
+        let ty = match ty {
+            Some(syn::Type::Path(path))
+                if path.qself.is_none()
+                    && inferred.iter().any(|ident| path.path.is_ident(*ident)) =>
+            {
+                ty.unwrap()
+            }
+            _ => continue,
+        };
+        let bound = bound(usage, ty);
+        if seen.contains(&bound.to_string()) {
+            continue;
+        }
+        seen.push(bound.to_string());
+        let bound: Punctuated<WherePredicate, Token![,]> = parse_quote!(#bound);
+        predicates.extend(bound);
+    }
+    predicates
+}
+
+fn has_where_bounds(generics: &Generics, ident: &Ident) -> bool {
+    generics.where_clause.iter().any(|where_clause| {
+        where_clause.predicates.iter().any(|predicate| {
+            matches!(predicate, WherePredicate::Type(predicate) if matches!(
+                &predicate.bounded_ty,
+                syn::Type::Path(path) if path.qself.is_none() && path.path.is_ident(ident)
+            ))
+        })
+    })
+}
+
+fn variant_fields(fields: &Fields) -> Vec<&Field> {
+    match fields {
+        Fields::Named { fields, .. } => fields.iter().collect(),
+        Fields::Newtype { .. } => Vec::new(),
+    }
+}
+
+fn newtype_usages(variants: &[Fields]) -> Vec<(Usage, Option<&syn::Type>)> {
+    variants
+        .iter()
+        .filter_map(|variant| match variant {
+            Fields::Newtype { ty, .. } => Some((Usage::Element, item_type(ty))),
+            Fields::Named { .. } => None,
+        })
+        .collect()
+}
+
+fn field_usages(field: &Field) -> Vec<(Usage, Option<&syn::Type>)> {
+    match field {
+        Field::Attribute { ty, .. } | Field::Text { ty, .. } | Field::FlattenText { ty, .. } => {
+            vec![(Usage::Value, item_type(ty))]
+        }
+        Field::Child { ty, .. } => vec![(Usage::Element, item_type(ty))],
+        Field::KeyedChild {
+            key_ty, value_ty, ..
+        } => vec![
+            (Usage::Value, item_type(key_ty)),
+            (Usage::Element, Some(value_ty)),
+        ],
+        Field::Map {
+            key_ty, value_ty, ..
+        } => vec![
+            (Usage::Value, item_type(key_ty)),
+            (Usage::Value, item_type(value_ty)),
+        ],
+        Field::Children { ty, .. } => vec![(Usage::Element, Some(ty))],
+        Field::Flatten { ty, .. } => vec![(Usage::Flatten, Some(ty))],
+        _ => Vec::new(),
+    }
+}
+
+fn item_type(ty: &Type) -> Option<&syn::Type> {
+    match ty {
+        Type::T(ty) | Type::OptionT(ty) | Type::VecT(ty) => Some(ty),
+        _ => None,
+    }
+}
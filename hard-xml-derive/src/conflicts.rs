@@ -0,0 +1,136 @@
+use proc_macro2::TokenStream;
+use syn::{Error, LitStr, Result};
+
+use crate::types::{Element, Field, Fields};
+use crate::utils::Errors;
+
+// names a field is read from, with the literal they're declared with
+struct Names<'a> {
+    attrs: Vec<&'a LitStr>,
+    children: Vec<&'a LitStr>,
+}
+
+fn names(field: &Field) -> Names<'_> {
+    let mut attrs = Vec::new();
+    let mut children = Vec::new();
+    match field {
+        Field::Attribute {
+            tag,
+            aliases,
+            deprecated,
+            or_child,
+            ..
+        } => {
+            attrs.push(tag);
+            attrs.extend(aliases);
+            attrs.extend(deprecated);
+            children.extend(or_child);
+        }
+        // children in a wrapper are read from inside of it
+        Field::Child {
+            wrapper: Some(wrapper),
+            ..
+        } => children.push(wrapper),
+        Field::Child { tags, .. } | Field::KeyedChild { tags, .. } => children.extend(tags),
+        Field::FlattenText {
+            tag,
+            deprecated,
+            or_attr,
+            ..
+        } => {
+            children.push(tag);
+            children.extend(deprecated);
+            attrs.extend(or_attr);
+        }
+        Field::Map { tag, .. } => children.push(tag),
+        _ => (),
+    }
+    // any other name is accepted by a wildcard, which doesn't conflict
+    children.retain(|tag| tag.value() != "*");
+    Names { attrs, children }
+}
+
+// two fields reading the same attribute or child are ambiguous, so it's an
+// error pointing at both declarations
+fn check_fields(fields: &[Field], case_insensitive: bool, errors: &mut Errors) {
+    let names = fields.iter().map(names).collect::<Vec<_>>();
+
+    let attrs = names.iter().map(|names| &names.attrs[..]);
+    check_names("Attribute", fields, attrs, case_insensitive, errors);
+
+    let children = names.iter().map(|names| &names.children[..]);
+    check_names("Child", fields, children, case_insensitive, errors);
+}
+
+fn check_names<'a>(
+    kind: &str,
+    fields: &[Field],
+    names: impl Iterator<Item = &'a [&'a LitStr]>,
+    case_insensitive: bool,
+    errors: &mut Errors,
+) {
+    let key = |lit: &LitStr| {
+        if case_insensitive {
+            lit.value().to_ascii_lowercase()
+        } else {
+            lit.value()
+        }
+    };
+
+    let mut seen: Vec<(String, &TokenStream, &LitStr)> = Vec::new();
+    for (field, names) in fields.iter().zip(names) {
+        // a field can accept the same name twice, e.g. as an alias
+        let mut own = Vec::new();
+        for lit in names {
+            let value = key(lit);
+            if own.contains(&value) {
+                continue;
+            }
+            match seen.iter().find(|(v, ..)| *v == value) {
+                Some((_, other, first)) => {
+                    let mut error = Error::new(
+                        lit.span(),
+                        format!(
+                            "{} `{}` of field `{}` is already read by field `{}`.",
+                            kind,
+                            lit.value(),
+                            field.name(),
+                            other
+                        ),
+                    );
+                    error.combine(Error::new(
+                        first.span(),
+                        format!("{} `{}` is first declared here.", kind, first.value()),
+                    ));
+                    errors.push(error);
+                }
+                None => seen.push((value.clone(), field.name(), lit)),
+            }
+            own.push(value);
+        }
+    }
+}
+
+/// Returns the errors for fields reading the same attribute or child
+/// element, if there are any.
+///
+/// Only the fields declared in the element are compared, the ones of the
+/// structs it flattens being declared by other derives.
+pub fn check(element: &Element, case_insensitive: bool) -> Result<()> {
+    let mut errors = Errors::default();
+
+    let mut check_named = |fields: &Fields| {
+        if let Fields::Named { fields, .. } = fields {
+            check_fields(fields, case_insensitive, &mut errors);
+        }
+    };
+
+    match element {
+        Element::Struct { fields, .. } => check_named(fields),
+        Element::Enum { variants, .. } => variants.iter().for_each(check_named),
+        Element::Flatten { fields, .. } => check_fields(fields, case_insensitive, &mut errors),
+        Element::TextEnum { .. } => (),
+    }
+
+    errors.finish()
+}
@@ -2,79 +2,486 @@
 
 extern crate proc_macro;
 
+mod bounds;
+mod conflicts;
 mod read;
+mod schema;
 mod types;
 mod utils;
 mod write;
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput};
-use types::Element;
+use syn::{parse_macro_input, parse_quote, DeriveInput, Error, Result};
+use types::{
+    check_readable, get_bound, get_crate_path, is_case_insensitive, is_impl_display,
+    is_impl_from_str, is_impl_try_from, is_owned, Access, Element,
+};
 
 #[proc_macro_derive(XmlRead, attributes(xml))]
 pub fn derive_xml_read(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
+    xml_read(&input)
+        .unwrap_or_else(|err| compile_error(&input, err, dummy_read(&input)))
+        .into()
+}
+
+fn xml_read(input: &DeriveInput) -> Result<proc_macro2::TokenStream> {
     let name = &input.ident;
     let generics = &input.generics;
 
-    let params = &generics.params;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let where_clause = &generics.where_clause;
+    let owned = is_owned(&input.attrs)?;
 
-    let input_lifetime = utils::gen_input_lifetime(&generics);
+    let mut input_lifetime = utils::gen_input_lifetime(generics);
 
-    let mut params_with_input_lifetime = generics.params.clone();
+    // nothing is borrowed from the input, so it doesn't have to outlive
+    // the lifetimes of the element
+    if owned {
+        input_lifetime.colon_token = None;
+        input_lifetime.bounds.clear();
+    }
 
-    params_with_input_lifetime.insert(0, input_lifetime.into());
+    // strings and bytes are converted into the field types with these
+    let conversions = if owned {
+        quote! {
+            use hard_xml::utils::{from_bytes_owned as from_bytes, from_cow_str_owned as from_cow_str};
+        }
+    } else {
+        quote! {
+            use hard_xml::utils::{from_bytes, from_cow_str};
+        }
+    };
 
-    let impl_read = read::impl_read(Element::parse(input.clone()));
+    let mut generics_with_input_lifetime = generics.clone();
 
-    let gen = quote! {
-        impl <#params_with_input_lifetime> hard_xml::XmlRead<'__input> for #name <#params>
-            #where_clause
-        {
-            fn from_reader(
-                mut reader: &mut hard_xml::XmlReader<'__input>
-            ) -> hard_xml::XmlResult<Self> {
-                use hard_xml::xmlparser::{ElementEnd, Token, Tokenizer};
-                use hard_xml::XmlError;
-                #impl_read
+    generics_with_input_lifetime
+        .params
+        .insert(0, input_lifetime.clone().into());
+
+    let (impl_generics_with_input_lifetime, _, _) = generics_with_input_lifetime.split_for_impl();
+
+    check_readable(input)?;
+
+    let element = Element::parse(input.clone())?.skip_access(Access::WriteOnly);
+
+    let case_insensitive = is_case_insensitive(&input.attrs)?;
+
+    conflicts::check(&element, case_insensitive)?;
+
+    let bounds =
+        get_bound(&input.attrs)?.unwrap_or_else(|| bounds::read_bounds(&element, generics));
+
+    let where_clause_with_bounds = bounds::add_bounds(generics, &bounds);
+
+    // names are compared ignoring case while the element is read
+    let case_insensitive = |read: proc_macro2::TokenStream| {
+        if !case_insensitive {
+            return read;
+        }
+        quote! {
+            let __ignore_case = reader.set_ignore_ascii_case(true);
+            #[allow(clippy::redundant_closure_call)]
+            let __res = (|| -> hard_xml::XmlResult<Self> { #read })();
+            reader.set_ignore_ascii_case(__ignore_case);
+            __res
+        }
+    };
+
+    // errors are positioned at the token being read when they're raised
+    let locate = |read: proc_macro2::TokenStream| {
+        quote! {
+            #[allow(clippy::redundant_closure_call)]
+            let __res = (|| -> hard_xml::XmlResult<Self> { #read })();
+            __res.map_err(|err| reader.locate(err))
+        }
+    };
+
+    let impl_read_fields = read::impl_read_fields(&element).map(|read_fields| {
+        let read_fields = locate(case_insensitive(read_fields));
+        quote! {
+            impl #impl_generics #name #ty_generics
+                #where_clause
+            {
+                /// Reads an element like `from_reader`, but only the fields
+                /// named in `fields`, leaving the other ones to their default
+                /// value.
+                pub fn read_fields<#input_lifetime>(
+                    mut reader: &mut hard_xml::XmlReader<'__input>,
+                    __fields: &[&str],
+                ) -> hard_xml::XmlResult<Self>
+                where
+                    #( #bounds, )*
+                {
+                    use hard_xml::xmlparser::{ElementEnd, Token, Tokenizer};
+                    use hard_xml::XmlError;
+                    #conversions
+                    #read_fields
+                }
+            }
+        }
+    });
+
+    let impl_tags = read::impl_tags(&element).map(|tags| {
+        quote! {
+            impl #impl_generics #name #ty_generics
+                #where_clause
+            {
+                #tags
             }
         }
+    });
+
+    let impl_from_str = is_impl_from_str(&input.attrs)?;
+    let impl_try_from = is_impl_try_from(&input.attrs)?;
+
+    // the string doesn't outlive `from_str`, so the element can't borrow it
+    if impl_from_str && !owned && generics.lifetimes().next().is_some() {
+        return Err(Error::new(
+            input.ident.span(),
+            "`impl_from_str` attribute requires `owned` attribute for types with lifetimes.",
+        ));
+    }
+
+    if let Element::Flatten { .. } = element {
+        if impl_from_str {
+            return Err(Error::new(
+                input.ident.span(),
+                "`impl_from_str` attribute requires `tag` attribute.",
+            ));
+        }
+        if impl_try_from {
+            return Err(Error::new(
+                input.ident.span(),
+                "`impl_try_from` attribute requires `tag` attribute.",
+            ));
+        }
+    }
+
+    let where_clause_from_str = match element {
+        Element::TextEnum { .. } => where_clause.cloned(),
+        _ => Some(bounds::add_bounds(
+            generics,
+            &[parse_quote!(Self: hard_xml::XmlReadOwned)],
+        )),
     };
 
-    gen.into()
+    let impl_from_str = read::impl_from_str(&element, impl_from_str).map(|from_str| {
+        quote! {
+            impl #impl_generics std::str::FromStr for #name #ty_generics
+                #where_clause_from_str
+            {
+                type Err = hard_xml::XmlError;
+
+                fn from_str(s: &str) -> hard_xml::XmlResult<Self> {
+                    #from_str
+                }
+            }
+        }
+    });
+
+    let impl_try_from = read::impl_try_from(&element, impl_try_from).map(|try_from| {
+        quote! {
+            impl #impl_generics_with_input_lifetime std::convert::TryFrom<&'__input str> for #name #ty_generics
+                #where_clause_with_bounds
+            {
+                type Error = hard_xml::XmlError;
+
+                fn try_from(s: &'__input str) -> hard_xml::XmlResult<Self> {
+                    #try_from
+                }
+            }
+        }
+    });
+
+    let impl_read_flatten = read::impl_read_flatten(&element, &conversions).map(|read_flatten| {
+        quote! {
+            impl #impl_generics_with_input_lifetime hard_xml::XmlReadFlatten<'__input> for #name #ty_generics
+                #where_clause_with_bounds
+            {
+                #read_flatten
+            }
+        }
+    });
+
+    let impl_read = read::impl_read(element).map(|impl_read| {
+        let impl_read = locate(case_insensitive(impl_read));
+        quote! {
+            impl #impl_generics_with_input_lifetime hard_xml::XmlRead<'__input> for #name #ty_generics
+                #where_clause_with_bounds
+            {
+                fn from_reader(
+                    mut reader: &mut hard_xml::XmlReader<'__input>
+                ) -> hard_xml::XmlResult<Self> {
+                    use hard_xml::xmlparser::{ElementEnd, Token, Tokenizer};
+                    use hard_xml::XmlError;
+                    #conversions
+                    #impl_read
+                }
+            }
+        }
+    });
+
+    let gen = quote! {
+        #impl_read_fields
+
+        #impl_tags
+
+        #impl_from_str
+
+        #impl_try_from
+
+        #impl_read_flatten
+
+        #impl_read
+    };
+
+    match get_crate_path(&input.attrs)? {
+        Some(path) => Ok(utils::replace_crate_path(gen, &path)),
+        None => Ok(gen),
+    }
 }
 
 #[proc_macro_derive(XmlWrite, attributes(xml))]
 pub fn derive_xml_write(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
+    xml_write(&input)
+        .unwrap_or_else(|err| compile_error(&input, err, dummy_write(&input)))
+        .into()
+}
+
+fn xml_write(input: &DeriveInput) -> Result<proc_macro2::TokenStream> {
     let name = &input.ident;
     let generics = &input.generics;
 
-    let params = &generics.params;
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+
+    let element = Element::parse(input.clone())?.skip_access(Access::ReadOnly);
+
+    let impl_display = is_impl_display(&input.attrs)?;
+    let impl_try_from = is_impl_try_from(&input.attrs)?;
+
+    if let Element::Flatten { .. } = element {
+        if impl_display {
+            return Err(Error::new(
+                input.ident.span(),
+                "`impl_display` attribute requires `tag` attribute.",
+            ));
+        }
+        if impl_try_from {
+            return Err(Error::new(
+                input.ident.span(),
+                "`impl_try_from` attribute requires `tag` attribute.",
+            ));
+        }
+    }
+
+    let bounds =
+        get_bound(&input.attrs)?.unwrap_or_else(|| bounds::write_bounds(&element, generics));
+
+    let where_clause = bounds::add_bounds(generics, &bounds);
+
+    let impl_display = write::impl_display(&element, impl_display).map(|display| {
+        quote! {
+            impl #impl_generics std::fmt::Display for #name #ty_generics
+                #where_clause
+            {
+                fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    #display
+                }
+            }
+        }
+    });
+
+    let mut generics_with_value_lifetime = generics.clone();
 
-    let where_clause = &generics.where_clause;
+    generics_with_value_lifetime
+        .params
+        .insert(0, parse_quote!('__value));
 
-    let impl_write = write::impl_write(Element::parse(input.clone()));
+    let (impl_generics_with_value_lifetime, _, _) = generics_with_value_lifetime.split_for_impl();
+
+    let impl_try_into = write::impl_try_into(&element, impl_try_from).map(|try_into| {
+        quote! {
+            impl #impl_generics_with_value_lifetime std::convert::TryFrom<&'__value #name #ty_generics> for std::string::String
+                #where_clause
+            {
+                type Error = hard_xml::XmlError;
+
+                fn try_from(value: &'__value #name #ty_generics) -> hard_xml::XmlResult<Self> {
+                    #try_into
+                }
+            }
+
+            impl #impl_generics std::convert::TryFrom<#name #ty_generics> for std::string::String
+                #where_clause
+            {
+                type Error = hard_xml::XmlError;
+
+                fn try_from(value: #name #ty_generics) -> hard_xml::XmlResult<Self> {
+                    std::string::String::try_from(&value)
+                }
+            }
+        }
+    });
+
+    let impl_write_flatten = write::impl_write_flatten(&element).map(|write_flatten| {
+        quote! {
+            impl #impl_generics hard_xml::XmlWriteFlatten for #name #ty_generics
+                #where_clause
+            {
+                #write_flatten
+            }
+        }
+    });
+
+    let impl_write = write::impl_write(element).map(|impl_write| {
+        quote! {
+            impl #impl_generics hard_xml::XmlWrite for #name #ty_generics
+                #where_clause
+            {
+                fn to_writer<__W: std::io::Write>(
+                    &self,
+                    mut writer: &mut hard_xml::XmlWriter<__W>
+                ) -> hard_xml::XmlResult<()> {
+                    #impl_write
+
+                    Ok(())
+                }
+            }
+        }
+    });
+
+    let gen = quote! {
+        #impl_display
+
+        #impl_try_into
+
+        #impl_write_flatten
+
+        #impl_write
+    };
+
+    match get_crate_path(&input.attrs)? {
+        Some(path) => Ok(utils::replace_crate_path(gen, &path)),
+        None => Ok(gen),
+    }
+}
+
+#[proc_macro_derive(XmlSchemaInfo, attributes(xml))]
+pub fn derive_xml_schema_info(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    xml_schema_info(&input)
+        .unwrap_or_else(|err| compile_error(&input, err, dummy_schema_info(&input)))
+        .into()
+}
+
+fn xml_schema_info(input: &DeriveInput) -> Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+
+    let element = Element::parse(input.clone())?;
+
+    let bounds =
+        get_bound(&input.attrs)?.unwrap_or_else(|| bounds::schema_bounds(&element, generics));
+
+    let where_clause = bounds::add_bounds(&input.generics, &bounds);
+
+    let impl_schema = schema::impl_schema(element);
 
     let gen = quote! {
-        impl <#params> hard_xml::XmlWrite for #name <#params>
+        impl #impl_generics hard_xml::XmlSchemaInfo for #name #ty_generics
             #where_clause
         {
-            fn to_writer<W: std::io::Write>(
+            fn elements() -> Vec<hard_xml::schema::ElementInfo> {
+                #impl_schema
+            }
+        }
+    };
+
+    match get_crate_path(&input.attrs)? {
+        Some(path) => Ok(utils::replace_crate_path(gen, &path)),
+        None => Ok(gen),
+    }
+}
+
+// the errors are emitted along with an impl doing nothing, so the uses of
+// the trait don't add errors of their own
+fn compile_error(
+    input: &DeriveInput,
+    err: Error,
+    dummy: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let dummy = match get_crate_path(&input.attrs) {
+        Ok(Some(path)) => utils::replace_crate_path(dummy, &path),
+        _ => dummy,
+    };
+    let err = err.to_compile_error();
+
+    quote! {
+        #err
+        #dummy
+    }
+}
+
+// the dummy impls are only emitted next to a compile error, so the crate
+// never builds and their methods are never called
+fn dummy_read(input: &DeriveInput) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    generics
+        .params
+        .insert(0, utils::gen_input_lifetime(&input.generics).into());
+
+    let (impl_generics, _, _) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics hard_xml::XmlRead<'__input> for #name #ty_generics
+            #where_clause
+        {
+            fn from_reader(_: &mut hard_xml::XmlReader<'__input>) -> hard_xml::XmlResult<Self> {
+                unreachable!("hard-xml derive failed")
+            }
+        }
+    }
+}
+
+fn dummy_write(input: &DeriveInput) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics hard_xml::XmlWrite for #name #ty_generics
+            #where_clause
+        {
+            fn to_writer<__W: std::io::Write>(
                 &self,
-                mut writer: &mut hard_xml::XmlWriter<W>
+                _: &mut hard_xml::XmlWriter<__W>,
             ) -> hard_xml::XmlResult<()> {
-                #impl_write
-
-                Ok(())
+                unreachable!("hard-xml derive failed")
             }
         }
-    };
+    }
+}
+
+fn dummy_schema_info(input: &DeriveInput) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
-    gen.into()
+    quote! {
+        impl #impl_generics hard_xml::XmlSchemaInfo for #name #ty_generics
+            #where_clause
+        {
+            fn elements() -> Vec<hard_xml::schema::ElementInfo> {
+                unreachable!("hard-xml derive failed")
+            }
+        }
+    }
 }
@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+
+use proc_macro2::{Ident, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{LitByteStr, LitStr};
+
+/// An arm of the `match` reading an attribute or a child by its name.
+pub struct Arm {
+    pub names: Vec<LitStr>,
+    pub guard: Option<TokenStream>,
+    pub body: TokenStream,
+}
+
+impl Arm {
+    pub fn new(names: Vec<LitStr>, body: TokenStream) -> Arm {
+        Arm {
+            names,
+            guard: None,
+            body,
+        }
+    }
+
+    /// Returns the arm only taken if `guard` is true.
+    pub fn with_guard(mut self, guard: TokenStream) -> Arm {
+        self.guard = Some(guard);
+        self
+    }
+}
+
+// the arm matching the names themselves, for a `match` over names
+impl ToTokens for Arm {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Arm { names, guard, body } = self;
+        let guard = guard.as_ref().map(|guard| quote! { if #guard });
+        tokens.extend(quote! {
+            #( #names )|* #guard => {
+                #body
+            }
+        });
+    }
+}
+
+/// Generates a `match` over `arms`, with a last arm binding any other name
+/// to `other`.
+///
+/// Names are compared grouped by length as byte strings, and the arms are
+/// matched by the index of their name, so the cost of finding an arm stays
+/// flat however many names there are. Names not found as they are, like
+/// the ones of a case insensitive reader, are resolved by the reader.
+pub fn dispatch(
+    found: TokenStream,
+    arms: &[Arm],
+    other: &Ident,
+    read_other: TokenStream,
+) -> TokenStream {
+    let mut names = Vec::<&LitStr>::new();
+    for name in arms.iter().flat_map(|arm| &arm.names) {
+        if !names.iter().any(|n| n.value() == name.value()) {
+            names.push(name);
+        }
+    }
+    let index = |name: &LitStr| {
+        names
+            .iter()
+            .position(|n| n.value() == name.value())
+            .unwrap()
+    };
+    let missing = names.len();
+
+    let mut buckets = BTreeMap::<usize, Vec<(&LitStr, usize)>>::new();
+    for (index, name) in names.iter().enumerate() {
+        buckets
+            .entry(name.value().len())
+            .or_default()
+            .push((name, index));
+    }
+    let buckets = buckets.into_iter().map(|(len, names)| {
+        let names = names.into_iter().map(|(name, index)| {
+            let value = LitByteStr::new(name.value().as_bytes(), name.span());
+            quote! { #value => #index, }
+        });
+        quote! {
+            #len => match __name.as_bytes() {
+                #( #names )*
+                _ => #missing,
+            },
+        }
+    });
+
+    let arms = arms.iter().map(|Arm { names, guard, body }| {
+        let indexes = names.iter().map(index);
+        let guard = guard.as_ref().map(|guard| quote! { if #guard });
+        quote! {
+            #( #indexes )|* #guard => {
+                #body
+            }
+        }
+    });
+
+    quote! {
+        let __lookup = |__name: &str| -> usize {
+            match __name.len() {
+                #( #buckets )*
+                _ => #missing,
+            }
+        };
+        let mut __index = __lookup(#found);
+        let __name = if __index == #missing {
+            let __name = reader.resolve_name(#found, &[ #( #names ),* ]);
+            __index = __lookup(__name);
+            __name
+        } else {
+            #found
+        };
+        #[allow(clippy::manual_range_patterns)]
+        match __index {
+            #( #arms )*
+            _ => {
+                #[allow(unused_variables)]
+                let #other: &str = __name;
+                #read_other
+            }
+        };
+    }
+}
@@ -0,0 +1,327 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+use super::named::{
+    attr_tags, check_occurs, child_tags, first_position, missing_field, read_attrs, read_children,
+    read_flatten_attrs, read_flatten_text, read_or_attr_fields, read_or_child_fields, return_value,
+};
+use crate::types::{Field, OnDuplicate};
+
+/// Generates the items of `XmlReadFlatten` for structs without `tag`
+/// attribute.
+///
+/// The values read so far are kept in a tuple with one element per field
+/// which isn't skipped, moved into the same bindings `from_reader` uses
+/// while an attribute or a child is read.
+pub fn read(
+    ele_name: &Ident,
+    fields: &[Field],
+    types: &[syn::Type],
+    conversions: &TokenStream,
+) -> TokenStream {
+    let ele_name = quote!(#ele_name);
+
+    let state = fields
+        .iter()
+        .zip(types)
+        .filter(|(field, _)| !matches!(field, Field::Skip { .. }))
+        .collect::<Vec<_>>();
+
+    // positions of the first occurrence of children which can't occur
+    // twice follow the values
+    let firsts = fields
+        .iter()
+        .filter_map(|field| match field {
+            Field::Child {
+                bind,
+                on_duplicate: OnDuplicate::Error,
+                ..
+            }
+            | Field::FlattenText {
+                bind,
+                on_duplicate: OnDuplicate::Error,
+                ..
+            } => Some(first_position(bind)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let binds = state
+        .iter()
+        .map(|(field, _)| field.bind().clone())
+        .chain(firsts.iter().cloned())
+        .collect::<Vec<_>>();
+
+    let first_types = firsts.iter().map(|_| quote! { Option<hard_xml::Position> });
+
+    let first_inits = firsts.iter().map(|_| quote! { None });
+
+    let state_types = state.iter().map(|(field, ty)| match field {
+        Field::Flatten { .. } => quote! { <#ty as hard_xml::XmlReadFlatten<'__input>>::State },
+        Field::Attribute { ty: field_ty, .. }
+        | Field::Child { ty: field_ty, .. }
+        | Field::FlattenText { ty: field_ty, .. }
+            if field_ty.is_vec() || field_ty.is_option() =>
+        {
+            quote! { #ty }
+        }
+        _ => quote! { Option<#ty> },
+    });
+
+    let init_state = state.iter().map(|(field, ty)| match field {
+        Field::Flatten { .. } => quote! { <#ty as hard_xml::XmlReadFlatten>::init_state() },
+        Field::Attribute { ty, .. } | Field::Child { ty, .. } | Field::FlattenText { ty, .. }
+            if ty.is_vec() =>
+        {
+            quote! { Vec::new() }
+        }
+        _ => quote! { None },
+    });
+
+    let read_or_attr_fields = read_or_attr_fields(fields, &ele_name);
+
+    let read_or_child_fields = read_or_child_fields(fields, &ele_name);
+
+    let attr_tags = attr_tags(fields);
+
+    let child_tags = child_tags(fields);
+
+    let read_attr_fields = fields.iter().flat_map(|field| match field {
+        Field::Attribute {
+            bind,
+            ty,
+            tag,
+            aliases,
+            deprecated,
+            name,
+            saturate,
+            radix,
+            list,
+            ..
+        } => read_attrs(
+            tag,
+            aliases,
+            deprecated,
+            bind,
+            name,
+            ty,
+            *saturate,
+            radix.as_ref(),
+            list.as_ref(),
+            &ele_name,
+        ),
+        _ => vec![],
+    });
+
+    let read_child_fields = fields.iter().filter_map(|field| match field {
+        Field::Child {
+            bind,
+            ty,
+            tags,
+            name,
+            wrapper,
+            on_duplicate,
+            ..
+        } => Some(read_children(
+            tags,
+            wrapper.as_ref(),
+            *on_duplicate,
+            bind,
+            name,
+            ty,
+            &ele_name,
+            None,
+            None,
+        )),
+        _ => None,
+    });
+
+    let read_flatten_text_fields = fields.iter().flat_map(|field| match field {
+        Field::FlattenText {
+            bind,
+            ty,
+            tag,
+            deprecated,
+            name,
+            trim,
+            saturate,
+            radix,
+            list,
+            on_duplicate,
+            ..
+        } => read_flatten_text(
+            tag,
+            deprecated,
+            *on_duplicate,
+            bind,
+            name,
+            ty,
+            *trim,
+            *saturate,
+            radix.as_ref(),
+            list.as_ref(),
+            &ele_name,
+            None,
+            None,
+        ),
+        _ => vec![],
+    });
+
+    let read_flatten_attrs = match read_flatten_attrs(fields) {
+        Some(read) => quote! {
+            #read
+            __rest
+        },
+        None => quote! { Some(__value) },
+    };
+
+    let read_flatten_children = fields.iter().filter_map(|field| match field {
+        Field::Flatten { bind, ty, .. } => Some(quote! {
+            <#ty as hard_xml::XmlReadFlatten>::read_child(&mut #bind, reader, __tag)? ||
+        }),
+        _ => None,
+    });
+
+    let check_occurs = fields.iter().filter_map(|field| match field {
+        Field::Child {
+            bind, name, occurs, ..
+        }
+        | Field::FlattenText {
+            bind, name, occurs, ..
+        } if occurs.is_bounded() => {
+            let check_occurs = check_occurs(bind, name, occurs, &ele_name);
+            Some(quote! { #check_occurs?; })
+        }
+        _ => None,
+    });
+
+    let return_fields = fields.iter().map(|field| match field {
+        Field::Attribute {
+            name,
+            bind,
+            ty,
+            default,
+            default_fn,
+            ..
+        }
+        | Field::Child {
+            name,
+            bind,
+            ty,
+            default,
+            default_fn,
+            ..
+        }
+        | Field::FlattenText {
+            name,
+            bind,
+            ty,
+            default,
+            default_fn,
+            ..
+        } => {
+            // the tag isn't known to flattened structs
+            let missing_field = missing_field(&ele_name, name, quote!(""), field);
+            let value = return_value(bind, ty, *default, default_fn.as_ref(), missing_field);
+            quote! { #name: #value }
+        }
+        Field::Skip {
+            name,
+            default_fn: Some(default_fn),
+            ..
+        } => quote! { #name: #default_fn() },
+        Field::Skip { name, .. } => quote! { #name: Default::default() },
+        Field::Flatten { name, bind, ty } => quote! {
+            #name: <#ty as hard_xml::XmlReadFlatten>::finish(#bind)?
+        },
+        // rejected when parsing the struct
+        Field::Text { .. }
+        | Field::Order { .. }
+        | Field::TagName { .. }
+        | Field::Attrs { .. }
+        | Field::Children { .. }
+        | Field::Map { .. }
+        | Field::KeyedChild { .. } => unreachable!(),
+    });
+
+    quote! {
+        type State = ( #( #state_types, )* #( #first_types, )* );
+
+        fn init_state() -> Self::State {
+            ( #( #init_state, )* #( #first_inits, )* )
+        }
+
+        fn read_attribute(
+            __state: &mut Self::State,
+            reader: &mut hard_xml::XmlReader<'__input>,
+            __key: &'__input str,
+            __value: std::borrow::Cow<'__input, str>,
+        ) -> hard_xml::XmlResult<Option<std::borrow::Cow<'__input, str>>> {
+            use hard_xml::XmlError;
+            #conversions
+
+            #[allow(unused_mut)]
+            let ( #( mut #binds, )* ) = std::mem::replace(__state, Self::init_state());
+
+            let __tags: &[&str] = &[ #( #attr_tags ),* ];
+            let __name = reader.resolve_name(__key, __tags);
+            let __rest = if __tags.contains(&__name) {
+                match __name {
+                    #( #read_attr_fields )*
+                    #( #read_or_attr_fields )*
+                    _ => (),
+                }
+                None
+            } else {
+                #read_flatten_attrs
+            };
+
+            *__state = ( #( #binds, )* );
+            Ok(__rest)
+        }
+
+        fn read_child(
+            __state: &mut Self::State,
+            reader: &mut hard_xml::XmlReader<'__input>,
+            __tag: &'__input str,
+        ) -> hard_xml::XmlResult<bool> {
+            use hard_xml::XmlError;
+            use hard_xml::xmlparser::{ElementEnd, Token};
+            #conversions
+
+            #[allow(unused_mut)]
+            let ( #( mut #binds, )* ) = std::mem::replace(__state, Self::init_state());
+
+            let __tags: &[&str] = &[ #( #child_tags ),* ];
+            let __name = reader.resolve_name(__tag, __tags);
+            let __read = if __tags.contains(&__name) {
+                match __name {
+                    #( #read_child_fields )*
+                    #( #read_flatten_text_fields )*
+                    #( #read_or_child_fields )*
+                    _ => (),
+                }
+                true
+            } else {
+                #( #read_flatten_children )* false
+            };
+
+            *__state = ( #( #binds, )* );
+            Ok(__read)
+        }
+
+        fn finish(__state: Self::State) -> hard_xml::XmlResult<Self> {
+            use hard_xml::XmlError;
+            #conversions
+
+            let ( #( #binds, )* ) = __state;
+
+            #( #check_occurs )*
+
+            Ok(#ele_name {
+                #( #return_fields, )*
+            })
+        }
+    }
+}
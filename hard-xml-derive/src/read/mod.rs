@@ -1,49 +1,440 @@
+mod dispatch;
+mod flatten;
 mod named;
 mod newtype;
 
-use crate::types::{Element, Fields};
+use crate::types::{Discriminator, Element, Fields, Other};
 
 use proc_macro2::TokenStream;
 use quote::quote;
+use syn::LitStr;
 
-pub fn impl_read(element: Element) -> TokenStream {
+/// Generates the body of `read_fields` for structs with `partial` attribute.
+pub fn impl_read_fields(element: &Element) -> Option<TokenStream> {
     match element {
+        Element::Struct {
+            fields:
+                Fields::Named {
+                    tag,
+                    aliases,
+                    name,
+                    fields,
+                    const_attrs,
+                    ordered,
+                    ..
+                },
+            partial: true,
+            ..
+        } => Some(named::read(
+            tag,
+            aliases,
+            const_attrs,
+            quote!(#name),
+            fields,
+            *ordered,
+            true,
+        )),
+        _ => None,
+    }
+}
+
+/// Generates the constants naming the tags an element is read from.
+pub fn impl_tags(element: &Element) -> Option<TokenStream> {
+    match element {
+        Element::Struct {
+            fields: Fields::Named { tag, aliases, .. },
+            ..
+        } => Some(quote! {
+            /// Tag of the element
+            pub const TAG: &'static str = #tag;
+
+            /// Other tags the element is read from
+            pub const ALIASES: &'static [&'static str] = &[ #( #aliases ),* ];
+        }),
+        Element::Struct {
+            fields: Fields::Newtype { tags, .. },
+            ..
+        } => {
+            let tag = &tags[0];
+            let aliases = &tags[1..];
+            Some(quote! {
+                /// Tag of the element
+                pub const TAG: &'static str = #tag;
+
+                /// Other tags the element is read from
+                pub const ALIASES: &'static [&'static str] = &[ #( #aliases ),* ];
+            })
+        }
+        Element::Enum {
+            variants,
+            untagged: false,
+            discriminator,
+            ..
+        } => {
+            let mut tags: Vec<&LitStr> = Vec::new();
+            match discriminator {
+                Some(Discriminator { tag, .. }) => tags.push(tag),
+                None => {
+                    for variant in variants {
+                        let variant_tags = match variant {
+                            Fields::Named { tag, aliases, .. } => {
+                                std::iter::once(tag).chain(aliases).collect()
+                            }
+                            Fields::Newtype { tags, .. } => tags.iter().collect::<Vec<_>>(),
+                        };
+                        for tag in variant_tags {
+                            if !tags.iter().any(|t| t.value() == tag.value()) {
+                                tags.push(tag);
+                            }
+                        }
+                    }
+                }
+            }
+            Some(quote! {
+                /// Tags of every element the enum is read from
+                pub const TAGS: &'static [&'static str] = &[ #( #tags ),* ];
+            })
+        }
+        Element::TextEnum { tag: Some(tag), .. } => Some(quote! {
+            /// Tag of the element
+            pub const TAG: &'static str = #tag;
+        }),
+        _ => None,
+    }
+}
+
+/// Generates the items of `XmlReadFlatten` for structs without `tag`
+/// attribute.
+pub fn impl_read_flatten(element: &Element, conversions: &TokenStream) -> Option<TokenStream> {
+    match element {
+        Element::Flatten {
+            name,
+            fields,
+            types,
+            ..
+        } => Some(flatten::read(name, fields, types, conversions)),
+        _ => None,
+    }
+}
+
+/// Generates the body of `FromStr::from_str` for enums read from text,
+/// and for elements with `impl_from_str` attribute.
+pub fn impl_from_str(element: &Element, impl_from_str: bool) -> Option<TokenStream> {
+    match element {
+        Element::TextEnum {
+            name,
+            variants,
+            other,
+            ..
+        } => {
+            let values = variants.iter().map(|(_, value)| value);
+            let variants = variants.iter().map(|(variant, _)| variant);
+
+            let read_other = match other {
+                Some(Other {
+                    name: other,
+                    ty: Some(_),
+                }) => quote! { Ok(#name::#other(s.to_owned().into())) },
+                Some(Other { name: other, .. }) => quote! { Ok(#name::#other) },
+                None => quote! {
+                    Err(hard_xml::XmlError::new(hard_xml::ErrorDetail::UnknownVariant {
+                        value: s.to_owned(),
+                    })
+                    .with_context(stringify!(#name), None))
+                },
+            };
+
+            Some(quote! {
+                match s {
+                    #( #values => Ok(#name::#variants), )*
+                    _ => #read_other,
+                }
+            })
+        }
+        Element::Flatten { .. } if impl_from_str => {
+            panic!("`impl_from_str` attribute requires `tag` attribute.")
+        }
+        Element::Struct { .. } | Element::Enum { .. } if impl_from_str => Some(quote! {
+            <Self as hard_xml::XmlRead>::from_str(s)
+        }),
+        _ => None,
+    }
+}
+
+/// Generates the body of `TryFrom<&str>::try_from` for elements with
+/// `impl_try_from` attribute.
+pub fn impl_try_from(element: &Element, impl_try_from: bool) -> Option<TokenStream> {
+    match element {
+        _ if !impl_try_from => None,
+        Element::Flatten { .. } => panic!("`impl_try_from` attribute requires `tag` attribute."),
+        Element::TextEnum { .. } => Some(quote! {
+            <Self as std::str::FromStr>::from_str(s)
+        }),
+        Element::Struct { .. } | Element::Enum { .. } => Some(quote! {
+            <Self as hard_xml::XmlRead<'__input>>::from_str(s)
+        }),
+    }
+}
+
+/// Generates the body of `from_reader`, or nothing for enums read from
+/// text and structs which have no `tag` attribute.
+pub fn impl_read(element: Element) -> Option<TokenStream> {
+    let read = match element {
         Element::Enum {
             name: ele_name,
             variants,
+            untagged,
+            discriminator,
+            other,
         } => {
             let tags = variants.iter().map(|variant| match variant {
                 Fields::Newtype { tags, .. } => tags.clone(),
-                Fields::Named { tag, .. } => vec![tag.clone()],
+                Fields::Named { tag, aliases, .. } => {
+                    let mut tags = vec![tag.clone()];
+                    tags.extend(aliases.iter().cloned());
+                    tags
+                }
             });
 
             let read = variants.iter().map(|variant| match variant {
-                Fields::Named { tag, name, fields } => {
-                    named::read(&tag, quote!(#ele_name::#name), &fields)
+                Fields::Named {
+                    tag,
+                    aliases,
+                    name,
+                    fields,
+                    const_attrs,
+                    ordered,
+                    ..
+                } => named::read(
+                    tag,
+                    aliases,
+                    const_attrs,
+                    quote!(#ele_name::#name),
+                    fields,
+                    *ordered,
+                    false,
+                ),
+                Fields::Newtype { name, ty, tags } => {
+                    newtype::read(tags, ty, quote!(#ele_name::#name))
+                }
+            });
+
+            let all_tags = tags.clone().flatten();
+
+            let read_other = other.map(|other| read_other(&ele_name, &other));
+
+            if untagged {
+                return Some(read_untagged(quote!(#ele_name), tags, read, read_other));
+            }
+
+            if let Some(discriminator) = discriminator {
+                return Some(read_discriminated(
+                    quote!(#ele_name),
+                    &discriminator,
+                    read,
+                    read_other,
+                ));
+            }
+
+            let read_unknown = read_other.unwrap_or_else(|| {
+                quote! {
+                    hard_xml::log_skip_element!(#ele_name, __tag);
+                    hard_xml::utils::skip_element(reader, __tag)?;
                 }
-                Fields::Newtype { name, ty, .. } => newtype::read(&ty, quote!(#ele_name::#name)),
             });
 
             quote! {
                 while let Some(tag) = reader.find_element_start(None)? {
-                    match tag {
+                    match reader.resolve_name(tag, &[ #( #all_tags ),* ]) {
                         #( #( #tags )|* => { #read } )*
-                        tag => {
-                            hard_xml::log_skip_element!(#ele_name, tag);
-                            // skip the start tag
-                            reader.next();
-                            reader.read_to_end(tag)?;
-                        },
+                        __tag => { #read_unknown },
                     }
                 }
 
-                Err(XmlError::UnexpectedEof)
+                Err(hard_xml::ErrorDetail::UnexpectedEof.into())
             }
         }
 
         Element::Struct { fields, .. } => match fields {
-            Fields::Named { tag, name, fields } => named::read(&tag, quote!(#name), &fields),
-            Fields::Newtype { name, ty, .. } => newtype::read(&ty, quote!(#name)),
+            Fields::Named {
+                tag,
+                aliases,
+                name,
+                fields,
+                const_attrs,
+                ordered,
+                ..
+            } => named::read(
+                &tag,
+                &aliases,
+                &const_attrs,
+                quote!(#name),
+                &fields,
+                ordered,
+                false,
+            ),
+            Fields::Newtype { name, ty, tags } => newtype::read(&tags, &ty, quote!(#name)),
+        },
+
+        Element::TextEnum { name, tag, .. } => {
+            let tag = tag?;
+
+            quote! {
+                hard_xml::log_start_reading!(#name);
+
+                reader.read_till_element_start(#tag)?;
+
+                let __value = reader.read_text(#tag)?;
+                let __res = <Self as std::str::FromStr>::from_str(&__value)?;
+
+                hard_xml::log_finish_reading!(#name);
+
+                Ok(__res)
+            }
+        }
+
+        Element::Flatten { .. } => return None,
+    };
+
+    Some(read)
+}
+
+// every variant accepting the element is tried in turn, rewinding the
+// reader after each failure, so errors aren't collected while trying them
+fn read_untagged(
+    ele_name: TokenStream,
+    tags: impl Iterator<Item = Vec<LitStr>>,
+    read: impl Iterator<Item = TokenStream>,
+    read_other: Option<TokenStream>,
+) -> TokenStream {
+    let try_read = tags.zip(read).map(|(tags, read)| {
+        quote! {
+            if #( reader.name_matches(#tags, __tag) )||* {
+                #[allow(clippy::redundant_closure_call)]
+                let __res = (|| -> hard_xml::XmlResult<Self> { #read })();
+                match __res {
+                    Ok(__res) => return Ok(__res),
+                    Err(err) => {
+                        reader.rewind(__checkpoint.clone());
+                        __err = Some(err);
+                    }
+                }
+            }
+        }
+    });
+
+    let read_unmatched = match read_other {
+        Some(read_other) => read_other,
+        None => quote! {
+            if let Some(err) = __err {
+                return Err(err);
+            }
+
+            hard_xml::log_skip_element!(#ele_name, __tag);
+            hard_xml::utils::skip_element(reader, __tag)?;
+        },
+    };
+
+    quote! {
+        let __collect_errors = reader.set_collect_errors(false);
+        #[allow(clippy::redundant_closure_call)]
+        let __res = (|| -> hard_xml::XmlResult<Self> {
+            while let Some(__tag) = reader.find_element_start(None)? {
+                let __checkpoint = reader.checkpoint();
+                let mut __err = None;
+
+                #( #try_read )*
+
+                #read_unmatched
+            }
+
+            Err(hard_xml::ErrorDetail::UnexpectedEof.into())
+        })();
+        reader.set_collect_errors(__collect_errors);
+        __res
+    }
+}
+
+// the variant is chosen by the value of an attribute, which is looked up
+// before reading the element from its start again
+fn read_discriminated(
+    ele_name: TokenStream,
+    discriminator: &Discriminator,
+    read: impl Iterator<Item = TokenStream>,
+    read_other: Option<TokenStream>,
+) -> TokenStream {
+    let Discriminator { tag, attr, values } = discriminator;
+    let expected = format!("attribute {:?}", attr.value());
+
+    let read_unknown = match read_other {
+        Some(read_other) => quote! { _ => { #read_other } },
+        None => quote! {
+            Some(__type) => {
+                return Err(XmlError::new(hard_xml::ErrorDetail::UnknownVariant {
+                    value: __type.to_owned(),
+                })
+                .with_context(stringify!(#ele_name), None));
+            }
+            None => {
+                return Err(hard_xml::utils::missing_field(
+                    stringify!(#ele_name),
+                    #attr,
+                    __tag,
+                    #expected,
+                ));
+            }
+        },
+    };
+
+    quote! {
+        while let Some(__tag) = reader.find_element_start(None)? {
+            if !reader.name_matches(#tag, __tag) {
+                hard_xml::log_skip_element!(#ele_name, __tag);
+                hard_xml::utils::skip_element(reader, __tag)?;
+                continue;
+            }
+
+            let __checkpoint = reader.checkpoint();
+            let mut __type = None;
+
+            // skip the start tag
+            reader.next();
+
+            while let Some((__key, __value)) = reader.find_attribute()? {
+                if reader.name_matches(#attr, __key) {
+                    __type = Some(__value);
+                }
+            }
+
+            reader.rewind(__checkpoint);
+
+            match __type.as_deref() {
+                #( Some(#values) => { #read } )*
+                #read_unknown
+            }
+        }
+
+        Err(hard_xml::ErrorDetail::UnexpectedEof.into())
+    }
+}
+
+fn read_other(ele_name: &syn::Ident, other: &Other) -> TokenStream {
+    let Other { name, ty } = other;
+
+    match ty {
+        Some(ty) => quote! {
+            hard_xml::log_start_reading!(#ele_name::#name);
+
+            let res = <#ty as XmlRead>::from_reader(reader)?;
+
+            hard_xml::log_finish_reading!(#ele_name::#name);
+
+            return Ok(#ele_name::#name(res));
+        },
+        None => quote! {
+            hard_xml::utils::skip_element(reader, __tag)?;
+
+            return Ok(#ele_name::#name);
         },
     }
 }
@@ -1,23 +1,85 @@
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{Ident, LitStr};
+use quote::{format_ident, quote};
+use syn::{ExprPath, Ident, LitStr};
 
-use crate::types::{Field, Type};
+use super::dispatch::{dispatch, Arm};
+use crate::types::{
+    BoolParse, ConstAttr, Encoding, Field, Occurs, OnDuplicate, Radix, Separator, Type,
+};
+
+/// Generates the body of `from_reader`, or of `read_fields` if `partial`,
+/// where only the fields named in `__fields` are read.
+pub fn read(
+    tag: &LitStr,
+    aliases: &[LitStr],
+    const_attrs: &[ConstAttr],
+    ele_name: TokenStream,
+    fields: &[Field],
+    ordered: bool,
+    partial: bool,
+) -> TokenStream {
+    let is_selected = |name: &TokenStream| quote! { __fields.contains(&stringify!(#name)) };
 
-pub fn read(tag: &LitStr, ele_name: TokenStream, fields: &[Field]) -> TokenStream {
     let init_fields = fields.iter().map(|field| match field {
+        Field::Child {
+            bind,
+            ty,
+            on_duplicate: OnDuplicate::Error,
+            ..
+        }
+        | Field::FlattenText {
+            bind,
+            ty,
+            on_duplicate: OnDuplicate::Error,
+            ..
+        } => {
+            let init_value = init_value(bind, ty);
+            let first = first_position(bind);
+            quote! {
+                #init_value
+                let mut #first = None;
+            }
+        }
         Field::Attribute { bind, ty, .. }
         | Field::Child { bind, ty, .. }
         | Field::FlattenText { bind, ty, .. } => init_value(bind, ty),
         Field::Text { bind, .. } => quote! { let #bind; },
+        Field::KeyedChild { bind, .. } => quote! { let mut #bind = Default::default(); },
+        Field::Order { bind, .. }
+        | Field::Map { bind, .. }
+        | Field::Attrs { bind, .. }
+        | Field::Children { bind, .. } => {
+            quote! { let mut #bind = Vec::new(); }
+        }
+        // initialized when reading the start tag
+        Field::TagName { .. } => quote! {},
+        Field::Skip { .. } => quote! {},
+        Field::Flatten { bind, ty, .. } => quote! {
+            let mut #bind = <#ty as hard_xml::XmlReadFlatten>::init_state();
+        },
     });
 
+    // fields which aren't selected are left to their default value
+    let return_field = |name: &TokenStream, default_fn: Option<&ExprPath>, value: TokenStream| {
+        if partial {
+            let is_selected = is_selected(name);
+            let default = match default_fn {
+                Some(default_fn) => quote! { #default_fn() },
+                None => quote! { Default::default() },
+            };
+            quote! { #name: if #is_selected { #value } else { #default } }
+        } else {
+            quote! { #name: #value }
+        }
+    };
+
     let return_fields = fields.iter().map(|field| match field {
         Field::Attribute {
             name,
             bind,
             ty,
             default,
+            default_fn,
             ..
         }
         | Field::Child {
@@ -25,6 +87,7 @@ pub fn read(tag: &LitStr, ele_name: TokenStream, fields: &[Field]) -> TokenStrea
             bind,
             ty,
             default,
+            default_fn,
             ..
         }
         | Field::FlattenText {
@@ -32,54 +95,509 @@ pub fn read(tag: &LitStr, ele_name: TokenStream, fields: &[Field]) -> TokenStrea
             bind,
             ty,
             default,
+            default_fn,
+            ..
+        } => return_field(
+            name,
+            default_fn.as_ref(),
+            return_value(
+                bind,
+                ty,
+                *default,
+                default_fn.as_ref(),
+                missing_field(&ele_name, name, quote!(__end_tag), field),
+            ),
+        ),
+        Field::Text { name, bind, ty, .. } => return_field(
+            name,
+            None,
+            return_value(
+                bind,
+                ty,
+                false,
+                None,
+                missing_field(&ele_name, name, quote!(__end_tag), field),
+            ),
+        ),
+        Field::Order { name, bind } | Field::TagName { name, bind } => quote! { #name: #bind },
+        Field::Children { name, bind, .. } | Field::KeyedChild { name, bind, .. } => {
+            return_field(name, None, quote! { #bind })
+        }
+        Field::Map { name, bind, .. } => {
+            return_field(name, None, quote! { #bind.into_iter().collect() })
+        }
+        // collected into whichever collection of pairs the field is
+        Field::Attrs { name, bind, .. } => return_field(
+            name,
+            None,
+            quote! {
+                #bind
+                    .into_iter()
+                    .map(|(__key, __value)| (__key.into(), __value.into()))
+                    .collect()
+            },
+        ),
+        Field::Skip {
+            name,
+            default_fn: Some(default_fn),
+            ..
+        } => quote! { #name: #default_fn() },
+        Field::Skip { name, .. } => quote! { #name: Default::default() },
+        // flattened fields are read whether they're selected or not
+        Field::Flatten { name, bind, ty } => quote! {
+            #name: <#ty as hard_xml::XmlReadFlatten>::finish(#bind)?
+        },
+    });
+
+    // fields which aren't selected are skipped before they're matched
+    let skip_attr_fields = fields.iter().filter_map(|field| match field {
+        Field::Attribute {
+            tag,
+            aliases,
+            deprecated,
+            name,
+            ..
+        } if partial => {
+            let is_selected = is_selected(name);
+            let mut tags = vec![tag.clone()];
+            tags.extend(aliases.iter().cloned());
+            tags.extend(deprecated.iter().cloned());
+            Some(Arm::new(tags, quote! {}).with_guard(quote! { !#is_selected }))
+        }
+        Field::FlattenText {
+            or_attr: Some(attr),
+            name,
             ..
-        } => return_value(name, bind, ty, *default, &ele_name),
-        Field::Text { name, bind, ty, .. } => return_value(name, bind, ty, false, &ele_name),
+        } if partial => {
+            let is_selected = is_selected(name);
+            Some(Arm::new(vec![attr.clone()], quote! {}).with_guard(quote! { !#is_selected }))
+        }
+        _ => None,
     });
 
-    let read_attr_fields = fields.iter().filter_map(|field| match field {
+    let skip_child_fields = fields.iter().filter_map(|field| {
+        let (tags, name) = match field {
+            Field::Child {
+                wrapper: Some(wrapper),
+                name,
+                ..
+            } => (vec![wrapper.clone()], name),
+            Field::Child { tags, name, .. } | Field::KeyedChild { tags, name, .. } => {
+                (tags.clone(), name)
+            }
+            Field::Map { tag, name, .. } if tag.value() != "*" => (vec![tag.clone()], name),
+            Field::FlattenText {
+                tag,
+                deprecated,
+                name,
+                ..
+            } => {
+                let mut tags = vec![tag.clone()];
+                tags.extend(deprecated.iter().cloned());
+                (tags, name)
+            }
+            Field::Attribute {
+                or_child: Some(child),
+                name,
+                ..
+            } => (vec![child.clone()], name),
+            _ => return None,
+        };
+        if !partial {
+            return None;
+        }
+        let is_selected = is_selected(name);
+        Some(
+            Arm::new(
+                tags,
+                quote! { hard_xml::utils::skip_element(reader, __tag)?; },
+            )
+            .with_guard(quote! { !#is_selected }),
+        )
+    });
+
+    let read_attr_fields = fields.iter().flat_map(|field| match field {
         Field::Attribute {
             bind,
             ty,
             tag,
+            aliases,
+            deprecated,
             name,
+            saturate,
+            radix,
+            list,
             ..
-        } => Some(read_attrs(&tag, &bind, &name, &ty, &ele_name)),
+        } => read_attrs(
+            tag,
+            aliases,
+            deprecated,
+            bind,
+            name,
+            ty,
+            *saturate,
+            radix.as_ref(),
+            list.as_ref(),
+            &ele_name,
+        ),
+        _ => vec![],
+    });
+
+    let order = fields.iter().find_map(|field| match field {
+        Field::Order { bind, .. } => Some(bind),
         _ => None,
     });
 
+    // position of every child and flatten text field in declaration order,
+    // only used when children must be read in that order
+    let positions = fields
+        .iter()
+        .filter(|field| matches!(field, Field::Child { .. } | Field::FlattenText { .. }))
+        .enumerate()
+        .map(|(position, field)| (field.bind(), position))
+        .collect::<Vec<_>>();
+
+    let check_order = |bind: &Ident, name: &TokenStream| {
+        if !ordered {
+            return None;
+        }
+        let position = positions.iter().find(|(b, _)| *b == bind).unwrap().1;
+        Some(quote! {
+            if __position > #position {
+                reader.recover(
+                    XmlError::new(hard_xml::ErrorDetail::UnorderedField)
+                        .with_context(stringify!(#ele_name), Some(stringify!(#name))),
+                )?;
+            }
+            __position = #position;
+        })
+    };
+
     let read_child_fields = fields.iter().filter_map(|field| match field {
         Field::Child {
             bind,
             ty,
             tags,
             name,
+            wrapper,
+            on_duplicate,
             ..
-        } => Some(read_children(tags, bind, name, ty, &ele_name)),
+        } => Some(read_children(
+            tags,
+            wrapper.as_ref(),
+            *on_duplicate,
+            bind,
+            name,
+            ty,
+            &ele_name,
+            order,
+            check_order(bind, name),
+        )),
         _ => None,
     });
 
-    let read_flatten_text_fields = fields.iter().filter_map(|field| match field {
+    let read_keyed_child_fields = fields.iter().filter_map(|field| match field {
+        Field::KeyedChild {
+            bind,
+            key_ty,
+            value_ty,
+            tags,
+            key,
+            on_duplicate,
+            name,
+            ..
+        } => Some(read_keyed_children(
+            tags,
+            key,
+            *on_duplicate,
+            bind,
+            name,
+            key_ty,
+            value_ty,
+            &ele_name,
+        )),
+        _ => None,
+    });
+
+    let read_map_fields = fields.iter().filter_map(|field| match field {
+        Field::Map {
+            bind,
+            key_ty,
+            value_ty,
+            tag,
+            key,
+            value,
+            name,
+            ..
+        } if tag.value() != "*" => Some(read_map_entries(
+            tag, key, value, bind, name, key_ty, value_ty, &ele_name,
+        )),
+        _ => None,
+    });
+
+    let read_flatten_text_fields = fields.iter().flat_map(|field| match field {
         Field::FlattenText {
             bind,
             ty,
             tag,
+            deprecated,
             name,
+            trim,
+            saturate,
+            radix,
+            list,
+            on_duplicate,
             ..
-        } => Some(read_flatten_text(tag, bind, name, ty, &ele_name)),
-        _ => None,
+        } => read_flatten_text(
+            tag,
+            deprecated,
+            *on_duplicate,
+            bind,
+            name,
+            ty,
+            *trim,
+            *saturate,
+            radix.as_ref(),
+            list.as_ref(),
+            &ele_name,
+            order,
+            check_order(bind, name),
+        ),
+        _ => vec![],
     });
 
+    let init_position = if ordered {
+        Some(quote! { let mut __position = 0usize; })
+    } else {
+        None
+    };
+
     let read_text_fields = fields.iter().filter_map(|field| match field {
-        Field::Text { bind, ty, name, .. } => Some(read_text(&tag, bind, name, ty, &ele_name)),
+        Field::Text {
+            bind,
+            ty,
+            name,
+            trim,
+            saturate,
+            radix,
+            list,
+            ..
+        } => Some(read_text(
+            bind,
+            name,
+            ty,
+            *trim,
+            *saturate,
+            radix.as_ref(),
+            list.as_ref(),
+            &ele_name,
+        )),
+        _ => None,
+    });
+
+    let read_or_attr_fields = read_or_attr_fields(fields, &ele_name);
+
+    let read_or_child_fields = read_or_child_fields(fields, &ele_name);
+
+    // constant attributes are either checked or ignored
+    let const_seen = |index: usize| format_ident!("__const_{}", index);
+
+    let init_const_attrs = const_attrs
+        .iter()
+        .enumerate()
+        .filter(|(_, attr)| attr.validate)
+        .map(|(index, _)| {
+            let seen = const_seen(index);
+            quote! { let mut #seen = false; }
+        });
+
+    let read_const_attrs = const_attrs.iter().enumerate().map(|(index, attr)| {
+        let ConstAttr {
+            name,
+            value,
+            validate,
+        } = attr;
+        if !validate {
+            return Arm::new(vec![name.clone()], quote! {});
+        }
+        let seen = const_seen(index);
+        let body = quote! {
+            if __value != #value {
+                reader.recover(
+                    XmlError::new(hard_xml::ErrorDetail::UnexpectedValue {
+                        expected: #value.to_owned(),
+                        found: __value.into_owned(),
+                    })
+                    .with_context(stringify!(#ele_name), Some(#name)),
+                )?;
+            }
+            #seen = true;
+        };
+        Arm::new(vec![name.clone()], body)
+    });
+
+    let check_const_attrs = const_attrs
+        .iter()
+        .enumerate()
+        .filter(|(_, attr)| attr.validate)
+        .map(|(index, ConstAttr { name, .. })| {
+            let seen = const_seen(index);
+            let expected = format!("attribute {:?}", name.value());
+            quote! {
+                if !#seen {
+                    reader.recover(hard_xml::utils::missing_field(
+                        stringify!(#ele_name),
+                        #name,
+                        __end_tag,
+                        #expected,
+                    ))?;
+                }
+            }
+        });
+
+    let attrs = fields.iter().find_map(|field| match field {
+        Field::Attrs { bind, .. } => Some(bind),
+        _ => None,
+    });
+
+    // attributes the flattened fields don't read either are collected by
+    // the catch-all field, or skipped
+    let read_flatten_attrs = match (read_flatten_attrs(fields), attrs) {
+        (Some(read), Some(attrs)) => quote! {
+            #read
+            if let Some(__value) = __rest {
+                #attrs.push((key, __value));
+            }
+        },
+        (Some(read), None) => quote! {
+            #read
+            if __rest.is_some() {
+                hard_xml::log_skip_attribute!(#ele_name, key);
+            }
+        },
+        (None, Some(attrs)) => quote! {
+            #attrs.push((key, __value));
+        },
+        (None, None) => quote! {
+            hard_xml::log_skip_attribute!(#ele_name, key);
+        },
+    };
+
+    let read_flatten_children = read_flatten_children(fields);
+
+    // children the flattened fields don't read either are collected by the
+    // catch-all field, or skipped
+    let skip_child = quote! {
+        hard_xml::log_skip_element!(#ele_name, tag);
+        hard_xml::utils::skip_element(reader, tag)?;
+    };
+
+    let read_other_children = match fields.iter().find_map(|field| match field {
+        Field::Children { name, bind, ty, .. } => Some((name, bind, ty)),
+        _ => None,
+    }) {
+        Some((name, bind, ty)) => {
+            let read = quote! {
+                if let Some(__value) = hard_xml::utils::read_child::<#ty>(reader)? {
+                    #bind.push(__value);
+                }
+            };
+            if partial {
+                let is_selected = is_selected(name);
+                quote! {
+                    if #is_selected {
+                        #read
+                    } else {
+                        #skip_child
+                    }
+                }
+            } else {
+                read
+            }
+        }
+        None => skip_child,
+    };
+
+    // `<key>value</key>` entries are read from children no other field reads
+    let read_other_children = match fields.iter().find_map(|field| match field {
+        Field::Map {
+            bind,
+            key_ty,
+            value_ty,
+            tag,
+            name,
+            ..
+        } if tag.value() == "*" => Some((bind, name, key_ty, value_ty)),
+        _ => None,
+    }) {
+        Some((bind, name, key_ty, value_ty)) => {
+            let read = read_map_elements(bind, name, key_ty, value_ty, &ele_name);
+            if partial {
+                let is_selected = is_selected(name);
+                quote! {
+                    if #is_selected {
+                        #read
+                    } else {
+                        #read_other_children
+                    }
+                }
+            } else {
+                read
+            }
+        }
+        None => read_other_children,
+    };
+
+    let check_occurs = fields.iter().filter_map(|field| match field {
+        Field::Child {
+            bind, name, occurs, ..
+        }
+        | Field::FlattenText {
+            bind, name, occurs, ..
+        } if occurs.is_bounded() => {
+            let check_occurs = check_occurs(bind, name, occurs, &ele_name);
+            let check_occurs = quote! { #check_occurs.or_else(|err| reader.recover(err))?; };
+            if partial {
+                let is_selected = is_selected(name);
+                Some(quote! { if #is_selected { #check_occurs } })
+            } else {
+                Some(check_occurs)
+            }
+        }
         _ => None,
     });
 
+    let tag_name = fields.iter().find_map(|field| match field {
+        Field::TagName { bind, .. } => Some(bind),
+        _ => None,
+    });
+
+    // the element is closed with the name it was opened with, which can
+    // be one of the aliases
+    let read_element_start = quote! {
+        let __end_tag = reader
+            .read_till_element_start_of(&[#tag #( , #aliases )*])?
+            .ok_or_else(|| {
+                hard_xml::XmlError::new(hard_xml::ErrorDetail::UnexpectedEof)
+                    .with_position(reader.position())
+            })?;
+    };
+
+    let read_element_start = match tag_name {
+        Some(bind) => quote! {
+            #read_element_start
+            let #bind = from_cow_str(std::borrow::Cow::Borrowed(__end_tag))?;
+        },
+        None => read_element_start,
+    };
+
     let is_text_element = fields
         .iter()
         .any(|field| matches!(field, Field::Text { .. }));
 
     let return_fields = quote! {
+        #( #check_occurs )*
+
         let __res = #ele_name {
             #( #return_fields, )*
         };
@@ -89,6 +607,38 @@ pub fn read(tag: &LitStr, ele_name: TokenStream, fields: &[Field]) -> TokenStrea
         return Ok(__res);
     };
 
+    let attr_arms = read_const_attrs
+        .chain(skip_attr_fields)
+        .chain(read_attr_fields)
+        .chain(read_or_attr_fields)
+        .collect::<Vec<_>>();
+
+    let dispatch_attr = dispatch(
+        quote!(__key),
+        &attr_arms,
+        &format_ident!("key"),
+        read_flatten_attrs,
+    );
+
+    let child_arms = skip_child_fields
+        .chain(read_child_fields)
+        .chain(read_keyed_child_fields)
+        .chain(read_map_fields)
+        .chain(read_flatten_text_fields)
+        .chain(read_or_child_fields)
+        .collect::<Vec<_>>();
+
+    let dispatch_child = dispatch(
+        quote!(__tag),
+        &child_arms,
+        &format_ident!("tag"),
+        quote! {
+            #read_flatten_children
+
+            #read_other_children
+        },
+    );
+
     let read_content = if is_text_element {
         quote! {
             #( #read_text_fields )*
@@ -96,22 +646,16 @@ pub fn read(tag: &LitStr, ele_name: TokenStream, fields: &[Field]) -> TokenStrea
         }
     } else {
         quote! {
-            if let Token::ElementEnd { end: ElementEnd::Empty, .. } = reader.next().unwrap()? {
+            if !hard_xml::utils::has_content(reader)? {
                 #return_fields
             }
 
-            while let Some(__tag) = reader.find_element_start(Some(#tag))? {
-                match __tag {
-                    #( #read_child_fields, )*
-                    #( #read_flatten_text_fields, )*
-                    tag => {
-                        hard_xml::log_skip_element!(#ele_name, tag);
-                        // skip the start tag
-                        reader.next();
-                        reader.read_to_end(tag)?;
-                    },
-                }
-            }
+            #init_position
+
+            hard_xml::utils::read_children(reader, __end_tag, |reader, __tag| {
+                #dispatch_child
+                Ok(())
+            })?;
 
             #return_fields
         }
@@ -122,21 +666,223 @@ pub fn read(tag: &LitStr, ele_name: TokenStream, fields: &[Field]) -> TokenStrea
 
         #( #init_fields )*
 
-        reader.read_till_element_start(#tag)?;
+        #( #init_const_attrs )*
 
-        while let Some((__key, __value)) = reader.find_attribute()? {
-            match __key {
-                #( #read_attr_fields, )*
-                key => {
-                    hard_xml::log_skip_attribute!(#ele_name, key);
-                },
-            }
-        }
+        #read_element_start
+
+        hard_xml::utils::read_attributes(reader, |reader, __key, __value| {
+            #dispatch_attr
+            Ok(())
+        })?;
+
+        #( #check_const_attrs )*
 
         #read_content
     }
 }
 
+pub(super) fn attr_tags(fields: &[Field]) -> Vec<LitStr> {
+    fields
+        .iter()
+        .flat_map(|field| match field {
+            Field::Attribute {
+                tag,
+                aliases,
+                deprecated,
+                ..
+            } => {
+                let mut tags = vec![tag.clone()];
+                tags.extend(aliases.iter().cloned());
+                tags.extend(deprecated.iter().cloned());
+                tags
+            }
+            Field::FlattenText {
+                or_attr: Some(attr),
+                ..
+            } => vec![attr.clone()],
+            _ => vec![],
+        })
+        .collect()
+}
+
+pub(super) fn child_tags(fields: &[Field]) -> Vec<LitStr> {
+    fields
+        .iter()
+        .flat_map(|field| match field {
+            Field::Child {
+                wrapper: Some(wrapper),
+                ..
+            } => vec![wrapper.clone()],
+            Field::Child { tags, .. } | Field::KeyedChild { tags, .. } => tags.clone(),
+            Field::Map { tag, .. } if tag.value() != "*" => vec![tag.clone()],
+            Field::FlattenText {
+                tag, deprecated, ..
+            } => {
+                let mut tags = vec![tag.clone()];
+                tags.extend(deprecated.iter().cloned());
+                tags
+            }
+            Field::Attribute {
+                or_child: Some(child),
+                ..
+            } => vec![child.clone()],
+            _ => vec![],
+        })
+        .collect()
+}
+
+// reads the values of flatten text fields which are written as attributes
+pub(super) fn read_or_attr_fields(fields: &[Field], ele_name: &TokenStream) -> Vec<Arm> {
+    fields
+        .iter()
+        .flat_map(|field| match field {
+            Field::FlattenText {
+                bind,
+                ty,
+                or_attr: Some(attr),
+                name,
+                saturate,
+                radix,
+                list,
+                ..
+            } => read_attrs(
+                attr,
+                &[],
+                &[],
+                bind,
+                name,
+                ty,
+                *saturate,
+                radix.as_ref(),
+                list.as_ref(),
+                ele_name,
+            ),
+            _ => vec![],
+        })
+        .collect()
+}
+
+// reads the values of attribute fields which are written as children
+pub(super) fn read_or_child_fields(fields: &[Field], ele_name: &TokenStream) -> Vec<Arm> {
+    fields
+        .iter()
+        .flat_map(|field| match field {
+            Field::Attribute {
+                bind,
+                ty,
+                or_child: Some(child),
+                name,
+                saturate,
+                radix,
+                list,
+                ..
+            } => read_flatten_text(
+                child,
+                &[],
+                OnDuplicate::Last,
+                bind,
+                name,
+                ty,
+                false,
+                *saturate,
+                radix.as_ref(),
+                list.as_ref(),
+                ele_name,
+                None,
+                None,
+            ),
+            _ => vec![],
+        })
+        .collect()
+}
+
+// offers an attribute no field is read from to the flattened fields, and
+// leaves it in `__rest` if none of them reads it
+pub(super) fn read_flatten_attrs(fields: &[Field]) -> Option<TokenStream> {
+    let read = fields
+        .iter()
+        .filter_map(|field| match field {
+        Field::Flatten { bind, ty, .. } => Some(quote! {
+            let __rest = match __rest {
+                Some(__value) => {
+                    <#ty as hard_xml::XmlReadFlatten>::read_attribute(&mut #bind, reader, __key, __value)?
+                }
+                None => None,
+            };
+        }),
+        _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    if read.is_empty() {
+        return None;
+    }
+
+    Some(quote! {
+        let __rest = Some(__value);
+        #( #read )*
+    })
+}
+
+// offers a child no field is read from to the flattened fields, and
+// returns if one of them reads it
+fn read_flatten_children(fields: &[Field]) -> TokenStream {
+    let read = fields.iter().filter_map(|field| match field {
+        Field::Flatten { bind, ty, .. } => Some(quote! {
+            if <#ty as hard_xml::XmlReadFlatten>::read_child(&mut #bind, reader, __tag)? {
+                return Ok(());
+            }
+        }),
+        _ => None,
+    });
+
+    quote! { #( #read )* }
+}
+
+// binding of the position of the first occurrence of a child, which is
+// kept to report it again if the child can't occur twice
+pub(super) fn first_position(bind: &Ident) -> Ident {
+    format_ident!("{}_first", bind)
+}
+
+// reads a child which is kept once according to the `duplicate` policy
+fn read_once(
+    on_duplicate: OnDuplicate,
+    bind: &Ident,
+    name: &TokenStream,
+    ele_name: &TokenStream,
+    read: TokenStream,
+) -> TokenStream {
+    match on_duplicate {
+        OnDuplicate::Last => read,
+        OnDuplicate::First => quote! {
+            if #bind.is_some() {
+                hard_xml::log_skip_element!(#ele_name, __tag);
+                hard_xml::utils::skip_element(reader, __tag)?;
+            } else {
+                #read
+            }
+        },
+        // the duplicate is skipped if errors are collected
+        OnDuplicate::Error => {
+            let first = first_position(bind);
+            quote! {
+                if let Some(__first) = &#first {
+                    reader.recover(hard_xml::utils::duplicate_field(
+                        stringify!(#ele_name),
+                        stringify!(#name),
+                        Clone::clone(__first),
+                    ))?;
+                    hard_xml::utils::skip_element(reader, __tag)?;
+                } else {
+                    #first = Some(reader.position());
+                    #read
+                }
+            }
+        }
+    }
+}
+
 fn init_value(name: &Ident, ty: &Type) -> TokenStream {
     if ty.is_vec() {
         quote! { let mut #name = Vec::new(); }
@@ -145,149 +891,747 @@ fn init_value(name: &Ident, ty: &Type) -> TokenStream {
     }
 }
 
-fn return_value(
+// describes what the field is read from in errors, like `child <title>`,
+// the same way `FieldKind` does in the schema info
+fn expected(field: &Field) -> String {
+    let children = |tags: &[LitStr]| {
+        let tags = tags.iter().map(LitStr::value).collect::<Vec<_>>();
+        format!("child <{}>", tags.join("> or <"))
+    };
+    match field {
+        Field::Attribute {
+            tag,
+            or_child: Some(child),
+            ..
+        } => format!("attribute {:?} or child <{}>", tag.value(), child.value()),
+        Field::Attribute { tag, .. } => format!("attribute {:?}", tag.value()),
+        Field::Child {
+            wrapper: Some(wrapper),
+            ..
+        } => children(std::slice::from_ref(wrapper)),
+        Field::Child { tags, .. } => children(tags),
+        Field::FlattenText {
+            tag,
+            or_attr: Some(attr),
+            ..
+        } => format!("child <{}> or attribute {:?}", tag.value(), attr.value()),
+        Field::FlattenText { tag, .. } => children(std::slice::from_ref(tag)),
+        Field::Text { .. } => "text".to_owned(),
+        _ => String::new(),
+    }
+}
+
+// the error of `field` missing from the element whose tag is `tag`
+pub(super) fn missing_field(
+    ele_name: &TokenStream,
     name: &TokenStream,
+    tag: TokenStream,
+    field: &Field,
+) -> TokenStream {
+    let expected = expected(field);
+    quote! {
+        hard_xml::utils::missing_field(stringify!(#ele_name), stringify!(#name), #tag, #expected)
+    }
+}
+
+pub(super) fn return_value(
     bind: &Ident,
     ty: &Type,
     default: bool,
-    ele_name: &TokenStream,
+    default_fn: Option<&ExprPath>,
+    missing_field: TokenStream,
 ) -> TokenStream {
-    if ty.is_vec() || ty.is_option() {
-        quote! { #name: #bind }
+    if let Some(default_fn) = default_fn {
+        // closures let the returned value coerce to the lifetimes of the field
+        if ty.is_vec() {
+            quote! { if #bind.is_empty() { #default_fn() } else { #bind } }
+        } else if ty.is_option() {
+            quote! { #bind.or_else(|| #default_fn()) }
+        } else {
+            quote! { #bind.unwrap_or_else(|| #default_fn()) }
+        }
+    } else if ty.is_vec() || ty.is_option() {
+        quote! { #bind }
     } else if default {
-        quote! { #name: #bind.unwrap_or_default() }
+        quote! { #bind.unwrap_or_default() }
     } else {
         quote! {
-            #name: #bind.ok_or(XmlError::MissingField {
-                name: stringify!(#ele_name).to_owned(),
-                field: stringify!(#name).to_owned(),
-            })?
+            #bind.ok_or_else(|| #missing_field)?
         }
     }
 }
 
-fn read_attrs(
+pub(super) fn check_occurs(
+    bind: &Ident,
+    name: &TokenStream,
+    occurs: &Occurs,
+    ele_name: &TokenStream,
+) -> TokenStream {
+    let min = occurs.min.unwrap_or(0);
+    let max = match occurs.max {
+        Some(max) => quote! { Some(#max) },
+        None => quote! { None },
+    };
+
+    quote! {
+        hard_xml::utils::check_occurs(
+            stringify!(#ele_name),
+            stringify!(#name),
+            #bind.len(),
+            #min,
+            #max,
+        )
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn read_attrs(
     tag: &LitStr,
+    aliases: &[LitStr],
+    deprecated: &[LitStr],
     bind: &Ident,
     name: &TokenStream,
     ty: &Type,
+    saturate: bool,
+    radix: Option<&Radix>,
+    list: Option<&Separator>,
     ele_name: &TokenStream,
-) -> TokenStream {
-    let from_str = from_str(ty);
+) -> Vec<Arm> {
+    let from_str = from_str(ty, saturate, radix, name, ele_name);
 
-    if ty.is_vec() {
-        panic!("`attr` attribute doesn't support Vec.");
+    let read_value = if let Some(separator) = list {
+        let read_list = read_list(separator, &from_str);
+        quote! {
+            hard_xml::log_start_reading_field!(#ele_name, #name);
+
+            #bind.extend(#read_list);
+
+            hard_xml::log_finish_reading_field!(#ele_name, #name);
+        }
+    } else if ty.is_vec() {
+        // every occurrence of a repeated attribute is collected
+        let push = convert(&from_str, quote! { #bind.push(__value); }, None);
+        quote! {
+            hard_xml::log_start_reading_field!(#ele_name, #name);
+
+            #push
+
+            hard_xml::log_finish_reading_field!(#ele_name, #name);
+        }
     } else {
+        let assign = convert(&from_str, quote! { #bind = Some(__value); }, None);
         quote! {
-            #tag => {
-                hard_xml::log_start_reading_field!(#ele_name, #name);
+            hard_xml::log_start_reading_field!(#ele_name, #name);
 
-                #bind = Some(#from_str);
+            #assign
 
-                hard_xml::log_finish_reading_field!(#ele_name, #name);
+            hard_xml::log_finish_reading_field!(#ele_name, #name);
+        }
+    };
+
+    let read_deprecated =
+        read_deprecated(deprecated, quote! { __key }, name, ele_name, &read_value);
+
+    let mut tags = vec![tag.clone()];
+    tags.extend(aliases.iter().cloned());
+
+    let mut arms = vec![Arm::new(tags, read_value)];
+    arms.extend(read_deprecated);
+    arms
+}
+
+// splits `__value` into a Vec, values borrowed from the document are split
+// without copying
+fn read_list(separator: &Separator, from_str: &TokenStream) -> TokenStream {
+    let push = convert(from_str, quote! { __values.push(__value); }, None);
+    let split = match separator {
+        Separator::Whitespace => quote! { __list.split_ascii_whitespace() },
+        Separator::Str(separator) => quote! {
+            __list.split(#separator).map(str::trim).filter(|__value| !__value.is_empty())
+        },
+    };
+
+    quote! {{
+        let mut __values = Vec::new();
+        match __value {
+            std::borrow::Cow::Borrowed(__list) => {
+                for __value in #split {
+                    let __value = std::borrow::Cow::Borrowed(__value);
+                    #push
+                }
+            }
+            std::borrow::Cow::Owned(__list) => {
+                for __value in #split {
+                    let __value: std::borrow::Cow<str> =
+                        std::borrow::Cow::Owned(__value.to_owned());
+                    #push
+                }
             }
         }
+        __values
+    }}
+}
+
+// reads the field from one of its deprecated names after reporting it, the
+// arm is emitted after the others so it never shadows the current name
+fn read_deprecated(
+    deprecated: &[LitStr],
+    found: TokenStream,
+    name: &TokenStream,
+    ele_name: &TokenStream,
+    read_value: &TokenStream,
+) -> Option<Arm> {
+    if deprecated.is_empty() {
+        return None;
     }
+
+    Some(Arm::new(
+        deprecated.to_vec(),
+        quote! {
+            reader.report_deprecated(stringify!(#ele_name), stringify!(#name), #found);
+
+            #read_value
+        },
+    ))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn read_text(
-    tag: &LitStr,
     bind: &Ident,
     name: &TokenStream,
     ty: &Type,
+    trim: bool,
+    saturate: bool,
+    radix: Option<&Radix>,
+    list: Option<&Separator>,
     ele_name: &TokenStream,
 ) -> TokenStream {
-    let from_str = from_str(ty);
+    let from_str = from_str(ty, saturate, radix, name, ele_name);
+    let trim_value = trim.then(|| quote! { let __value = hard_xml::utils::trim_text(__value); });
 
-    if ty.is_vec() {
+    if let Some(separator) = list {
+        let read_list = read_list(separator, &from_str);
+        quote! {
+            hard_xml::log_start_reading_field!(#ele_name, #name);
+
+            let __value = reader.read_text(__end_tag)?;
+
+            #trim_value
+            #bind = #read_list;
+
+            hard_xml::log_finish_reading_field!(#ele_name, #name);
+        }
+    } else if ty.is_vec() {
         panic!("`text` attribute doesn't support Vec.");
+    } else if ty.is_option() {
+        // empty text is read as `None`
+        let assign = convert(
+            &from_str,
+            quote! { #bind = Some(__value); },
+            Some(quote! { #bind = None; }),
+        );
+        quote! {
+            hard_xml::log_start_reading_field!(#ele_name, #name);
+
+            let __value = reader.read_text(__end_tag)?;
+
+            #trim_value
+            if __value.is_empty() {
+                #bind = None;
+            } else {
+                #assign
+            }
+
+            hard_xml::log_finish_reading_field!(#ele_name, #name);
+        }
     } else {
+        let assign = convert(
+            &from_str,
+            quote! { #bind = Some(__value); },
+            Some(quote! { #bind = None; }),
+        );
         quote! {
             hard_xml::log_start_reading_field!(#ele_name, #name);
 
-            let __value = reader.read_text(#tag)?;
-            #bind = Some(#from_str);
+            let __value = reader.read_text(__end_tag)?;
+
+            #trim_value
+            #assign
 
             hard_xml::log_finish_reading_field!(#ele_name, #name);
         }
     }
 }
 
-fn read_children(
+#[allow(clippy::too_many_arguments)]
+pub(super) fn read_children(
     tags: &[LitStr],
+    wrapper: Option<&LitStr>,
+    on_duplicate: OnDuplicate,
     bind: &Ident,
     name: &TokenStream,
     ty: &Type,
     ele_name: &TokenStream,
-) -> TokenStream {
+    order: Option<&Ident>,
+    check_order: Option<TokenStream>,
+) -> Arm {
+    let push_order = order.map(|order| quote! { #order.push(stringify!(#name)); });
+
     let from_reader = match &ty {
         Type::VecT(ty) => quote! {
-            #bind.push(<#ty as hard_xml::XmlRead>::from_reader(reader)?);
-        },
-        Type::OptionT(ty) | Type::T(ty) => quote! {
-            #bind = Some(<#ty as hard_xml::XmlRead>::from_reader(reader)?);
+            if let Some(__value) = hard_xml::utils::read_child::<#ty>(reader)? {
+                #bind.push(__value);
+            }
         },
+        Type::OptionT(ty) | Type::T(ty) => {
+            let read = quote! {
+                if let Some(__value) = hard_xml::utils::read_child::<#ty>(reader)? {
+                    #bind = Some(__value);
+                }
+            };
+            read_once(on_duplicate, bind, name, ele_name, read)
+        }
         _ => panic!("`child` attribute only supports Vec<T>, Option<T> and T."),
     };
 
-    quote! {
-        #( #tags )|* => {
+    if let Some(wrapper) = wrapper {
+        // `Option<Vec<T>>` is `Some` as soon as the wrapper is present
+        let (init_values, from_reader) = match ty.option_vec() {
+            Some(ty) => (
+                Some(quote! { let __values = #bind.get_or_insert_with(Vec::new); }),
+                quote! {
+                    if let Some(__value) = hard_xml::utils::read_child::<#ty>(reader)? {
+                        __values.push(__value);
+                    }
+                },
+            ),
+            None => (None, from_reader),
+        };
+
+        return Arm::new(
+            vec![wrapper.clone()],
+            quote! {
+                #check_order
+
+                hard_xml::log_start_reading_field!(#ele_name, #name);
+
+                // skip the start tag and attributes of the wrapper
+                let __has_content = hard_xml::utils::skip_start_tag(reader)?;
+
+                #init_values
+
+                if __has_content {
+                    while let Some(__tag) = reader.find_element_start(Some(#wrapper))? {
+                        match reader.resolve_name(__tag, &[ #( #tags ),* ]) {
+                            #( #tags )|* => {
+                                #from_reader
+                            }
+                            tag => {
+                                hard_xml::log_skip_element!(#ele_name, tag);
+                                hard_xml::utils::skip_element(reader, tag)?;
+                            }
+                        }
+                    }
+                }
+
+                #push_order
+
+                hard_xml::log_finish_reading_field!(#ele_name, #name);
+            },
+        );
+    }
+
+    Arm::new(
+        tags.to_vec(),
+        quote! {
+            #check_order
+
             hard_xml::log_start_reading_field!(#ele_name, #name);
 
             #from_reader
+            #push_order
 
             hard_xml::log_finish_reading_field!(#ele_name, #name);
-        }
-    }
+        },
+    )
 }
 
-fn read_flatten_text(
+#[allow(clippy::too_many_arguments)]
+pub(super) fn read_flatten_text(
     tag: &LitStr,
+    deprecated: &[LitStr],
+    on_duplicate: OnDuplicate,
     bind: &Ident,
     name: &TokenStream,
     ty: &Type,
+    trim: bool,
+    saturate: bool,
+    radix: Option<&Radix>,
+    list: Option<&Separator>,
     ele_name: &TokenStream,
-) -> TokenStream {
-    let from_str = from_str(ty);
+    order: Option<&Ident>,
+    check_order: Option<TokenStream>,
+) -> Vec<Arm> {
+    let push_order = order.map(|order| quote! { #order.push(stringify!(#name)); });
 
-    let read_text = if ty.is_vec() {
+    let from_str = from_str(ty, saturate, radix, name, ele_name);
+    let trim_value = trim.then(|| quote! { let __value = hard_xml::utils::trim_text(__value); });
+
+    let read_text = if let Some(separator) = list {
+        let read_list = read_list(separator, &from_str);
         quote! {
-            let __value = reader.read_text(#tag)?;
-            #bind.push(#from_str);
+            let __value = hard_xml::utils::read_element_text(reader, __tag)?;
+            #trim_value
+            #bind.extend(#read_list);
+        }
+    } else if ty.is_vec() {
+        let push = convert(&from_str, quote! { #bind.push(__value); }, None);
+        quote! {
+            let __value = hard_xml::utils::read_element_text(reader, __tag)?;
+            #trim_value
+            #push
         }
     } else {
+        let assign = convert(&from_str, quote! { #bind = Some(__value); }, None);
+        let read = quote! {
+            let __value = hard_xml::utils::read_element_text(reader, __tag)?;
+            #trim_value
+            #assign
+        };
+        read_once(on_duplicate, bind, name, ele_name, read)
+    };
+
+    let read_value = quote! {
+        #check_order
+
+        hard_xml::log_start_reading_field!(#ele_name, #name);
+
+        #read_text
+        #push_order
+
+        hard_xml::log_finish_reading_field!(#ele_name, #name);
+    };
+
+    let read_deprecated =
+        read_deprecated(deprecated, quote! { __tag }, name, ele_name, &read_value);
+
+    let mut arms = vec![Arm::new(vec![tag.clone()], read_value)];
+    arms.extend(read_deprecated);
+    arms
+}
+
+// the key is read from the attributes of the child before rewinding, so
+// the child is read as usual
+#[allow(clippy::too_many_arguments)]
+fn read_keyed_children(
+    tags: &[LitStr],
+    key: &LitStr,
+    on_duplicate: OnDuplicate,
+    bind: &Ident,
+    name: &TokenStream,
+    key_ty: &Type,
+    value_ty: &syn::Type,
+    ele_name: &TokenStream,
+) -> Arm {
+    let key_from_str = from_str(key_ty, false, None, name, ele_name);
+    let expected_key = format!("attribute {:?}", key.value());
+
+    let insert = match on_duplicate {
+        OnDuplicate::Error => quote! {
+            if hard_xml::XmlMap::contains_key(&#bind, &__entry_key) {
+                return Err(
+                    XmlError::new(hard_xml::ErrorDetail::DuplicateField { first: None })
+                        .with_context(stringify!(#ele_name), Some(stringify!(#name))),
+                );
+            }
+            hard_xml::XmlMap::insert(&mut #bind, __entry_key, __entry_value);
+        },
+        OnDuplicate::First => quote! {
+            if !hard_xml::XmlMap::contains_key(&#bind, &__entry_key) {
+                hard_xml::XmlMap::insert(&mut #bind, __entry_key, __entry_value);
+            }
+        },
+        OnDuplicate::Last => quote! {
+            hard_xml::XmlMap::insert(&mut #bind, __entry_key, __entry_value);
+        },
+    };
+
+    Arm::new(
+        tags.to_vec(),
         quote! {
-            let __value = reader.read_text(#tag)?;
-            #bind = Some(#from_str);
+        hard_xml::log_start_reading_field!(#ele_name, #name);
+
+        let __checkpoint = reader.checkpoint();
+
+        // skip the start tag
+        reader.next();
+
+        let mut __entry_key = None;
+
+        while let Some((__key, __value)) = reader.find_attribute()? {
+            if reader.name_matches(#key, __key) {
+                __entry_key = Some(#key_from_str);
+            }
         }
+
+        reader.rewind(__checkpoint);
+
+        let __entry_value = <#value_ty as hard_xml::XmlRead>::from_reader(reader)?;
+        let __entry_key = __entry_key.ok_or_else(|| {
+            hard_xml::utils::missing_field(
+                stringify!(#ele_name),
+                stringify!(#name),
+                __tag,
+                #expected_key,
+            )
+        })?;
+        #insert
+
+        hard_xml::log_finish_reading_field!(#ele_name, #name);
+        },
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn read_map_entries(
+    tag: &LitStr,
+    key: &LitStr,
+    value: &LitStr,
+    bind: &Ident,
+    name: &TokenStream,
+    key_ty: &Type,
+    value_ty: &Type,
+    ele_name: &TokenStream,
+) -> Arm {
+    let key_from_str = from_str(key_ty, false, None, name, ele_name);
+    let value_from_str = from_str(value_ty, false, None, name, ele_name);
+    let expected_key = format!("attribute {:?}", key.value());
+    let expected_value = format!("attribute {:?}", value.value());
+
+    Arm::new(
+        vec![tag.clone()],
+        quote! {
+        hard_xml::log_start_reading_field!(#ele_name, #name);
+
+        // skip the start tag
+        reader.next();
+
+        let mut __entry_key = None;
+        let mut __entry_value = None;
+
+        while let Some((__key, __value)) = reader.find_attribute()? {
+            match reader.resolve_name(__key, &[#key, #value]) {
+                #key => __entry_key = Some(#key_from_str),
+                #value => __entry_value = Some(#value_from_str),
+                key => {
+                    hard_xml::log_skip_attribute!(#ele_name, key);
+                }
+            }
+        }
+
+        reader.read_to_end(__tag)?;
+
+        let __entry_key = __entry_key.ok_or_else(|| {
+            hard_xml::utils::missing_field(
+                stringify!(#ele_name),
+                stringify!(#name),
+                __tag,
+                #expected_key,
+            )
+        })?;
+        let __entry_value = __entry_value.ok_or_else(|| {
+            hard_xml::utils::missing_field(
+                stringify!(#ele_name),
+                stringify!(#name),
+                __tag,
+                #expected_value,
+            )
+        })?;
+        #bind.push((__entry_key, __entry_value));
+
+        hard_xml::log_finish_reading_field!(#ele_name, #name);
+        },
+    )
+}
+
+fn read_map_elements(
+    bind: &Ident,
+    name: &TokenStream,
+    key_ty: &Type,
+    value_ty: &Type,
+    ele_name: &TokenStream,
+) -> TokenStream {
+    let key_from_str = {
+        let from_str = from_str(key_ty, false, None, name, ele_name);
+        quote! {{
+            let __value = std::borrow::Cow::Borrowed(tag);
+            #from_str
+        }}
     };
+    let value_from_str = from_str(value_ty, false, None, name, ele_name);
 
     quote! {
-        #tag => {
-            // skip element start
-            reader.next();
+        hard_xml::log_start_reading_field!(#ele_name, #name);
 
-            hard_xml::log_start_reading_field!(#ele_name, #name);
+        let __entry_key = #key_from_str;
+        let __value = hard_xml::utils::read_element_text(reader, tag)?;
+        #bind.push((__entry_key, #value_from_str));
 
-            #read_text
+        hard_xml::log_finish_reading_field!(#ele_name, #name);
+    }
+}
 
-            hard_xml::log_finish_reading_field!(#ele_name, #name);
+// runs `read` with `__value` converted by `from_str`, or records the error
+// and runs `recovered` instead if errors are collected
+fn convert(
+    from_str: &TokenStream,
+    read: TokenStream,
+    recovered: Option<TokenStream>,
+) -> TokenStream {
+    quote! {
+        #[allow(clippy::redundant_closure_call, clippy::needless_question_mark)]
+        match (|| -> hard_xml::XmlResult<_> { Ok(#from_str) })() {
+            Ok(__value) => {
+                #read
+            }
+            Err(err) => {
+                reader.recover(err)?;
+                #recovered
+            }
         }
     }
 }
 
-fn from_str(ty: &Type) -> TokenStream {
+fn from_str(
+    ty: &Type,
+    saturate: bool,
+    radix: Option<&Radix>,
+    name: &TokenStream,
+    ele_name: &TokenStream,
+) -> TokenStream {
+    // names the field, the value and the type it's converted into
+    let map_err = |type_name: String| {
+        quote! {
+            map_err(|err| hard_xml::utils::from_str_error(
+                stringify!(#ele_name),
+                stringify!(#name),
+                &__value,
+                #type_name,
+                err,
+            ))
+        }
+    };
+
     match &ty {
-        Type::CowStr | Type::OptionCowStr | Type::VecCowStr => quote! { __value },
-        Type::Bool | Type::OptionBool | Type::VecBool => quote! {
-            match &*__value {
-                "t" | "true" | "y" | "yes" | "on" | "1" => true,
-                "f" | "false" | "n" | "no" | "off" | "0" => false,
-                _ => <bool as std::str::FromStr>::from_str(&__value).map_err(|e| XmlError::FromStr(e.into()))?
+        Type::T(ty) | Type::OptionT(ty) | Type::VecT(ty) if radix.is_some() => {
+            let Radix { radix, prefix } = radix.unwrap();
+            let prefix = prefix.as_ref().map_or(String::new(), LitStr::value);
+            let map_err = map_err(type_name(ty));
+            quote! {
+                hard_xml::utils::parse_radix::<#ty>(&__value, #radix, #prefix).#map_err?
             }
+        }
+        Type::T(ty) | Type::OptionT(ty) | Type::VecT(ty) if saturate => {
+            let map_err = map_err(type_name(ty));
+            quote! {
+                hard_xml::utils::parse_saturating::<#ty>(&__value).#map_err?
+            }
+        }
+        // converted into whichever of `Cow<str>` and `&str` the field is
+        Type::CowStr | Type::OptionCowStr | Type::VecCowStr => quote! {
+            from_cow_str(__value)?
         },
-        Type::T(ty) | Type::OptionT(ty) | Type::VecT(ty) => quote! {
-            <#ty as std::str::FromStr>::from_str(&__value).map_err(|e| XmlError::FromStr(e.into()))?
-        },
+        Type::Bool(format) | Type::OptionBool(format) | Type::VecBool(format) => {
+            let value = match format.parse {
+                BoolParse::Default => quote! { &*__value },
+                BoolParse::Strict => quote! { __value.trim() },
+                BoolParse::Lenient => quote! { &*__value.trim().to_ascii_lowercase() },
+            };
+            let (mut true_forms, mut false_forms) = match format.parse {
+                BoolParse::Strict => (vec!["true", "1"], vec!["false", "0"]),
+                _ => (
+                    vec!["t", "true", "y", "yes", "on", "1"],
+                    vec!["f", "false", "n", "no", "off", "0"],
+                ),
+            };
+            let mut true_forms: Vec<String> = true_forms.drain(..).map(String::from).collect();
+            let mut false_forms: Vec<String> = false_forms.drain(..).map(String::from).collect();
+            // the forms written are read as well, and take precedence
+            let custom_form = |lit: &LitStr| match format.parse {
+                BoolParse::Lenient => lit.value().to_ascii_lowercase(),
+                _ => lit.value(),
+            };
+            if let Some(form) = format.true_str.as_ref().map(custom_form) {
+                false_forms.retain(|f| *f != form);
+                if !true_forms.contains(&form) {
+                    true_forms.push(form);
+                }
+            }
+            if let Some(form) = format.false_str.as_ref().map(custom_form) {
+                true_forms.retain(|f| *f != form);
+                if !false_forms.contains(&form) {
+                    false_forms.push(form);
+                }
+            }
+            let map_err = map_err("bool".to_owned());
+            quote! {
+                match #value {
+                    #( #true_forms )|* => true,
+                    #( #false_forms )|* => false,
+                    _ => <bool as std::str::FromStr>::from_str(&__value).#map_err?
+                }
+            }
+        }
+        // `XmlValue` is used instead of `FromStr` if it's implemented
+        Type::T(ty) | Type::OptionT(ty) | Type::VecT(ty) => {
+            let map_err = map_err(type_name(ty));
+            quote! {{
+                use hard_xml::utils::{ParseFromStr as _, ParseXmlValue as _};
+                (&&hard_xml::utils::Parse::<#ty>::new())
+                    .parse_value(&__value)
+                    .#map_err?
+            }}
+        }
+        Type::CowBytes(encoding) | Type::OptionCowBytes(encoding) | Type::VecCowBytes(encoding) => {
+            match encoding {
+                // converted into whichever of `Cow<[u8]>`, `Vec<u8>` and
+                // `[u8; N]` the field is
+                // the value is moved into the bytes, so it isn't named
+                Encoding::Utf8 => quote! {
+                    from_bytes(hard_xml::utils::bytes_from_utf8(__value)).map_err(|err| {
+                        hard_xml::utils::from_str_error(
+                            stringify!(#ele_name),
+                            stringify!(#name),
+                            "",
+                            "",
+                            err,
+                        )
+                    })?
+                },
+                Encoding::Hex => {
+                    let map_err = map_err("hexBinary".to_owned());
+                    quote! {
+                        from_bytes(std::borrow::Cow::Owned(
+                            hard_xml::utils::hex_decode(&__value).#map_err?,
+                        ))
+                        .#map_err?
+                    }
+                }
+                Encoding::Base64 => {
+                    let map_err = map_err("base64Binary".to_owned());
+                    quote! {
+                        from_bytes(std::borrow::Cow::Owned(
+                            hard_xml::utils::base64_decode(&__value).#map_err?,
+                        ))
+                        .#map_err?
+                    }
+                }
+            }
+        }
     }
 }
+
+// the type as it's written in the field, like `std::net::IpAddr`
+fn type_name(ty: &syn::Type) -> String {
+    quote!(#ty).to_string().replace(' ', "")
+}
@@ -1,21 +1,52 @@
 use proc_macro2::TokenStream;
 use quote::quote;
+use syn::LitStr;
 
 use crate::types::Type;
 
-pub fn read(ty: &Type, ele_name: TokenStream) -> TokenStream {
-    let ty = match ty {
-        Type::T(ty) => ty,
-        _ => panic!("hard-xml only supports newtype_struct and newtype_enum for now."),
-    };
+pub fn read(tags: &[LitStr], ty: &Type, ele_name: TokenStream) -> TokenStream {
+    match ty {
+        Type::T(ty) => quote! {
+            hard_xml::log_start_reading!(#ele_name);
 
-    quote! {
-        hard_xml::log_start_reading!(#ele_name);
+            let res = <#ty as XmlRead>::from_reader(reader)?;
 
-        let res = <#ty as XmlRead>::from_reader(reader)?;
+            hard_xml::log_finish_reading!(#ele_name);
 
-        hard_xml::log_finish_reading!(#ele_name);
+            return Ok(#ele_name(res));
+        },
+        Type::VecT(_) if tags.is_empty() => panic!("Missing `tag` attribute."),
+        // the tag is the one of the element wrapping the items
+        Type::VecT(ty) => quote! {
+            hard_xml::log_start_reading!(#ele_name);
 
-        return Ok(#ele_name(res));
+            let __end_tag = reader
+                .read_till_element_start_of(&[ #( #tags ),* ])?
+                .ok_or_else(|| {
+                    hard_xml::XmlError::new(hard_xml::ErrorDetail::UnexpectedEof)
+                        .with_position(reader.position())
+                })?;
+
+            hard_xml::utils::read_attributes(reader, |_, __key, _| {
+                hard_xml::log_skip_attribute!(#ele_name, __key);
+                Ok(())
+            })?;
+
+            let mut res = Vec::new();
+
+            if hard_xml::utils::has_content(reader)? {
+                hard_xml::utils::read_children(reader, __end_tag, |reader, _| {
+                    if let Some(__value) = hard_xml::utils::read_child::<#ty>(reader)? {
+                        res.push(__value);
+                    }
+                    Ok(())
+                })?;
+            }
+
+            hard_xml::log_finish_reading!(#ele_name);
+
+            return Ok(#ele_name(res));
+        },
+        _ => panic!("hard-xml only supports newtype_struct and newtype_enum of T or Vec<T>."),
     }
 }
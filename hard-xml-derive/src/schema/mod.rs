@@ -0,0 +1,349 @@
+use crate::types::{Element, Field, Fields, Occurs, Type};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Ident, LitStr};
+
+pub fn impl_schema(element: Element) -> TokenStream {
+    match element {
+        Element::Enum { variants, .. } => {
+            let elements = variants.iter().map(|variant| match variant {
+                Fields::Named {
+                    tag,
+                    aliases,
+                    name,
+                    fields,
+                    ordered,
+                    doc,
+                    ..
+                } => {
+                    let element = element_info(tag, aliases, name, fields, *ordered, doc);
+                    quote! { __elements.push(#element); }
+                }
+                Fields::Newtype { ty, .. } => {
+                    let ty = newtype_ty(ty);
+                    quote! {
+                        __elements.extend(<#ty as hard_xml::XmlSchemaInfo>::elements());
+                    }
+                }
+            });
+
+            quote! {
+                let mut __elements = Vec::new();
+                #( #elements )*
+                __elements
+            }
+        }
+
+        Element::Struct { fields, .. } => match fields {
+            Fields::Named {
+                tag,
+                aliases,
+                name,
+                fields,
+                ordered,
+                doc,
+                ..
+            } => {
+                let element = element_info(&tag, &aliases, &name, &fields, ordered, &doc);
+                quote! { vec![#element] }
+            }
+            Fields::Newtype { ty, .. } => {
+                let ty = newtype_ty(&ty);
+                quote! { <#ty as hard_xml::XmlSchemaInfo>::elements() }
+            }
+        },
+
+        Element::TextEnum {
+            name,
+            tag: Some(tag),
+            doc,
+            ..
+        } => {
+            let doc = quote_doc(&doc);
+            quote! {
+                vec![hard_xml::schema::ElementInfo {
+                    name: stringify!(#name),
+                    tag: #tag,
+                    aliases: Vec::new(),
+                    fields: vec![hard_xml::schema::FieldInfo {
+                        name: stringify!(#name),
+                        kind: hard_xml::schema::FieldKind::Text,
+                        optional: false,
+                        multiple: false,
+                        min_occurs: 1,
+                        max_occurs: Some(1),
+                        doc: None,
+                    }],
+                    ordered: false,
+                    doc: #doc,
+                }]
+            }
+        }
+
+        Element::TextEnum { tag: None, .. } => quote! { Vec::new() },
+
+        // the fields are listed by the elements they're flattened into
+        Element::Flatten {
+            name, fields, doc, ..
+        } => {
+            let element = element_info(
+                &LitStr::new("", name.span()),
+                &[],
+                &name,
+                &fields,
+                false,
+                &doc,
+            );
+            quote! { vec![#element] }
+        }
+    }
+}
+
+fn newtype_ty(ty: &Type) -> &syn::Type {
+    match ty {
+        Type::T(ty) => ty,
+        _ => panic!("hard-xml only supports newtype_struct and newtype_enum for now."),
+    }
+}
+
+fn element_info(
+    tag: &LitStr,
+    aliases: &[LitStr],
+    name: &Ident,
+    fields: &[Field],
+    ordered: bool,
+    doc: &Option<String>,
+) -> TokenStream {
+    let has_flatten = fields
+        .iter()
+        .any(|field| matches!(field, Field::Flatten { .. }));
+
+    // flattened fields are listed in place of the field they're in
+    let fields = if has_flatten {
+        let push = fields.iter().filter_map(|field| match field {
+            Field::Flatten { ty, .. } => Some(quote! {
+                __fields.extend(
+                    <#ty as hard_xml::XmlSchemaInfo>::elements()
+                        .into_iter()
+                        .flat_map(|element| element.fields),
+                );
+            }),
+            field => field_info(field).map(|info| quote! { __fields.push(#info); }),
+        });
+        quote! {{
+            let mut __fields = Vec::new();
+            #( #push )*
+            __fields
+        }}
+    } else {
+        let fields = fields.iter().filter_map(field_info);
+        quote! { vec![ #( #fields ),* ] }
+    };
+    let doc = quote_doc(doc);
+
+    quote! {
+        hard_xml::schema::ElementInfo {
+            name: stringify!(#name),
+            tag: #tag,
+            aliases: vec![ #( #aliases ),* ],
+            fields: #fields,
+            ordered: #ordered,
+            doc: #doc,
+        }
+    }
+}
+
+fn field_info(field: &Field) -> Option<TokenStream> {
+    let occurs = match field {
+        Field::Child { occurs, .. } | Field::FlattenText { occurs, .. } => *occurs,
+        _ => Occurs::default(),
+    };
+
+    let (name, kind, ty, optional, doc) = match field {
+        Field::Attribute {
+            name,
+            ty,
+            tag,
+            default,
+            doc,
+            ..
+        } => (
+            name,
+            quote! { hard_xml::schema::FieldKind::Attribute { name: #tag } },
+            ty,
+            *default,
+            doc,
+        ),
+        Field::Child {
+            name,
+            ty,
+            tags,
+            default,
+            wrapper,
+            doc,
+            ..
+        } => {
+            let option_vec = ty.option_vec();
+            let child_ty = match (ty, &option_vec) {
+                (_, Some(ty)) => ty,
+                (Type::VecT(ty) | Type::OptionT(ty) | Type::T(ty), None) => ty,
+                _ => panic!("`child` attribute only supports Vec<T>, Option<T> and T."),
+            };
+            let wrapper = match wrapper {
+                Some(wrapper) => quote! { Some(#wrapper) },
+                None => quote! { None },
+            };
+            (
+                name,
+                quote! {
+                    hard_xml::schema::FieldKind::Child {
+                        tags: vec![ #( #tags ),* ],
+                        elements: <#child_ty as hard_xml::XmlSchemaInfo>::elements,
+                        wrapper: #wrapper,
+                    }
+                },
+                ty,
+                *default,
+                doc,
+            )
+        }
+        // missing text is read as empty text
+        Field::Text { name, ty, doc, .. } => (
+            name,
+            quote! { hard_xml::schema::FieldKind::Text },
+            ty,
+            true,
+            doc,
+        ),
+        Field::FlattenText {
+            name,
+            ty,
+            tag,
+            default,
+            doc,
+            ..
+        } => (
+            name,
+            quote! { hard_xml::schema::FieldKind::FlattenText { tag: #tag } },
+            ty,
+            *default,
+            doc,
+        ),
+        Field::KeyedChild {
+            name,
+            value_ty,
+            tags,
+            doc,
+            ..
+        } => {
+            let doc = quote_doc(doc);
+            return Some(quote! {
+                hard_xml::schema::FieldInfo {
+                    name: stringify!(#name),
+                    kind: hard_xml::schema::FieldKind::Child {
+                        tags: vec![ #( #tags ),* ],
+                        elements: <#value_ty as hard_xml::XmlSchemaInfo>::elements,
+                        wrapper: None,
+                    },
+                    optional: true,
+                    multiple: true,
+                    min_occurs: 0,
+                    max_occurs: None,
+                    doc: #doc,
+                }
+            });
+        }
+        // entries are optional and repeated whatever the collection is
+        Field::Map {
+            name,
+            tag,
+            key,
+            value,
+            doc,
+            ..
+        } => {
+            let doc = quote_doc(doc);
+            return Some(quote! {
+                hard_xml::schema::FieldInfo {
+                    name: stringify!(#name),
+                    kind: hard_xml::schema::FieldKind::Map {
+                        tag: #tag,
+                        key: #key,
+                        value: #value,
+                    },
+                    optional: true,
+                    multiple: true,
+                    min_occurs: 0,
+                    max_occurs: None,
+                    doc: #doc,
+                }
+            });
+        }
+        // any number of catch-all attributes or children can occur
+        Field::Attrs { name, doc, .. } | Field::Children { name, doc, .. } => {
+            let kind = match field {
+                Field::Attrs { .. } => quote! { hard_xml::schema::FieldKind::AnyAttributes },
+                _ => quote! { hard_xml::schema::FieldKind::AnyChildren },
+            };
+            let doc = quote_doc(doc);
+            return Some(quote! {
+                hard_xml::schema::FieldInfo {
+                    name: stringify!(#name),
+                    kind: #kind,
+                    optional: true,
+                    multiple: true,
+                    min_occurs: 0,
+                    max_occurs: None,
+                    doc: #doc,
+                }
+            });
+        }
+        Field::Order { .. }
+        | Field::TagName { .. }
+        | Field::Skip { .. }
+        | Field::Flatten { .. } => return None,
+    };
+
+    // the values of a list occur once
+    let is_list = matches!(
+        field,
+        Field::Attribute { list: Some(_), .. }
+            | Field::Text { list: Some(_), .. }
+            | Field::FlattenText { list: Some(_), .. }
+    );
+
+    let optional = optional || ty.is_option() || ty.is_vec();
+    let multiple = (ty.is_vec() || ty.option_vec().is_some()) && !is_list;
+
+    let min_occurs = match occurs.min {
+        Some(min) => min,
+        None if optional => 0,
+        None => 1,
+    };
+    let max_occurs = match occurs.max {
+        Some(max) => quote! { Some(#max) },
+        None if multiple => quote! { None },
+        None => quote! { Some(1) },
+    };
+    let doc = quote_doc(doc);
+
+    Some(quote! {
+        hard_xml::schema::FieldInfo {
+            name: stringify!(#name),
+            kind: #kind,
+            optional: #optional,
+            multiple: #multiple,
+            min_occurs: #min_occurs,
+            max_occurs: #max_occurs,
+            doc: #doc,
+        }
+    })
+}
+
+fn quote_doc(doc: &Option<String>) -> TokenStream {
+    match doc {
+        Some(doc) => quote! { Some(#doc) },
+        None => quote! { None },
+    }
+}
@@ -1,19 +1,116 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream, TokenTree};
 use quote::{format_ident, quote};
+use syn::spanned::Spanned;
 use syn::{Lit::*, Meta::*, *};
 
-use crate::utils::elide_type_lifetimes;
+use crate::utils::{elide_type_lifetimes, Errors, RenameRule};
 
+// returns the error at `span` from the enclosing function
+macro_rules! bail {
+    ($span:expr, $($arg:tt)+) => {
+        return Err(Error::new($span, format_args!($($arg)+)))
+    };
+}
+
+#[allow(clippy::large_enum_variant)]
 pub enum Element {
-    Struct { name: Ident, fields: Fields },
-    Enum { name: Ident, variants: Vec<Fields> },
+    Struct {
+        name: Ident,
+        fields: Fields,
+        partial: bool,
+    },
+    Enum {
+        name: Ident,
+        variants: Vec<Fields>,
+        untagged: bool,
+        discriminator: Option<Discriminator>,
+        other: Option<Other>,
+    },
+    /// Enum of unit variants, chosen by the text of an element or of a
+    /// `text` field
+    ///
+    /// ```ignore
+    /// #[xml($( tag = "$tag" )?)]
+    /// enum $name {
+    ///     #[xml(rename = "$value")]
+    ///     $variant,
+    ///     #[xml(other)]
+    ///     $other $( (String) )?,
+    /// }
+    /// ```
+    TextEnum {
+        name: Ident,
+        tag: Option<LitStr>,
+        /// Variants with the text they're read from and written as
+        variants: Vec<(Ident, LitStr)>,
+        /// Variant for any other text, which keeps it if it has a field
+        other: Option<Other>,
+        doc: Option<String>,
+    },
+    /// Struct without `tag` attribute, whose fields are read from and
+    /// written to the element of the struct it's flattened into
+    ///
+    /// ```ignore
+    /// struct $name {
+    ///     $( $fields )*
+    /// }
+    /// ```
+    Flatten {
+        name: Ident,
+        fields: Vec<Field>,
+        /// Declared type of every field
+        types: Vec<syn::Type>,
+        doc: Option<String>,
+    },
+}
+
+/// Attribute written with a constant value, which is checked when reading
+/// if `validate` is set
+///
+/// ```ignore
+/// #[xml(const_attr("$name", "$value", $validate))]
+/// ```
+pub struct ConstAttr {
+    pub name: LitStr,
+    pub value: LitStr,
+    pub validate: bool,
+}
+
+/// Variant for elements no other variant accepts, which captures them
+/// if it has a field
+///
+/// ```ignore
+/// enum Foo {
+///     #[xml(other)]
+///     $name $( ($ty) )?
+/// }
+/// ```
+pub struct Other {
+    pub name: Ident,
+    pub ty: Option<syn::Type>,
+}
+
+/// Attribute selecting the variant of an enum
+///
+/// ```ignore
+/// #[xml(tag = "$tag", type_attr = "$attr")]
+/// enum Foo {
+///     #[xml(type = "$values")]
+///     Bar { .. }
+/// }
+/// ```
+pub struct Discriminator {
+    pub tag: LitStr,
+    pub attr: LitStr,
+    /// Value of the attribute for every variant
+    pub values: Vec<LitStr>,
 }
 
 pub enum Fields {
     /// Named fields of a struct or struct variant
     ///
     /// ```ignore
-    /// #[xml(tag = "$tag")]
+    /// #[xml(tag = "$tag", $( alias = "$aliases", )* $( const_attr("$name", "$value", $validate), )* $ordered)]
     /// struct $name {
     ///     $( $fields )*
     /// }
@@ -29,8 +126,14 @@ pub enum Fields {
     /// ```
     Named {
         tag: LitStr,
+        /// Other tags accepted when reading
+        aliases: Vec<LitStr>,
         name: Ident,
         fields: Vec<Field>,
+        /// Attributes always written with the same value
+        const_attrs: Vec<ConstAttr>,
+        ordered: bool,
+        doc: Option<String>,
     },
     /// Newtype struct or newtype variant
     ///
@@ -57,7 +160,7 @@ pub enum Field {
     ///
     /// ```ignore
     /// struct Foo {
-    ///     #[xml(attr = "$tag", $default)]
+    ///     #[xml(attr = "$tag", $( alias = "$aliases", )* $( deprecated = "$deprecated", )* or_child = "$or_child", $default, default = "$default_fn", $overflow, precision = $precision, radix = $radix, prefix = "$prefix", $list, separator = "$separator", empty = "$empty", skip_serializing_if = "$skip_if", $access)]
     ///     $name: $ty,
     /// }
     /// ```
@@ -66,13 +169,34 @@ pub enum Field {
         bind: Ident,
         ty: Type,
         tag: LitStr,
+        /// Other names accepted when reading
+        aliases: Vec<LitStr>,
+        /// Old names accepted when reading, which are reported
+        deprecated: Vec<LitStr>,
+        /// Child element the value is read from as well
+        or_child: Option<LitStr>,
         default: bool,
+        /// Function called instead of `Default::default` for a missing field
+        default_fn: Option<ExprPath>,
+        saturate: bool,
+        /// Decimal places floats are written with
+        precision: Option<usize>,
+        /// Radix integers are read and written in
+        radix: Option<Radix>,
+        /// Values of a Vec are separated in one attribute
+        list: Option<Separator>,
+        /// Empty values are omitted when writing
+        omit_empty: bool,
+        /// Function deciding whether the field is omitted when writing
+        skip_if: Option<ExprPath>,
+        access: Access,
+        doc: Option<String>,
     },
     /// Child(ren) Field
     ///
     /// ```ignore
     /// struct Foo {
-    ///     #[xml(child = "$tag", child = "$tag", $default)]
+    ///     #[xml(child = "$tag", child = "$tag", $default, default = "$default_fn", min = $min, max = $max, wrapper = "$wrapper", empty = "$empty", duplicate = "$on_duplicate", skip_serializing_if = "$skip_if", $access)]
     ///     $name: $ty,
     /// }
     /// ```
@@ -81,13 +205,43 @@ pub enum Field {
         bind: Ident,
         ty: Type,
         default: bool,
+        default_fn: Option<ExprPath>,
+        tags: Vec<LitStr>,
+        occurs: Occurs,
+        wrapper: Option<LitStr>,
+        empty: EmptyPolicy,
+        on_duplicate: OnDuplicate,
+        skip_if: Option<ExprPath>,
+        access: Access,
+        doc: Option<String>,
+    },
+    /// Keyed Children Field
+    ///
+    /// Children read into a map, keyed by one of their attributes.
+    ///
+    /// ```ignore
+    /// struct Foo {
+    ///     #[xml(child = "$tag", key = "$key", duplicate_key = "$on_duplicate", skip_serializing_if = "$skip_if", $access)]
+    ///     $name: $ty<$key_ty, $value_ty>,
+    /// }
+    /// ```
+    KeyedChild {
+        name: TokenStream,
+        bind: Ident,
+        key_ty: Type,
+        value_ty: syn::Type,
         tags: Vec<LitStr>,
+        key: LitStr,
+        on_duplicate: OnDuplicate,
+        skip_if: Option<ExprPath>,
+        access: Access,
+        doc: Option<String>,
     },
     /// Text Field
     ///
     /// ```ignore
     /// struct Foo {
-    ///     #[xml(text, $default)]
+    ///     #[xml(text, $default, $trim, $overflow, precision = $precision, radix = $radix, prefix = "$prefix", $list, separator = "$separator")]
     ///     $name: $ty,
     /// }
     /// ```
@@ -96,12 +250,21 @@ pub enum Field {
         bind: Ident,
         ty: Type,
         is_cdata: bool,
+        /// Surrounding whitespace is trimmed when reading
+        trim: bool,
+        saturate: bool,
+        /// Decimal places floats are written with
+        precision: Option<usize>,
+        /// Radix integers are read and written in
+        radix: Option<Radix>,
+        list: Option<Separator>,
+        doc: Option<String>,
     },
     /// Flatten Text
     ///
     /// ```ignore
     /// struct Foo {
-    ///     #[xml(flatten_text = "$tag", $default)]
+    ///     #[xml(flatten_text = "$tag", or_attr = "$or_attr", $default, default = "$default_fn", min = $min, max = $max, duplicate = "$on_duplicate", $trim, $overflow, precision = $precision, radix = $radix, prefix = "$prefix", $list, separator = "$separator", empty = "$empty", skip_serializing_if = "$skip_if", $access)]
     ///     $name: $ty,
     /// }
     /// ```
@@ -110,8 +273,137 @@ pub enum Field {
         bind: Ident,
         ty: Type,
         default: bool,
+        default_fn: Option<ExprPath>,
         tag: LitStr,
+        /// Old tags accepted when reading, which are reported
+        deprecated: Vec<LitStr>,
+        /// Attribute the value is read from as well
+        or_attr: Option<LitStr>,
         is_cdata: bool,
+        /// Surrounding whitespace is trimmed when reading
+        trim: bool,
+        occurs: Occurs,
+        on_duplicate: OnDuplicate,
+        saturate: bool,
+        /// Decimal places floats are written with
+        precision: Option<usize>,
+        /// Radix integers are read and written in
+        radix: Option<Radix>,
+        list: Option<Separator>,
+        /// What an empty list is written as
+        empty: EmptyPolicy,
+        skip_if: Option<ExprPath>,
+        access: Access,
+        doc: Option<String>,
+    },
+    /// Map Field
+    ///
+    /// Entries written as `<$tag $key="..." $value="..."/>` elements, or as
+    /// `<key>value</key>` elements if the tag is `*`.
+    ///
+    /// ```ignore
+    /// struct Foo {
+    ///     #[xml(map = "$tag", key = "$key", value = "$value", skip_serializing_if = "$skip_if", $access)]
+    ///     $name: $ty<$key_ty, $value_ty>,
+    /// }
+    /// ```
+    Map {
+        name: TokenStream,
+        bind: Ident,
+        key_ty: Type,
+        value_ty: Type,
+        tag: LitStr,
+        key: LitStr,
+        value: LitStr,
+        skip_if: Option<ExprPath>,
+        access: Access,
+        doc: Option<String>,
+    },
+    /// Order of children
+    ///
+    /// Names of the child fields in the order they appeared in the document.
+    ///
+    /// ```ignore
+    /// struct Foo {
+    ///     #[xml(order)]
+    ///     $name: Vec<&'static str>,
+    /// }
+    /// ```
+    Order { name: TokenStream, bind: Ident },
+    /// Tag Name
+    ///
+    /// Name of the element as written in the document, which is used as
+    /// its tag when writing.
+    ///
+    /// ```ignore
+    /// struct Foo {
+    ///     #[xml(tag_name)]
+    ///     $name: Cow<'a, str>,
+    /// }
+    /// ```
+    TagName { name: TokenStream, bind: Ident },
+    /// Skipped Field
+    ///
+    /// Neither read nor written, it's initialized with its default value.
+    ///
+    /// ```ignore
+    /// struct Foo {
+    ///     #[xml(skip, default = "$default_fn")]
+    ///     $name: $ty,
+    /// }
+    /// ```
+    Skip {
+        name: TokenStream,
+        bind: Ident,
+        default_fn: Option<ExprPath>,
+    },
+    /// Flattened Field
+    ///
+    /// Fields of a struct without `tag` attribute, which are read from and
+    /// written to this element.
+    ///
+    /// ```ignore
+    /// struct Foo {
+    ///     #[xml(flatten)]
+    ///     $name: $ty,
+    /// }
+    /// ```
+    Flatten {
+        name: TokenStream,
+        bind: Ident,
+        ty: syn::Type,
+    },
+    /// Catch-all Attributes
+    ///
+    /// Every attribute no other field is read from, as pairs of name and
+    /// value.
+    ///
+    /// ```ignore
+    /// struct Foo {
+    ///     #[xml(attrs)]
+    ///     $name: $ty,
+    /// }
+    /// ```
+    Attrs {
+        name: TokenStream,
+        bind: Ident,
+        doc: Option<String>,
+    },
+    /// Catch-all Children
+    ///
+    /// Every child element no other field is read from.
+    ///
+    /// ```ignore
+    /// struct Foo {
+    ///     #[xml(children)]
+    ///     $name: Vec<$ty>,
+    /// }
+    /// ```
+    Children {
+        name: TokenStream,
+        bind: Ident,
+        ty: syn::Type,
+        doc: Option<String>,
     },
 }
 
@@ -129,257 +421,2109 @@ pub enum Type {
     // Vec<T>
     VecT(syn::Type),
     // bool
-    Bool,
+    Bool(BoolFormat),
     // Vec<bool>
-    VecBool,
+    VecBool(BoolFormat),
     // Option<bool>
-    OptionBool,
+    OptionBool(BoolFormat),
+    // Cow<'a, [u8]>, Vec<u8> or [u8; N]
+    CowBytes(Encoding),
+    // Option of the above
+    OptionCowBytes(Encoding),
+    // Vec of the above
+    VecCowBytes(Encoding),
 }
 
-impl Element {
-    pub fn parse(input: DeriveInput) -> Element {
-        match input.data {
-            Data::Struct(data) => Element::Struct {
-                name: input.ident.clone(),
-                fields: Fields::parse(data.fields, input.attrs, input.ident),
-            },
-            Data::Enum(data) => Element::Enum {
-                name: input.ident,
-                variants: data
-                    .variants
-                    .into_iter()
-                    .map(|variant| Fields::parse(variant.fields, variant.attrs, variant.ident))
-                    .collect::<Vec<_>>(),
-            },
-            Data::Union(_) => panic!("hard-xml doesn't support Union."),
+/// Whether a field is only read or only written
+#[derive(Clone, Copy, PartialEq)]
+pub enum Access {
+    ReadWrite,
+    /// `#[xml(read_only)]`
+    ReadOnly,
+    /// `#[xml(write_only)]`
+    WriteOnly,
+}
+
+/// Allowed number of occurrences of a Vec field
+#[derive(Clone, Copy, Default)]
+pub struct Occurs {
+    pub min: Option<usize>,
+    pub max: Option<usize>,
+}
+
+impl Occurs {
+    pub fn is_bounded(&self) -> bool {
+        self.min.is_some() || self.max.is_some()
+    }
+}
+
+/// How the values of a Vec read from a single attribute or text are
+/// separated
+pub enum Separator {
+    /// `#[xml(list)]`
+    Whitespace,
+    /// `#[xml(separator = "")]`
+    Str(LitStr),
+}
+
+/// Which forms are accepted when reading a bool
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum BoolParse {
+    /// `true`, `false`, `1`, `0`, and `t`, `y`, `yes`, `on` and the like
+    #[default]
+    Default,
+    /// `#[xml(bool = "strict")]`, only the xs:boolean forms
+    Strict,
+    /// `#[xml(bool = "lenient")]`, the default forms in any case
+    Lenient,
+}
+
+impl BoolParse {
+    fn parse(lit: &LitStr) -> Result<BoolParse> {
+        match lit.value().as_str() {
+            "strict" => Ok(BoolParse::Strict),
+            "lenient" => Ok(BoolParse::Lenient),
+            _ => bail!(
+                lit.span(),
+                "`bool` attribute only supports \"strict\" and \"lenient\"."
+            ),
         }
     }
 }
 
-impl Fields {
-    pub fn parse(fields: syn::Fields, attrs: Vec<Attribute>, name: Ident) -> Fields {
-        // Finding `tag` attribute
-        let mut tags = Vec::new();
+/// How a bool field is read and written
+#[derive(Clone, Default)]
+pub struct BoolFormat {
+    pub parse: BoolParse,
+    /// `#[xml(true = "")]`
+    pub true_str: Option<LitStr>,
+    /// `#[xml(false = "")]`
+    pub false_str: Option<LitStr>,
+}
 
-        for meta in attrs.into_iter().filter_map(get_xml_meta).flatten() {
-            match meta {
-                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("tag") => {
-                    if let Str(lit) = m.lit {
-                        tags.push(lit);
-                    } else {
-                        panic!("Expected a string literal.");
-                    }
-                }
-                _ => (),
-            }
+/// Radix of an integer field, and the prefix written before its digits
+pub struct Radix {
+    pub radix: u32,
+    pub prefix: Option<LitStr>,
+}
+
+/// What happens when a keyed child has the key of a previous one, or when
+/// a child which isn't repeated occurs again
+#[derive(Clone, Copy)]
+pub enum OnDuplicate {
+    Error,
+    First,
+    Last,
+}
+
+impl OnDuplicate {
+    fn parse(lit: &LitStr, attr: &str) -> Result<OnDuplicate> {
+        match lit.value().as_str() {
+            "error" => Ok(OnDuplicate::Error),
+            "first" => Ok(OnDuplicate::First),
+            "last" => Ok(OnDuplicate::Last),
+            _ => bail!(
+                lit.span(),
+                "`{}` attribute only supports \"error\", \"first\" and \"last\".",
+                attr
+            ),
         }
+    }
+}
 
-        if tags.is_empty() {
-            panic!("Missing `tag` attribute.");
+/// What a wrapper or a `flatten_text` list is written as when it's empty
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum EmptyPolicy {
+    #[default]
+    Omit,
+    Expanded,
+    SelfClosing,
+}
+
+impl EmptyPolicy {
+    fn parse(lit: &LitStr) -> Result<EmptyPolicy> {
+        match lit.value().as_str() {
+            "omit" => Ok(EmptyPolicy::Omit),
+            "expanded" => Ok(EmptyPolicy::Expanded),
+            "self_closing" => Ok(EmptyPolicy::SelfClosing),
+            _ => bail!(
+                lit.span(),
+                "`empty` attribute only supports \"omit\", \"expanded\" and \"self_closing\"."
+            ),
         }
+    }
+}
 
-        match fields {
-            syn::Fields::Unit => Fields::Named {
-                name,
-                tag: tags.remove(0),
-                fields: Vec::new(),
-            },
-            syn::Fields::Unnamed(fields) => {
-                // we will assume it's a newtype stuct/enum
-                // if it has only one field and no field attribute
-                if fields.unnamed.len() == 1 {
-                    let field = fields.unnamed.first().unwrap().clone();
-                    if field.attrs.into_iter().filter_map(get_xml_meta).count() == 0 {
-                        return Fields::Newtype {
-                            name,
-                            tags,
-                            ty: Type::parse(field.ty),
-                        };
-                    }
-                }
+/// Textual encoding of a binary field
+#[derive(Clone, Copy)]
+pub enum Encoding {
+    Utf8,
+    Hex,
+    Base64,
+}
 
-                Fields::Named {
-                    name,
-                    tag: tags.remove(0),
-                    fields: fields
-                        .unnamed
-                        .into_iter()
-                        .enumerate()
-                        .map(|(index, field)| {
-                            let index = syn::Index::from(index);
-                            let bind = format_ident!("__self_{}", index);
-                            Field::parse(quote!(#index), bind, field)
-                        })
-                        .collect::<Vec<_>>(),
+impl Element {
+    fn flatten(
+        name: Ident,
+        fields: Fields,
+        types: Vec<syn::Type>,
+        doc: Option<String>,
+    ) -> Result<Element> {
+        let span = name.span();
+        let fields = match fields {
+            Fields::Named {
+                fields,
+                const_attrs,
+                ordered,
+                ..
+            } => {
+                if ordered {
+                    bail!(span, "`ordered` attribute requires `tag` attribute.");
+                }
+                if !const_attrs.is_empty() {
+                    bail!(span, "`const_attr` attribute requires `tag` attribute.");
                 }
+                fields
+            }
+            Fields::Newtype { .. } => unreachable!(),
+        };
+        for field in &fields {
+            if matches!(
+                field,
+                Field::Text { .. }
+                    | Field::Order { .. }
+                    | Field::TagName { .. }
+                    | Field::Attrs { .. }
+                    | Field::Children { .. }
+                    | Field::Map { .. }
+                    | Field::KeyedChild { .. }
+            ) {
+                bail!(span, "Structs without `tag` attribute only support `attr`, `child`, `flatten_text`, `skip` and `flatten` fields.");
             }
-            syn::Fields::Named(_) => Fields::Named {
-                name,
-                tag: tags.remove(0),
-                fields: fields
-                    .into_iter()
-                    .map(|field| {
-                        let name = field.ident.clone().unwrap();
-                        let bind = format_ident!("__self_{}", name);
-                        Field::parse(quote!(#name), bind, field)
-                    })
-                    .collect::<Vec<_>>(),
-            },
         }
+        Ok(Element::Flatten {
+            name,
+            fields,
+            types,
+            doc,
+        })
     }
-}
 
-impl Field {
-    pub fn parse(name: TokenStream, bind: Ident, field: syn::Field) -> Field {
-        let mut default = false;
-        let mut attr_tag = None;
-        let mut child_tags = Vec::new();
-        let mut is_text = false;
-        let mut flatten_text_tag = None;
-        let mut is_cdata = false;
+    pub fn parse(input: DeriveInput) -> Result<Element> {
+        let mut untagged = false;
+        let mut partial = false;
+        let mut tag = None;
+        let mut type_attr = None;
+        let mut rename_all = None;
+
+        let mut errors = Errors::default();
 
-        for meta in field.attrs.into_iter().filter_map(get_xml_meta).flatten() {
+        let mut parse_meta = |meta: NestedMeta| -> Result<()> {
+            let span = meta.span();
             match meta {
-                NestedMeta::Meta(Path(p)) if p.is_ident("default") => {
-                    if default {
-                        panic!("Duplicate `default` attribute.");
+                NestedMeta::Meta(Path(p)) if p.is_ident("untagged") => {
+                    if untagged {
+                        bail!(span, "Duplicate `untagged` attribute.");
                     } else {
-                        default = true;
+                        untagged = true;
                     }
                 }
-                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("attr") => {
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("tag") => {
                     if let Str(lit) = m.lit {
-                        if attr_tag.is_some() {
-                            panic!("Duplicate `attr` attribute.");
-                        } else if is_text {
-                            panic!("`attr` attribute and `text` attribute is disjoint.");
-                        } else if is_cdata {
-                            panic!("`attr` attribute and `cdata` attribute is disjoint.")
-                        } else if !child_tags.is_empty() {
-                            panic!("`attr` attribute and `child` attribute is disjoint.");
-                        } else if flatten_text_tag.is_some() {
-                            panic!("`attr` attribute and `flatten_text` attribute is disjoint.");
-                        } else {
-                            attr_tag = Some(lit);
-                        }
-                    } else {
-                        panic!("Expected a string literal.");
-                    }
-                }
-                NestedMeta::Meta(Path(ref p)) if p.is_ident("text") => {
-                    if is_text {
-                        panic!("Duplicate `text` attribute.");
-                    } else if attr_tag.is_some() {
-                        panic!("`text` attribute and `attr` attribute is disjoint.");
-                    } else if !child_tags.is_empty() {
-                        panic!("`text` attribute and `child` attribute is disjoint.");
-                    } else if flatten_text_tag.is_some() {
-                        panic!("`text` attribute and `flatten_text` attribute is disjoint.");
+                        tag = Some(lit);
                     } else {
-                        is_text = true;
+                        bail!(span, "Expected a string literal.");
                     }
                 }
-                NestedMeta::Meta(Path(ref p)) if p.is_ident("cdata") => {
-                    if is_cdata {
-                        panic!("Duplicate `cdata` attribute.");
-                    } else if attr_tag.is_some() {
-                        panic!("`text` attribute and `attr` attribute is disjoint.");
-                    } else if !child_tags.is_empty() {
-                        panic!("`text` attribute and `child` attribute is disjoint.");
+                NestedMeta::Meta(Path(p)) if p.is_ident("partial") => {
+                    if partial {
+                        bail!(span, "Duplicate `partial` attribute.");
                     } else {
-                        is_cdata = true;
+                        partial = true;
                     }
                 }
-                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("child") => {
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("rename_all") => {
                     if let Str(lit) = m.lit {
-                        if is_text {
-                            panic!("`child` attribute and `text` attribute is disjoint.");
-                        } else if attr_tag.is_some() {
-                            panic!("`child` attribute and `attr` attribute is disjoint.");
-                        } else if is_cdata {
-                            panic!("`child` attribute and `cdata` attribute is disjoint.")
-                        } else if flatten_text_tag.is_some() {
-                            panic!("`child` attribute and `flatten_text` attribute is disjoint.");
+                        if rename_all.is_some() {
+                            bail!(span, "Duplicate `rename_all` attribute.");
                         } else {
-                            child_tags.push(lit);
+                            rename_all = Some(RenameRule::parse(&lit)?);
                         }
                     } else {
-                        panic!("Expected a string literal.");
+                        bail!(span, "Expected a string literal.");
                     }
                 }
-                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("flatten_text") => {
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("type_attr") => {
                     if let Str(lit) = m.lit {
-                        if is_text {
-                            panic!("`flatten_text` attribute and `text` attribute is disjoint.");
-                        } else if !child_tags.is_empty() {
-                            panic!("`flatten_text` attribute and `child` attribute is disjoint.");
-                        } else if attr_tag.is_some() {
-                            panic!("`flatten_text` attribute and `attr` attribute is disjoint.");
-                        } else if flatten_text_tag.is_some() {
-                            panic!("Duplicate `flatten_text` attribute.");
+                        if type_attr.is_some() {
+                            bail!(span, "Duplicate `type_attr` attribute.");
                         } else {
-                            flatten_text_tag = Some(lit);
+                            type_attr = Some(lit);
                         }
                     } else {
-                        panic!("Expected a string literal.");
+                        bail!(span, "Expected a string literal.");
                     }
                 }
                 _ => (),
             }
-        }
+            Ok(())
+        };
 
-        if let Some(tag) = attr_tag {
-            Field::Attribute {
-                name,
-                bind,
-                ty: Type::parse(field.ty),
-                tag,
-                default,
-            }
-        } else if !child_tags.is_empty() {
-            Field::Child {
-                name,
-                bind,
-                ty: Type::parse(field.ty),
-                default,
-                tags: child_tags,
-            }
-        } else if is_text {
-            Field::Text {
-                name,
-                bind,
-                ty: Type::parse(field.ty),
-                is_cdata,
-            }
-        } else if let Some(tag) = flatten_text_tag {
-            Field::FlattenText {
-                name,
-                bind,
-                ty: Type::parse(field.ty),
-                default,
-                tag,
-                is_cdata,
-            }
-        } else {
-            panic!("Field should have one of `attr`, `child`, `text` or `flatten_text` attribute.");
+        for meta in xml_metas(&input.attrs)? {
+            errors.ok(parse_meta(meta));
         }
-    }
-}
+
+        errors.finish()?;
+
+        let span = input.ident.span();
+
+        match input.data {
+            Data::Struct(data) => {
+                if untagged {
+                    bail!(span, "`untagged` attribute only supports enums.");
+                }
+                if type_attr.is_some() {
+                    bail!(span, "`type_attr` attribute only supports enums.");
+                }
+                if tag.is_none() {
+                    if let syn::Fields::Named(named) = &data.fields {
+                        if partial {
+                            bail!(span, "`partial` attribute requires `tag` attribute.");
+                        }
+                        let types = named.named.iter().map(|field| field.ty.clone()).collect();
+                        let doc = get_doc(&input.attrs)?;
+                        let fields = Fields::parse(
+                            data.fields,
+                            input.attrs,
+                            input.ident.clone(),
+                            Some(&LitStr::new("", input.ident.span())),
+                        )?;
+                        return Element::flatten(input.ident, fields, types, doc);
+                    }
+                }
+                let fields = Fields::parse(data.fields, input.attrs, input.ident.clone(), None)?;
+                if partial && matches!(fields, Fields::Newtype { .. }) {
+                    bail!(span, "`partial` attribute requires named fields.");
+                }
+                Ok(Element::Struct {
+                    name: input.ident,
+                    fields,
+                    partial,
+                })
+            }
+            Data::Enum(data) => {
+                if partial {
+                    bail!(span, "`partial` attribute only supports structs.");
+                }
+                if untagged && type_attr.is_some() {
+                    bail!(
+                        span,
+                        "`untagged` attribute and `type_attr` attribute is disjoint."
+                    );
+                }
+
+                if rename_all.is_some() && !is_text_enum(&data) {
+                    bail!(
+                        span,
+                        "`rename_all` attribute requires unit variants without `tag` attribute."
+                    );
+                }
+
+                if is_text_enum(&data) {
+                    if untagged {
+                        bail!(
+                            span,
+                            "`untagged` attribute requires variants with `tag` attribute."
+                        );
+                    }
+                    if type_attr.is_some() {
+                        bail!(
+                            span,
+                            "`type_attr` attribute requires variants with `type` attribute."
+                        );
+                    }
+                    let mut variants = Vec::new();
+                    let mut other = None;
+                    let mut errors = Errors::default();
+                    for variant in data.variants {
+                        let span = variant.ident.span();
+                        match errors.ok(Other::parse(&variant)) {
+                            Some(Some(_)) if other.is_some() => {
+                                errors.push(Error::new(span, "Duplicate `other` attribute."));
+                            }
+                            Some(Some(variant)) => other = Some(variant),
+                            Some(None) => {
+                                let value = errors.ok(get_str_value(&variant.attrs, "rename"));
+                                let value = value.flatten().unwrap_or_else(|| {
+                                    let value = variant.ident.to_string();
+                                    let value = match rename_all {
+                                        Some(rule) => rule.apply_to_variant(&value),
+                                        None => value,
+                                    };
+                                    LitStr::new(&value, variant.ident.span())
+                                });
+                                variants.push((variant.ident, value));
+                            }
+                            None => (),
+                        }
+                    }
+                    errors.finish()?;
+                    return Ok(Element::TextEnum {
+                        name: input.ident,
+                        tag,
+                        variants,
+                        other,
+                        doc: get_doc(&input.attrs)?,
+                    });
+                }
+
+                // variants of a discriminated enum share the tag of the enum
+                let tag = match (&type_attr, tag) {
+                    (Some(_), None) => {
+                        bail!(span, "`type_attr` attribute requires `tag` attribute.")
+                    }
+                    (Some(_), tag) => tag,
+                    (None, _) => None,
+                };
+
+                let mut values = Vec::new();
+                let mut variants = Vec::new();
+                let mut other = None;
+
+                // every variant is parsed to report all their errors
+                let mut errors = Errors::default();
+                for variant in data.variants {
+                    let span = variant.ident.span();
+                    match errors.ok(Other::parse(&variant)) {
+                        Some(Some(_)) if other.is_some() => {
+                            errors.push(Error::new(span, "Duplicate `other` attribute."));
+                            continue;
+                        }
+                        Some(Some(variant)) => {
+                            other = Some(variant);
+                            continue;
+                        }
+                        Some(None) => (),
+                        None => continue,
+                    }
+                    match (&type_attr, errors.ok(get_str_value(&variant.attrs, "type"))) {
+                        (Some(_), Some(Some(value))) => values.push(value),
+                        (Some(_), Some(None)) => {
+                            errors.push(Error::new(span, "Missing `type` attribute."));
+                        }
+                        (None, Some(Some(value))) => {
+                            errors.push(Error::new(
+                                value.span(),
+                                "`type` attribute requires `type_attr` attribute.",
+                            ));
+                        }
+                        _ => (),
+                    }
+                    variants.extend(errors.ok(Fields::parse(
+                        variant.fields,
+                        variant.attrs,
+                        variant.ident,
+                        tag.as_ref(),
+                    )));
+                }
+
+                errors.finish()?;
+
+                Ok(Element::Enum {
+                    name: input.ident,
+                    variants,
+                    untagged,
+                    other,
+                    discriminator: type_attr.map(|attr| Discriminator {
+                        tag: tag.unwrap(),
+                        attr,
+                        values,
+                    }),
+                })
+            }
+            Data::Union(_) => bail!(span, "hard-xml doesn't support Union."),
+        }
+    }
+
+    /// Replaces the fields which are only accessed the other way with
+    /// skipped fields, before reading or writing code is generated.
+    pub fn skip_access(self, skipped: Access) -> Element {
+        let skip = |fields: Vec<Field>| {
+            fields
+                .into_iter()
+                .map(|field| field.skip_access(skipped))
+                .collect()
+        };
+        let skip_fields = |fields: Fields| match fields {
+            Fields::Named {
+                name,
+                tag,
+                aliases,
+                fields,
+                const_attrs,
+                ordered,
+                doc,
+            } => Fields::Named {
+                name,
+                tag,
+                aliases,
+                fields: skip(fields),
+                const_attrs,
+                ordered,
+                doc,
+            },
+            fields => fields,
+        };
+        match self {
+            Element::Struct {
+                name,
+                fields,
+                partial,
+            } => Element::Struct {
+                name,
+                fields: skip_fields(fields),
+                partial,
+            },
+            Element::Enum {
+                name,
+                variants,
+                untagged,
+                discriminator,
+                other,
+            } => Element::Enum {
+                name,
+                variants: variants.into_iter().map(skip_fields).collect(),
+                untagged,
+                discriminator,
+                other,
+            },
+            Element::Flatten {
+                name,
+                fields,
+                types,
+                doc,
+            } => Element::Flatten {
+                name,
+                fields: skip(fields),
+                types,
+                doc,
+            },
+            element => element,
+        }
+    }
+}
+
+// enums whose variants are all units without `tag` or `type` attribute,
+// apart from the `other` variant, are read from text
+fn is_text_enum(data: &DataEnum) -> bool {
+    // malformed attributes are reported when the variant is parsed
+    let is_text_variant = |variant: &Variant| {
+        let metas = xml_metas(&variant.attrs).unwrap_or_default();
+
+        if metas
+            .iter()
+            .any(|meta| matches!(meta, NestedMeta::Meta(Path(p)) if p.is_ident("other")))
+        {
+            return None;
+        }
+
+        Some(
+            matches!(variant.fields, syn::Fields::Unit)
+                && metas.iter().all(|meta| match meta {
+                    NestedMeta::Meta(NameValue(m)) => {
+                        m.path.is_ident("rename") || m.path.is_ident("doc")
+                    }
+                    _ => false,
+                }),
+        )
+    };
+
+    let mut variants = data.variants.iter().filter_map(is_text_variant).peekable();
+
+    variants.peek().is_some() && variants.all(|is_text| is_text)
+}
+
+impl ConstAttr {
+    fn parse(list: MetaList) -> Result<ConstAttr> {
+        let span = list.span();
+        let mut nested = list.nested.into_iter();
+        let mut next_str = || match nested.next() {
+            Some(NestedMeta::Lit(Str(lit))) => Ok(lit),
+            _ => bail!(
+                span,
+                "Expected `const_attr(\"name\", \"value\")` attribute."
+            ),
+        };
+        let name = next_str()?;
+        let value = next_str()?;
+        let validate = match nested.next() {
+            None => false,
+            Some(NestedMeta::Meta(Path(p))) if p.is_ident("validate") => true,
+            Some(meta) => bail!(
+                meta.span(),
+                "Expected `validate` after the value of `const_attr` attribute."
+            ),
+        };
+        if let Some(meta) = nested.next() {
+            bail!(
+                meta.span(),
+                "Expected `const_attr(\"name\", \"value\")` attribute."
+            );
+        }
+        Ok(ConstAttr {
+            name,
+            value,
+            validate,
+        })
+    }
+}
+
+impl Other {
+    fn parse(variant: &Variant) -> Result<Option<Other>> {
+        let span = variant.ident.span();
+        let metas = xml_metas(&variant.attrs)?;
+
+        let count = metas
+            .iter()
+            .filter(|meta| matches!(meta, NestedMeta::Meta(Path(p)) if p.is_ident("other")))
+            .count();
+
+        if count == 0 {
+            return Ok(None);
+        } else if count > 1 {
+            bail!(span, "Duplicate `other` attribute.");
+        } else if metas.len() > 1 {
+            bail!(
+                span,
+                "`other` attribute can't be used together with other attributes."
+            );
+        }
+
+        let ty = match &variant.fields {
+            syn::Fields::Unit => None,
+            syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let mut ty = fields.unnamed[0].ty.clone();
+                elide_type_lifetimes(&mut ty);
+                Some(ty)
+            }
+            _ => bail!(
+                span,
+                "`other` attribute only supports unit and newtype variants."
+            ),
+        };
+
+        Ok(Some(Other {
+            name: variant.ident.clone(),
+            ty,
+        }))
+    }
+}
+
+impl Fields {
+    pub fn parse(
+        fields: syn::Fields,
+        attrs: Vec<Attribute>,
+        name: Ident,
+        default_tag: Option<&LitStr>,
+    ) -> Result<Fields> {
+        // Finding `tag` attribute
+        let mut tags = Vec::new();
+        let mut aliases = Vec::new();
+        let mut ordered = false;
+        let mut rename_all = None;
+        let mut empty = None;
+        let mut precision = None;
+        let mut trim = false;
+        let mut const_attrs = Vec::new();
+        let doc = get_doc(&attrs)?;
+
+        let mut errors = Errors::default();
+
+        let mut parse_meta = |meta: NestedMeta| -> Result<()> {
+            let span = meta.span();
+            match meta {
+                NestedMeta::Meta(List(l)) if l.path.is_ident("const_attr") => {
+                    const_attrs.push(ConstAttr::parse(l)?);
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("tag") => {
+                    if let Str(lit) = m.lit {
+                        tags.push(lit);
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("rename_all") => {
+                    if let Str(lit) = m.lit {
+                        if rename_all.is_some() {
+                            bail!(span, "Duplicate `rename_all` attribute.");
+                        } else {
+                            rename_all = Some(RenameRule::parse(&lit)?);
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("alias") => {
+                    if let Str(lit) = m.lit {
+                        aliases.push(lit);
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("empty") => {
+                    if let Str(lit) = m.lit {
+                        if empty.is_some() {
+                            bail!(span, "Duplicate `empty` attribute.");
+                        } else {
+                            empty = Some(EmptyPolicy::parse(&lit)?);
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("precision") => {
+                    if let Int(lit) = m.lit {
+                        if precision.is_some() {
+                            bail!(span, "Duplicate `precision` attribute.");
+                        } else {
+                            precision = Some(lit.base10_parse()?);
+                        }
+                    } else {
+                        bail!(span, "Expected an integer literal.");
+                    }
+                }
+                NestedMeta::Meta(Path(p)) if p.is_ident("trim") => {
+                    if trim {
+                        bail!(span, "Duplicate `trim` attribute.");
+                    } else {
+                        trim = true;
+                    }
+                }
+                NestedMeta::Meta(Path(p)) if p.is_ident("ordered") => {
+                    if ordered {
+                        bail!(span, "Duplicate `ordered` attribute.");
+                    } else {
+                        ordered = true;
+                    }
+                }
+                _ => (),
+            }
+            Ok(())
+        };
+
+        for meta in xml_metas(&attrs)? {
+            errors.ok(parse_meta(meta));
+        }
+
+        errors.finish()?;
+
+        let span = name.span();
+
+        if tags.is_empty() {
+            match default_tag {
+                Some(tag) => tags.push(tag.clone()),
+                None => bail!(span, "Missing `tag` attribute."),
+            }
+        }
+
+        // every field is parsed to report all their errors
+        let mut errors = Errors::default();
+
+        let fields = match fields {
+            syn::Fields::Unit => Fields::Named {
+                name,
+                tag: tags.remove(0),
+                aliases,
+                fields: Vec::new(),
+                const_attrs,
+                ordered,
+                doc,
+            },
+            syn::Fields::Unnamed(mut fields) => {
+                // we will assume it's a newtype stuct/enum
+                // if it has only one field and no field attribute
+                if fields.unnamed.len() == 1 {
+                    let field = fields.unnamed.first_mut().unwrap();
+                    let has_attrs = !xml_metas(&field.attrs)?.is_empty();
+                    if !has_attrs && is_text_value(&field.ty) {
+                        // plain values are the text of the element
+                        field.attrs.push(parse_quote!(#[xml(text)]));
+                    } else if !has_attrs && !is_marker(&field.ty) {
+                        let field = field.clone();
+                        if ordered {
+                            bail!(span, "`ordered` attribute requires named fields.");
+                        }
+                        if !const_attrs.is_empty() {
+                            bail!(span, "`const_attr` attribute requires named fields.");
+                        }
+                        // the inner type is written with its own tag anyway
+                        tags.extend(aliases);
+                        return Ok(Fields::Newtype {
+                            name,
+                            tags,
+                            ty: Type::parse(field.ty, None)?,
+                        });
+                    }
+                }
+
+                Fields::Named {
+                    name,
+                    tag: tags.remove(0),
+                    aliases,
+                    fields: fields
+                        .unnamed
+                        .into_iter()
+                        .enumerate()
+                        .filter_map(|(index, field)| {
+                            let index = syn::Index::from(index);
+                            let bind = format_ident!("__self_{}", index);
+                            errors.ok(Field::parse(
+                                quote!(#index),
+                                bind,
+                                field,
+                                rename_all,
+                                empty,
+                                precision,
+                                trim,
+                            ))
+                        })
+                        .collect::<Vec<_>>(),
+                    const_attrs,
+                    ordered,
+                    doc,
+                }
+            }
+            syn::Fields::Named(_) => Fields::Named {
+                name,
+                tag: tags.remove(0),
+                aliases,
+                fields: fields
+                    .into_iter()
+                    .filter_map(|field| {
+                        let name = field.ident.clone().unwrap();
+                        let bind = format_ident!("__self_{}", name);
+                        errors.ok(Field::parse(
+                            quote!(#name),
+                            bind,
+                            field,
+                            rename_all,
+                            empty,
+                            precision,
+                            trim,
+                        ))
+                    })
+                    .collect::<Vec<_>>(),
+                const_attrs,
+                ordered,
+                doc,
+            },
+        };
+
+        errors.finish()?;
+
+        fields.check_wildcard()
+    }
+
+    // elements matching any tag need to know which one to write
+    fn check_wildcard(self) -> Result<Fields> {
+        if let Fields::Named {
+            name, tag, fields, ..
+        } = &self
+        {
+            if tag.value() == "*"
+                && !fields
+                    .iter()
+                    .any(|field| matches!(field, Field::TagName { .. }))
+            {
+                bail!(
+                    name.span(),
+                    "`tag = \"*\"` attribute requires a `tag_name` field."
+                );
+            }
+        }
+        Ok(self)
+    }
+}
+
+impl Field {
+    pub fn name(&self) -> &TokenStream {
+        match self {
+            Field::Attribute { name, .. }
+            | Field::Child { name, .. }
+            | Field::Text { name, .. }
+            | Field::FlattenText { name, .. }
+            | Field::Map { name, .. }
+            | Field::KeyedChild { name, .. }
+            | Field::Order { name, .. }
+            | Field::TagName { name, .. }
+            | Field::Skip { name, .. }
+            | Field::Flatten { name, .. }
+            | Field::Attrs { name, .. }
+            | Field::Children { name, .. } => name,
+        }
+    }
+
+    fn skip_access(self, skipped: Access) -> Field {
+        match self {
+            Field::Attribute {
+                name,
+                bind,
+                default_fn,
+                access,
+                ..
+            }
+            | Field::Child {
+                name,
+                bind,
+                default_fn,
+                access,
+                ..
+            }
+            | Field::FlattenText {
+                name,
+                bind,
+                default_fn,
+                access,
+                ..
+            } if access == skipped => Field::Skip {
+                name,
+                bind,
+                default_fn,
+            },
+            Field::Map {
+                name, bind, access, ..
+            }
+            | Field::KeyedChild {
+                name, bind, access, ..
+            } if access == skipped => Field::Skip {
+                name,
+                bind,
+                default_fn: None,
+            },
+            field => field,
+        }
+    }
+
+    pub fn bind(&self) -> &Ident {
+        match self {
+            Field::Attribute { bind, .. }
+            | Field::Child { bind, .. }
+            | Field::Text { bind, .. }
+            | Field::FlattenText { bind, .. }
+            | Field::Map { bind, .. }
+            | Field::KeyedChild { bind, .. }
+            | Field::Order { bind, .. }
+            | Field::TagName { bind, .. }
+            | Field::Skip { bind, .. }
+            | Field::Flatten { bind, .. }
+            | Field::Attrs { bind, .. }
+            | Field::Children { bind, .. } => bind,
+        }
+    }
+
+    pub fn parse(
+        name: TokenStream,
+        bind: Ident,
+        field: syn::Field,
+        rename_all: Option<RenameRule>,
+        default_empty: Option<EmptyPolicy>,
+        default_precision: Option<usize>,
+        default_trim: bool,
+    ) -> Result<Field> {
+        let span = match &field.ident {
+            Some(ident) => ident.span(),
+            None => field.ty.span(),
+        };
+
+        let mut default = false;
+        let mut attr_tag = None;
+        let mut child_tags = Vec::new();
+        let mut is_text = false;
+        let mut flatten_text_tag = None;
+        let mut is_cdata = false;
+        let mut trim = None;
+        let mut encoding = None;
+        let mut is_order = false;
+        let mut is_tag_name = false;
+        let mut occurs = Occurs::default();
+        let mut overflow = None;
+        let mut precision = None;
+        let mut radix = None;
+        let mut prefix = None;
+        let mut wrapper = None;
+        let mut empty = None;
+        let mut aliases = Vec::new();
+        let mut deprecated = Vec::new();
+        let mut skip_if = None;
+        let mut default_fn = None;
+        let mut is_skip = false;
+        let mut is_flatten = false;
+        let mut is_attrs = false;
+        let mut is_children = false;
+        let mut is_list = false;
+        let mut separator = None;
+        let mut or_child = None;
+        let mut or_attr = None;
+        let mut bool_parse = None;
+        let mut true_str = None;
+        let mut false_str = None;
+        let mut map_tag = None;
+        let mut map_key = None;
+        let mut map_value = None;
+        let mut on_duplicate = None;
+        let mut on_duplicate_child = None;
+        let mut access = Access::ReadWrite;
+        let doc = get_doc(&field.attrs)?;
+
+        // `attr`, `child` and `flatten_text` without a value are named
+        // after the field
+        let field_name = field.ident.as_ref().map(|ident| {
+            let name = ident.to_string();
+            let name = name.trim_start_matches("r#");
+            let name = match rename_all {
+                Some(rule) => rule.apply_to_field(name),
+                None => name.to_owned(),
+            };
+            LitStr::new(&name, ident.span())
+        });
+
+        let mut errors = Errors::default();
+
+        let mut parse_meta = |meta: NestedMeta| -> Result<()> {
+            let span = meta.span();
+            let meta = match meta {
+                NestedMeta::Meta(Path(p))
+                    if p.is_ident("attr") || p.is_ident("child") || p.is_ident("flatten_text") =>
+                {
+                    let lit = match &field_name {
+                        Some(lit) => lit.clone(),
+                        None => bail!(
+                            span,
+                            "Tuple fields require a name in `attr`, `child` or `flatten_text` attribute."
+                        ),
+                    };
+                    NestedMeta::Meta(NameValue(MetaNameValue {
+                        path: p,
+                        eq_token: Default::default(),
+                        lit: Str(lit),
+                    }))
+                }
+                meta => meta,
+            };
+            match meta {
+                NestedMeta::Meta(Path(p)) if p.is_ident("default") => {
+                    if default {
+                        bail!(span, "Duplicate `default` attribute.");
+                    } else {
+                        default = true;
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("default") => {
+                    if let Str(lit) = m.lit {
+                        if default {
+                            bail!(span, "Duplicate `default` attribute.");
+                        } else {
+                            default = true;
+                            default_fn = Some(lit.parse::<ExprPath>().map_err(|_| {
+                                Error::new(lit.span(), "Expected a path in `default` attribute.")
+                            })?);
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("attr") => {
+                    if let Str(lit) = m.lit {
+                        if attr_tag.is_some() {
+                            bail!(span, "Duplicate `attr` attribute.");
+                        } else if is_text {
+                            bail!(span, "`attr` attribute and `text` attribute is disjoint.");
+                        } else if is_cdata {
+                            bail!(span, "`attr` attribute and `cdata` attribute is disjoint.")
+                        } else if !child_tags.is_empty() {
+                            bail!(span, "`attr` attribute and `child` attribute is disjoint.");
+                        } else if flatten_text_tag.is_some() {
+                            bail!(
+                                span,
+                                "`attr` attribute and `flatten_text` attribute is disjoint."
+                            );
+                        } else {
+                            attr_tag = Some(lit);
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(Path(ref p)) if p.is_ident("text") => {
+                    if is_text {
+                        bail!(span, "Duplicate `text` attribute.");
+                    } else if attr_tag.is_some() {
+                        bail!(span, "`text` attribute and `attr` attribute is disjoint.");
+                    } else if !child_tags.is_empty() {
+                        bail!(span, "`text` attribute and `child` attribute is disjoint.");
+                    } else if flatten_text_tag.is_some() {
+                        bail!(
+                            span,
+                            "`text` attribute and `flatten_text` attribute is disjoint."
+                        );
+                    } else {
+                        is_text = true;
+                    }
+                }
+                NestedMeta::Meta(Path(ref p)) if p.is_ident("trim") || p.is_ident("preserve") => {
+                    if trim.is_some() {
+                        bail!(
+                            span,
+                            "`trim` attribute and `preserve` attribute is disjoint."
+                        );
+                    } else {
+                        trim = Some(p.is_ident("trim"));
+                    }
+                }
+                NestedMeta::Meta(Path(ref p)) if p.is_ident("cdata") => {
+                    if is_cdata {
+                        bail!(span, "Duplicate `cdata` attribute.");
+                    } else if attr_tag.is_some() {
+                        bail!(span, "`text` attribute and `attr` attribute is disjoint.");
+                    } else if !child_tags.is_empty() {
+                        bail!(span, "`text` attribute and `child` attribute is disjoint.");
+                    } else {
+                        is_cdata = true;
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("child") => {
+                    if let Str(lit) = m.lit {
+                        if is_text {
+                            bail!(span, "`child` attribute and `text` attribute is disjoint.");
+                        } else if attr_tag.is_some() {
+                            bail!(span, "`child` attribute and `attr` attribute is disjoint.");
+                        } else if is_cdata {
+                            bail!(span, "`child` attribute and `cdata` attribute is disjoint.")
+                        } else if flatten_text_tag.is_some() {
+                            bail!(
+                                span,
+                                "`child` attribute and `flatten_text` attribute is disjoint."
+                            );
+                        } else if encoding.is_some() {
+                            bail!(
+                                span,
+                                "`child` attribute and `encoding` attribute is disjoint."
+                            );
+                        } else {
+                            child_tags.push(lit);
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("flatten_text") => {
+                    if let Str(lit) = m.lit {
+                        if is_text {
+                            bail!(
+                                span,
+                                "`flatten_text` attribute and `text` attribute is disjoint."
+                            );
+                        } else if !child_tags.is_empty() {
+                            bail!(
+                                span,
+                                "`flatten_text` attribute and `child` attribute is disjoint."
+                            );
+                        } else if attr_tag.is_some() {
+                            bail!(
+                                span,
+                                "`flatten_text` attribute and `attr` attribute is disjoint."
+                            );
+                        } else if flatten_text_tag.is_some() {
+                            bail!(span, "Duplicate `flatten_text` attribute.");
+                        } else {
+                            flatten_text_tag = Some(lit);
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(Path(ref p)) if p.is_ident("order") => {
+                    if is_order {
+                        bail!(span, "Duplicate `order` attribute.");
+                    } else {
+                        is_order = true;
+                    }
+                }
+                NestedMeta::Meta(Path(ref p)) if p.is_ident("read_only") => {
+                    if access == Access::ReadOnly {
+                        bail!(span, "Duplicate `read_only` attribute.");
+                    } else if access == Access::WriteOnly {
+                        bail!(
+                            span,
+                            "`read_only` attribute and `write_only` attribute is disjoint."
+                        );
+                    } else {
+                        access = Access::ReadOnly;
+                    }
+                }
+                NestedMeta::Meta(Path(ref p)) if p.is_ident("write_only") => {
+                    if access == Access::WriteOnly {
+                        bail!(span, "Duplicate `write_only` attribute.");
+                    } else if access == Access::ReadOnly {
+                        bail!(
+                            span,
+                            "`write_only` attribute and `read_only` attribute is disjoint."
+                        );
+                    } else {
+                        access = Access::WriteOnly;
+                    }
+                }
+                NestedMeta::Meta(Path(ref p)) if p.is_ident("flatten") => {
+                    if is_flatten {
+                        bail!(span, "Duplicate `flatten` attribute.");
+                    } else {
+                        is_flatten = true;
+                    }
+                }
+                NestedMeta::Meta(Path(ref p)) if p.is_ident("attrs") => {
+                    if is_attrs {
+                        bail!(span, "Duplicate `attrs` attribute.");
+                    } else {
+                        is_attrs = true;
+                    }
+                }
+                NestedMeta::Meta(Path(ref p)) if p.is_ident("list") => {
+                    if is_list {
+                        bail!(span, "Duplicate `list` attribute.");
+                    } else {
+                        is_list = true;
+                    }
+                }
+                NestedMeta::Meta(Path(ref p)) if p.is_ident("children") => {
+                    if is_children {
+                        bail!(span, "Duplicate `children` attribute.");
+                    } else {
+                        is_children = true;
+                    }
+                }
+                NestedMeta::Meta(Path(ref p)) if p.is_ident("skip") => {
+                    if is_skip {
+                        bail!(span, "Duplicate `skip` attribute.");
+                    } else {
+                        is_skip = true;
+                    }
+                }
+                NestedMeta::Meta(Path(ref p)) if p.is_ident("tag_name") => {
+                    if is_tag_name {
+                        bail!(span, "Duplicate `tag_name` attribute.");
+                    } else {
+                        is_tag_name = true;
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("min") => {
+                    if let Int(lit) = m.lit {
+                        if occurs.min.is_some() {
+                            bail!(span, "Duplicate `min` attribute.");
+                        } else {
+                            occurs.min = Some(lit.base10_parse()?);
+                        }
+                    } else {
+                        bail!(span, "Expected an integer literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("max") => {
+                    if let Int(lit) = m.lit {
+                        if occurs.max.is_some() {
+                            bail!(span, "Duplicate `max` attribute.");
+                        } else {
+                            occurs.max = Some(lit.base10_parse()?);
+                        }
+                    } else {
+                        bail!(span, "Expected an integer literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("alias") => {
+                    if let Str(lit) = m.lit {
+                        aliases.push(lit);
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("deprecated") => {
+                    if let Str(lit) = m.lit {
+                        deprecated.push(lit);
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("map") => {
+                    if let Str(lit) = m.lit {
+                        if map_tag.is_some() {
+                            bail!(span, "Duplicate `map` attribute.");
+                        } else {
+                            map_tag = Some(lit);
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("key") => {
+                    if let Str(lit) = m.lit {
+                        if map_key.is_some() {
+                            bail!(span, "Duplicate `key` attribute.");
+                        } else {
+                            map_key = Some(lit);
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("value") => {
+                    if let Str(lit) = m.lit {
+                        if map_value.is_some() {
+                            bail!(span, "Duplicate `value` attribute.");
+                        } else {
+                            map_value = Some(lit);
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("separator") => {
+                    if let Str(lit) = m.lit {
+                        if separator.is_some() {
+                            bail!(span, "Duplicate `separator` attribute.");
+                        } else if lit.value().is_empty() {
+                            bail!(span, "`separator` attribute can't be empty.");
+                        } else {
+                            separator = Some(lit);
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("or_child") => {
+                    if let Str(lit) = m.lit {
+                        if or_child.is_some() {
+                            bail!(span, "Duplicate `or_child` attribute.");
+                        } else {
+                            or_child = Some(lit);
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("or_attr") => {
+                    if let Str(lit) = m.lit {
+                        if or_attr.is_some() {
+                            bail!(span, "Duplicate `or_attr` attribute.");
+                        } else {
+                            or_attr = Some(lit);
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("duplicate_key") => {
+                    if let Str(lit) = m.lit {
+                        if on_duplicate.is_some() {
+                            bail!(span, "Duplicate `duplicate_key` attribute.");
+                        } else {
+                            on_duplicate = Some(OnDuplicate::parse(&lit, "duplicate_key")?);
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("duplicate") => {
+                    if let Str(lit) = m.lit {
+                        if on_duplicate_child.is_some() {
+                            bail!(span, "Duplicate `duplicate` attribute.");
+                        } else {
+                            on_duplicate_child = Some(OnDuplicate::parse(&lit, "duplicate")?);
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("wrapper") => {
+                    if let Str(lit) = m.lit {
+                        if wrapper.is_some() {
+                            bail!(span, "Duplicate `wrapper` attribute.");
+                        } else {
+                            wrapper = Some(lit);
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("empty") => {
+                    if let Str(lit) = m.lit {
+                        if empty.is_some() {
+                            bail!(span, "Duplicate `empty` attribute.");
+                        } else {
+                            empty = Some(EmptyPolicy::parse(&lit)?);
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("bool") => {
+                    if let Str(lit) = m.lit {
+                        if bool_parse.is_some() {
+                            bail!(span, "Duplicate `bool` attribute.");
+                        } else {
+                            bool_parse = Some(BoolParse::parse(&lit)?);
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("__true") => {
+                    if let Str(lit) = m.lit {
+                        if true_str.is_some() {
+                            bail!(span, "Duplicate `true` attribute.");
+                        } else {
+                            true_str = Some(lit);
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("__false") => {
+                    if let Str(lit) = m.lit {
+                        if false_str.is_some() {
+                            bail!(span, "Duplicate `false` attribute.");
+                        } else {
+                            false_str = Some(lit);
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("precision") => {
+                    if let Int(lit) = m.lit {
+                        if precision.is_some() {
+                            bail!(span, "Duplicate `precision` attribute.");
+                        } else {
+                            precision = Some(lit.base10_parse()?);
+                        }
+                    } else {
+                        bail!(span, "Expected an integer literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("radix") => {
+                    if let Int(lit) = m.lit {
+                        if radix.is_some() {
+                            bail!(span, "Duplicate `radix` attribute.");
+                        } else {
+                            radix = Some(lit.base10_parse()?);
+                        }
+                    } else {
+                        bail!(span, "Expected an integer literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("prefix") => {
+                    if let Str(lit) = m.lit {
+                        if prefix.is_some() {
+                            bail!(span, "Duplicate `prefix` attribute.");
+                        } else {
+                            prefix = Some(lit);
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("overflow") => {
+                    if let Str(lit) = m.lit {
+                        if overflow.is_some() {
+                            bail!(span, "Duplicate `overflow` attribute.");
+                        } else {
+                            overflow = match lit.value().as_str() {
+                                "error" => Some(false),
+                                "saturate" => Some(true),
+                                _ => bail!(span, "`overflow` attribute only supports \"error\" and \"saturate\"."),
+                            };
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("skip_serializing_if") => {
+                    if let Str(lit) = m.lit {
+                        if skip_if.is_some() {
+                            bail!(span, "Duplicate `skip_serializing_if` attribute.");
+                        } else {
+                            skip_if = Some(lit.parse::<ExprPath>().map_err(|_| {
+                                Error::new(
+                                    lit.span(),
+                                    "Expected a path in `skip_serializing_if` attribute.",
+                                )
+                            })?);
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(NameValue(m)) if m.path.is_ident("encoding") => {
+                    if let Str(lit) = m.lit {
+                        if encoding.is_some() {
+                            bail!(span, "Duplicate `encoding` attribute.");
+                        } else if !child_tags.is_empty() {
+                            bail!(
+                                span,
+                                "`encoding` attribute and `child` attribute is disjoint."
+                            );
+                        } else {
+                            encoding = Some(Encoding::parse(&lit)?);
+                        }
+                    } else {
+                        bail!(span, "Expected a string literal.");
+                    }
+                }
+                NestedMeta::Meta(Path(ref p)) if p.is_ident("base64") || p.is_ident("hex") => {
+                    if encoding.is_some() {
+                        bail!(span, "Duplicate `encoding` attribute.");
+                    } else if !child_tags.is_empty() {
+                        bail!(
+                            span,
+                            "`{}` attribute and `child` attribute is disjoint.",
+                            p.get_ident().unwrap()
+                        );
+                    } else if p.is_ident("hex") {
+                        encoding = Some(Encoding::Hex);
+                    } else {
+                        encoding = Some(Encoding::Base64);
+                    }
+                }
+                _ => (),
+            }
+            Ok(())
+        };
+
+        for meta in xml_metas(&field.attrs)? {
+            errors.ok(parse_meta(meta));
+        }
+
+        errors.finish()?;
+
+        if occurs.is_bounded() && child_tags.is_empty() && flatten_text_tag.is_none() {
+            bail!(
+                span,
+                "`min` and `max` attributes require `child` or `flatten_text` attribute."
+            );
+        }
+
+        if let (Some(min), Some(max)) = (occurs.min, occurs.max) {
+            if min > max {
+                bail!(span, "`min` attribute is greater than `max` attribute.");
+            }
+        }
+
+        if overflow.is_some() && attr_tag.is_none() && !is_text && flatten_text_tag.is_none() {
+            bail!(
+                span,
+                "`overflow` attribute requires `attr`, `text` or `flatten_text` attribute."
+            );
+        }
+
+        let saturate = overflow.unwrap_or(false);
+
+        if trim.is_some() && !is_text && flatten_text_tag.is_none() {
+            bail!(
+                span,
+                "`trim` and `preserve` attributes require `text` or `flatten_text` attribute."
+            );
+        }
+
+        let trim = trim.unwrap_or(default_trim);
+
+        if precision.is_some() && attr_tag.is_none() && !is_text && flatten_text_tag.is_none() {
+            bail!(
+                span,
+                "`precision` attribute requires `attr`, `text` or `flatten_text` attribute."
+            );
+        }
+
+        if radix.is_some() && attr_tag.is_none() && !is_text && flatten_text_tag.is_none() {
+            bail!(
+                span,
+                "`radix` attribute requires `attr`, `text` or `flatten_text` attribute."
+            );
+        }
+
+        if prefix.is_some() && radix.is_none() {
+            bail!(span, "`prefix` attribute requires `radix` attribute.");
+        }
+
+        if radix.is_some() && saturate {
+            bail!(
+                span,
+                "`radix` attribute and `overflow` attribute is disjoint."
+            );
+        }
+
+        if radix.is_some() && precision.is_some() {
+            bail!(
+                span,
+                "`radix` attribute and `precision` attribute is disjoint."
+            );
+        }
+
+        let radix = match radix {
+            Some(radix) if ![2, 8, 16].contains(&radix) => {
+                bail!(span, "`radix` attribute only supports 2, 8 and 16.")
+            }
+            Some(radix) => Some(Radix { radix, prefix }),
+            None => None,
+        };
+
+        let bool_format = if bool_parse.is_some() || true_str.is_some() || false_str.is_some() {
+            if attr_tag.is_none() && !is_text && flatten_text_tag.is_none() {
+                bail!(span, "`bool`, `true` and `false` attributes require `attr`, `text` or `flatten_text` attribute.");
+            }
+            if true_str.is_some()
+                && true_str.as_ref().map(LitStr::value) == false_str.as_ref().map(LitStr::value)
+            {
+                bail!(span, "`true` attribute and `false` attribute is the same.");
+            }
+            Some(BoolFormat {
+                parse: bool_parse.unwrap_or_default(),
+                true_str,
+                false_str,
+            })
+        } else {
+            None
+        };
+
+        if !deprecated.is_empty() && attr_tag.is_none() && flatten_text_tag.is_none() {
+            bail!(
+                span,
+                "`deprecated` attribute requires `attr` or `flatten_text` attribute."
+            );
+        }
+
+        let list = match separator {
+            Some(separator) => Some(Separator::Str(separator)),
+            None if is_list => Some(Separator::Whitespace),
+            None => None,
+        };
+
+        if list.is_some() && attr_tag.is_none() && !is_text && flatten_text_tag.is_none() {
+            bail!(span, "`list` and `separator` attributes require `attr`, `text` or `flatten_text` attribute.");
+        }
+
+        if list.is_some() && occurs.is_bounded() {
+            bail!(span, "`list` and `separator` attributes can't be used together with `min` and `max` attributes.");
+        }
+
+        if !aliases.is_empty() && attr_tag.is_none() {
+            bail!(span, "`alias` attribute requires `attr` attribute.");
+        }
+
+        if skip_if.is_some()
+            && attr_tag.is_none()
+            && child_tags.is_empty()
+            && flatten_text_tag.is_none()
+            && map_tag.is_none()
+        {
+            bail!(span, "`skip_serializing_if` attribute requires `attr`, `child`, `flatten_text` or `map` attribute.");
+        }
+
+        if access != Access::ReadWrite
+            && attr_tag.is_none()
+            && child_tags.is_empty()
+            && flatten_text_tag.is_none()
+            && map_tag.is_none()
+        {
+            bail!(span, "`read_only` and `write_only` attributes require `attr`, `child`, `flatten_text` or `map` attribute.");
+        }
+
+        if map_key.is_some() && map_tag.is_none() && child_tags.is_empty() {
+            bail!(span, "`key` attribute requires `map` or `child` attribute.");
+        }
+
+        if map_value.is_some() && map_tag.is_none() {
+            bail!(span, "`value` attribute requires `map` attribute.");
+        }
+
+        if on_duplicate.is_some() && (child_tags.is_empty() || map_key.is_none()) {
+            bail!(
+                span,
+                "`duplicate_key` attribute requires `child` and `key` attributes."
+            );
+        }
+
+        if on_duplicate_child.is_some()
+            && (child_tags.is_empty() || map_key.is_some())
+            && flatten_text_tag.is_none()
+        {
+            bail!(span, "`duplicate` attribute requires `child` or `flatten_text` attribute, use `duplicate_key` with `key` attribute.");
+        }
+
+        if wrapper.is_some() && child_tags.is_empty() {
+            bail!(span, "`wrapper` attribute requires `child` attribute.");
+        }
+
+        if or_child.is_some() && attr_tag.is_none() {
+            bail!(span, "`or_child` attribute requires `attr` attribute.");
+        }
+
+        if or_attr.is_some() && flatten_text_tag.is_none() {
+            bail!(
+                span,
+                "`or_attr` attribute requires `flatten_text` attribute."
+            );
+        }
+
+        if empty.is_some()
+            && wrapper.is_none()
+            && attr_tag.is_none()
+            && (flatten_text_tag.is_none() || list.is_none())
+        {
+            bail!(
+                span,
+                "`empty` attribute requires `wrapper`, `attr` or `flatten_text` with `list` attribute."
+            );
+        }
+
+        let field = if let Some(tag) = map_tag {
+            if is_children
+                || is_attrs
+                || is_flatten
+                || is_skip
+                || is_tag_name
+                || is_order
+                || attr_tag.is_some()
+                || !child_tags.is_empty()
+                || is_text
+                || flatten_text_tag.is_some()
+                || default
+                || is_cdata
+                || encoding.is_some()
+                || occurs.is_bounded()
+                || overflow.is_some()
+            {
+                bail!(span, "`map` attribute can't be used together with other attributes except `key`, `value`, `skip_serializing_if`, `read_only` and `write_only`.");
+            }
+            if tag.value() == "*" && (map_key.is_some() || map_value.is_some()) {
+                bail!(
+                    span,
+                    "`key` and `value` attributes can't be used with `map = \"*\"`."
+                );
+            }
+            let (key_ty, value_ty) = map_types(&field.ty).ok_or_else(|| {
+                Error::new(
+                    span,
+                    "`map` attribute only supports maps with a key and a value type.",
+                )
+            })?;
+            Field::Map {
+                name,
+                bind,
+                key_ty: Type::parse(key_ty, None)?,
+                value_ty: Type::parse(value_ty, None)?,
+                key: map_key.unwrap_or_else(|| LitStr::new("key", tag.span())),
+                value: map_value.unwrap_or_else(|| LitStr::new("value", tag.span())),
+                tag,
+                skip_if,
+                access,
+                doc,
+            }
+        } else if is_children {
+            if is_attrs
+                || is_flatten
+                || is_skip
+                || is_tag_name
+                || is_order
+                || attr_tag.is_some()
+                || !child_tags.is_empty()
+                || is_text
+                || flatten_text_tag.is_some()
+                || default
+                || is_cdata
+                || encoding.is_some()
+                || occurs.is_bounded()
+                || skip_if.is_some()
+                || access != Access::ReadWrite
+            {
+                bail!(
+                    span,
+                    "`children` attribute can't be used together with other attributes."
+                );
+            }
+            match Type::parse(field.ty, None)? {
+                Type::VecT(ty) => Field::Children {
+                    name,
+                    bind,
+                    ty,
+                    doc,
+                },
+                _ => bail!(span, "`children` attribute only supports Vec<T>."),
+            }
+        } else if is_attrs {
+            if is_flatten
+                || is_skip
+                || is_tag_name
+                || is_order
+                || attr_tag.is_some()
+                || !child_tags.is_empty()
+                || is_text
+                || flatten_text_tag.is_some()
+                || default
+                || is_cdata
+                || encoding.is_some()
+                || occurs.is_bounded()
+                || skip_if.is_some()
+                || access != Access::ReadWrite
+            {
+                bail!(
+                    span,
+                    "`attrs` attribute can't be used together with other attributes."
+                );
+            }
+            Field::Attrs { name, bind, doc }
+        } else if is_flatten {
+            if is_skip
+                || is_tag_name
+                || is_order
+                || attr_tag.is_some()
+                || !child_tags.is_empty()
+                || is_text
+                || flatten_text_tag.is_some()
+                || default
+                || is_cdata
+                || encoding.is_some()
+                || occurs.is_bounded()
+            {
+                bail!(
+                    span,
+                    "`flatten` attribute can't be used together with other attributes."
+                );
+            }
+            Field::Flatten {
+                name,
+                bind,
+                ty: field.ty,
+            }
+        } else if is_skip {
+            if is_tag_name
+                || is_order
+                || attr_tag.is_some()
+                || !child_tags.is_empty()
+                || is_text
+                || flatten_text_tag.is_some()
+                || is_cdata
+                || encoding.is_some()
+                || occurs.is_bounded()
+            {
+                bail!(
+                    span,
+                    "`skip` attribute can't be used together with other attributes."
+                );
+            }
+            Field::Skip {
+                name,
+                bind,
+                default_fn,
+            }
+        } else if is_tag_name {
+            if is_order
+                || attr_tag.is_some()
+                || !child_tags.is_empty()
+                || is_text
+                || flatten_text_tag.is_some()
+                || default
+                || is_cdata
+                || encoding.is_some()
+                || occurs.is_bounded()
+            {
+                bail!(
+                    span,
+                    "`tag_name` attribute can't be used together with other attributes."
+                );
+            }
+            if !matches!(Type::parse(field.ty, None)?, Type::CowStr) {
+                bail!(
+                    span,
+                    "`tag_name` attribute only supports Cow<str> and &str."
+                );
+            }
+            Field::TagName { name, bind }
+        } else if is_order {
+            if attr_tag.is_some()
+                || !child_tags.is_empty()
+                || is_text
+                || flatten_text_tag.is_some()
+                || default
+                || is_cdata
+                || encoding.is_some()
+                || occurs.is_bounded()
+            {
+                bail!(
+                    span,
+                    "`order` attribute can't be used together with other attributes."
+                );
+            }
+            Field::Order { name, bind }
+        } else if let Some(tag) = attr_tag {
+            let ty = Type::parse(field.ty, encoding)?.with_bool_format(bool_format, span)?;
+            if saturate {
+                ty.check_saturate(span)?;
+            }
+            let precision = ty.float_precision(precision, default_precision, span)?;
+            if radix.is_some() {
+                ty.check_radix(span)?;
+            }
+            if list.is_some() && !ty.is_vec() {
+                bail!(span, "`list` and `separator` attributes only support Vec.");
+            }
+            Field::Attribute {
+                name,
+                bind,
+                ty,
+                tag,
+                aliases,
+                deprecated,
+                or_child,
+                default,
+                default_fn,
+                saturate,
+                precision,
+                radix,
+                list,
+                omit_empty: match empty {
+                    Some(EmptyPolicy::SelfClosing) => bail!(
+                        span,
+                        "`empty` attribute only supports \"omit\" and \"expanded\" with `attr` attribute."
+                    ),
+                    empty => empty == Some(EmptyPolicy::Omit),
+                },
+                skip_if,
+                access,
+                doc,
+            }
+        } else if let (false, Some(key)) = (child_tags.is_empty(), map_key) {
+            if wrapper.is_some() || default || occurs.is_bounded() {
+                bail!(span, "`key` attribute can't be used together with `wrapper`, `default`, `min` and `max` attributes.");
+            }
+            let (key_ty, value_ty) = map_types(&field.ty).ok_or_else(|| {
+                Error::new(
+                    span,
+                    "`key` attribute only supports maps with a key and a value type.",
+                )
+            })?;
+            let value_ty = match Type::parse(value_ty, None)? {
+                Type::T(ty) => ty,
+                _ => bail!(span, "`key` attribute only supports maps of T."),
+            };
+            Field::KeyedChild {
+                name,
+                bind,
+                key_ty: Type::parse(key_ty, None)?,
+                value_ty,
+                tags: child_tags,
+                key,
+                on_duplicate: on_duplicate.unwrap_or(OnDuplicate::Error),
+                skip_if,
+                access,
+                doc,
+            }
+        } else if !child_tags.is_empty() {
+            let ty = Type::parse(field.ty, encoding)?;
+            if !matches!(ty, Type::T(_) | Type::OptionT(_) | Type::VecT(_)) {
+                bail!(
+                    span,
+                    "`child` attribute only supports Vec<T>, Option<T> and T."
+                );
+            }
+            if occurs.is_bounded() && !ty.is_vec() {
+                bail!(span, "`min` and `max` attributes only support Vec.");
+            }
+            if ty.option_vec().is_some() && wrapper.is_none() {
+                bail!(
+                    span,
+                    "`child` attribute only supports Option<Vec<T>> with `wrapper` attribute."
+                );
+            }
+            if on_duplicate_child.is_some() && (ty.is_vec() || ty.option_vec().is_some()) {
+                bail!(span, "`duplicate` attribute only supports Option<T> and T.");
+            }
+            Field::Child {
+                name,
+                bind,
+                ty,
+                default,
+                default_fn,
+                tags: child_tags,
+                occurs,
+                empty: empty.or(default_empty).unwrap_or_default(),
+                wrapper,
+                // a later child overwrites the previous one by default
+                on_duplicate: on_duplicate_child.unwrap_or(OnDuplicate::Last),
+                skip_if,
+                access,
+                doc,
+            }
+        } else if is_text {
+            let ty = Type::parse(field.ty, encoding)?.with_bool_format(bool_format, span)?;
+            if saturate {
+                ty.check_saturate(span)?;
+            }
+            let precision = ty.float_precision(precision, default_precision, span)?;
+            if radix.is_some() {
+                ty.check_radix(span)?;
+            }
+            if list.is_some() && !ty.is_vec() {
+                bail!(span, "`list` and `separator` attributes only support Vec.");
+            }
+            if list.is_none() && ty.is_vec() {
+                bail!(span, "`text` attribute doesn't support Vec.");
+            }
+            Field::Text {
+                name,
+                bind,
+                ty,
+                is_cdata,
+                trim,
+                saturate,
+                precision,
+                radix,
+                list,
+                doc,
+            }
+        } else if let Some(tag) = flatten_text_tag {
+            let ty = Type::parse(field.ty, encoding)?.with_bool_format(bool_format, span)?;
+            if occurs.is_bounded() && !ty.is_vec() {
+                bail!(span, "`min` and `max` attributes only support Vec.");
+            }
+            if saturate {
+                ty.check_saturate(span)?;
+            }
+            let precision = ty.float_precision(precision, default_precision, span)?;
+            if radix.is_some() {
+                ty.check_radix(span)?;
+            }
+            if list.is_some() && !ty.is_vec() {
+                bail!(span, "`list` and `separator` attributes only support Vec.");
+            }
+            if on_duplicate_child.is_some() && ty.is_vec() {
+                bail!(span, "`duplicate` attribute only supports Option<T> and T.");
+            }
+            Field::FlattenText {
+                name,
+                bind,
+                ty,
+                default,
+                default_fn,
+                tag,
+                deprecated,
+                or_attr,
+                is_cdata,
+                trim,
+                occurs,
+                on_duplicate: on_duplicate_child.unwrap_or(OnDuplicate::Last),
+                saturate,
+                precision,
+                radix,
+                // only a list has an empty value written apart from a missing one
+                empty: match list {
+                    Some(_) => empty.or(default_empty).unwrap_or_default(),
+                    None => EmptyPolicy::Omit,
+                },
+                list,
+                skip_if,
+                access,
+                doc,
+            }
+        } else if is_marker(&field.ty) {
+            Field::Skip {
+                name,
+                bind,
+                default_fn: None,
+            }
+        } else {
+            bail!(span, "Field should have one of `attr`, `child`, `text`, `flatten_text`, `map`, `order`, `tag_name`, `skip`, `flatten`, `attrs` or `children` attribute.");
+        };
+
+        Ok(field)
+    }
+}
 
 impl Type {
     pub fn is_option(&self) -> bool {
         matches!(
             self,
-            Type::OptionCowStr | Type::OptionT(_) | Type::OptionBool
+            Type::OptionCowStr | Type::OptionT(_) | Type::OptionBool(_) | Type::OptionCowBytes(_)
         )
     }
 
     pub fn is_vec(&self) -> bool {
-        matches!(self, Type::VecCowStr | Type::VecT(_) | Type::VecBool)
+        matches!(
+            self,
+            Type::VecCowStr | Type::VecT(_) | Type::VecBool(_) | Type::VecCowBytes(_)
+        )
+    }
+
+    /// Returns `T` of `Option<Vec<T>>`.
+    pub fn option_vec(&self) -> Option<syn::Type> {
+        match self {
+            Type::OptionT(ty) => match Type::parse(ty.clone(), None) {
+                Ok(Type::VecT(ty)) => Some(ty),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn with_bool_format(self, format: Option<BoolFormat>, span: Span) -> Result<Type> {
+        match (self, format) {
+            (ty, None) => Ok(ty),
+            (Type::Bool(_), Some(format)) => Ok(Type::Bool(format)),
+            (Type::OptionBool(_), Some(format)) => Ok(Type::OptionBool(format)),
+            (Type::VecBool(_), Some(format)) => Ok(Type::VecBool(format)),
+            _ => bail!(
+                span,
+                "`bool` attribute only supports bool, Option<bool> and Vec<bool>."
+            ),
+        }
+    }
+
+    // an explicit precision only applies to numbers, and the one of the
+    // container only to f32 and f64
+    fn float_precision(
+        &self,
+        precision: Option<usize>,
+        default: Option<usize>,
+        span: Span,
+    ) -> Result<Option<usize>> {
+        let ty = match self {
+            Type::T(ty) | Type::OptionT(ty) | Type::VecT(ty) => ty,
+            _ if precision.is_some() => {
+                bail!(span, "`precision` attribute only supports numeric types.")
+            }
+            _ => return Ok(None),
+        };
+        let is_float =
+            matches!(ty, syn::Type::Path(ty) if ty.path.is_ident("f32") || ty.path.is_ident("f64"));
+        Ok(precision.or(default.filter(|_| is_float)))
+    }
+
+    fn check_radix(&self, span: Span) -> Result<()> {
+        if !matches!(self, Type::T(_) | Type::OptionT(_) | Type::VecT(_)) {
+            bail!(span, "`radix` attribute only supports integer types.");
+        }
+        Ok(())
+    }
+
+    fn check_saturate(&self, span: Span) -> Result<()> {
+        if !matches!(self, Type::T(_) | Type::OptionT(_) | Type::VecT(_)) {
+            bail!(span, "`overflow` attribute only supports numeric types.");
+        }
+        Ok(())
     }
 
-    fn parse(mut ty: syn::Type) -> Self {
+    fn parse(mut ty: syn::Type, encoding: Option<Encoding>) -> Result<Self> {
         fn is_vec(ty: &syn::Type) -> Option<&syn::Type> {
+            // slices are written like `Vec`, but can't be read
+            if let Some(ty) = is_slice(ty) {
+                return Some(ty);
+            }
             let path = match ty {
                 syn::Type::Path(ty) => &ty.path,
                 _ => return None,
@@ -420,6 +2564,11 @@ impl Type {
         }
 
         fn is_cow_str(ty: &syn::Type) -> bool {
+            // `&str` is read like `Cow<str>`, but fails if it's not borrowed
+            if let syn::Type::Reference(ty) = ty {
+                return ty.mutability.is_none()
+                    && matches!(&*ty.elem, syn::Type::Path(ty) if ty.path.is_ident("str"));
+            }
             let path = match ty {
                 syn::Type::Path(ty) => &ty.path,
                 _ => return false,
@@ -442,45 +2591,501 @@ impl Type {
             }
         }
 
+        fn is_cow_bytes(ty: &syn::Type) -> bool {
+            let path = match ty {
+                syn::Type::Path(ty) => &ty.path,
+                _ => return false,
+            };
+            let seg = match path.segments.last() {
+                Some(seg) => seg,
+                None => return false,
+            };
+            let args = match &seg.arguments {
+                PathArguments::AngleBracketed(bracketed) => &bracketed.args,
+                _ => return false,
+            };
+            if seg.ident == "Cow" && args.len() == 2 {
+                match &args[1] {
+                    GenericArgument::Type(syn::Type::Slice(ty)) => {
+                        matches!(&*ty.elem, syn::Type::Path(ty) if ty.path.is_ident("u8"))
+                    }
+                    _ => false,
+                }
+            } else {
+                false
+            }
+        }
+
+        fn is_bytes(ty: &syn::Type) -> bool {
+            fn is_u8(ty: &syn::Type) -> bool {
+                matches!(ty, syn::Type::Path(ty) if ty.path.is_ident("u8"))
+            }
+
+            match ty {
+                syn::Type::Array(ty) => is_u8(&ty.elem),
+                _ => is_cow_bytes(ty) || is_vec(ty).is_some_and(is_u8),
+            }
+        }
+
         fn is_bool(ty: &syn::Type) -> bool {
             matches!(ty, syn::Type::Path(ty) if ty.path.is_ident("bool"))
         }
 
+        let span = ty.span();
+
         elide_type_lifetimes(&mut ty);
 
-        if let Some(ty) = is_vec(&ty) {
-            if is_cow_str(&ty) {
+        if let Some(encoding) = encoding {
+            return if is_vec(&ty).is_some_and(is_bytes) {
+                Ok(Type::VecCowBytes(encoding))
+            } else if is_option(&ty).is_some_and(is_bytes) {
+                Ok(Type::OptionCowBytes(encoding))
+            } else if is_bytes(&ty) {
+                Ok(Type::CowBytes(encoding))
+            } else {
+                bail!(span, "`encoding` attribute only supports Cow<[u8]>, Vec<u8> and [u8; N], optionally in Option or Vec.");
+            };
+        }
+
+        if is_cow_bytes(&ty)
+            || is_vec(&ty).is_some_and(is_cow_bytes)
+            || is_option(&ty).is_some_and(is_cow_bytes)
+        {
+            bail!(span, "Cow<[u8]> requires an `encoding` attribute.");
+        }
+
+        let ty = if let Some(ty) = is_vec(&ty) {
+            if is_cow_str(ty) {
                 Type::VecCowStr
-            } else if is_bool(&ty) {
-                Type::VecBool
+            } else if is_bool(ty) {
+                Type::VecBool(BoolFormat::default())
             } else {
                 Type::VecT(ty.clone())
             }
         } else if let Some(ty) = is_option(&ty) {
-            if is_cow_str(&ty) {
+            if is_cow_str(ty) {
                 Type::OptionCowStr
-            } else if is_bool(&ty) {
-                Type::OptionBool
+            } else if is_bool(ty) {
+                Type::OptionBool(BoolFormat::default())
             } else {
                 Type::OptionT(ty.clone())
             }
         } else if is_cow_str(&ty) {
             Type::CowStr
         } else if is_bool(&ty) {
-            Type::Bool
+            Type::Bool(BoolFormat::default())
         } else {
             Type::T(ty)
-        }
+        };
+
+        Ok(ty)
     }
 }
 
-fn get_xml_meta(attr: Attribute) -> Option<Vec<NestedMeta>> {
-    if attr.path.segments.len() == 1 && attr.path.segments[0].ident == "xml" {
-        match attr.parse_meta() {
-            Ok(Meta::List(meta)) => Some(meta.nested.iter().cloned().collect()),
+// whether a field is a zero-sized marker like `PhantomData<T>`, which is
+// skipped without any attribute
+fn is_marker(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(ty) => ty.path.segments.last().is_some_and(|segment| {
+            segment.ident == "PhantomData" || segment.ident == "PhantomPinned"
+        }),
+        syn::Type::Tuple(ty) => ty.elems.is_empty(),
+        _ => false,
+    }
+}
+
+// item type of a shared slice like `&[T]`
+fn is_slice(ty: &syn::Type) -> Option<&syn::Type> {
+    match ty {
+        syn::Type::Reference(ty) if ty.mutability.is_none() => match &*ty.elem {
+            syn::Type::Slice(slice) => Some(&slice.elem),
             _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Checks that every field read by `XmlRead` can be built from the
+/// document, which isn't the case of slices borrowed from other storage.
+pub fn check_readable(input: &DeriveInput) -> Result<()> {
+    let fields: Vec<&syn::Field> = match &input.data {
+        Data::Struct(data) => data.fields.iter().collect(),
+        Data::Enum(data) => data.variants.iter().flat_map(|v| &v.fields).collect(),
+        Data::Union(_) => return Ok(()),
+    };
+
+    let mut errors = Errors::default();
+
+    for field in fields {
+        if is_slice(&field.ty).is_none() {
+            continue;
+        }
+        let is_read = !xml_metas(&field.attrs)?.into_iter().any(|meta| {
+            matches!(meta, NestedMeta::Meta(Path(p)) if p.is_ident("skip") || p.is_ident("write_only"))
+        });
+        if is_read {
+            errors.push(Error::new(
+                field.ty.span(),
+                "`&[T]` fields can only be written, use `Vec<T>` or add `write_only` attribute.",
+            ));
+        }
+    }
+
+    errors.finish()
+}
+
+// whether a newtype wraps a value read from text, rather than an element
+fn is_text_value(ty: &syn::Type) -> bool {
+    const NAMES: &[&str] = &[
+        "String", "char", "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16",
+        "u32", "u64", "u128", "usize",
+    ];
+
+    match Type::parse(ty.clone(), None) {
+        Ok(Type::CowStr | Type::Bool(_)) => true,
+        Ok(Type::T(syn::Type::Path(ty))) => NAMES.iter().any(|name| ty.path.is_ident(name)),
+        _ => false,
+    }
+}
+
+// key and value types of a map like `HashMap<K, V>`, which are its first two
+// type arguments
+fn map_types(ty: &syn::Type) -> Option<(syn::Type, syn::Type)> {
+    let path = match ty {
+        syn::Type::Path(ty) => &ty.path,
+        _ => return None,
+    };
+    let args = match &path.segments.last()?.arguments {
+        PathArguments::AngleBracketed(bracketed) => &bracketed.args,
+        _ => return None,
+    };
+    let mut types = args.iter().filter_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty.clone()),
+        _ => None,
+    });
+    match (types.next(), types.next()) {
+        (Some(key), Some(value)) => Some((key, value)),
+        _ => None,
+    }
+}
+
+impl Encoding {
+    fn parse(lit: &LitStr) -> Result<Encoding> {
+        match lit.value().as_str() {
+            "utf8" | "utf-8" => Ok(Encoding::Utf8),
+            "hex" => Ok(Encoding::Hex),
+            "base64" => Ok(Encoding::Base64),
+            _ => bail!(
+                lit.span(),
+                "Unknown encoding, expected one of `utf8`, `hex` or `base64`."
+            ),
+        }
+    }
+}
+
+// whether `#[xml(case_insensitive)]` is given, which applies to the
+// whole element rather than to its fields
+pub fn is_case_insensitive(attrs: &[Attribute]) -> Result<bool> {
+    has_flag(attrs, "case_insensitive")
+}
+
+// whether `#[xml(owned)]` is given, so strings are copied rather than
+// borrowed from the input
+pub fn is_owned(attrs: &[Attribute]) -> Result<bool> {
+    has_flag(attrs, "owned")
+}
+
+// whether `#[xml(impl_display)]` is given, so `Display` writes the element
+pub fn is_impl_display(attrs: &[Attribute]) -> Result<bool> {
+    has_flag(attrs, "impl_display")
+}
+
+// whether `#[xml(impl_from_str)]` is given, so `FromStr` reads the element
+pub fn is_impl_from_str(attrs: &[Attribute]) -> Result<bool> {
+    has_flag(attrs, "impl_from_str")
+}
+
+// whether `#[xml(impl_try_from)]` is given, so the element converts from
+// `&str` and into `String`
+pub fn is_impl_try_from(attrs: &[Attribute]) -> Result<bool> {
+    has_flag(attrs, "impl_try_from")
+}
+
+fn has_flag(attrs: &[Attribute], key: &str) -> Result<bool> {
+    let mut found = false;
+
+    for meta in xml_metas(attrs)? {
+        if matches!(&meta, NestedMeta::Meta(Path(p)) if p.is_ident(key)) {
+            if found {
+                bail!(meta.span(), "Duplicate `{}` attribute.", key);
+            }
+            found = true;
+        }
+    }
+
+    Ok(found)
+}
+
+// path of `#[xml(crate = "")]`, which is used instead of `hard_xml` in
+// the generated code
+pub fn get_crate_path(attrs: &[Attribute]) -> Result<Option<syn::Path>> {
+    get_str_value(attrs, "crate")?
+        .map(|lit| {
+            lit.parse()
+                .map_err(|_| Error::new(lit.span(), "Expected a path."))
+        })
+        .transpose()
+}
+
+// predicates of `#[xml(bound = "")]`, which replace the ones added for
+// generic fields
+pub fn get_bound(attrs: &[Attribute]) -> Result<Option<Vec<WherePredicate>>> {
+    get_str_value(attrs, "bound")?
+        .map(|lit| {
+            lit.parse_with(punctuated::Punctuated::<WherePredicate, Token![,]>::parse_terminated)
+                .map(|predicates| predicates.into_iter().collect())
+                .map_err(|_| Error::new(lit.span(), "Expected where predicates."))
+        })
+        .transpose()
+}
+
+// value of an attribute like `#[xml(type = "")]`, which can only be given once
+fn get_str_value(attrs: &[Attribute], key: &str) -> Result<Option<LitStr>> {
+    let mut value = None;
+
+    for meta in xml_metas(attrs)? {
+        match meta {
+            NestedMeta::Meta(NameValue(m)) if m.path.is_ident(key) => {
+                if let Str(lit) = m.lit {
+                    if value.is_some() {
+                        bail!(lit.span(), "Duplicate `{}` attribute.", key);
+                    } else {
+                        value = Some(lit);
+                    }
+                } else {
+                    bail!(m.lit.span(), "Expected a string literal.");
+                }
+            }
+            _ => (),
         }
+    }
+
+    Ok(value)
+}
+
+// `#[xml(doc = "")]` takes precedence over doc comments
+fn get_doc(attrs: &[Attribute]) -> Result<Option<String>> {
+    if let Some(doc) = get_str_value(attrs, "doc")? {
+        return Ok(Some(doc.value()));
+    }
+
+    let lines = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(NameValue(MetaNameValue { lit: Str(lit), .. })) => Some(lit.value()),
+            _ => None,
+        })
+        .map(|line| match line.strip_prefix(' ') {
+            Some(line) => line.to_owned(),
+            None => line,
+        })
+        .collect::<Vec<_>>();
+
+    let doc = lines.join("\n").trim().to_owned();
+
+    if doc.is_empty() {
+        Ok(None)
     } else {
-        None
+        Ok(Some(doc))
+    }
+}
+
+// `true = ""` and `false = ""` aren't valid meta since they start with a
+// literal, so they're parsed as `__true = ""` and `__false = ""`
+fn rename_bool_keys(tokens: TokenStream) -> TokenStream {
+    let mut tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    for i in 0..tokens.len() {
+        match (&tokens[i], tokens.get(i + 1)) {
+            (TokenTree::Ident(ident), Some(TokenTree::Punct(punct)))
+                if (ident == "true" || ident == "false") && punct.as_char() == '=' =>
+            {
+                tokens[i] = TokenTree::Ident(format_ident!("__{}", ident, span = ident.span()));
+            }
+            (TokenTree::Group(group), _) => {
+                let mut renamed =
+                    proc_macro2::Group::new(group.delimiter(), rename_bool_keys(group.stream()));
+                renamed.set_span(group.span());
+                tokens[i] = TokenTree::Group(renamed);
+            }
+            _ => (),
+        }
+    }
+    tokens.into_iter().collect()
+}
+
+// every key of `#[xml(...)]`, as they're parsed
+const KEYS: &[&str] = &[
+    "__false",
+    "__true",
+    "alias",
+    "attr",
+    "attrs",
+    "base64",
+    "bool",
+    "bound",
+    "case_insensitive",
+    "cdata",
+    "child",
+    "children",
+    "const_attr",
+    "crate",
+    "default",
+    "deprecated",
+    "doc",
+    "duplicate",
+    "duplicate_key",
+    "empty",
+    "encoding",
+    "flatten",
+    "flatten_text",
+    "hex",
+    "impl_display",
+    "impl_from_str",
+    "impl_try_from",
+    "key",
+    "list",
+    "map",
+    "max",
+    "min",
+    "or_attr",
+    "or_child",
+    "order",
+    "ordered",
+    "other",
+    "overflow",
+    "owned",
+    "partial",
+    "precision",
+    "prefix",
+    "preserve",
+    "radix",
+    "read_only",
+    "rename",
+    "rename_all",
+    "separator",
+    "skip",
+    "skip_serializing_if",
+    "tag",
+    "tag_name",
+    "text",
+    "trim",
+    "type",
+    "type_attr",
+    "untagged",
+    "value",
+    "wrapper",
+    "write_only",
+];
+
+// nested metas of every `#[xml(...)]` attribute, with the errors of all
+// malformed ones and unknown keys
+fn xml_metas(attrs: &[Attribute]) -> Result<Vec<NestedMeta>> {
+    let mut metas = Vec::new();
+    let mut errors = Errors::default();
+
+    for attr in attrs {
+        if !attr.path.is_ident("xml") {
+            continue;
+        }
+        let attr = Attribute {
+            tokens: rename_bool_keys(attr.tokens.clone()),
+            ..attr.clone()
+        };
+        match attr.parse_meta() {
+            Ok(Meta::List(meta)) => {
+                for meta in meta.nested {
+                    if errors.ok(check_key(&meta)).is_some() {
+                        metas.push(meta);
+                    }
+                }
+            }
+            Ok(meta) => errors.push(Error::new(meta.span(), "Expected `#[xml(...)]` attribute.")),
+            Err(err) => errors.push(Error::new(
+                err.span(),
+                format_args!("Malformed `xml` attribute: {}.", err),
+            )),
+        }
+    }
+
+    errors.finish()?;
+
+    Ok(metas)
+}
+
+// unknown keys are reported with the closest known one, since they would
+// be ignored otherwise
+fn check_key(meta: &NestedMeta) -> Result<()> {
+    let path = match meta {
+        NestedMeta::Meta(meta) => meta.path(),
+        NestedMeta::Lit(lit) => bail!(
+            lit.span(),
+            "Expected an attribute like `tag = \"...\"`, found a literal."
+        ),
+    };
+    let key = match path.get_ident() {
+        Some(ident) => ident.to_string(),
+        None => String::new(),
+    };
+    if KEYS.contains(&key.as_str()) {
+        return Ok(());
+    }
+    let span = path.span();
+    let key = quote!(#path).to_string().replace(' ', "");
+    let closest = KEYS
+        .iter()
+        .map(|known| known.trim_start_matches("__"))
+        .map(|known| (distance(&key, known), known))
+        .min();
+    match closest {
+        Some((distance, known)) if distance <= 2 => {
+            bail!(
+                span,
+                "Unknown `{}` attribute, did you mean `{}`?",
+                key,
+                known
+            )
+        }
+        _ => {
+            let mut keys = KEYS
+                .iter()
+                .map(|known| format!("`{}`", known.trim_start_matches("__")))
+                .collect::<Vec<_>>();
+            keys.sort();
+            bail!(
+                span,
+                "Unknown `{}` attribute, expected one of {}.",
+                key,
+                keys.join(", ")
+            )
+        }
+    }
+}
+
+// edit distance between two keys
+fn distance(a: &str, b: &str) -> usize {
+    let b = b.as_bytes();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.bytes().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let next = (prev + (ca != *cb) as usize)
+                .min(row[j] + 1)
+                .min(row[j + 1] + 1);
+            prev = row[j + 1];
+            row[j + 1] = next;
+        }
     }
+    row[b.len()]
 }
@@ -0,0 +1,23 @@
+use proc_macro2::{Group, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::Path;
+
+// replace every `hard_xml` identifier with the given path, e.g. one the
+// crate is re-exported at
+//
+// hard_xml::XmlRead => my_facade::xml::XmlRead
+pub fn replace_crate_path(tokens: TokenStream, path: &Path) -> TokenStream {
+    tokens
+        .into_iter()
+        .flat_map(|token| match token {
+            TokenTree::Ident(ident) if ident == "hard_xml" => path.to_token_stream(),
+            TokenTree::Group(group) => {
+                let mut replaced =
+                    Group::new(group.delimiter(), replace_crate_path(group.stream(), path));
+                replaced.set_span(group.span());
+                TokenTree::Group(replaced).into()
+            }
+            token => token.into(),
+        })
+        .collect()
+}
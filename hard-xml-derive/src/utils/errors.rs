@@ -0,0 +1,35 @@
+use syn::{Error, Result};
+
+/// Errors found while parsing the attributes, which are reported together
+/// rather than stopping at the first one.
+#[derive(Default)]
+pub struct Errors(Option<Error>);
+
+impl Errors {
+    pub fn push(&mut self, err: Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(err),
+            None => self.0 = Some(err),
+        }
+    }
+
+    /// Returns the value of `result`, keeping its error to report it with
+    /// the other ones.
+    pub fn ok<T>(&mut self, result: Result<T>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(err) => {
+                self.push(err);
+                None
+            }
+        }
+    }
+
+    /// Returns the errors found so far, if any.
+    pub fn finish(self) -> Result<()> {
+        match self.0 {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
@@ -1,5 +1,11 @@
+mod crate_path;
 mod elide_lifetime;
+mod errors;
 mod input_lifetime;
+mod rename_rule;
 
+pub use crate_path::replace_crate_path;
 pub use elide_lifetime::elide_type_lifetimes;
+pub use errors::Errors;
 pub use input_lifetime::gen_input_lifetime;
+pub use rename_rule::RenameRule;
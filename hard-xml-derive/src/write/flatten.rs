@@ -0,0 +1,65 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+use super::named::{content_is_empty, write_attributes, write_children};
+use crate::types::Field;
+
+/// Generates the items of `XmlWriteFlatten` for structs without `tag`
+/// attribute.
+pub fn write(ele_name: &Ident, fields: &[Field]) -> TokenStream {
+    let bindings = fields
+        .iter()
+        .map(|field| {
+            let name = field.name();
+            match field {
+                Field::Skip { .. } => quote!( #name: _ ),
+                field => {
+                    let bind = field.bind();
+                    quote!( #name: #bind )
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let write_attributes = write_attributes(fields, &quote!(#ele_name));
+
+    let write_children = write_children(fields, &quote!(#ele_name));
+
+    let content_is_empty = content_is_empty(fields);
+
+    quote! {
+        #[allow(unused_mut)]
+        fn write_attributes<__W: std::io::Write>(
+            &self,
+            mut writer: &mut hard_xml::XmlWriter<__W>,
+        ) -> hard_xml::XmlResult<()> {
+            #[allow(unused_variables)]
+            let #ele_name { #( #bindings ),* } = self;
+
+            #write_attributes
+
+            Ok(())
+        }
+
+        #[allow(unused_mut)]
+        fn write_children<__W: std::io::Write>(
+            &self,
+            mut writer: &mut hard_xml::XmlWriter<__W>,
+        ) -> hard_xml::XmlResult<()> {
+            #[allow(unused_variables)]
+            let #ele_name { #( #bindings ),* } = self;
+
+            #write_children
+
+            Ok(())
+        }
+
+        fn is_content_empty(&self) -> bool {
+            #[allow(unused_variables)]
+            let #ele_name { #( #bindings ),* } = self;
+
+            #content_is_empty
+        }
+    }
+}
@@ -1,16 +1,83 @@
+mod flatten;
 mod named;
 mod newtype;
 
-use crate::types::{Element, Field, Fields};
+use crate::types::{Element, Field, Fields, Other};
 
 use proc_macro2::TokenStream;
 use quote::quote;
 
-pub fn impl_write(element: Element) -> TokenStream {
+/// Generates the body of `Display::fmt` for enums written as text, and for
+/// elements with `impl_display` attribute.
+pub fn impl_display(element: &Element, impl_display: bool) -> Option<TokenStream> {
     match element {
+        Element::TextEnum {
+            name,
+            variants,
+            other,
+            ..
+        } => {
+            let values = variants.iter().map(|(_, value)| value);
+            let variants = variants.iter().map(|(variant, _)| variant);
+
+            // the text of a unit `other` variant is lost, so it's written
+            // as its name
+            let write_other = other.as_ref().map(|Other { name: other, ty }| match ty {
+                Some(_) => quote! { #name::#other(__inner) => &**__inner, },
+                None => quote! { #name::#other => stringify!(#other), },
+            });
+
+            Some(quote! {
+                f.write_str(match self {
+                    #( #name::#variants => #values, )*
+                    #write_other
+                })
+            })
+        }
+        Element::Flatten { .. } if impl_display => {
+            panic!("`impl_display` attribute requires `tag` attribute.")
+        }
+        Element::Struct { .. } | Element::Enum { .. } if impl_display => Some(quote! {
+            hard_xml::XmlWrite::write_to(self, f)
+        }),
+        _ => None,
+    }
+}
+
+/// Generates the body of `TryFrom<&T>::try_from` for `String`, for elements
+/// with `impl_try_from` attribute.
+pub fn impl_try_into(element: &Element, impl_try_from: bool) -> Option<TokenStream> {
+    match element {
+        _ if !impl_try_from => None,
+        Element::Flatten { .. } => panic!("`impl_try_from` attribute requires `tag` attribute."),
+        Element::TextEnum { .. } => Some(quote! {
+            Ok(std::string::ToString::to_string(value))
+        }),
+        Element::Struct { .. } | Element::Enum { .. } => Some(quote! {
+            hard_xml::XmlWrite::to_string(value)
+        }),
+    }
+}
+
+/// Generates the items of `XmlWriteFlatten` for structs without `tag`
+/// attribute.
+pub fn impl_write_flatten(element: &Element) -> Option<TokenStream> {
+    match element {
+        Element::Flatten { name, fields, .. } => Some(flatten::write(name, fields)),
+        _ => None,
+    }
+}
+
+/// Generates the body of `to_writer`, or nothing for enums written as
+/// text and structs which have no `tag` attribute.
+pub fn impl_write(element: Element) -> Option<TokenStream> {
+    let write = match element {
         Element::Enum {
             name: ele_name,
             variants,
+            discriminator,
+            other,
+            ..
         } => {
             let branches = variants.iter().map(|variant| match variant {
                 Fields::Named { name, fields, .. } => {
@@ -18,7 +85,15 @@ pub fn impl_write(element: Element) -> TokenStream {
                         Field::Attribute { bind, name, .. }
                         | Field::Child { bind, name, .. }
                         | Field::Text { bind, name, .. }
-                        | Field::FlattenText { bind, name, .. } => quote!( #name: #bind ),
+                        | Field::FlattenText { bind, name, .. }
+                        | Field::Map { bind, name, .. }
+                        | Field::KeyedChild { bind, name, .. }
+                        | Field::Order { bind, name }
+                        | Field::TagName { bind, name }
+                        | Field::Flatten { bind, name, .. }
+                        | Field::Attrs { bind, name, .. }
+                        | Field::Children { bind, name, .. } => quote!( #name: #bind ),
+                        Field::Skip { name, .. } => quote!( #name: _ ),
                     });
                     quote!( #ele_name::#name { #( #bindings ),* } )
                 }
@@ -26,15 +101,47 @@ pub fn impl_write(element: Element) -> TokenStream {
             });
 
             let read = variants.iter().map(|variant| match variant {
-                Fields::Named { tag, name, fields } => {
-                    named::write(&tag, quote!( #ele_name::#name ), &fields)
+                Fields::Named {
+                    tag,
+                    name,
+                    fields,
+                    const_attrs,
+                    ordered,
+                    ..
+                } => named::write(
+                    tag,
+                    const_attrs,
+                    quote!( #ele_name::#name ),
+                    fields,
+                    *ordered,
+                ),
+                Fields::Newtype { name, tags, ty } => {
+                    newtype::write(tags, ty, quote!( #ele_name::#name ))
                 }
-                Fields::Newtype { name, .. } => newtype::write(quote!( #ele_name::#name )),
+            });
+
+            let push_type = variants.iter().enumerate().map(|(index, _)| {
+                discriminator.as_ref().map(|discriminator| {
+                    let attr = &discriminator.attr;
+                    let value = &discriminator.values[index];
+                    quote! { writer.push_attribute(#attr, #value); }
+                })
+            });
+
+            // unknown elements which weren't captured are written as nothing
+            let write_other = other.map(|Other { name, ty }| match ty {
+                Some(_) => quote! {
+                    #ele_name::#name(__inner) => {
+                        __inner.to_writer(writer)?;
+                    },
+                },
+                None => quote! { #ele_name::#name => (), },
             });
 
             quote! {
                 match self {
-                    #( #branches => { #read }, )*
+                    #( #branches => { #push_type #read }, )*
+                    #write_other
                 }
             }
         }
@@ -42,16 +149,32 @@ pub fn impl_write(element: Element) -> TokenStream {
         Element::Struct {
             name: ele_name,
             fields,
+            ..
         } => match fields {
-            Fields::Named { tag, name, fields } => {
+            Fields::Named {
+                tag,
+                name,
+                fields,
+                const_attrs,
+                ordered,
+                ..
+            } => {
                 let bindings = fields.iter().map(|field| match field {
                     Field::Attribute { bind, name, .. }
                     | Field::Child { bind, name, .. }
                     | Field::Text { bind, name, .. }
-                    | Field::FlattenText { bind, name, .. } => quote!( #name: #bind ),
+                    | Field::FlattenText { bind, name, .. }
+                    | Field::Map { bind, name, .. }
+                    | Field::KeyedChild { bind, name, .. }
+                    | Field::Order { bind, name }
+                    | Field::TagName { bind, name }
+                    | Field::Flatten { bind, name, .. }
+                    | Field::Attrs { bind, name, .. }
+                    | Field::Children { bind, name, .. } => quote!( #name: #bind ),
+                    Field::Skip { name, .. } => quote!( #name: _ ),
                 });
 
-                let read = named::write(&tag, quote!(#name), &fields);
+                let read = named::write(&tag, &const_attrs, quote!(#name), &fields, ordered);
 
                 quote! {
                     let #ele_name { #( #bindings ),* } = self;
@@ -59,8 +182,8 @@ pub fn impl_write(element: Element) -> TokenStream {
                     #read
                 }
             }
-            Fields::Newtype { name, .. } => {
-                let read = newtype::write(quote!(#name));
+            Fields::Newtype { name, tags, ty } => {
+                let read = newtype::write(&tags, &ty, quote!(#name));
 
                 quote! {
                     let __inner = &self.0;
@@ -69,5 +192,17 @@ pub fn impl_write(element: Element) -> TokenStream {
                 }
             }
         },
-    }
+
+        Element::TextEnum { tag, .. } => {
+            let tag = tag?;
+
+            quote! {
+                writer.write_flatten_text(#tag, self, false)?;
+            }
+        }
+
+        Element::Flatten { .. } => return None,
+    };
+
+    Some(write)
 }
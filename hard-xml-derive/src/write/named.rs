@@ -1,63 +1,165 @@
-use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{Ident, LitStr};
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{ExprPath, Ident, LitStr};
 
-use crate::types::{Field, Type};
+use crate::types::{ConstAttr, EmptyPolicy, Encoding, Field, Radix, Separator, Type};
 
-pub fn write(tag: &LitStr, ele_name: TokenStream, fields: &[Field]) -> TokenStream {
-    let write_attributes = fields.iter().filter_map(|field| match field {
-        Field::Attribute { tag, bind, ty, .. } => Some(write_attrs(&tag, &bind, &ty, &ele_name)),
+pub fn write(
+    tag: &LitStr,
+    const_attrs: &[ConstAttr],
+    ele_name: TokenStream,
+    fields: &[Field],
+    ordered: bool,
+) -> TokenStream {
+    let tag_name = fields.iter().find_map(|field| match field {
+        Field::TagName { bind, name } => Some((bind, name)),
         _ => None,
     });
 
+    // an empty tag name falls back to the declared tag, unless it's a wildcard
+    let (init_tag, tag) = match tag_name {
+        Some((bind, name)) if tag.value() == "*" => (
+            quote! {
+                if #bind.is_empty() {
+                    return Err(hard_xml::utils::missing_field(
+                        stringify!(#ele_name),
+                        stringify!(#name),
+                        "",
+                        "",
+                    ));
+                }
+                let __tag: &str = &**#bind;
+            },
+            quote! { __tag },
+        ),
+        Some((bind, _)) => (
+            quote! { let __tag: &str = if #bind.is_empty() { #tag } else { &**#bind }; },
+            quote! { __tag },
+        ),
+        None => (quote! {}, quote! { #tag }),
+    };
+
+    let write_const_attrs = const_attrs.iter().map(|ConstAttr { name, value, .. }| {
+        quote! { writer.write_attribute(#name, #value)?; }
+    });
+
+    let write_attributes = write_attributes(fields, &ele_name);
+
     let write_text = fields.iter().filter_map(|field| match field {
         Field::Text {
-            bind, ty, is_cdata, ..
-        } => Some(write_text(tag, bind, ty, &ele_name, *is_cdata)),
+            bind,
+            ty,
+            is_cdata,
+            precision,
+            radix,
+            list,
+            ..
+        } => Some(write_text(
+            &tag,
+            bind,
+            ty,
+            *precision,
+            radix.as_ref(),
+            list.as_ref(),
+            &ele_name,
+            *is_cdata,
+        )),
         _ => None,
     });
 
-    let write_flatten_text = fields.iter().filter_map(|field| match field {
+    // children of ordered elements are written in declaration order
+    let write_declared = fields.iter().filter_map(|field| match field {
+        Field::Child {
+            bind,
+            ty,
+            wrapper,
+            empty,
+            skip_if,
+            ..
+        } => Some(skip_write(
+            skip_if.as_ref(),
+            bind,
+            write_child(bind, ty, wrapper.as_ref(), *empty, &ele_name),
+        )),
         Field::FlattenText {
             tag,
             bind,
             ty,
             is_cdata,
+            precision,
+            radix,
+            list,
+            empty,
+            skip_if,
             ..
-        } => Some(write_flatten_text(tag, bind, ty, &ele_name, *is_cdata)),
+        } => Some(skip_write(
+            skip_if.as_ref(),
+            bind,
+            write_flatten_text(
+                tag,
+                bind,
+                ty,
+                *precision,
+                radix.as_ref(),
+                list.as_ref(),
+                *empty,
+                &ele_name,
+                *is_cdata,
+            ),
+        )),
+        Field::KeyedChild { bind, skip_if, .. } => Some(skip_write(
+            skip_if.as_ref(),
+            bind,
+            write_keyed_children(bind, &ele_name),
+        )),
+        Field::Map {
+            bind,
+            key_ty,
+            value_ty,
+            tag,
+            key,
+            value,
+            skip_if,
+            ..
+        } => Some(skip_write(
+            skip_if.as_ref(),
+            bind,
+            write_map(tag, key, value, bind, key_ty, value_ty, &ele_name),
+        )),
+        Field::Flatten { bind, .. } => Some(write_flatten_children(bind)),
+        Field::Children { bind, .. } => Some(write_other_children(bind)),
         _ => None,
     });
 
-    let write_child = fields.iter().filter_map(|field| match field {
-        Field::Child { bind, ty, .. } => Some(write_child(bind, ty, &ele_name)),
+    let order = fields.iter().find_map(|field| match field {
+        Field::Order { bind, .. } => Some(bind),
         _ => None,
     });
 
-    let is_leaf_element = fields
-        .iter()
-        .all(|field| matches!(field, Field::Attribute { .. }));
+    let write_children = match order {
+        Some(order) => write_ordered(order, fields, &ele_name),
+        None if ordered => quote! {
+            #( #write_declared )*
+        },
+        None => write_children(fields, &ele_name),
+    };
+
+    let is_leaf_element = fields.iter().all(|field| {
+        matches!(
+            field,
+            Field::Attribute { .. }
+                | Field::Order { .. }
+                | Field::TagName { .. }
+                | Field::Skip { .. }
+                | Field::Attrs { .. }
+        )
+    });
 
     let is_text_element = fields
         .iter()
         .any(|field| matches!(field, Field::Text { .. }));
 
-    let can_self_close = fields.iter().all(|field| match field {
-        Field::Child { ty, .. } | Field::FlattenText { ty, .. } => ty.is_vec() || ty.is_option(),
-        _ => true,
-    });
-
-    let content_is_empty = fields.iter().filter_map(|field| match field {
-        Field::Child { ty, bind, .. } | Field::FlattenText { ty, bind, .. } => {
-            if ty.is_vec() {
-                Some(quote! { #bind.is_empty() })
-            } else if ty.is_option() {
-                Some(quote! { #bind.is_none() })
-            } else {
-                None
-            }
-        }
-        _ => None,
-    });
+    let content_is_empty = content_is_empty(fields);
 
     let write_element_end = if is_leaf_element {
         quote! { writer.write_element_end_empty()?; }
@@ -65,12 +167,11 @@ pub fn write(tag: &LitStr, ele_name: TokenStream, fields: &[Field]) -> TokenStre
         quote! { #( #write_text )* }
     } else {
         quote! {
-            if #can_self_close #( && #content_is_empty )* {
+            if #content_is_empty {
                 writer.write_element_end_empty()?;
             } else {
                 writer.write_element_end_open()?;
-                #( #write_child )*
-                #( #write_flatten_text )*
+                #write_children
                 writer.write_element_end_close(#tag)?;
             }
         }
@@ -79,9 +180,13 @@ pub fn write(tag: &LitStr, ele_name: TokenStream, fields: &[Field]) -> TokenStre
     quote! {
         hard_xml::log_start_writing!(#ele_name);
 
+        #init_tag
+
         writer.write_element_start(#tag)?;
 
-        #( #write_attributes )*
+        #( #write_const_attrs )*
+
+        #write_attributes
 
         #write_element_end
 
@@ -89,34 +194,376 @@ pub fn write(tag: &LitStr, ele_name: TokenStream, fields: &[Field]) -> TokenStre
     }
 }
 
-fn write_attrs(tag: &LitStr, name: &Ident, ty: &Type, ele_name: &TokenStream) -> TokenStream {
-    let to_str = to_str(ty);
+pub(super) fn write_attributes(fields: &[Field], ele_name: &TokenStream) -> TokenStream {
+    let write = fields.iter().filter_map(|field| match field {
+        Field::Attribute {
+            tag,
+            bind,
+            ty,
+            precision,
+            radix,
+            list,
+            omit_empty,
+            skip_if,
+            ..
+        } => Some(skip_write(
+            skip_if.as_ref(),
+            bind,
+            write_attrs(
+                tag,
+                bind,
+                ty,
+                *precision,
+                radix.as_ref(),
+                list.as_ref(),
+                *omit_empty,
+                ele_name,
+            ),
+        )),
+        Field::Flatten { bind, .. } => Some(quote! {
+            hard_xml::XmlWriteFlatten::write_attributes(#bind, &mut writer)?;
+        }),
+        Field::Attrs { bind, .. } => Some(quote! {
+            for (__key, __value) in #bind {
+                writer.write_attribute(&**__key, &**__value)?;
+            }
+        }),
+        _ => None,
+    });
+
+    quote! { #( #write )* }
+}
+
+// children, then keyed children, then map entries, then flatten text
+// elements, then flattened children
+pub(super) fn write_children(fields: &[Field], ele_name: &TokenStream) -> TokenStream {
+    let write_flatten_text = fields.iter().filter_map(|field| match field {
+        Field::FlattenText {
+            tag,
+            bind,
+            ty,
+            is_cdata,
+            precision,
+            radix,
+            list,
+            empty,
+            skip_if,
+            ..
+        } => Some(skip_write(
+            skip_if.as_ref(),
+            bind,
+            write_flatten_text(
+                tag,
+                bind,
+                ty,
+                *precision,
+                radix.as_ref(),
+                list.as_ref(),
+                *empty,
+                ele_name,
+                *is_cdata,
+            ),
+        )),
+        _ => None,
+    });
+
+    let write_child = fields.iter().filter_map(|field| match field {
+        Field::Child {
+            bind,
+            ty,
+            wrapper,
+            empty,
+            skip_if,
+            ..
+        } => Some(skip_write(
+            skip_if.as_ref(),
+            bind,
+            write_child(bind, ty, wrapper.as_ref(), *empty, ele_name),
+        )),
+        _ => None,
+    });
+
+    let write_keyed_child = fields.iter().filter_map(|field| match field {
+        Field::KeyedChild { bind, skip_if, .. } => Some(skip_write(
+            skip_if.as_ref(),
+            bind,
+            write_keyed_children(bind, ele_name),
+        )),
+        _ => None,
+    });
+
+    let write_map = fields.iter().filter_map(|field| match field {
+        Field::Map {
+            bind,
+            key_ty,
+            value_ty,
+            tag,
+            key,
+            value,
+            skip_if,
+            ..
+        } => Some(skip_write(
+            skip_if.as_ref(),
+            bind,
+            write_map(tag, key, value, bind, key_ty, value_ty, ele_name),
+        )),
+        _ => None,
+    });
+
+    let write_flatten_children = fields.iter().filter_map(|field| match field {
+        Field::Flatten { bind, .. } => Some(write_flatten_children(bind)),
+        _ => None,
+    });
+
+    let write_other_children = fields.iter().filter_map(|field| match field {
+        Field::Children { bind, .. } => Some(write_other_children(bind)),
+        _ => None,
+    });
+
+    quote! {
+        #( #write_child )*
+        #( #write_keyed_child )*
+        #( #write_map )*
+        #( #write_flatten_text )*
+        #( #write_flatten_children )*
+        #( #write_other_children )*
+    }
+}
+
+fn write_flatten_children(bind: &Ident) -> TokenStream {
+    quote! {
+        hard_xml::XmlWriteFlatten::write_children(#bind, &mut writer)?;
+    }
+}
 
-    if ty.is_vec() {
-        panic!("`attr` attribute doesn't support Vec.");
+fn write_other_children(bind: &Ident) -> TokenStream {
+    quote! {
+        for __value in #bind {
+            __value.to_writer(&mut writer)?;
+        }
+    }
+}
+
+/// Whether no child would be written, so the element can be self-closed.
+pub(super) fn content_is_empty(fields: &[Field]) -> TokenStream {
+    // a kept wrapper or list is written even if there's nothing in it
+    let keeps_wrapper = |field: &Field| match field {
+        Field::Child {
+            ty,
+            wrapper: Some(_),
+            empty,
+            ..
+        } => *empty != EmptyPolicy::Omit && ty.option_vec().is_none(),
+        Field::FlattenText { empty, .. } => *empty != EmptyPolicy::Omit,
+        _ => false,
+    };
+
+    let can_self_close = fields.iter().all(|field| match field {
+        Field::Child { skip_if, .. } | Field::FlattenText { skip_if, .. }
+            if keeps_wrapper(field) =>
+        {
+            skip_if.is_some()
+        }
+        Field::Child { ty, skip_if, .. } | Field::FlattenText { ty, skip_if, .. } => {
+            ty.is_vec() || ty.is_option() || skip_if.is_some()
+        }
+        _ => true,
+    });
+
+    let content_is_empty = fields.iter().filter_map(|field| match field {
+        Field::Child {
+            ty, bind, skip_if, ..
+        }
+        | Field::FlattenText {
+            ty, bind, skip_if, ..
+        } => {
+            let is_empty = if keeps_wrapper(field) {
+                None
+            } else if ty.is_vec() {
+                Some(quote! { #bind.is_empty() })
+            } else if ty.is_option() {
+                Some(quote! { #bind.is_none() })
+            } else {
+                None
+            };
+            match (skip_if, is_empty) {
+                (Some(skip_if), Some(is_empty)) => Some(quote! { (#skip_if(#bind) || #is_empty) }),
+                (Some(skip_if), None) => Some(quote! { #skip_if(#bind) }),
+                (None, is_empty) => is_empty,
+            }
+        }
+        Field::Flatten { bind, .. } => Some(quote! {
+            hard_xml::XmlWriteFlatten::is_content_empty(#bind)
+        }),
+        Field::Children { bind, .. } => Some(quote! { #bind.is_empty() }),
+        Field::Map { bind, skip_if, .. } | Field::KeyedChild { bind, skip_if, .. } => {
+            let is_empty = quote! { #bind.into_iter().next().is_none() };
+            match skip_if {
+                Some(skip_if) => Some(quote! { (#skip_if(#bind) || #is_empty) }),
+                None => Some(is_empty),
+            }
+        }
+        _ => None,
+    });
+
+    quote! { #can_self_close #( && #content_is_empty )* }
+}
+
+// guards the writing of a field with its `skip_serializing_if` function
+fn skip_write(skip_if: Option<&ExprPath>, bind: &Ident, write: TokenStream) -> TokenStream {
+    match skip_if {
+        Some(skip_if) => quote! {
+            if !#skip_if(#bind) {
+                #write
+            }
+        },
+        None => write,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn write_attrs(
+    tag: &LitStr,
+    name: &Ident,
+    ty: &Type,
+    precision: Option<usize>,
+    radix: Option<&Radix>,
+    list: Option<&Separator>,
+    omit_empty: bool,
+    ele_name: &TokenStream,
+) -> TokenStream {
+    let to_str = to_str(ty, precision, radix);
+    let write_attribute = |value: &TokenStream| {
+        if omit_empty {
+            quote! {
+                let __attr = #value.to_string();
+                if !__attr.is_empty() {
+                    writer.write_attribute(#tag, __attr)?;
+                }
+            }
+        } else {
+            quote! { writer.write_attribute(#tag, #value)?; }
+        }
+    };
+
+    if let Some(separator) = list {
+        let write_attribute = write_attribute(&join_list(separator, name, &to_str));
+        // an empty list is written as a missing attribute
+        quote! {
+            hard_xml::log_start_writing_field!(#ele_name, #name);
+
+            if !#name.is_empty() {
+                #write_attribute
+            }
+
+            hard_xml::log_finish_writing_field!(#ele_name, #name);
+        }
+    } else if ty.is_vec() {
+        let write_attribute = write_attribute(&to_str);
+        quote! {
+            hard_xml::log_start_writing_field!(#ele_name, #name);
+
+            for __value in #name.iter() {
+                #write_attribute
+            }
+
+            hard_xml::log_finish_writing_field!(#ele_name, #name);
+        }
     } else if ty.is_option() {
+        let write_attribute = write_attribute(&to_str);
         quote! {
             hard_xml::log_start_writing_field!(#ele_name, #name);
 
             if let Some(__value) = #name {
-                writer.write_attribute(#tag, #to_str)?;
+                #write_attribute
             }
 
             hard_xml::log_finish_writing_field!(#ele_name, #name);
         }
     } else {
+        let write_attribute = write_attribute(&to_str);
         quote! {
             hard_xml::log_start_writing_field!(#ele_name, #name);
 
             let __value = #name;
-            writer.write_attribute(#tag, #to_str)?;
+            #write_attribute
 
             hard_xml::log_finish_writing_field!(#ele_name, #name);
         }
     }
 }
 
-fn write_child(name: &Ident, ty: &Type, ele_name: &TokenStream) -> TokenStream {
+fn write_child(
+    name: &Ident,
+    ty: &Type,
+    wrapper: Option<&LitStr>,
+    empty: EmptyPolicy,
+    ele_name: &TokenStream,
+) -> TokenStream {
+    if let (Some(wrapper), Some(_)) = (wrapper, ty.option_vec()) {
+        let write_empty = match empty {
+            EmptyPolicy::Expanded => quote! {
+                writer.write_element_end_open()?;
+                writer.write_element_end_close(#wrapper)?;
+            },
+            _ => quote! { writer.write_element_end_empty()?; },
+        };
+        // an empty wrapper is kept, only `None` omits it
+        return quote! {
+            hard_xml::log_start_writing_field!(#ele_name, #name);
+
+            if let Some(#name) = #name {
+                writer.write_element_start(#wrapper)?;
+                if #name.is_empty() {
+                    #write_empty
+                } else {
+                    writer.write_element_end_open()?;
+                    for ele in #name.iter() {
+                        ele.to_writer(&mut writer)?;
+                    }
+                    writer.write_element_end_close(#wrapper)?;
+                }
+            }
+
+            hard_xml::log_finish_writing_field!(#ele_name, #name);
+        };
+    }
+
+    if let Some(wrapper) = wrapper {
+        let is_empty = match ty {
+            Type::VecT(_) => quote! { #name.is_empty() },
+            Type::OptionT(_) => quote! { #name.is_none() },
+            _ => quote! { false },
+        };
+        let write_child = write_child(name, ty, None, empty, ele_name);
+        return match empty {
+            EmptyPolicy::Omit => quote! {
+                if !#is_empty {
+                    writer.write_element_start(#wrapper)?;
+                    writer.write_element_end_open()?;
+                    #write_child
+                    writer.write_element_end_close(#wrapper)?;
+                }
+            },
+            EmptyPolicy::Expanded => quote! {
+                writer.write_element_start(#wrapper)?;
+                writer.write_element_end_open()?;
+                #write_child
+                writer.write_element_end_close(#wrapper)?;
+            },
+            EmptyPolicy::SelfClosing => quote! {
+                writer.write_element_start(#wrapper)?;
+                if #is_empty {
+                    writer.write_element_end_empty()?;
+                } else {
+                    writer.write_element_end_open()?;
+                    #write_child
+                    writer.write_element_end_close(#wrapper)?;
+                }
+            },
+        };
+    }
+
     match ty {
         Type::OptionT(_) => quote! {
             hard_xml::log_start_writing_field!(#ele_name, #name);
@@ -130,7 +577,7 @@ fn write_child(name: &Ident, ty: &Type, ele_name: &TokenStream) -> TokenStream {
         Type::VecT(_) => quote! {
             hard_xml::log_start_writing_field!(#ele_name, #name);
 
-            for ele in #name {
+            for ele in #name.iter() {
                 ele.to_writer(&mut writer)?;
             }
 
@@ -147,28 +594,289 @@ fn write_child(name: &Ident, ty: &Type, ele_name: &TokenStream) -> TokenStream {
     }
 }
 
-fn write_text(
+// writes children in the recorded order, followed by the ones not recorded
+fn write_ordered(order: &Ident, fields: &[Field], ele_name: &TokenStream) -> TokenStream {
+    // (name, bind, whether every value is written at once, write)
+    let children = fields
+        .iter()
+        .filter_map(|field| match field {
+            Field::Child {
+                name,
+                bind,
+                ty,
+                wrapper: Some(wrapper),
+                empty,
+                skip_if,
+                ..
+            } => {
+                // the wrapper holds all the values, so it's written once
+                let write_child = write_child(bind, ty, Some(wrapper), *empty, ele_name);
+                Some((
+                    name,
+                    bind,
+                    true,
+                    skip_write(
+                        skip_if.as_ref(),
+                        bind,
+                        quote! {
+                            let #bind = __value;
+                            #write_child
+                        },
+                    ),
+                ))
+            }
+            Field::Child {
+                name,
+                bind,
+                ty,
+                skip_if,
+                ..
+            } => Some((
+                name,
+                bind,
+                !ty.is_vec() && !ty.is_option(),
+                skip_write(
+                    skip_if.as_ref(),
+                    bind,
+                    quote! { __value.to_writer(&mut writer)?; },
+                ),
+            )),
+            // the values of a list are in one element, so they're written once
+            Field::FlattenText {
+                name,
+                bind,
+                ty,
+                tag,
+                is_cdata,
+                precision,
+                radix,
+                list: Some(separator),
+                empty,
+                skip_if,
+                ..
+            } => {
+                let write_flatten_text = write_flatten_text(
+                    tag,
+                    bind,
+                    ty,
+                    *precision,
+                    radix.as_ref(),
+                    Some(separator),
+                    *empty,
+                    ele_name,
+                    *is_cdata,
+                );
+                Some((
+                    name,
+                    bind,
+                    true,
+                    skip_write(
+                        skip_if.as_ref(),
+                        bind,
+                        quote! {
+                            let #bind = __value;
+                            #write_flatten_text
+                        },
+                    ),
+                ))
+            }
+            Field::FlattenText {
+                name,
+                bind,
+                ty,
+                tag,
+                is_cdata,
+                precision,
+                radix,
+                skip_if,
+                ..
+            } => {
+                let to_str = to_str(ty, *precision, radix.as_ref());
+                Some((
+                    name,
+                    bind,
+                    !ty.is_vec() && !ty.is_option(),
+                    skip_write(
+                        skip_if.as_ref(),
+                        bind,
+                        quote! { writer.write_flatten_text(#tag, #to_str, #is_cdata)?; },
+                    ),
+                ))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let iters = children.iter().map(|(_, bind, once, _)| {
+        let iter = format_ident!("__iter_{}", bind.to_string().trim_start_matches("__"));
+        if *once {
+            quote! { let mut #iter = std::iter::once(#bind); }
+        } else {
+            quote! { let mut #iter = #bind.iter(); }
+        }
+    });
+
+    let write_next = children.iter().map(|(name, bind, _, write)| {
+        let iter = format_ident!("__iter_{}", bind.to_string().trim_start_matches("__"));
+        let name = LitStr::new(&name.to_string(), Span::call_site());
+        quote! {
+            #name => {
+                if let Some(__value) = #iter.next() {
+                    #write
+                }
+            }
+        }
+    });
+
+    let write_rest = children.iter().map(|(_, bind, _, write)| {
+        let iter = format_ident!("__iter_{}", bind.to_string().trim_start_matches("__"));
+        quote! {
+            for __value in #iter {
+                #write
+            }
+        }
+    });
+
+    // keyed children, map entries, flattened and unknown children aren't
+    // recorded
+    let write_flatten_children = fields.iter().filter_map(|field| match field {
+        Field::KeyedChild { bind, skip_if, .. } => Some(skip_write(
+            skip_if.as_ref(),
+            bind,
+            write_keyed_children(bind, ele_name),
+        )),
+        Field::Map {
+            bind,
+            key_ty,
+            value_ty,
+            tag,
+            key,
+            value,
+            skip_if,
+            ..
+        } => Some(skip_write(
+            skip_if.as_ref(),
+            bind,
+            write_map(tag, key, value, bind, key_ty, value_ty, ele_name),
+        )),
+        Field::Flatten { bind, .. } => Some(write_flatten_children(bind)),
+        Field::Children { bind, .. } => Some(write_other_children(bind)),
+        _ => None,
+    });
+
+    quote! {
+        #( #iters )*
+
+        for __field in #order.iter() {
+            match *__field {
+                #( #write_next )*
+                _ => (),
+            }
+        }
+
+        #( #write_rest )*
+
+        #( #write_flatten_children )*
+    }
+}
+
+fn write_keyed_children(bind: &Ident, ele_name: &TokenStream) -> TokenStream {
+    quote! {
+        hard_xml::log_start_writing_field!(#ele_name, #bind);
+
+        for (_, __value) in #bind {
+            __value.to_writer(&mut writer)?;
+        }
+
+        hard_xml::log_finish_writing_field!(#ele_name, #bind);
+    }
+}
+
+fn write_map(
     tag: &LitStr,
+    key: &LitStr,
+    value: &LitStr,
+    bind: &Ident,
+    key_ty: &Type,
+    value_ty: &Type,
+    ele_name: &TokenStream,
+) -> TokenStream {
+    let key_to_str = to_str(key_ty, None, None);
+    let value_to_str = to_str(value_ty, None, None);
+
+    let write_entry = if tag.value() == "*" {
+        quote! {
+            let __tag = {
+                let __value = __key;
+                #key_to_str.to_string()
+            };
+            writer.write_flatten_text(&__tag, #value_to_str, false)?;
+        }
+    } else {
+        quote! {
+            writer.write_element_start(#tag)?;
+            {
+                let __value = __key;
+                writer.write_attribute(#key, #key_to_str)?;
+            }
+            writer.write_attribute(#value, #value_to_str)?;
+            writer.write_element_end_empty()?;
+        }
+    };
+
+    quote! {
+        hard_xml::log_start_writing_field!(#ele_name, #bind);
+
+        for (__key, __value) in #bind {
+            #write_entry
+        }
+
+        hard_xml::log_finish_writing_field!(#ele_name, #bind);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn write_text(
+    tag: &TokenStream,
     name: &Ident,
     ty: &Type,
+    precision: Option<usize>,
+    radix: Option<&Radix>,
+    list: Option<&Separator>,
     ele_name: &TokenStream,
     is_cdata: bool,
 ) -> TokenStream {
-    let to_str = to_str(ty);
+    let to_str = to_str(ty, precision, radix);
     let wrtie_fn = if is_cdata {
         quote!(write_cdata_text)
     } else {
         quote!(write_text)
     };
 
+    let write_value = match list {
+        Some(separator) => {
+            let join_list = join_list(separator, name, &to_str);
+            quote! { writer.#wrtie_fn(#join_list)?; }
+        }
+        // `None` is written as empty text
+        None if ty.is_option() => quote! {
+            if let Some(__value) = #name {
+                writer.#wrtie_fn(#to_str)?;
+            }
+        },
+        None => quote! {
+            let __value = #name;
+
+            writer.#wrtie_fn(#to_str)?;
+        },
+    };
+
     quote! {
         writer.write_element_end_open()?;
 
         hard_xml::log_start_writing_field!(#ele_name, #name);
 
-        let __value = &#name;
-
-        writer.#wrtie_fn(#to_str)?;
+        #write_value
 
         hard_xml::log_finish_writing_field!(#ele_name, #name);
 
@@ -176,20 +884,51 @@ fn write_text(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn write_flatten_text(
     tag: &LitStr,
     name: &Ident,
     ty: &Type,
+    precision: Option<usize>,
+    radix: Option<&Radix>,
+    list: Option<&Separator>,
+    empty: EmptyPolicy,
     ele_name: &TokenStream,
     is_cdata: bool,
 ) -> TokenStream {
-    let to_str = to_str(ty);
+    let to_str = to_str(ty, precision, radix);
+
+    if let Some(separator) = list {
+        let join_list = join_list(separator, name, &to_str);
+        let write_empty = match empty {
+            // an empty list is written as a missing element
+            EmptyPolicy::Omit => quote! {},
+            EmptyPolicy::Expanded => quote! {
+                writer.write_element_start(#tag)?;
+                writer.write_element_end_open()?;
+                writer.write_element_end_close(#tag)?;
+            },
+            EmptyPolicy::SelfClosing => quote! {
+                writer.write_element_start(#tag)?;
+                writer.write_element_end_empty()?;
+            },
+        };
+        quote! {
+            hard_xml::log_start_writing_field!(#ele_name, #name);
 
-    if ty.is_vec() {
+            if #name.is_empty() {
+                #write_empty
+            } else {
+                writer.write_flatten_text(#tag, #join_list, #is_cdata)?;
+            }
+
+            hard_xml::log_finish_writing_field!(#ele_name, #name);
+        }
+    } else if ty.is_vec() {
         quote! {
             hard_xml::log_finish_writing_field!(#ele_name, #name);
 
-            for __value in #name {
+            for __value in #name.iter() {
                 writer.write_flatten_text(#tag, #to_str, #is_cdata)?;
             }
 
@@ -209,7 +948,7 @@ fn write_flatten_text(
         quote! {
             hard_xml::log_finish_writing_field!(#ele_name, #name);
 
-            let __value = &#name;
+            let __value = #name;
             writer.write_flatten_text(#tag, #to_str, #is_cdata)?;
 
             hard_xml::log_finish_writing_field!(#ele_name, #name);
@@ -217,19 +956,66 @@ fn write_flatten_text(
     }
 }
 
-fn to_str(ty: &Type) -> TokenStream {
+// joins the values of a Vec into a String
+fn join_list(separator: &Separator, name: &Ident, to_str: &TokenStream) -> TokenStream {
+    let separator = match separator {
+        Separator::Whitespace => quote! { " " },
+        Separator::Str(separator) => quote! { #separator },
+    };
+
+    quote! {{
+        let mut __list = String::new();
+        for (__index, __value) in #name.iter().enumerate() {
+            if __index > 0 {
+                __list.push_str(#separator);
+            }
+            __list.push_str(&#to_str.to_string());
+        }
+        __list
+    }}
+}
+
+fn to_str(ty: &Type, precision: Option<usize>, radix: Option<&Radix>) -> TokenStream {
     match &ty {
         Type::CowStr | Type::OptionCowStr | Type::VecCowStr => {
             quote! { __value }
         }
-        Type::Bool | Type::OptionBool | Type::VecBool => quote! {
-            match __value {
-                true => "true",
-                false => "false"
+        Type::Bool(format) | Type::OptionBool(format) | Type::VecBool(format) => {
+            let true_str = format
+                .true_str
+                .as_ref()
+                .map_or("true".to_owned(), LitStr::value);
+            let false_str = format
+                .false_str
+                .as_ref()
+                .map_or("false".to_owned(), LitStr::value);
+            quote! {
+                match __value {
+                    true => #true_str,
+                    false => #false_str
+                }
+            }
+        }
+        Type::T(_) | Type::OptionT(_) | Type::VecT(_) if radix.is_some() => {
+            let Radix { radix, prefix } = radix.unwrap();
+            let prefix = prefix.as_ref().map_or(String::new(), LitStr::value);
+            quote! { hard_xml::utils::RadixDisplay(__value, #radix, #prefix) }
+        }
+        Type::T(_) | Type::OptionT(_) | Type::VecT(_) if precision.is_some() => quote! {{
+            use hard_xml::utils::{FormatDisplay as _, FormatXmlValue as _};
+            format!("{:.1$}", (&&hard_xml::utils::Format(__value)).format_value(), #precision)
+        }},
+        // formatted while being written, with `XmlValue` if it's implemented
+        Type::T(_) | Type::OptionT(_) | Type::VecT(_) => quote! {{
+            use hard_xml::utils::{FormatDisplay as _, FormatXmlValue as _};
+            (&&hard_xml::utils::Format(__value)).format_value()
+        }},
+        Type::CowBytes(encoding) | Type::OptionCowBytes(encoding) | Type::VecCowBytes(encoding) => {
+            match encoding {
+                Encoding::Utf8 => quote! { std::str::from_utf8(__value)? },
+                Encoding::Hex => quote! { &hard_xml::utils::hex_encode(__value) },
+                Encoding::Base64 => quote! { &hard_xml::utils::base64_encode(__value) },
             }
-        },
-        Type::T(_) | Type::OptionT(_) | Type::VecT(_) => {
-            quote! { &format!("{}", __value) }
         }
     }
 }
@@ -1,11 +1,35 @@
 use proc_macro2::TokenStream;
 use quote::quote;
+use syn::LitStr;
+
+use crate::types::Type;
+
+pub fn write(tags: &[LitStr], ty: &Type, name: TokenStream) -> TokenStream {
+    let write = match ty {
+        // items are written in an element with the first tag
+        Type::VecT(_) => {
+            let tag = tags.first().expect("Missing `tag` attribute.");
+            quote! {
+                writer.write_element_start(#tag)?;
+
+                if __inner.is_empty() {
+                    writer.write_element_end_empty()?;
+                } else {
+                    writer.write_element_end_open()?;
+                    for __item in __inner {
+                        __item.to_writer(writer)?;
+                    }
+                    writer.write_element_end_close(#tag)?;
+                }
+            }
+        }
+        _ => quote! { __inner.to_writer(writer)?; },
+    };
 
-pub fn write(name: TokenStream) -> TokenStream {
     quote! {
         hard_xml::log_start_writing!(#name);
 
-        __inner.to_writer(writer)?;
+        #write
 
         hard_xml::log_finish_writing!(#name);
     }
@@ -45,6 +45,23 @@
 //!
 //! ## Attributes
 //!
+//! Unknown keys, malformed values and fields of unsupported types are
+//! compile errors pointing at the attribute or field, and misspelled keys are
+//! reported with the closest known one. Every error of the type is reported at
+//! once, rather than only the first one.
+//!
+//! ```compile_fail
+//! use hard_xml::XmlRead;
+//!
+//! #[derive(XmlRead)]
+//! #[xml(tag = "parent")]
+//! struct Parent {
+//!     // error: Unknown `atr` attribute, did you mean `attr`?
+//!     #[xml(atr = "id")]
+//!     id: usize,
+//! }
+//! ```
+//!
 //! ### `#[xml(tag = "")]`
 //!
 //! Specifies the xml tag of a struct or an enum variant.
@@ -95,11 +112,102 @@
 //! );
 //! ```
 //!
+//! Variants can also declare their fields inline, like a struct.
+//!
+//! ```rust
+//! # use hard_xml::{XmlRead, XmlWrite};
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! enum Shape {
+//!     #[xml(tag = "circle")]
+//!     Circle {
+//!         #[xml(attr = "r")]
+//!         r: u32,
+//!     },
+//!     #[xml(tag = "label")]
+//!     Label(#[xml(text)] String),
+//! }
+//!
+//! assert_eq!(
+//!     Shape::from_str(r#"<circle r="2"/>"#).unwrap(),
+//!     Shape::Circle { r: 2 }
+//! );
+//!
+//! assert_eq!(
+//!     (Shape::Label("foo".into())).to_string().unwrap(),
+//!     r#"<label>foo</label>"#
+//! );
+//! ```
+//!
+//! Newtype structs and variants of `Vec<T>` read the elements of `T` nested
+//! in an element with their tag.
+//!
+//! ```rust
+//! # use hard_xml::{XmlRead, XmlWrite};
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "item")]
+//! struct Item;
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! enum Content {
+//!     #[xml(tag = "item")]
+//!     Item(Item),
+//!     #[xml(tag = "items")]
+//!     Items(Vec<Item>),
+//! }
+//!
+//! assert_eq!(
+//!     Content::from_str(r#"<items><item/><item/></items>"#).unwrap(),
+//!     Content::Items(vec![Item, Item])
+//! );
+//! ```
+//!
+//! Newtypes of strings, numbers, `bool` and `char` hold the text of the
+//! element, as if their field had the `text` attribute.
+//!
+//! ```rust
+//! # use hard_xml::{XmlRead, XmlWrite};
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "distance")]
+//! struct Meters(f64);
+//!
+//! assert_eq!(Meters::from_str(r#"<distance>1.5</distance>"#).unwrap(), Meters(1.5));
+//! assert_eq!(Meters(2.0).to_string().unwrap(), r#"<distance>2</distance>"#);
+//! ```
+//!
+//! Deriving `XmlRead` adds constants naming the tags: `TAG` and `ALIASES` to
+//! structs and to enums read from text, and `TAGS` with the tag of every variant
+//! to other enums.
+//!
+//! ```rust
+//! # use hard_xml::XmlRead;
+//! #[derive(XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "item", alias = "entry")]
+//! struct Item;
+//!
+//! #[derive(XmlRead, PartialEq, Debug)]
+//! enum Message {
+//!     #[xml(tag = "ping")]
+//!     Ping,
+//!     #[xml(tag = "item")]
+//!     Item(Item),
+//! }
+//!
+//! assert_eq!(Item::TAG, "item");
+//! assert_eq!(Item::ALIASES, &["entry"]);
+//! assert_eq!(Message::TAGS, &["ping", "item"]);
+//! ```
+//!
 //! ### `#[xml(attr = "")]`
 //!
 //! Specifies that a struct field is attribute. Support
-//! `Cow<str>`, `Option<Cow<str>>`, `T` and `Option<T>`
-//! where `T: FromStr + Display`.
+//! `Cow<str>`, `Option<Cow<str>>`, `Vec<Cow<str>>`, `T`, `Option<T>`
+//! and `Vec<T>` where `T: FromStr + Display`.
+//!
+//! `Vec` fields collect every occurrence of a repeated attribute in order.
+//!
+//! `&str` can be used instead of `Cow<str>` here and in `text`, `flatten_text`
+//! and `tag_name` fields, borrowing the value from the document. Reading fails
+//! with `ErrorDetail::Unborrowable` if the value contains escapes.
 //!
 //! ```rust
 //! use hard_xml::{XmlRead, XmlWrite};
@@ -122,6 +230,29 @@
 //! );
 //! ```
 //!
+//! Two fields can't read the same attribute or child element, including
+//! through `alias`, `deprecated`, `or_attr` and `or_child`. The derive fails
+//! with an error pointing at both declarations instead of leaving one of
+//! them empty.
+//!
+//! The fields of a struct embedded with `flatten` aren't known to the derive
+//! of the struct embedding it, so they aren't checked against its own fields.
+//! A name read by both is read by the field of the embedding struct, and the
+//! flattened field is left empty.
+//!
+//! ```compile_fail
+//! use hard_xml::XmlRead;
+//!
+//! #[derive(XmlRead)]
+//! #[xml(tag = "parent")]
+//! struct Parent {
+//!     #[xml(attr = "id")]
+//!     id: usize,
+//!     #[xml(attr = "key", alias = "id")]
+//!     key: usize,
+//! }
+//! ```
+//!
 //! ### `#[xml(child = "")]`
 //!
 //! Specifies that a struct field is a child element. Support
@@ -170,12 +301,50 @@
 //! );
 //! ```
 //!
+//! A unit struct models an element that only matters by its presence, like
+//! `<enabled/>`: any content is skipped when reading it, and it's written as an
+//! empty element. As an `Option` child, it's `Some` if the element is present.
+//!
+//! ```rust
+//! # use hard_xml::{XmlRead, XmlWrite};
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "enabled")]
+//! struct Enabled;
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "feature")]
+//! struct Feature {
+//!     #[xml(child = "enabled")]
+//!     enabled: Option<Enabled>,
+//! }
+//!
+//! assert_eq!(
+//!     Feature::from_str(r#"<feature><enabled></enabled></feature>"#).unwrap(),
+//!     Feature { enabled: Some(Enabled) }
+//! );
+//! assert_eq!(
+//!     (Feature { enabled: Some(Enabled) }).to_string().unwrap(),
+//!     r#"<feature><enabled/></feature>"#
+//! );
+//! ```
+//!
+//! `Box<T>`, `Rc<T>` and `Arc<T>` are read and written like `T`, so recursive
+//! types can contain themselves, e.g. in an `Option<Box<T>>` field, and parsed
+//! elements can be shared without cloning them. Attributes and text can be
+//! read into `Rc<str>` and `Arc<str>` as well. The nesting of elements can be
+//! limited with `XmlReader::with_max_depth` when reading untrusted documents.
+//!
 //! ### `#[xml(text)]`
 //!
 //! Specifies that a struct field is text content.
 //! Support `Cow<str>`, `Vec<Cow<str>>`, `Option<Cow<str>>`,
 //! `T`, `Vec<T>`, `Option<T>` where `T: FromStr + Display`.
 //!
+//! Numbers, `bool` and other `T` are parsed from the whole text, so an element
+//! like `<count>42</count>` can be read into a `u32` field directly. An
+//! `Option<T>` is `None` when the text is empty, and is written as empty text
+//! then.
+//!
 //! ```rust
 //! use std::borrow::Cow;
 //! use hard_xml::{XmlRead, XmlWrite};
@@ -204,6 +373,9 @@
 //! Support `Cow<str>`, `Vec<Cow<str>>`, `Option<Cow<str>>`,
 //! `T`, `Vec<T>`, `Option<T>` where `T: FromStr + Display`.
 //!
+//! A `Vec` is read from every child element with the tag, each parsed on its
+//! own, and written as one element per item.
+//!
 //! ```rust
 //! use std::borrow::Cow;
 //! use hard_xml::{XmlRead, XmlWrite};
@@ -226,6 +398,26 @@
 //! );
 //! ```
 //!
+//! ```rust
+//! use hard_xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "ids")]
+//! struct Ids {
+//!     #[xml(flatten_text = "id")]
+//!     ids: Vec<u64>,
+//! }
+//!
+//! assert_eq!(
+//!     Ids::from_str(r#"<ids><id>1</id><id>2</id></ids>"#).unwrap(),
+//!     Ids { ids: vec![1, 2] }
+//! );
+//! assert_eq!(
+//!     Ids { ids: vec![3] }.to_string().unwrap(),
+//!     r#"<ids><id>3</id></ids>"#
+//! );
+//! ```
+//!
 //! ### `#[xml(cdata)]`
 //!
 //! Specifies a CDATA text. Should be used together with `text` or `flatten_text`.
@@ -267,6 +459,37 @@
 //! );
 //! ```
 //!
+//! ### `#[xml(trim)]`
+//!
+//! Trims leading and trailing whitespace off the text of a field when reading,
+//! like the indentation of pretty-printed documents. Should be used together with
+//! `text` or `flatten_text`, or on an element to trim all of its text fields, where
+//! `#[xml(preserve)]` keeps the whitespace of a field.
+//!
+//! ```rust
+//! use hard_xml::XmlRead;
+//!
+//! #[derive(XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "book", trim)]
+//! struct Book {
+//!     #[xml(flatten_text = "title")]
+//!     title: String,
+//!     #[xml(flatten_text = "pages")]
+//!     pages: u32,
+//!     #[xml(flatten_text = "code", preserve)]
+//!     code: String,
+//! }
+//!
+//! let book = Book::from_str(
+//!     "<book>\n  <title>\n    Dune\n  </title>\n  <pages> 412 </pages>\n  <code>  x</code>\n</book>",
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(book.title, "Dune");
+//! assert_eq!(book.pages, 412);
+//! assert_eq!(book.code, "  x");
+//! ```
+//!
 //! ### `#[xml(default)]`
 //!
 //! Use `Default::default()` if the value is not present when reading.
@@ -293,40 +516,1623 @@
 //! );
 //! ```
 //!
-//! ## License
+//! With a path to a function, the function is called instead, so a missing
+//! field can get a domain-specific default.
 //!
-//! MIT
-
-#[cfg(feature = "log")]
-mod log;
-#[cfg(not(feature = "log"))]
-mod noop_log;
-
-#[cfg(feature = "log")]
-#[doc(hidden)]
-pub mod lib {
-    pub use log;
-}
-
-mod xml_error;
-mod xml_escape;
-mod xml_read;
-mod xml_reader;
-mod xml_unescape;
-mod xml_write;
-mod xml_writer;
-
-pub use self::xml_error::{XmlError, XmlResult};
-pub use self::xml_read::{XmlRead, XmlReadOwned};
-pub use self::xml_reader::XmlReader;
-pub use self::xml_write::XmlWrite;
-pub use self::xml_writer::XmlWriter;
-
-pub use hard_xml_derive::{XmlRead, XmlWrite};
-
-pub use xmlparser;
+//! ```rust
+//! # use hard_xml::XmlRead;
+//! fn default_port() -> u16 {
+//!     443
+//! }
+//!
+//! #[derive(XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "server")]
+//! struct Server {
+//!     #[xml(attr = "port", default = "default_port")]
+//!     port: u16,
+//! }
+//!
+//! assert_eq!(Server::from_str(r#"<server/>"#).unwrap(), Server { port: 443 });
+//! assert_eq!(Server::from_str(r#"<server port="80"/>"#).unwrap(), Server { port: 80 });
+//! ```
+//!
+//! ### `#[xml(encoding = "")]`
+//!
+//! Specifies that a `Cow<[u8]>`, `Vec<u8>` or `[u8; N]` field, or an `Option`
+//! or `Vec` of them, is binary data carried as text. Supported encodings are `utf8`,
+//! `hex` and `base64` (requires the `base64` feature). Should be used
+//! together with `attr`, `text` or `flatten_text`.
+//!
+//! `#[xml(base64)]` and `#[xml(hex)]` are short for `#[xml(encoding = "base64")]`
+//! and `#[xml(encoding = "hex")]`. Reading into `[u8; N]` fails if the length
+//! doesn't match.
+//!
+//! > `utf8` borrows from the input whenever the text didn't need unescaping.
+//!
+//! ```rust
+//! use std::borrow::Cow;
+//! use hard_xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "file")]
+//! struct File<'a> {
+//!     #[xml(attr = "digest", encoding = "hex")]
+//!     digest: Cow<'a, [u8]>,
+//!     #[xml(text, encoding = "utf8")]
+//!     content: Cow<'a, [u8]>,
+//! }
+//!
+//! assert_eq!(
+//!     (File { digest: Cow::Borrowed(&[0xbe, 0xef]), content: Cow::Borrowed(b"data") }).to_string().unwrap(),
+//!     r#"<file digest="BEEF">data</file>"#
+//! );
+//!
+//! assert_eq!(
+//!     File::from_str(r#"<file digest="beef">data</file>"#).unwrap(),
+//!     File { digest: Cow::Borrowed(&[0xbe, 0xef]), content: Cow::Borrowed(b"data") }
+//! );
+//! ```
+//!
+//! ### `#[xml(order)]`
+//!
+//! Records the names of the child fields in the order their elements
+//! appeared in the document, so children of different fields are written
+//! back interleaved as they were read. Children not covered by the order
+//! are written afterwards. Only supports `Vec<&'static str>`.
+//!
+//! ```rust
+//! use hard_xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "parent")]
+//! struct Parent {
+//!     #[xml(flatten_text = "a")]
+//!     a: Vec<usize>,
+//!     #[xml(flatten_text = "b")]
+//!     b: Vec<usize>,
+//!     #[xml(order)]
+//!     order: Vec<&'static str>,
+//! }
+//!
+//! let parent = Parent::from_str(r#"<parent><a>1</a><b>2</b><a>3</a></parent>"#).unwrap();
+//!
+//! assert_eq!(parent.order, vec!["a", "b", "a"]);
+//!
+//! assert_eq!(
+//!     parent.to_string().unwrap(),
+//!     r#"<parent><a>1</a><b>2</b><a>3</a></parent>"#
+//! );
+//! ```
+//!
+//! ### `#[xml(ordered)]`
+//!
+//! Requires children to appear in the order their fields are declared.
+//! Reading fails with `ErrorDetail::UnorderedField` otherwise, and children
+//! are written in that order. Children of the same field can still repeat.
+//! Only applies to structs and struct variants.
+//!
+//! ```rust
+//! use hard_xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "parent", ordered)]
+//! struct Parent {
+//!     #[xml(flatten_text = "a")]
+//!     a: Vec<usize>,
+//!     #[xml(flatten_text = "b")]
+//!     b: Option<usize>,
+//! }
+//!
+//! assert_eq!(
+//!     Parent::from_str(r#"<parent><a>1</a><a>2</a><b>3</b></parent>"#).unwrap(),
+//!     Parent { a: vec![1, 2], b: Some(3) }
+//! );
+//!
+//! assert!(Parent::from_str(r#"<parent><a>1</a><b>3</b><a>2</a></parent>"#).is_err());
+//! ```
+//!
+//! ### `#[xml(min = .., max = ..)]`
+//!
+//! Limits the number of elements a `Vec` field with `child` or `flatten_text`
+//! attribute accepts, like `minOccurs` and `maxOccurs` in XSD. Reading fails
+//! with `ErrorDetail::UnexpectedOccurrences` if the document has fewer or more
+//! of them, and so does `XmlBuilder`. The bounds are also exposed in the
+//! schema info as `FieldInfo::min_occurs` and `FieldInfo::max_occurs`.
+//!
+//! ```rust
+//! use hard_xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "parent")]
+//! struct Parent {
+//!     #[xml(flatten_text = "child", min = 1, max = 2)]
+//!     children: Vec<usize>,
+//! }
+//!
+//! assert!(Parent::from_str(r#"<parent/>"#).is_err());
+//!
+//! assert_eq!(
+//!     Parent::from_str(r#"<parent><child>1</child><child>2</child></parent>"#).unwrap(),
+//!     Parent { children: vec![1, 2] }
+//! );
+//!
+//! assert!(Parent::from_str(r#"<parent><child>1</child><child>2</child><child>3</child></parent>"#).is_err());
+//! ```
+//!
+//! ### `#[xml(tag_name)]`
+//!
+//! Captures the name of the element as written in the document, which can
+//! differ from the declared tag when it's matched by a prefix, alias or
+//! another matcher. It's used as the tag when writing, unless it's empty.
+//! Only supports `Cow<str>`.
+//!
+//! ```rust
+//! use std::borrow::Cow;
+//! use hard_xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "parent")]
+//! struct Parent<'a> {
+//!     #[xml(tag_name)]
+//!     tag: Cow<'a, str>,
+//! }
+//!
+//! let parent = Parent::from_str(r#"<x:parent/>"#).unwrap();
+//!
+//! assert_eq!(parent.tag, "x:parent");
+//! assert_eq!(parent.to_string().unwrap(), r#"<x:parent/>"#);
+//! ```
+//!
+//! ### `#[xml(tag = "*")]`
+//!
+//! Matches elements with any name, e.g. for key-value style documents where
+//! the name is data. The struct must have a `#[xml(tag_name)]` field, which
+//! receives the name when reading and is used as the tag when writing.
+//!
+//! A `child = "*"` field receives the children no other field accepts.
+//!
+//! ```rust
+//! use std::borrow::Cow;
+//! use hard_xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "*")]
+//! struct Property<'a> {
+//!     #[xml(tag_name)]
+//!     key: Cow<'a, str>,
+//!     #[xml(text)]
+//!     value: Cow<'a, str>,
+//! }
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "size")]
+//! struct Size<'a> {
+//!     #[xml(child = "*")]
+//!     properties: Vec<Property<'a>>,
+//! }
+//!
+//! let size = Size::from_str(r#"<size><width>10</width><height>20</height></size>"#).unwrap();
+//!
+//! assert_eq!(size.properties[0].key, "width");
+//! assert_eq!(size.properties[1].value, "20");
+//! ```
+//!
+//! ### `#[xml(overflow = "")]`
+//!
+//! Chooses what happens when a number in an `attr`, `text` or `flatten_text`
+//! field doesn't fit into its type. `"error"` (the default) fails reading and
+//! `"saturate"` clamps the value to the `MIN` or `MAX` of the type, so negative
+//! numbers are read as 0 into unsigned types.
+//!
+//! Fields of type `Numeric<T>` keep the original text of such numbers instead.
+//!
+//! ```rust
+//! use hard_xml::{Numeric, XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "sensor")]
+//! struct Sensor {
+//!     #[xml(attr = "level", overflow = "saturate")]
+//!     level: u8,
+//!     #[xml(attr = "raw")]
+//!     raw: Numeric<u8>,
+//! }
+//!
+//! assert_eq!(
+//!     Sensor::from_str(r#"<sensor level="300" raw="300"/>"#).unwrap(),
+//!     Sensor { level: 255, raw: Numeric::OutOfRange("300".into()) }
+//! );
+//! ```
+//!
+//! ### `#[xml(precision = ..)]`
+//!
+//! Writes a float with a fixed number of decimal places. Without it, floats are
+//! written in the shortest form that reads back to the same value, never in
+//! scientific notation. Applies to a field used together with `attr`, `text` or
+//! `flatten_text`, or to all `f32` and `f64` fields of an element.
+//!
+//! Infinities are read and written as `INF` and `-INF`, and not-a-number as
+//! `NaN`, like `xs:float` and `xs:double`.
+//!
+//! ```rust
+//! use hard_xml::XmlWrite;
+//!
+//! #[derive(XmlWrite, PartialEq, Debug)]
+//! #[xml(tag = "trkpt", precision = 6)]
+//! struct TrackPoint {
+//!     #[xml(attr = "lat")]
+//!     lat: f64,
+//!     #[xml(attr = "lon")]
+//!     lon: f64,
+//!     #[xml(attr = "ele", precision = 1)]
+//!     ele: f64,
+//! }
+//!
+//! assert_eq!(
+//!     TrackPoint { lat: 12.3456789, lon: 1e1, ele: 4.25 }.to_string().unwrap(),
+//!     r#"<trkpt lat="12.345679" lon="10.000000" ele="4.2"/>"#
+//! );
+//! ```
+//!
+//! ### `#[xml(radix = .., prefix = "")]`
+//!
+//! Reads and writes an integer in radix 2, 8 or 16 instead of 10, with digits
+//! above 9 written in upper case. The optional prefix is written before the
+//! digits, after the sign, and is accepted in any case or left out when reading.
+//! Should be used together with `attr`, `text` or `flatten_text`.
+//!
+//! ```rust
+//! use hard_xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "register")]
+//! struct Register {
+//!     #[xml(attr = "mask", radix = 16, prefix = "0x")]
+//!     mask: u32,
+//! }
+//!
+//! assert_eq!(Register::from_str(r#"<register mask="0x1f"/>"#).unwrap(), Register { mask: 31 });
+//! assert_eq!(Register { mask: 31 }.to_string().unwrap(), r#"<register mask="0x1F"/>"#);
+//! ```
+//!
+//! ### `#[xml(bool = "")]`
+//!
+//! Chooses which forms a `bool` field accepts. By default they're `true`, `t`,
+//! `yes`, `y`, `on` and `1`, and `false`, `f`, `no`, `n`, `off` and `0`, in
+//! lower case. `"lenient"` accepts them in any case with surrounding whitespace,
+//! and `"strict"` only accepts the `xs:boolean` forms `true`, `false`, `1` and
+//! `0`. Should be used together with `attr`, `text` or `flatten_text`.
+//!
+//! ```rust
+//! use hard_xml::XmlRead;
+//!
+//! #[derive(XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "option")]
+//! struct Opt {
+//!     #[xml(attr = "enabled", bool = "lenient")]
+//!     enabled: bool,
+//! }
+//!
+//! assert_eq!(Opt::from_str(r#"<option enabled="Yes"/>"#).unwrap(), Opt { enabled: true });
+//! ```
+//!
+//! ### `#[xml(true = "", false = "")]`
+//!
+//! Chooses how a `bool` field is written, instead of `true` and `false`. The
+//! chosen forms are read as well, next to the ones accepted by `bool`. Should be
+//! used together with `attr`, `text` or `flatten_text`.
+//!
+//! ```rust
+//! use hard_xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "option")]
+//! struct Opt {
+//!     #[xml(attr = "enabled", true = "Y", false = "N")]
+//!     enabled: bool,
+//! }
+//!
+//! assert_eq!(Opt::from_str(r#"<option enabled="N"/>"#).unwrap(), Opt { enabled: false });
+//! assert_eq!(Opt { enabled: true }.to_string().unwrap(), r#"<option enabled="Y"/>"#);
+//! ```
+//!
+//! ### `#[xml(wrapper = "")]`
+//!
+//! Nests the elements of a `child` field in a wrapper element, so reading
+//! looks through it and writing adds it. The wrapper is omitted if the field
+//! is empty, unless the field is an `Option<Vec<T>>`: then `None` stands for a
+//! missing wrapper and `Some(vec![])` for an empty one.
+//!
+//! ```rust
+//! use hard_xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "item")]
+//! struct Item {
+//!     #[xml(attr = "id")]
+//!     id: usize,
+//! }
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "feed")]
+//! struct Feed {
+//!     #[xml(child = "item", wrapper = "items")]
+//!     items: Vec<Item>,
+//! }
+//!
+//! assert_eq!(
+//!     Feed::from_str(r#"<feed><items><item id="1"/><item id="2"/></items></feed>"#).unwrap(),
+//!     Feed { items: vec![Item { id: 1 }, Item { id: 2 }] }
+//! );
+//!
+//! assert_eq!(
+//!     (Feed { items: vec![Item { id: 1 }] }).to_string().unwrap(),
+//!     r#"<feed><items><item id="1"/></items></feed>"#
+//! );
+//! ```
+//!
+//! ### `#[xml(empty = "")]`
+//!
+//! Chooses what a `wrapper` is written as when there's nothing to wrap:
+//! `"omit"` (the default) leaves it out, `"expanded"` writes `<items></items>`
+//! and `"self_closing"` writes `<items/>`. On a struct, it applies to all of its
+//! wrappers and `flatten_text` lists.
+//!
+//! ```rust
+//! use hard_xml::XmlWrite;
+//!
+//! #[derive(XmlWrite)]
+//! #[xml(tag = "item")]
+//! struct Item;
+//!
+//! #[derive(XmlWrite)]
+//! #[xml(tag = "feed")]
+//! struct Feed {
+//!     #[xml(child = "item", wrapper = "items", empty = "self_closing")]
+//!     items: Vec<Item>,
+//! }
+//!
+//! assert_eq!(
+//!     (Feed { items: vec![] }).to_string().unwrap(),
+//!     r#"<feed><items/></feed>"#
+//! );
+//! ```
+//!
+//! With `flatten_text` and `list`, it chooses what an empty `Vec` is written
+//! as, and an empty element is read back as an empty `Vec` either way.
+//! It doesn't apply to other `flatten_text` fields, which write an element per
+//! value, nor to `text` fields, whose element is always written.
+//!
+//! ```rust
+//! use hard_xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "point")]
+//! struct Point {
+//!     #[xml(flatten_text = "coords", list, empty = "self_closing")]
+//!     coords: Vec<f64>,
+//! }
+//!
+//! let point = Point { coords: vec![] };
+//! assert_eq!(point.to_string().unwrap(), r#"<point><coords/></point>"#);
+//! assert_eq!(Point::from_str(r#"<point><coords/></point>"#).unwrap(), point);
+//! ```
+//!
+//! Together with `attr`, it chooses what an empty value is written as:
+//! `"expanded"` (the default) writes `name=""` and `"omit"` leaves the attribute
+//! out. An `Option` attribute is read as `None` when it's missing and as
+//! `Some("")` when it's empty either way.
+//!
+//! ```rust
+//! use hard_xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "input")]
+//! struct Input {
+//!     #[xml(attr = "value")]
+//!     value: Option<String>,
+//!     #[xml(attr = "hint", empty = "omit")]
+//!     hint: Option<String>,
+//! }
+//!
+//! let input = Input::from_str(r#"<input value="" hint=""/>"#).unwrap();
+//! assert_eq!(input.value.as_deref(), Some(""));
+//! assert_eq!(input.to_string().unwrap(), r#"<input value=""/>"#);
+//! ```
+//!
+//! ### `#[xml(duplicate = "")]`
+//!
+//! Chooses what happens when a `child` or `flatten_text` which isn't a `Vec`
+//! occurs more than once: `"last"` (the default) keeps the last one, `"first"`
+//! keeps the first one and skips the others, and `"error"` fails with
+//! `ErrorDetail::DuplicateField`, which has the positions of both occurrences.
+//!
+//! ```rust
+//! use hard_xml::XmlRead;
+//!
+//! #[derive(XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "title")]
+//! struct Title {
+//!     #[xml(text)]
+//!     text: String,
+//! }
+//!
+//! #[derive(XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "book")]
+//! struct Book {
+//!     #[xml(child = "title", duplicate = "error")]
+//!     title: Title,
+//! }
+//!
+//! assert_eq!(
+//!     Book::from_str("<book><title>A</title><title>B</title></book>")
+//!         .unwrap_err()
+//!         .to_string(),
+//!     r#"duplicate field in XML of "Book": "title" (first at 1:7 in book > title) at 1:23 in book > title[2]"#
+//! );
+//! ```
+//!
+//! ### `#[derive(XmlSchemaInfo)]`
+//!
+//! Describes the elements a type is read from and written as at runtime, with
+//! the tag, the name and kind of every field, and whether it's optional or
+//! repeated, so validators, form generators or schema exporters can be built
+//! for any type. Child fields link to the description of their type, and the
+//! fields of flattened structs are listed in place of the field they're in.
+//!
+//! ```rust
+//! use hard_xml::schema::FieldKind;
+//! use hard_xml::XmlSchemaInfo;
+//!
+//! #[derive(XmlSchemaInfo)]
+//! #[xml(tag = "user")]
+//! struct User {
+//!     #[xml(attr = "id")]
+//!     id: u32,
+//!     #[xml(flatten_text = "email")]
+//!     emails: Vec<String>,
+//!     #[xml(attrs)]
+//!     extra: Vec<(String, String)>,
+//! }
+//!
+//! let user = &User::elements()[0];
+//!
+//! assert_eq!(user.tag, "user");
+//! assert_eq!(user.fields[0].name, "id");
+//! assert!(matches!(user.fields[0].kind, FieldKind::Attribute { name: "id" }));
+//! assert!(!user.fields[0].optional);
+//! assert!(user.fields[1].optional && user.fields[1].multiple);
+//! assert!(matches!(user.fields[2].kind, FieldKind::AnyAttributes));
+//! ```
+//!
+//! ### `#[xml(doc = "")]`
+//!
+//! Documents a struct, enum variant or field in the output of
+//! `#[derive(XmlSchemaInfo)]`. Doc comments are used if it's absent.
+//!
+//! ```rust
+//! use hard_xml::XmlSchemaInfo;
+//!
+//! /// A book in the catalog
+//! #[derive(XmlSchemaInfo)]
+//! #[xml(tag = "book")]
+//! struct Book {
+//!     #[xml(attr = "isbn", doc = "ISBN-13 without dashes")]
+//!     isbn: String,
+//! }
+//!
+//! let book = &Book::elements()[0];
+//!
+//! assert_eq!(book.doc, Some("A book in the catalog"));
+//! assert_eq!(book.fields[0].doc, Some("ISBN-13 without dashes"));
+//! ```
+//!
+//! ### `#[xml(untagged)]`
+//!
+//! Tries every variant of an enum accepting the element in declaration order,
+//! going back to the start of the element after each failure, so variants can
+//! share a tag and differ in their content. The error of the last variant is
+//! returned if none matches.
+//!
+//! ```rust
+//! use std::borrow::Cow;
+//! use hard_xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(untagged)]
+//! enum Value<'a> {
+//!     #[xml(tag = "value")]
+//!     Number {
+//!         #[xml(attr = "n")]
+//!         n: i32,
+//!     },
+//!     #[xml(tag = "value")]
+//!     Text {
+//!         #[xml(text)]
+//!         text: Cow<'a, str>,
+//!     },
+//! }
+//!
+//! assert_eq!(
+//!     Value::from_str(r#"<value n="1"/>"#).unwrap(),
+//!     Value::Number { n: 1 }
+//! );
+//!
+//! assert_eq!(
+//!     Value::from_str(r#"<value>one</value>"#).unwrap(),
+//!     Value::Text { text: "one".into() }
+//! );
+//! ```
+//!
+//! ### `#[xml(type_attr = "")]`
+//!
+//! Chooses the variant of an enum by the value of an attribute, rather than by
+//! the tag. Every variant gives its value with `#[xml(type = "")]`, and the
+//! elements of all variants use the `tag` of the enum.
+//!
+//! ```rust
+//! use hard_xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "shape", type_attr = "xsi:type")]
+//! enum Shape {
+//!     #[xml(type = "circle")]
+//!     Circle {
+//!         #[xml(attr = "r")]
+//!         r: u32,
+//!     },
+//!     #[xml(type = "point")]
+//!     Point,
+//! }
+//!
+//! assert_eq!(
+//!     Shape::from_str(r#"<shape xsi:type="circle" r="1"/>"#).unwrap(),
+//!     Shape::Circle { r: 1 }
+//! );
+//!
+//! assert_eq!(
+//!     Shape::Point.to_string().unwrap(),
+//!     r#"<shape xsi:type="point"/>"#
+//! );
+//! ```
+//!
+//! ### `#[xml(other)]`
+//!
+//! Marks the variant of an enum used for elements no other variant accepts,
+//! instead of skipping them. A newtype variant reads the element into its
+//! field, e.g. a `RawXml`, and writes it back. A unit variant just skips the
+//! element, and is written as nothing.
+//!
+//! ```rust
+//! use hard_xml::{RawXml, XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! enum Block<'a> {
+//!     #[xml(tag = "hr")]
+//!     Rule,
+//!     #[xml(other)]
+//!     Unknown(RawXml<'a>),
+//! }
+//!
+//! let block = Block::from_str(r#"<table><tr/></table>"#).unwrap();
+//!
+//! assert!(matches!(&block, Block::Unknown(raw) if raw.tag() == "table"));
+//! assert_eq!(block.to_string().unwrap(), r#"<table><tr/></table>"#);
+//! ```
+//!
+//! ### `#[xml(partial)]`
+//!
+//! Generates a `read_fields` function which reads only the fields whose names
+//! are given, skipping the attributes and children of the other ones without
+//! parsing them. Fields which aren't read are set to their default value, so
+//! every field type must implement `Default`.
+//!
+//! ```rust
+//! use std::borrow::Cow;
+//! use hard_xml::{XmlRead, XmlReader};
+//!
+//! #[derive(XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "doc", partial)]
+//! struct Doc<'a> {
+//!     #[xml(attr = "id")]
+//!     id: u32,
+//!     #[xml(flatten_text = "p")]
+//!     body: Vec<Cow<'a, str>>,
+//! }
+//!
+//! assert_eq!(
+//!     Doc::read_fields(&mut XmlReader::new(r#"<doc id="1"><p>...</p></doc>"#), &["id"]).unwrap(),
+//!     Doc { id: 1, body: vec![] }
+//! );
+//! ```
+//!
+//! ### `#[xml(bound = "")]`
+//!
+//! Generic structs and enums get bounds for the type parameters used as the type
+//! of a field, or of the items of an `Option` or `Vec` field: `XmlRead` and
+//! `XmlWrite` for children, `FromStr` and `Display` for attributes and text,
+//! unless they're bounded already. They can be replaced with other where
+//! predicates, which apply to every derived impl.
+//!
+//! ```rust
+//! use hard_xml::{XmlRead, XmlReadOwned, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "envelope")]
+//! struct Envelope<T> {
+//!     #[xml(child = "body")]
+//!     body: T,
+//! }
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "owned", bound = "T: XmlReadOwned + XmlWrite")]
+//! struct Owned<T> {
+//!     #[xml(child = "body")]
+//!     body: Vec<T>,
+//! }
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "body")]
+//! struct Body {
+//!     #[xml(text)]
+//!     text: String,
+//! }
+//!
+//! assert_eq!(
+//!     Envelope::<Body>::from_str(r#"<envelope><body>hi</body></envelope>"#).unwrap(),
+//!     Envelope { body: Body { text: "hi".into() } }
+//! );
+//! ```
+//!
+//! ### `#[xml(owned)]`
+//!
+//! Copies strings and bytes instead of borrowing them from the input, so the
+//! element can be read with any input lifetime, e.g. into `Book<'static>` which
+//! implements `XmlReadOwned`. Children must be `owned` as well, and `&str`, `RawXml`
+//! and `tree::Element` fields can't be read then.
+//!
+//! ```rust
+//! use std::borrow::Cow;
+//! use hard_xml::{XmlRead, XmlResult};
+//!
+//! #[derive(XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "book", owned)]
+//! struct Book<'a> {
+//!     #[xml(attr = "title")]
+//!     title: Cow<'a, str>,
+//! }
+//!
+//! fn load(xml: String) -> XmlResult<Book<'static>> {
+//!     Book::from_str(&xml)
+//! }
+//!
+//! assert_eq!(load(r#"<book title="Dune"/>"#.into()).unwrap().title, "Dune");
+//! ```
+//!
+//! ### `#[xml(crate = "")]`
+//!
+//! Path the generated code refers to this crate with, which is `hard_xml` by
+//! default, for crates which re-export it.
+//!
+//! ```rust
+//! mod facade {
+//!     pub mod xml {
+//!         pub use hard_xml::*;
+//!     }
+//! }
+//!
+//! use facade::xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "ping", crate = "facade::xml")]
+//! struct Ping;
+//!
+//! assert_eq!(Ping::from_str("<ping/>").unwrap(), Ping);
+//! ```
+//!
+//! ### `#[xml(impl_display)]` and `#[xml(impl_from_str)]`
+//!
+//! Implements `Display` writing the element, with `XmlWrite`, and `FromStr`
+//! reading it, with `XmlRead`, so the type can be used where these traits are
+//! expected. `FromStr` can't borrow from the string, so types with lifetimes
+//! require `#[xml(owned)]`. Enums read from text implement both anyway.
+//!
+//! Calling `to_string` requires naming the trait if `XmlWrite` is in scope.
+//!
+//! ```rust
+//! use hard_xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "point", impl_display, impl_from_str)]
+//! struct Point {
+//!     #[xml(attr = "x")]
+//!     x: i32,
+//!     #[xml(attr = "y")]
+//!     y: i32,
+//! }
+//!
+//! let point: Point = r#"<point x="1" y="2"/>"#.parse().unwrap();
+//!
+//! assert_eq!(point, Point { x: 1, y: 2 });
+//! assert_eq!(format!("{}", point), r#"<point x="1" y="2"/>"#);
+//! ```
+//!
+//! ### `#[xml(impl_try_from)]`
+//!
+//! Implements `TryFrom<&str>` reading the element, with `XmlRead`, and
+//! `TryFrom<T>` and `TryFrom<&T>` for `String` writing it, with `XmlWrite`,
+//! for code that's generic over the conversion traits. Unlike `FromStr`, the
+//! element can borrow from the string.
+//!
+//! ```rust
+//! use std::borrow::Cow;
+//! use std::convert::{TryFrom, TryInto};
+//! use hard_xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "user", impl_try_from)]
+//! struct User<'a> {
+//!     #[xml(attr = "name")]
+//!     name: Cow<'a, str>,
+//! }
+//!
+//! let user = User::try_from(r#"<user name="bob"/>"#).unwrap();
+//!
+//! assert_eq!(user.name, "bob");
+//!
+//! let xml: String = user.try_into().unwrap();
+//!
+//! assert_eq!(xml, r#"<user name="bob"/>"#);
+//! ```
+//!
+//! ### `#[xml(rename = "")]`
+//!
+//! Enums whose variants are all units without `tag` attribute are read from
+//! text: the derives implement `FromStr` and `Display`, matching the text of
+//! each variant against its `rename` attribute, or its name if it has none.
+//! Unknown text fails with `ErrorDetail::UnknownVariant`, unless a variant has
+//! `#[xml(other)]` attribute: a `String` newtype variant keeps the text and
+//! writes it back, while a unit variant is written as its name. Such enums
+//! can be used in `attr`, `text` and `flatten_text` fields, and as `child`
+//! fields if the enum has a `tag` attribute.
+//!
+//! ```rust
+//! use hard_xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "status")]
+//! enum Status {
+//!     #[xml(rename = "active")]
+//!     Active,
+//!     #[xml(rename = "inactive")]
+//!     Inactive,
+//! }
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "account")]
+//! struct Account {
+//!     #[xml(child = "status")]
+//!     status: Status,
+//! }
+//!
+//! assert_eq!(
+//!     Account::from_str(r#"<account><status>inactive</status></account>"#).unwrap(),
+//!     Account { status: Status::Inactive }
+//! );
+//!
+//! assert!(Account::from_str(r#"<account><status>deleted</status></account>"#).is_err());
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! enum Kind {
+//!     #[xml(rename = "file")]
+//!     File,
+//!     #[xml(other)]
+//!     Other(String),
+//! }
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "entry")]
+//! struct Entry {
+//!     #[xml(attr = "kind")]
+//!     kind: Kind,
+//! }
+//!
+//! assert_eq!(
+//!     Entry::from_str(r#"<entry kind="link"/>"#).unwrap(),
+//!     Entry { kind: Kind::Other("link".into()) }
+//! );
+//! ```
+//!
+//! ### `#[xml(alias = "")]`
+//!
+//! Accepts other tags when reading a struct or enum variant, which is still
+//! written with its `tag`. The element must be closed with the name it was
+//! opened with. Parents list the aliases as `child` tags too, since they
+//! choose the field by tag before reading it.
+//!
+//! ```rust
+//! use std::borrow::Cow;
+//! use hard_xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "author", alias = "Author", alias = "creator")]
+//! struct Author<'a> {
+//!     #[xml(text)]
+//!     name: Cow<'a, str>,
+//! }
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "book")]
+//! struct Book<'a> {
+//!     #[xml(child = "author", child = "creator")]
+//!     author: Author<'a>,
+//! }
+//!
+//! let book = Book::from_str(r#"<book><creator>foo</creator></book>"#).unwrap();
+//!
+//! assert_eq!(book.author.name, "foo");
+//! assert_eq!(book.to_string().unwrap(), r#"<book><author>foo</author></book>"#);
+//! ```
+//!
+//! Together with `attr` attribute, it accepts other names for the attribute.
+//!
+//! ```rust
+//! # use hard_xml::{XmlRead, XmlWrite};
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "link")]
+//! struct Link {
+//!     #[xml(attr = "id", alias = "ID", alias = "Id")]
+//!     id: u32,
+//! }
+//!
+//! assert_eq!(Link::from_str(r#"<link ID="1"/>"#).unwrap(), Link { id: 1 });
+//! assert_eq!((Link { id: 1 }).to_string().unwrap(), r#"<link id="1"/>"#);
+//! ```
+//!
+//! ### `#[xml(rename_all = "")]`
+//!
+//! Derives the text of every variant of an enum read from text from its name,
+//! using one of `"lowercase"`, `"UPPERCASE"`, `"PascalCase"`, `"camelCase"`,
+//! `"snake_case"`, `"SCREAMING_SNAKE_CASE"`, `"kebab-case"` or
+//! `"SCREAMING-KEBAB-CASE"`. Variants with `rename` attribute keep their own.
+//!
+//! ```rust
+//! use hard_xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(rename_all = "kebab-case")]
+//! enum Color {
+//!     DarkRed,
+//!     #[xml(rename = "blue")]
+//!     LightBlue,
+//! }
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "pen")]
+//! struct Pen {
+//!     #[xml(attr = "color")]
+//!     color: Color,
+//! }
+//!
+//! assert_eq!(
+//!     Pen::from_str(r#"<pen color="dark-red"/>"#).unwrap(),
+//!     Pen { color: Color::DarkRed }
+//! );
+//!
+//! assert_eq!(
+//!     (Pen { color: Color::LightBlue }).to_string().unwrap(),
+//!     r#"<pen color="blue"/>"#
+//! );
+//! ```
+//!
+//! On structs and struct variants, it names the `attr`, `child` and
+//! `flatten_text` fields given without a value after the field instead.
+//! Without `rename_all`, they're named after the field as is.
+//!
+//! ```rust
+//! # use hard_xml::{XmlRead, XmlWrite};
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "order", rename_all = "camelCase")]
+//! struct Order {
+//!     #[xml(attr)]
+//!     order_id: u32,
+//!     #[xml(flatten_text)]
+//!     customer_name: String,
+//! }
+//!
+//! assert_eq!(
+//!     (Order { order_id: 1, customer_name: "foo".into() }).to_string().unwrap(),
+//!     r#"<order orderId="1"><customerName>foo</customerName></order>"#
+//! );
+//! ```
+//!
+//! ### `#[xml(case_insensitive)]`
+//!
+//! Compares the names of elements and attributes ignoring ASCII case while
+//! the struct or enum is read, including the elements nested in it. Writing
+//! keeps the declared names. To ignore case in whole documents, read them
+//! with the `IgnoreAsciiCase` name matcher instead.
+//!
+//! ```rust
+//! use hard_xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "item", case_insensitive)]
+//! struct Item {
+//!     #[xml(attr = "id")]
+//!     id: u32,
+//! }
+//!
+//! assert_eq!(Item::from_str(r#"<Item ID="1"/>"#).unwrap(), Item { id: 1 });
+//! assert_eq!((Item { id: 1 }).to_string().unwrap(), r#"<item id="1"/>"#);
+//! ```
+//!
+//! ### `#[xml(or_child = "")]` and `#[xml(or_attr = "")]`
+//!
+//! Reads an `attr` field from a child element as well, or a `flatten_text` field
+//! from an attribute as well, for documents which use either form. The field is
+//! still written in the form of its main attribute.
+//!
+//! ```rust
+//! use hard_xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "item")]
+//! struct Item {
+//!     #[xml(attr = "id", or_child = "id")]
+//!     id: u32,
+//!     #[xml(flatten_text = "name", or_attr = "name")]
+//!     name: String,
+//! }
+//!
+//! let item = Item::from_str(r#"<item name="pen"><id>1</id></item>"#).unwrap();
+//!
+//! assert_eq!(item, Item { id: 1, name: "pen".into() });
+//! assert_eq!(item.to_string().unwrap(), r#"<item id="1"><name>pen</name></item>"#);
+//! ```
+//!
+//! ### `#[xml(deprecated = "")]`
+//!
+//! Together with `attr` or `flatten_text` attribute, it reads the field from an
+//! old name as well, while the field is always written with its current name.
+//! Every time the old name is read, a warning is logged and the hook set with
+//! `XmlReader::with_deprecation_hook` is called.
+//!
+//! ```rust
+//! # use hard_xml::{XmlRead, XmlWrite};
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "server")]
+//! struct Server {
+//!     #[xml(attr = "host", deprecated = "hostname")]
+//!     host: String,
+//!     #[xml(flatten_text = "timeout", deprecated = "timeout-secs")]
+//!     timeout: u32,
+//! }
+//!
+//! let server = Server::from_str(r#"<server hostname="a"><timeout-secs>5</timeout-secs></server>"#).unwrap();
+//!
+//! assert_eq!(server, Server { host: "a".into(), timeout: 5 });
+//! assert_eq!(
+//!     server.to_string().unwrap(),
+//!     r#"<server host="a"><timeout>5</timeout></server>"#
+//! );
+//! ```
+//!
+//! ### `#[xml(skip_serializing_if = "")]`
+//!
+//! Together with `attr`, `child` or `flatten_text` attribute, it omits the field
+//! when writing if the given function returns `true` for it. The function is
+//! called with a reference to the field. Combine it with `default` attribute
+//! so that the omitted field can be read back.
+//!
+//! ```rust
+//! # use hard_xml::{XmlRead, XmlWrite};
+//! fn is_zero(value: &u32) -> bool {
+//!     *value == 0
+//! }
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "item")]
+//! struct Item {
+//!     #[xml(attr = "count", default, skip_serializing_if = "is_zero")]
+//!     count: u32,
+//!     #[xml(flatten_text = "note", default, skip_serializing_if = "String::is_empty")]
+//!     note: String,
+//! }
+//!
+//! let item = Item { count: 0, note: String::new() };
+//!
+//! assert_eq!(item.to_string().unwrap(), r#"<item/>"#);
+//! assert_eq!(Item::from_str(r#"<item/>"#).unwrap(), item);
+//! assert_eq!(
+//!     (Item { count: 2, note: "foo".into() }).to_string().unwrap(),
+//!     r#"<item count="2"><note>foo</note></item>"#
+//! );
+//! ```
+//!
+//! ### `#[xml(skip)]`
+//!
+//! Neither reads nor writes the field, which is initialized with
+//! `Default::default()` when reading. Together with `default = ""` attribute,
+//! the given function is called instead.
+//!
+//! Markers of type `PhantomData<T>`, `PhantomPinned` and `()` are skipped
+//! without this attribute.
+//!
+//! ```rust
+//! # use hard_xml::{XmlRead, XmlWrite};
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "point")]
+//! struct Point {
+//!     #[xml(attr = "x")]
+//!     x: f64,
+//!     #[xml(attr = "y")]
+//!     y: f64,
+//!     #[xml(skip)]
+//!     cached_length: Option<f64>,
+//! }
+//!
+//! let point = Point::from_str(r#"<point x="3" y="4"/>"#).unwrap();
+//!
+//! assert_eq!(point, Point { x: 3.0, y: 4.0, cached_length: None });
+//!
+//! let point = Point { cached_length: Some(5.0), ..point };
+//!
+//! assert_eq!(point.to_string().unwrap(), r#"<point x="3" y="4"/>"#);
+//! ```
+//!
+//! ### `#[xml(flatten)]`
+//!
+//! Reads and writes the fields of another struct as if they were declared in
+//! this one, so a block of common attributes and children can be shared. The
+//! flattened struct has no `tag` attribute, and derives `XmlReadFlatten` and
+//! `XmlWriteFlatten` instead of `XmlRead` and `XmlWrite`. It supports `attr`,
+//! `child`, `flatten_text`, `skip` and `flatten` fields.
+//!
+//! Flattened children are written after the other children, and aren't read
+//! from elements with a `text` field.
+//!
+//! ```rust
+//! # use hard_xml::{XmlRead, XmlWrite};
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! struct Common {
+//!     #[xml(attr = "id")]
+//!     id: u32,
+//!     #[xml(attr = "created")]
+//!     created: Option<u64>,
+//! }
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "user")]
+//! struct User {
+//!     #[xml(attr = "name")]
+//!     name: String,
+//!     #[xml(flatten)]
+//!     common: Common,
+//! }
+//!
+//! let user = User::from_str(r#"<user id="1" name="foo"/>"#).unwrap();
+//!
+//! assert_eq!(user.common, Common { id: 1, created: None });
+//! assert_eq!(user.to_string().unwrap(), r#"<user name="foo" id="1"/>"#);
+//! ```
+//!
+//! ### `#[xml(read_only)]` and `#[xml(write_only)]`
+//!
+//! Together with `attr`, `child` or `flatten_text` attribute, `read_only`
+//! reads the field but never writes it, and `write_only` writes the field but
+//! never reads it, leaving it to `Default::default()` or the function given
+//! with `default = ""` attribute.
+//!
+//! ```rust
+//! # use hard_xml::{XmlRead, XmlWrite};
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "user")]
+//! struct User {
+//!     #[xml(attr = "id", read_only)]
+//!     id: u32,
+//!     #[xml(attr = "name")]
+//!     name: String,
+//!     #[xml(attr = "version", write_only)]
+//!     version: u32,
+//! }
+//!
+//! let user = User::from_str(r#"<user id="1" name="foo" version="2"/>"#).unwrap();
+//!
+//! assert_eq!(user, User { id: 1, name: "foo".into(), version: 0 });
+//!
+//! let user = User { version: 3, ..user };
+//!
+//! assert_eq!(user.to_string().unwrap(), r#"<user name="foo" version="3"/>"#);
+//! ```
+//!
+//! ### `#[xml(const_attr("", ""))]`
+//!
+//! Always writes an attribute with a constant value, which has no field. With
+//! `validate`, reading fails with `ErrorDetail::UnexpectedValue` if the attribute
+//! has another value, or with `ErrorDetail::MissingField` if it's absent.
+//! Otherwise the attribute is ignored when reading.
+//!
+//! ```rust
+//! # use hard_xml::{ErrorKind, XmlRead, XmlWrite};
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "config", const_attr("version", "1.2", validate))]
+//! struct Config {
+//!     #[xml(attr = "name")]
+//!     name: String,
+//! }
+//!
+//! let config = Config { name: "foo".into() };
+//!
+//! assert_eq!(config.to_string().unwrap(), r#"<config version="1.2" name="foo"/>"#);
+//! assert_eq!(Config::from_str(r#"<config version="1.2" name="foo"/>"#).unwrap(), config);
+//!
+//! assert_eq!(
+//!     Config::from_str(r#"<config version="1.1" name="foo"/>"#).unwrap_err().kind(),
+//!     ErrorKind::Mismatch
+//! );
+//! ```
+//!
+//! ### `#[xml(attrs)]`
+//!
+//! Collects every attribute that isn't read by another field, including the
+//! fields of flattened structs, and writes them back after the other attributes.
+//! The field is any collection of name and value pairs, like
+//! `Vec<(Cow<str>, Cow<str>)>`, `HashMap<String, String>` or
+//! `BTreeMap<String, String>`. Attributes are written in the iteration order of
+//! the collection.
+//!
+//! ```rust
+//! # use hard_xml::{XmlRead, XmlWrite};
+//! # use std::borrow::Cow;
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "div")]
+//! struct Div<'a> {
+//!     #[xml(attr = "id")]
+//!     id: Cow<'a, str>,
+//!     #[xml(attrs)]
+//!     data: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+//! }
+//!
+//! let div = Div::from_str(r#"<div id="main" data-role="page"/>"#).unwrap();
+//!
+//! assert_eq!(div.data, vec![("data-role".into(), "page".into())]);
+//!
+//! assert_eq!(div.to_string().unwrap(), r#"<div id="main" data-role="page"/>"#);
+//! ```
+//!
+//! ### `#[xml(children)]`
+//!
+//! Collects every child element that isn't read by another field, including the
+//! fields of flattened structs, and writes them back after the other children, so
+//! extensions of other tools survive editing a document. The field is a `Vec` of
+//! any type implementing `XmlRead` and `XmlWrite`, usually `RawXml` or
+//! `tree::Element`. Children aren't collected from elements with a `text` field.
+//!
+//! ```rust
+//! # use hard_xml::{RawXml, XmlRead, XmlWrite};
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "package")]
+//! struct Package<'a> {
+//!     #[xml(flatten_text = "name")]
+//!     name: String,
+//!     #[xml(children)]
+//!     extensions: Vec<RawXml<'a>>,
+//! }
+//!
+//! let xml = r#"<package><name>foo</name><x:lock hash="ab"/></package>"#;
+//!
+//! let package = Package::from_str(xml).unwrap();
+//!
+//! assert_eq!(package.extensions[0].tag(), "x:lock");
+//!
+//! assert_eq!(package.to_string().unwrap(), xml);
+//! ```
+//!
+//! ### `#[xml(map = "")]`
+//!
+//! Reads and writes a map, like `HashMap<K, V>` or `BTreeMap<K, V>`, as repeated
+//! `<entry key="..." value="..."/>` elements, where `key = ""` and `value = ""`
+//! attributes rename the attributes holding the key and the value. With
+//! `map = "*"`, entries are written as `<key>value</key>` elements instead, and
+//! read from every child that isn't read by another field.
+//!
+//! Keys and values are parsed and formatted like attribute values. Entries are
+//! written after the other children, in the iteration order of the map.
+//!
+//! ```rust
+//! # use hard_xml::{XmlRead, XmlWrite};
+//! # use std::collections::BTreeMap;
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "config")]
+//! struct Config {
+//!     #[xml(map = "limit", key = "name", value = "max")]
+//!     limits: BTreeMap<String, u32>,
+//!     #[xml(map = "*")]
+//!     env: BTreeMap<String, String>,
+//! }
+//!
+//! let config = Config::from_str(
+//!     r#"<config><limit name="cpu" max="4"/><HOME>/root</HOME></config>"#,
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(config.limits["cpu"], 4);
+//! assert_eq!(config.env["HOME"], "/root");
+//!
+//! assert_eq!(
+//!     config.to_string().unwrap(),
+//!     r#"<config><limit name="cpu" max="4"/><HOME>/root</HOME></config>"#
+//! );
+//! ```
+//!
+//! ### `#[xml(key = "")]`
+//!
+//! Reads the children of a `child` field into a map, like `HashMap<K, V>` or
+//! `BTreeMap<K, V>`, keyed by the value of their `key` attribute, which is parsed
+//! like other attribute values. Children are written from the values of the map,
+//! which are expected to write their key themselves. The map must implement
+//! `XmlMap`.
+//!
+//! A duplicate key is an error, unless `duplicate_key = "first"` keeps the first
+//! child or `duplicate_key = "last"` keeps the last one.
+//!
+//! ```rust
+//! # use hard_xml::{XmlRead, XmlWrite};
+//! # use std::collections::BTreeMap;
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "users")]
+//! struct Users {
+//!     #[xml(child = "user", key = "id")]
+//!     users: BTreeMap<String, User>,
+//! }
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "user")]
+//! struct User {
+//!     #[xml(attr = "id")]
+//!     id: String,
+//!     #[xml(text)]
+//!     name: String,
+//! }
+//!
+//! let users = Users::from_str(
+//!     r#"<users><user id="bob">Bob</user><user id="alice">Alice</user></users>"#,
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(users.users["alice"].name, "Alice");
+//!
+//! assert_eq!(
+//!     users.to_string().unwrap(),
+//!     r#"<users><user id="alice">Alice</user><user id="bob">Bob</user></users>"#
+//! );
+//! ```
+//!
+//! ### `indexmap` feature
+//!
+//! Implements `XmlMap` for `IndexMap`, which keeps the entries of `attrs`, `map`
+//! and keyed `child` fields in the order they appear in the document, so it
+//! round-trips unchanged, unlike with `HashMap`.
+//!
+//! ### `chrono` feature
+//!
+//! Adds `Xs`, which lets `DateTime<Utc>`, `DateTime<FixedOffset>` and
+//! `NaiveDateTime` fields wrapped in it be read and written in the `xs:dateTime`
+//! form, e.g. `2024-01-02T03:04:05Z`, instead of the one of their `Display`
+//! implementations, and `NaiveDate` and `NaiveTime` fields in the `xs:date` and
+//! `xs:time` forms. Surrounding whitespace is ignored when reading, and the
+//! `Display` forms are read as well.
+//!
+//! Fields of the `chrono` types themselves are still read with `FromStr` and
+//! written with `Display`, so enabling the feature, which Cargo does for every
+//! crate depending on hard-xml if one of them does, doesn't change how they're
+//! written.
+//!
+//! Other types can do the same by implementing `XmlValue`.
+//!
+//! ### `rust_decimal` feature
+//!
+//! Lets `Decimal` fields be read from the `xs:decimal` form and written back
+//! with the same scale, so `19.90` stays `19.90`. Values with more digits than a
+//! `Decimal` holds fail to read instead of being rounded.
+//!
+//! ### `time` feature
+//!
+//! Lets `OffsetDateTime`, `PrimitiveDateTime`, `Date` and `Time` fields of the
+//! `time` crate be read and written in the `xs:dateTime`, `xs:date` and
+//! `xs:time` forms, like the `chrono` feature.
+//!
+//! ### `url` feature
+//!
+//! Lets `Url` fields be read with validation, ignoring surrounding whitespace,
+//! and written as they're displayed. Reading a malformed URL fails with an error
+//! naming it.
+//!
+//! ### `uuid` feature
+//!
+//! Lets `Uuid` fields read any of the hyphenated, simple, braced and urn forms,
+//! ignoring surrounding whitespace, and write the hyphenated one. The wrappers
+//! in `uuid::fmt`, like `Braced`, can be used for fields which must be written
+//! in another form.
+//!
+//! ### `miette` feature
+//!
+//! Implements `miette::Diagnostic` for `XmlError`, so errors with a position are
+//! shown with the lines around it and a caret under the token the error was
+//! raised at. Reports need the document to show it, e.g.
+//! `Report::new(err).with_source_code(xml.to_owned())`. The code of a diagnostic
+//! is its kind, like `hard_xml::syntax`.
+//!
+//! ### Durations
+//!
+//! `std::time::Duration` fields are read and written in the `xs:duration` form,
+//! e.g. `PT1H30M`. `XsdDuration` keeps all of its components, including years,
+//! months and the sign, for durations which don't fit a `Duration`.
+//!
+//! ### `#[xml(list)]`
+//!
+//! Reads a `Vec` attribute from a single attribute whose values are separated by
+//! whitespace, like `xs:list` types or the `class` attribute, and writes the
+//! values separated by spaces. An empty `Vec` isn't written. It also applies to
+//! `text` and `flatten_text` fields, whose values are then read from a single
+//! text.
+//!
+//! ```rust
+//! # use hard_xml::{XmlRead, XmlWrite};
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "rect")]
+//! struct Rect {
+//!     #[xml(attr = "class", list)]
+//!     class: Vec<String>,
+//!     #[xml(attr = "viewBox", list)]
+//!     view_box: Vec<f64>,
+//! }
+//!
+//! let rect = Rect::from_str(r#"<rect class="big  red" viewBox="0 0 10 20"/>"#).unwrap();
+//!
+//! assert_eq!(rect.class, vec!["big", "red"]);
+//! assert_eq!(rect.view_box, vec![0.0, 0.0, 10.0, 20.0]);
+//!
+//! assert_eq!(
+//!     rect.to_string().unwrap(),
+//!     r#"<rect class="big red" viewBox="0 0 10 20"/>"#
+//! );
+//! ```
+//!
+//! ### `#[xml(separator = "")]`
+//!
+//! Like `list` attribute, but the values are separated by the given string, and
+//! the whitespace around them is trimmed. Empty values are skipped.
+//!
+//! ```rust
+//! # use hard_xml::{XmlRead, XmlWrite};
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "query")]
+//! struct Query {
+//!     #[xml(attr = "ids", separator = ",")]
+//!     ids: Vec<u32>,
+//!     #[xml(flatten_text = "tags", separator = ";")]
+//!     tags: Vec<String>,
+//! }
+//!
+//! let query = Query::from_str(r#"<query ids="1, 2, 3"><tags>a; b</tags></query>"#).unwrap();
+//!
+//! assert_eq!(query.ids, vec![1, 2, 3]);
+//! assert_eq!(query.tags, vec!["a", "b"]);
+//!
+//! assert_eq!(
+//!     query.to_string().unwrap(),
+//!     r#"<query ids="1,2,3"><tags>a;b</tags></query>"#
+//! );
+//! ```
+//!
+//! ### Writing from iterators
+//!
+//! `XmlIter` wraps an iterator so it can be written as a `child` field or on its
+//! own without collecting it into a `Vec`, and `XmlWriter::write_elements` writes
+//! the elements of any `IntoIterator` one after another.
+//!
+//! ```rust
+//! use hard_xml::{XmlIter, XmlWrite};
+//!
+//! #[derive(XmlWrite)]
+//! #[xml(tag = "id")]
+//! struct Id {
+//!     #[xml(text)]
+//!     value: u32,
+//! }
+//!
+//! let ids = XmlIter::new((1..=3).map(|value| Id { value }));
+//!
+//! assert_eq!(ids.to_string().unwrap(), "<id>1</id><id>2</id><id>3</id>");
+//! ```
+//!
+//! ### Writing from views
+//!
+//! Fields of types only deriving `XmlWrite` can borrow from other storage, so
+//! domain data is written without being cloned into the XML types first:
+//! `&T` is written like `T`, and `&[T]` like `Vec<T>`. Slices can't be read,
+//! so they require `write_only` in types deriving `XmlRead`.
+//!
+//! ```rust
+//! use hard_xml::XmlWrite;
+//!
+//! struct Track {
+//!     title: String,
+//!     seconds: u32,
+//! }
+//!
+//! #[derive(XmlWrite)]
+//! #[xml(tag = "playlist")]
+//! struct PlaylistView<'a> {
+//!     #[xml(attr = "name")]
+//!     name: &'a str,
+//!     #[xml(child = "track")]
+//!     tracks: &'a [TrackView<'a>],
+//! }
+//!
+//! #[derive(XmlWrite)]
+//! #[xml(tag = "track")]
+//! struct TrackView<'a> {
+//!     #[xml(attr = "seconds")]
+//!     seconds: &'a u32,
+//!     #[xml(text)]
+//!     title: &'a str,
+//! }
+//!
+//! let tracks = vec![Track { title: "Intro".into(), seconds: 90 }];
+//!
+//! let views: Vec<_> = tracks
+//!     .iter()
+//!     .map(|track| TrackView { seconds: &track.seconds, title: &track.title })
+//!     .collect();
+//!
+//! let playlist = PlaylistView { name: "mix", tracks: &views };
+//!
+//! assert_eq!(
+//!     playlist.to_string().unwrap(),
+//!     r#"<playlist name="mix"><track seconds="90">Intro</track></playlist>"#
+//! );
+//! ```
+//!
+//! ### Conditional fields
+//!
+//! `#[cfg]` and `#[cfg_attr]` are evaluated before the derives run, so fields
+//! and variants which are compiled out are neither read nor written, and
+//! `cfg_attr` can change how a field is read depending on a feature. Fields
+//! guarded by exclusive features can read the same attribute or child.
+//!
+//! ```rust
+//! use hard_xml::{XmlRead, XmlWrite};
+//!
+//! #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+//! #[xml(tag = "config")]
+//! struct Config {
+//!     #[xml(attr = "name")]
+//!     name: String,
+//!     #[cfg(feature = "ports")]
+//!     #[xml(attr = "port")]
+//!     port: u16,
+//!     #[cfg(not(feature = "ports"))]
+//!     #[xml(attr = "port")]
+//!     port: String,
+//!     #[cfg_attr(feature = "ports", xml(attr = "hosts", list))]
+//!     #[cfg_attr(not(feature = "ports"), xml(flatten_text = "host"))]
+//!     hosts: Vec<String>,
+//! }
+//!
+//! let config = Config::from_str(r#"<config name="db" port="5432"><host>a</host></config>"#)?;
+//!
+//! assert_eq!(config.port, "5432");
+//! assert_eq!(config.hosts, ["a"]);
+//! # Ok::<(), hard_xml::XmlError>(())
+//! ```
+//!
+//! ## License
+//!
+//! MIT
+
+#[cfg(feature = "log")]
+mod log;
+#[cfg(not(feature = "log"))]
+mod noop_log;
+
+#[cfg(feature = "log")]
+#[doc(hidden)]
+pub mod lib {
+    pub use log;
+}
+
+mod xml_binary;
+mod xml_builder;
+mod xml_duplicate;
+mod xml_duration;
+mod xml_error;
+mod xml_escape;
+mod xml_flatten;
+mod xml_map;
+mod xml_name_matcher;
+mod xml_number;
+mod xml_raw;
+mod xml_read;
+mod xml_reader;
+mod xml_registry;
+mod xml_runtime;
+mod xml_schema;
+mod xml_str;
+mod xml_token_filter;
+mod xml_tree;
+mod xml_unescape;
+mod xml_value;
+mod xml_whitespace;
+mod xml_write;
+mod xml_writer;
+
+pub use self::xml_builder::XmlBuilder;
+pub use self::xml_duplicate::DuplicateAttrs;
+pub use self::xml_duration::{ParseDurationError, XsdDuration};
+pub use self::xml_error::{ErrorDetail, ErrorKind, Position, XmlError, XmlResult};
+pub use self::xml_flatten::{XmlReadFlatten, XmlWriteFlatten};
+pub use self::xml_map::XmlMap;
+pub use self::xml_name_matcher::NameMatcher;
+pub use self::xml_number::Numeric;
+pub use self::xml_raw::RawXml;
+pub use self::xml_read::{XmlRead, XmlReadOwned};
+pub use self::xml_reader::{Checkpoint, XmlReader, XmlReaderState};
+pub use self::xml_schema::XmlSchemaInfo;
+pub use self::xml_token_filter::TokenFilter;
+pub use self::xml_value::XmlValue;
+#[cfg(feature = "chrono")]
+pub use self::xml_value::Xs;
+pub use self::xml_whitespace::AttrWhitespace;
+pub use self::xml_write::{XmlDisplay, XmlIter, XmlWrite};
+pub use self::xml_writer::XmlWriter;
+
+pub use hard_xml_derive::{XmlRead, XmlSchemaInfo, XmlWrite};
+
+pub use xmlparser;
+
+pub mod filter {
+    pub use super::xml_token_filter::{DropAttribute, Rename, UnwrapElement};
+}
+
+pub mod matcher {
+    pub use super::xml_name_matcher::{
+        AliasTable, Exact, IgnoreAsciiCase, LocalName, Namespaces, XML_NAMESPACE,
+    };
+}
+
+pub mod registry {
+    pub use super::xml_registry::{write_dyn, DynXmlWrite, Registry};
+}
+
+pub mod schema {
+    pub use super::xml_schema::{ElementInfo, FieldInfo, FieldKind};
+}
+
+pub mod tree {
+    pub use super::xml_tree::{Element, Node, Subtrees};
+}
 
 pub mod utils {
+    #[cfg(feature = "base64")]
+    pub use super::xml_binary::{base64_decode, base64_encode};
+    pub use super::xml_binary::{
+        bytes_from_utf8, from_bytes, from_bytes_owned, hex_decode, hex_encode, FromBytes,
+    };
     pub use super::xml_escape::xml_escape;
+    pub use super::xml_number::{
+        parse_radix, parse_saturating, Bounded, Overflow, RadixDisplay, RadixInt,
+    };
+    pub use super::xml_runtime::{
+        check_occurs, duplicate_field, from_str_error, has_content, missing_field, read_attributes,
+        read_child, read_children, read_element_text, skip_element, skip_start_tag,
+    };
+    pub use super::xml_str::{from_cow_str, from_cow_str_owned, FromCowStr};
     pub use super::xml_unescape::xml_unescape;
+    pub use super::xml_value::{
+        Format, FormatDisplay, FormatXmlValue, Parse, ParseFromStr, ParseXmlValue, XmlValueDisplay,
+    };
+    pub use super::xml_whitespace::trim_text;
 }
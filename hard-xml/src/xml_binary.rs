@@ -0,0 +1,150 @@
+use std::borrow::Cow;
+
+use crate::{XmlError, XmlResult};
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+pub fn bytes_from_utf8(text: Cow<'_, str>) -> Cow<'_, [u8]> {
+    match text {
+        Cow::Borrowed(text) => Cow::Borrowed(text.as_bytes()),
+        Cow::Owned(text) => Cow::Owned(text.into_bytes()),
+    }
+}
+
+pub fn hex_encode(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len() * 2);
+
+    for byte in bytes {
+        result.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+        result.push(HEX_DIGITS[(byte & 0xf) as usize] as char);
+    }
+
+    result
+}
+
+pub fn hex_decode(text: &str) -> XmlResult<Vec<u8>> {
+    fn digit(c: u8) -> XmlResult<u8> {
+        match c {
+            b'0'..=b'9' => Ok(c - b'0'),
+            b'a'..=b'f' => Ok(c - b'a' + 10),
+            b'A'..=b'F' => Ok(c - b'A' + 10),
+            _ => Err(XmlError::conversion(format!(
+                "invalid hex digit {:?}",
+                c as char
+            ))),
+        }
+    }
+
+    let bytes = text.trim().as_bytes();
+
+    // `usize::is_multiple_of` needs Rust 1.87
+    #[allow(clippy::manual_is_multiple_of)]
+    if bytes.len() % 2 != 0 {
+        return Err(XmlError::conversion(
+            "hex encoded value has an odd number of digits",
+        ));
+    }
+
+    bytes
+        .chunks(2)
+        .map(|pair| Ok((digit(pair[0])? << 4) | digit(pair[1])?))
+        .collect()
+}
+
+/// Binary field types, built from the decoded bytes.
+pub trait FromBytes<'a>: Sized {
+    fn from_bytes(bytes: Cow<'a, [u8]>) -> XmlResult<Self>;
+}
+
+impl<'a> FromBytes<'a> for Cow<'a, [u8]> {
+    fn from_bytes(bytes: Cow<'a, [u8]>) -> XmlResult<Self> {
+        Ok(bytes)
+    }
+}
+
+impl<'a> FromBytes<'a> for Vec<u8> {
+    fn from_bytes(bytes: Cow<'a, [u8]>) -> XmlResult<Self> {
+        Ok(bytes.into_owned())
+    }
+}
+
+impl<'a, const N: usize> FromBytes<'a> for [u8; N] {
+    fn from_bytes(bytes: Cow<'a, [u8]>) -> XmlResult<Self> {
+        (*bytes).try_into().map_err(|_| {
+            XmlError::conversion(format!("expected {} bytes, found {}", N, bytes.len()))
+        })
+    }
+}
+
+pub fn from_bytes<'a, T: FromBytes<'a>>(bytes: Cow<'a, [u8]>) -> XmlResult<T> {
+    T::from_bytes(bytes)
+}
+
+/// Like `from_bytes`, but copies the bytes, so they don't have to outlive
+/// the field.
+pub fn from_bytes_owned<'a, T: FromBytes<'a>>(bytes: Cow<'_, [u8]>) -> XmlResult<T> {
+    T::from_bytes(Cow::Owned(bytes.into_owned()))
+}
+
+#[cfg(feature = "base64")]
+pub fn base64_encode(bytes: &[u8]) -> String {
+    use base64::Engine;
+
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
+
+#[cfg(feature = "base64")]
+pub fn base64_decode(text: &str) -> XmlResult<Vec<u8>> {
+    use base64::Engine;
+
+    // xs:base64Binary allows whitespace anywhere in the lexical form
+    let text = if text.bytes().any(|b| b.is_ascii_whitespace()) {
+        Cow::Owned(text.split_ascii_whitespace().collect::<String>())
+    } else {
+        Cow::Borrowed(text)
+    };
+
+    base64::engine::general_purpose::STANDARD
+        .decode(text.as_bytes())
+        .map_err(XmlError::conversion)
+}
+
+#[test]
+fn test_hex() {
+    assert_eq!(hex_encode(b""), "");
+    assert_eq!(hex_encode(&[0x00, 0x1f, 0xab, 0xff]), "001FABFF");
+    assert_eq!(
+        hex_decode("001fAbFF").unwrap(),
+        vec![0x00, 0x1f, 0xab, 0xff]
+    );
+    assert_eq!(hex_decode(" 00 ").unwrap(), vec![0x00]);
+    assert!(hex_decode("0").is_err());
+    assert!(hex_decode("zz").is_err());
+}
+
+#[test]
+fn test_from_bytes() {
+    assert_eq!(
+        from_bytes::<[u8; 2]>(Cow::Borrowed(&[1, 2])).unwrap(),
+        [1, 2]
+    );
+    assert!(from_bytes::<[u8; 2]>(Cow::Borrowed(&[1])).is_err());
+    assert_eq!(from_bytes::<Vec<u8>>(Cow::Borrowed(&[1])).unwrap(), vec![1]);
+}
+
+#[test]
+fn test_bytes_from_utf8() {
+    assert!(matches!(
+        bytes_from_utf8(Cow::Borrowed("text")),
+        Cow::Borrowed(b"text")
+    ));
+}
+
+#[cfg(feature = "base64")]
+#[test]
+fn test_base64() {
+    assert_eq!(base64_encode(b"hello"), "aGVsbG8=");
+    assert_eq!(base64_decode("aGVsbG8=").unwrap(), b"hello");
+    assert_eq!(base64_decode("aGVs\n  bG8=").unwrap(), b"hello");
+    assert!(base64_decode("a").is_err());
+}
@@ -0,0 +1,299 @@
+use std::io::Write;
+
+use crate::schema::{ElementInfo, FieldInfo, FieldKind};
+use crate::{ErrorDetail, XmlError, XmlResult, XmlSchemaInfo, XmlWrite, XmlWriter};
+
+/// Document builder
+///
+/// Builds an element step by step, checking every attribute, text and
+/// child against the schema info of a target type, so structural mistakes
+/// are reported when they are made rather than when the document is read.
+///
+/// ```rust
+/// use std::borrow::Cow;
+/// use hard_xml::{XmlBuilder, XmlRead, XmlSchemaInfo, XmlWrite};
+///
+/// #[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+/// #[xml(tag = "parent")]
+/// struct Parent<'a> {
+///     #[xml(attr = "id")]
+///     id: u32,
+///     #[xml(child = "child")]
+///     children: Vec<Child<'a>>,
+/// }
+///
+/// #[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+/// #[xml(tag = "child")]
+/// struct Child<'a> {
+///     #[xml(text)]
+///     text: Cow<'a, str>,
+/// }
+///
+/// let document = XmlBuilder::new::<Parent>("parent")?
+///     .attr("id", "1")?
+///     .child("child", |child| child.text("foo"))?
+///     .finish()?;
+///
+/// assert_eq!(document.to_string()?, r#"<parent id="1"><child>foo</child></parent>"#);
+///
+/// assert!(XmlBuilder::new::<Parent>("parent")?.attr("name", "foo").is_err());
+/// assert!(XmlBuilder::new::<Parent>("parent")?.finish().is_err());
+/// # Ok::<(), hard_xml::XmlError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct XmlBuilder {
+    info: ElementInfo,
+    tag: String,
+    attributes: Vec<(&'static str, String)>,
+    content: Vec<Content>,
+    // number of occurrences of every field, indexed like `info.fields`
+    counts: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+enum Content {
+    Text(String),
+    FlattenText(&'static str, String),
+    Child(XmlBuilder),
+    // children of the field with the given index, nested in the wrapper
+    Wrapper(usize, &'static str, Vec<XmlBuilder>),
+}
+
+impl XmlBuilder {
+    /// Starts building the element of `T` with the given tag.
+    pub fn new<T: XmlSchemaInfo>(tag: &str) -> XmlResult<XmlBuilder> {
+        T::elements()
+            .into_iter()
+            .find(|info| info.tag == tag || info.tag == "*")
+            .map(|info| XmlBuilder::from_info(info).with_tag(tag))
+            .ok_or_else(|| {
+                XmlError::new(ErrorDetail::UnknownVariant {
+                    value: tag.to_owned(),
+                })
+                .with_context(std::any::type_name::<T>(), None)
+            })
+    }
+
+    pub fn from_info(info: ElementInfo) -> XmlBuilder {
+        XmlBuilder {
+            counts: vec![0; info.fields.len()],
+            tag: info.tag.to_owned(),
+            info,
+            attributes: Vec::new(),
+            content: Vec::new(),
+        }
+    }
+
+    // elements matching any tag are written with the requested one
+    fn with_tag(mut self, tag: &str) -> Self {
+        self.tag = tag.to_owned();
+        self
+    }
+
+    pub fn info(&self) -> &ElementInfo {
+        &self.info
+    }
+
+    pub fn attr(mut self, key: &str, value: impl Into<String>) -> XmlResult<Self> {
+        let index = self.find(
+            key,
+            |kind| matches!(kind, FieldKind::Attribute { name } if *name == key),
+        )?;
+        let key = match self.info.fields[index].kind {
+            FieldKind::Attribute { name } => name,
+            _ => unreachable!(),
+        };
+        self.occur(index)?;
+        self.attributes.push((key, value.into()));
+        Ok(self)
+    }
+
+    pub fn text(mut self, text: impl Into<String>) -> XmlResult<Self> {
+        let index = self.find("text", |kind| matches!(kind, FieldKind::Text))?;
+        // multiple text segments are concatenated when reading
+        self.counts[index] += 1;
+        self.content.push(Content::Text(text.into()));
+        Ok(self)
+    }
+
+    pub fn flatten_text(mut self, tag: &str, text: impl Into<String>) -> XmlResult<Self> {
+        let index = self.find(
+            tag,
+            |kind| matches!(kind, FieldKind::FlattenText { tag: t } if *t == tag),
+        )?;
+        let tag = match self.info.fields[index].kind {
+            FieldKind::FlattenText { tag } => tag,
+            _ => unreachable!(),
+        };
+        self.follow(index)?;
+        self.occur(index)?;
+        self.content.push(Content::FlattenText(tag, text.into()));
+        Ok(self)
+    }
+
+    /// Adds a child element, which is built by `build` and validated
+    /// against the schema info of the field accepting `tag`.
+    pub fn child<F>(mut self, tag: &str, build: F) -> XmlResult<Self>
+    where
+        F: FnOnce(XmlBuilder) -> XmlResult<XmlBuilder>,
+    {
+        // fields accepting any tag are only used if no other one accepts it
+        let index = self
+            .find(
+                tag,
+                |kind| matches!(kind, FieldKind::Child { tags, .. } if tags.contains(&tag)),
+            )
+            .or_else(|_| {
+                self.find(
+                    tag,
+                    |kind| matches!(kind, FieldKind::Child { tags, .. } if tags.contains(&"*")),
+                )
+            })?;
+        let field = &self.info.fields[index];
+        let (info, wrapper) = match &field.kind {
+            FieldKind::Child {
+                elements, wrapper, ..
+            } => (
+                elements()
+                    .into_iter()
+                    .find(|info| info.tag == tag || info.tag == "*")
+                    .ok_or_else(|| {
+                        XmlError::new(ErrorDetail::UnknownVariant {
+                            value: tag.to_owned(),
+                        })
+                        .with_context(field.name, None)
+                    })?,
+                *wrapper,
+            ),
+            _ => unreachable!(),
+        };
+        self.follow(index)?;
+        self.occur(index)?;
+        let child = build(XmlBuilder::from_info(info).with_tag(tag))?.finish()?;
+        match (wrapper, self.content.last_mut()) {
+            (Some(_), Some(Content::Wrapper(i, _, children))) if *i == index => {
+                children.push(child)
+            }
+            (Some(wrapper), _) => self
+                .content
+                .push(Content::Wrapper(index, wrapper, vec![child])),
+            (None, _) => self.content.push(Content::Child(child)),
+        }
+        Ok(self)
+    }
+
+    /// Checks that every required field is present.
+    pub fn finish(self) -> XmlResult<Self> {
+        for (field, count) in self.info.fields.iter().zip(&self.counts) {
+            if *count == 0 && !field.optional {
+                return Err(XmlError::new(ErrorDetail::MissingField {
+                    tag: self.tag.to_owned(),
+                    expected: field.kind.expected(),
+                })
+                .with_context(self.info.name, Some(field.name)));
+            }
+            if *count < field.min_occurs {
+                return Err(self.unexpected_occurrences(field, *count));
+            }
+        }
+        Ok(self)
+    }
+
+    fn find<P>(&self, key: &str, predicate: P) -> XmlResult<usize>
+    where
+        P: Fn(&FieldKind) -> bool,
+    {
+        self.info
+            .fields
+            .iter()
+            .position(|field| predicate(&field.kind))
+            .ok_or_else(|| {
+                XmlError::new(ErrorDetail::UnknownField).with_context(self.info.name, Some(key))
+            })
+    }
+
+    // checks that no child declared after the field was added yet,
+    // if the element is ordered
+    fn follow(&self, index: usize) -> XmlResult<()> {
+        if !self.info.ordered {
+            return Ok(());
+        }
+        let later = self.info.fields[index + 1..]
+            .iter()
+            .zip(&self.counts[index + 1..])
+            .any(|(field, count)| {
+                *count > 0
+                    && matches!(
+                        field.kind,
+                        FieldKind::Child { .. } | FieldKind::FlattenText { .. }
+                    )
+            });
+        if later {
+            return Err(XmlError::new(ErrorDetail::UnorderedField)
+                .with_context(self.info.name, Some(self.info.fields[index].name)));
+        }
+        Ok(())
+    }
+
+    fn occur(&mut self, index: usize) -> XmlResult<()> {
+        let field = &self.info.fields[index];
+        if self.counts[index] > 0 && !field.multiple {
+            return Err(XmlError::new(ErrorDetail::DuplicateField { first: None })
+                .with_context(self.info.name, Some(field.name)));
+        }
+        if field
+            .max_occurs
+            .is_some_and(|max| self.counts[index] >= max)
+        {
+            return Err(self.unexpected_occurrences(field, self.counts[index] + 1));
+        }
+        self.counts[index] += 1;
+        Ok(())
+    }
+
+    fn unexpected_occurrences(&self, field: &FieldInfo, count: usize) -> XmlError {
+        XmlError::new(ErrorDetail::UnexpectedOccurrences {
+            count,
+            min: field.min_occurs,
+            max: field.max_occurs,
+        })
+        .with_context(self.info.name, Some(field.name))
+    }
+}
+
+impl XmlWrite for XmlBuilder {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        writer.write_element_start(&self.tag)?;
+
+        for (key, value) in &self.attributes {
+            writer.write_attribute(key, value)?;
+        }
+
+        if self.content.is_empty() {
+            writer.write_element_end_empty()?;
+            return Ok(());
+        }
+
+        writer.write_element_end_open()?;
+
+        for content in &self.content {
+            match content {
+                Content::Text(text) => writer.write_text(text)?,
+                Content::FlattenText(tag, text) => writer.write_flatten_text(tag, text, false)?,
+                Content::Child(child) => child.to_writer(writer)?,
+                Content::Wrapper(_, wrapper, children) => {
+                    writer.write_element_start(wrapper)?;
+                    writer.write_element_end_open()?;
+                    for child in children {
+                        child.to_writer(writer)?;
+                    }
+                    writer.write_element_end_close(wrapper)?;
+                }
+            }
+        }
+
+        writer.write_element_end_close(&self.tag)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+/// How an [`XmlReader`](crate::XmlReader) treats an attribute written more
+/// than once in a start tag, which isn't well-formed XML
+///
+/// Names are compared as they're read, prefixes included, once the
+/// [`TokenFilter`](crate::TokenFilter)s of the reader are applied.
+///
+/// ```rust
+/// use hard_xml::{DuplicateAttrs, ErrorDetail, XmlRead, XmlReader};
+///
+/// #[derive(XmlRead, PartialEq, Debug)]
+/// #[xml(tag = "link")]
+/// struct Link {
+///     #[xml(attr = "rel")]
+///     rel: String,
+/// }
+///
+/// let xml = r#"<link rel="next" rel="prev"/>"#;
+///
+/// let mut reader = XmlReader::new(xml).with_duplicate_attrs(DuplicateAttrs::First);
+/// assert_eq!(Link::from_reader(&mut reader)?.rel, "next");
+///
+/// let mut reader = XmlReader::new(xml).with_duplicate_attrs(DuplicateAttrs::Error);
+/// let err = Link::from_reader(&mut reader).unwrap_err();
+/// assert!(matches!(err.detail(), ErrorDetail::DuplicateAttribute { .. }));
+/// # Ok::<(), hard_xml::XmlError>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicateAttrs {
+    /// Every occurrence is read, so the last one is kept by single value
+    /// fields and all of them are collected by `Vec` fields
+    #[default]
+    Allow,
+    /// Reading fails with `ErrorDetail::DuplicateAttribute`, as strict XML
+    /// parsers do
+    Error,
+    /// Only the first occurrence is read
+    First,
+    /// Only the last occurrence is read
+    Last,
+}
@@ -0,0 +1,291 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::XmlValue;
+
+/// Duration in the `xs:duration` form, e.g. `PT1H30M` or `-P1Y2M`
+///
+/// Years and months are kept apart from the other components since their
+/// length varies. `std::time::Duration` fields are read and written in the
+/// same form, as long as they don't use years or months.
+///
+/// ```rust
+/// use std::time::Duration;
+/// use hard_xml::{XmlRead, XmlWrite, XsdDuration};
+///
+/// #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+/// #[xml(tag = "episode")]
+/// struct Episode {
+///     #[xml(attr = "length")]
+///     length: Duration,
+///     #[xml(attr = "expires")]
+///     expires: XsdDuration,
+/// }
+///
+/// let episode = Episode::from_str(r#"<episode length="PT1H30M" expires="P1Y"/>"#)?;
+///
+/// assert_eq!(episode.length, Duration::from_secs(5400));
+/// assert_eq!(episode.expires.years, 1);
+/// assert_eq!(episode.to_string()?, r#"<episode length="PT1H30M" expires="P1Y"/>"#);
+/// # Ok::<(), hard_xml::XmlError>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct XsdDuration {
+    pub negative: bool,
+    pub years: u64,
+    pub months: u64,
+    pub days: u64,
+    pub hours: u64,
+    pub minutes: u64,
+    pub seconds: u64,
+    pub nanos: u32,
+}
+
+impl XsdDuration {
+    /// Returns the duration as a `std::time::Duration`, if it's positive and
+    /// doesn't use years or months.
+    pub fn to_std(&self) -> Option<Duration> {
+        if (self.negative && !self.is_zero()) || self.years != 0 || self.months != 0 {
+            return None;
+        }
+        let secs = self
+            .days
+            .checked_mul(86400)?
+            .checked_add(self.hours.checked_mul(3600)?)?
+            .checked_add(self.minutes.checked_mul(60)?)?
+            .checked_add(self.seconds)?;
+        Duration::new(secs, 0).checked_add(Duration::from_nanos(self.nanos as u64))
+    }
+
+    fn is_zero(&self) -> bool {
+        *self
+            == XsdDuration {
+                negative: self.negative,
+                ..XsdDuration::default()
+            }
+    }
+}
+
+impl From<Duration> for XsdDuration {
+    fn from(duration: Duration) -> Self {
+        let secs = duration.as_secs();
+        XsdDuration {
+            negative: false,
+            years: 0,
+            months: 0,
+            days: secs / 86400,
+            hours: secs / 3600 % 24,
+            minutes: secs / 60 % 60,
+            seconds: secs % 60,
+            nanos: duration.subsec_nanos(),
+        }
+    }
+}
+
+/// Error returned when reading an invalid `xs:duration`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDurationError(String);
+
+impl fmt::Display for ParseDurationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid duration {:?}", self.0)
+    }
+}
+
+impl Error for ParseDurationError {}
+
+impl FromStr for XsdDuration {
+    type Err = ParseDurationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseDurationError(s.to_owned());
+
+        let mut rest = s.trim();
+        let mut duration = XsdDuration::default();
+
+        if let Some(r) = rest.strip_prefix('-') {
+            duration.negative = true;
+            rest = r;
+        }
+        rest = rest.strip_prefix('P').ok_or_else(err)?;
+
+        let (date, time) = match rest.split_once('T') {
+            // `T` has to be followed by a component
+            Some((_, "")) => return Err(err()),
+            Some((date, time)) => (date, Some(time)),
+            None => (rest, None),
+        };
+        if date.is_empty() && time.is_none() {
+            return Err(err());
+        }
+
+        // each component is a number followed by its designator, in order
+        fn components<'s>(mut s: &'s str, designators: &[char]) -> Option<Vec<(char, &'s str)>> {
+            let mut result = Vec::new();
+            let mut designators = designators.iter();
+            while !s.is_empty() {
+                let end = s.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+                let (number, r) = s.split_at(end);
+                let designator = r.chars().next()?;
+                designators.find(|d| **d == designator)?;
+                if number.is_empty() {
+                    return None;
+                }
+                result.push((designator, number));
+                s = &r[1..];
+            }
+            Some(result)
+        }
+
+        fn integer(number: &str) -> Option<u64> {
+            if number.contains('.') {
+                None
+            } else {
+                number.parse().ok()
+            }
+        }
+
+        for (designator, number) in components(date, &['Y', 'M', 'D']).ok_or_else(err)? {
+            let number = integer(number).ok_or_else(err)?;
+            match designator {
+                'Y' => duration.years = number,
+                'M' => duration.months = number,
+                _ => duration.days = number,
+            }
+        }
+
+        for (designator, number) in
+            components(time.unwrap_or(""), &['H', 'M', 'S']).ok_or_else(err)?
+        {
+            match designator {
+                'H' => duration.hours = integer(number).ok_or_else(err)?,
+                'M' => duration.minutes = integer(number).ok_or_else(err)?,
+                _ => {
+                    let (secs, frac) = number.split_once('.').unwrap_or((number, ""));
+                    if secs.is_empty() || !frac.bytes().all(|b| b.is_ascii_digit()) {
+                        return Err(err());
+                    }
+                    duration.seconds = integer(secs).ok_or_else(err)?;
+                    // digits past nanoseconds are dropped
+                    let frac = &frac[..frac.len().min(9)];
+                    duration.nanos = format!("{:0<9}", frac).parse().map_err(|_| err())?;
+                }
+            }
+        }
+
+        Ok(duration)
+    }
+}
+
+impl fmt::Display for XsdDuration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.negative && !self.is_zero() {
+            f.write_str("-")?;
+        }
+        f.write_str("P")?;
+
+        for (number, designator) in [(self.years, 'Y'), (self.months, 'M'), (self.days, 'D')] {
+            if number != 0 {
+                write!(f, "{}{}", number, designator)?;
+            }
+        }
+
+        let has_date = self.years != 0 || self.months != 0 || self.days != 0;
+        let has_seconds = self.seconds != 0 || self.nanos != 0;
+        if self.hours == 0 && self.minutes == 0 && !has_seconds {
+            // zero is written as `PT0S`
+            return if has_date { Ok(()) } else { f.write_str("T0S") };
+        }
+
+        f.write_str("T")?;
+        for (number, designator) in [(self.hours, 'H'), (self.minutes, 'M')] {
+            if number != 0 {
+                write!(f, "{}{}", number, designator)?;
+            }
+        }
+        if has_seconds {
+            write!(f, "{}", self.seconds)?;
+            if self.nanos != 0 {
+                let frac = format!("{:09}", self.nanos);
+                write!(f, ".{}", frac.trim_end_matches('0'))?;
+            }
+            f.write_str("S")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl XmlValue for Duration {
+    fn from_xml_str(s: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let duration: XsdDuration = s.parse()?;
+        duration.to_std().ok_or_else(|| {
+            format!(
+                "duration {:?} is negative or uses years or months",
+                s.trim()
+            )
+            .into()
+        })
+    }
+
+    fn fmt_xml(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&XsdDuration::from(*self), f)
+    }
+}
+
+#[test]
+fn test_parse() {
+    let parse = |s: &str| s.parse::<XsdDuration>();
+
+    assert_eq!(
+        parse("-P1Y2M3DT4H5M6.7S").unwrap(),
+        XsdDuration {
+            negative: true,
+            years: 1,
+            months: 2,
+            days: 3,
+            hours: 4,
+            minutes: 5,
+            seconds: 6,
+            nanos: 700_000_000,
+        }
+    );
+    assert_eq!(parse("PT1H30M").unwrap().minutes, 30);
+    assert_eq!(parse(" P1M ").unwrap().months, 1);
+    assert_eq!(parse("PT1M").unwrap().minutes, 1);
+    assert_eq!(parse("PT0.000000001S").unwrap().nanos, 1);
+
+    for s in [
+        "", "P", "PT", "P1YT", "1Y", "P1H", "PT1D", "P1M1Y", "P1.5Y", "PT.5S", "P-1D", "PYT1S",
+    ] {
+        assert!(parse(s).is_err(), "{}", s);
+    }
+}
+
+#[test]
+fn test_display() {
+    let display = |s: &str| s.parse::<XsdDuration>().unwrap().to_string();
+
+    assert_eq!(display("P1Y2M3DT4H5M6.70S"), "P1Y2M3DT4H5M6.7S");
+    assert_eq!(display("PT0H"), "PT0S");
+    assert_eq!(display("-PT0S"), "PT0S");
+    assert_eq!(display("P1DT0S"), "P1D");
+    assert_eq!(display("PT90M"), "PT90M");
+    assert_eq!(
+        XsdDuration::from(Duration::from_millis(90_061_500)).to_string(),
+        "P1DT1H1M1.5S"
+    );
+}
+
+#[test]
+fn test_std() {
+    let std = |s: &str| s.parse::<XsdDuration>().unwrap().to_std();
+
+    assert_eq!(std("PT1H30M"), Some(Duration::from_secs(5400)));
+    assert_eq!(std("P1DT0.5S"), Some(Duration::from_millis(86_400_500)));
+    assert_eq!(std("-PT0S"), Some(Duration::ZERO));
+    assert_eq!(std("-PT1S"), None);
+    assert_eq!(std("P1M"), None);
+}
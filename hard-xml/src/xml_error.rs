@@ -1,80 +1,552 @@
-use std::{error::Error, io::Error as IOError, str::Utf8Error, string::FromUtf8Error};
+use std::{error::Error, fmt, io::Error as IOError, str::Utf8Error, string::FromUtf8Error};
 use xmlparser::Error as ParserError;
 
+/// Error raised when reading or writing XML
+///
+/// Its category is returned by `kind` and what went wrong by `detail`,
+/// along with the position in the document and the type and field being
+/// read, when they're known.
+///
+/// ```rust
+/// use hard_xml::{ErrorDetail, ErrorKind, XmlRead};
+///
+/// #[derive(XmlRead, Debug)]
+/// #[xml(tag = "item")]
+/// struct Item {
+///     #[xml(attr = "id")]
+///     id: u32,
+/// }
+///
+/// let err = Item::from_str("<item/>").unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::Missing);
+/// assert_eq!((err.name(), err.field()), (Some("Item"), Some("id")));
+/// assert!(matches!(err.detail(), ErrorDetail::MissingField { tag, .. } if tag == "item"));
+///
+/// let err = Item::from_str(r#"<item id="1"></items>"#).unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::Syntax);
+/// ```
 #[derive(Debug)]
-pub enum XmlError {
+#[non_exhaustive]
+pub struct XmlError {
+    detail: ErrorDetail,
+    position: Option<Box<Position>>,
+    context: Option<Box<Context>>,
+}
+
+// type and field being read when an error is raised
+#[derive(Debug)]
+struct Context {
+    name: String,
+    field: Option<String>,
+}
+
+/// Category of an [`XmlError`], to handle errors without looking into
+/// their messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Reading or writing the underlying data failed
+    Io,
+    /// The document isn't well-formed XML or valid UTF-8
+    Syntax,
+    /// The document doesn't match the type it's read into, like an
+    /// unexpected element or an unknown field or variant
+    Mismatch,
+    /// A required field is missing, or occurs fewer times than it must
+    Missing,
+    /// A field or attribute occurs more times than it can
+    Duplicate,
+    /// A value can't be converted into the type of its field
+    Conversion,
+    /// A limit set on the reader is exceeded
+    Limit,
+}
+
+/// What went wrong, with the values involved
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ErrorDetail {
     IO(IOError),
     Parser(ParserError),
     Utf8(Utf8Error),
     UnexpectedEof,
-    UnexpectedToken { token: String },
-    TagMismatch { expected: String, found: String },
-    MissingField { name: String, field: String },
-    UnterminatedEntity { entity: String },
-    UnrecognizedSymbol { symbol: String },
-    FromStr(Box<dyn Error + Send + Sync>),
+    UnexpectedToken {
+        token: String,
+    },
+    TagMismatch {
+        expected: String,
+        found: String,
+    },
+    MissingField {
+        /// Tag of the element missing the field, empty if it isn't known
+        tag: String,
+        /// What the field is read from, like `attribute "id"` or
+        /// `child <title>`, empty if it isn't known
+        expected: String,
+    },
+    /// The field named by the context isn't one of the type.
+    UnknownField,
+    DuplicateField {
+        /// Position of the first occurrence, if it's known
+        first: Option<Box<Position>>,
+    },
+    UnorderedField,
+    /// An attribute is written more than once in a start tag, and the
+    /// reader is set to reject it with `DuplicateAttrs::Error`.
+    DuplicateAttribute {
+        /// Tag of the element
+        tag: String,
+        attribute: String,
+    },
+    UnknownVariant {
+        value: String,
+    },
+    /// An attribute declared with `const_attr` has another value.
+    UnexpectedValue {
+        expected: String,
+        found: String,
+    },
+    UnexpectedOccurrences {
+        count: usize,
+        min: usize,
+        max: Option<usize>,
+    },
+    UnterminatedEntity {
+        entity: String,
+    },
+    UnrecognizedSymbol {
+        symbol: String,
+    },
+    /// Elements are nested deeper than the limit set with
+    /// `XmlReader::with_max_depth`.
+    DepthLimitExceeded {
+        limit: usize,
+    },
+    /// A value read into a `&str` field needs unescaping, so it can't be
+    /// borrowed from the document.
+    Unborrowable {
+        value: String,
+    },
+    /// A value can't be converted into the type of its field.
+    FromStr {
+        /// Text of the value, empty if it isn't known
+        value: String,
+        /// Type the value is converted into as it's written, like `u32`,
+        /// empty if it isn't known
+        type_name: String,
+        source: Box<dyn Error + Send + Sync>,
+    },
+}
+
+impl From<ErrorDetail> for XmlError {
+    fn from(detail: ErrorDetail) -> Self {
+        XmlError::new(detail)
+    }
 }
 
 impl From<IOError> for XmlError {
     fn from(err: IOError) -> Self {
-        XmlError::IO(err)
+        XmlError::new(ErrorDetail::IO(err))
     }
 }
 
 impl From<Utf8Error> for XmlError {
     fn from(err: Utf8Error) -> Self {
-        XmlError::Utf8(err)
+        XmlError::new(ErrorDetail::Utf8(err))
     }
 }
 
 impl From<FromUtf8Error> for XmlError {
     fn from(err: FromUtf8Error) -> Self {
-        XmlError::Utf8(err.utf8_error())
+        XmlError::new(ErrorDetail::Utf8(err.utf8_error()))
     }
 }
 
 impl From<ParserError> for XmlError {
     fn from(err: ParserError) -> Self {
-        XmlError::Parser(err)
+        XmlError::new(ErrorDetail::Parser(err))
     }
 }
 
 /// Specialized `Result` which the error value is `Error`.
 pub type XmlResult<T> = Result<T, XmlError>;
 
+/// Position of a token in a document
+///
+/// Lines and columns start at 1, and columns are counted in characters.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Position {
+    /// Byte offset from the start of the document
+    pub offset: usize,
+    pub line: u32,
+    pub column: u32,
+    /// Elements containing the token, like `rss > channel > item[3]`
+    ///
+    /// Elements are followed by their index among the preceding siblings
+    /// of the same name, unless they are the first one. The path is empty
+    /// if it isn't known.
+    pub path: String,
+}
+
+impl Position {
+    /// Returns the position of the byte `offset` of `text`.
+    pub fn from_offset(text: &str, offset: usize) -> Position {
+        let offset = offset.min(text.len());
+        let before = &text.as_bytes()[..offset];
+        let line_start = before
+            .iter()
+            .rposition(|b| *b == b'\n')
+            .map_or(0, |index| index + 1);
+        Position {
+            offset,
+            line: before.iter().filter(|b| **b == b'\n').count() as u32 + 1,
+            // continuation bytes aren't characters of their own
+            column: before[line_start..]
+                .iter()
+                .filter(|b| **b & 0xC0 != 0x80)
+                .count() as u32
+                + 1,
+            path: String::new(),
+        }
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+impl XmlError {
+    /// Returns an error of `detail`, whose position and context aren't
+    /// known yet.
+    pub fn new(detail: ErrorDetail) -> XmlError {
+        XmlError {
+            detail,
+            position: None,
+            context: None,
+        }
+    }
+
+    // conversion error whose value and type are filled in by
+    // `from_str_error` if it's raised while reading a field
+    pub(crate) fn conversion<E: Into<Box<dyn Error + Send + Sync>>>(source: E) -> XmlError {
+        XmlError::new(ErrorDetail::FromStr {
+            value: String::new(),
+            type_name: String::new(),
+            source: source.into(),
+        })
+    }
+
+    /// Returns the category of the error.
+    pub fn kind(&self) -> ErrorKind {
+        use ErrorDetail::*;
+        match &self.detail {
+            IO(_) => ErrorKind::Io,
+            Parser(_)
+            | Utf8(_)
+            | UnexpectedEof
+            | TagMismatch { .. }
+            | UnterminatedEntity { .. }
+            | UnrecognizedSymbol { .. } => ErrorKind::Syntax,
+            UnexpectedToken { .. }
+            | UnknownField
+            | UnorderedField
+            | UnknownVariant { .. }
+            | UnexpectedValue { .. } => ErrorKind::Mismatch,
+            MissingField { .. } => ErrorKind::Missing,
+            UnexpectedOccurrences { count, min, .. } if count < min => ErrorKind::Missing,
+            UnexpectedOccurrences { .. } | DuplicateField { .. } | DuplicateAttribute { .. } => {
+                ErrorKind::Duplicate
+            }
+            FromStr { .. } | Unborrowable { .. } => ErrorKind::Conversion,
+            DepthLimitExceeded { .. } => ErrorKind::Limit,
+        }
+    }
+
+    /// Returns what went wrong.
+    pub fn detail(&self) -> &ErrorDetail {
+        &self.detail
+    }
+
+    /// Returns what went wrong, dropping the position and context.
+    pub fn into_detail(self) -> ErrorDetail {
+        self.detail
+    }
+
+    /// Returns the position in the document the error was raised at, if
+    /// it's known.
+    ///
+    /// Errors returned by `XmlRead` implementations are positioned at the
+    /// token being read when they were raised.
+    ///
+    /// ```rust
+    /// use hard_xml::{Position, XmlRead};
+    ///
+    /// #[derive(XmlRead, Debug)]
+    /// #[xml(tag = "item")]
+    /// struct Item {
+    ///     #[xml(attr = "id")]
+    ///     id: u32,
+    /// }
+    ///
+    /// let err = Item::from_str("<!-- no id -->\n<item/>").unwrap_err();
+    ///
+    /// let position = err.position().unwrap();
+    /// assert_eq!((position.offset, position.line, position.column), (20, 2, 6));
+    /// assert_eq!(position.path, "item");
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     r#"missing field in XML of "Item": "id" (expected attribute "id" in <item>) at 2:6 in item"#
+    /// );
+    /// ```
+    pub fn position(&self) -> Option<&Position> {
+        self.position.as_deref()
+    }
+
+    /// Sets the position of the error, unless it's already known or the
+    /// error isn't about a part of the document.
+    pub fn with_position(mut self, position: Position) -> Self {
+        use ErrorDetail::*;
+        if !matches!(self.detail, IO(_) | Utf8(_)) {
+            self.position.get_or_insert_with(|| Box::new(position));
+        }
+        self
+    }
+
+    /// Returns the name of the type being read when the error was raised,
+    /// like `Item` or `Enum::Variant`, if it's known.
+    pub fn name(&self) -> Option<&str> {
+        self.context.as_ref().map(|context| context.name.as_str())
+    }
+
+    /// Returns the name of the field being read when the error was raised,
+    /// if it's known.
+    pub fn field(&self) -> Option<&str> {
+        self.context.as_ref()?.field.as_deref()
+    }
+
+    /// Sets the type and field being read, unless they're already known.
+    pub fn with_context(mut self, name: &str, field: Option<&str>) -> Self {
+        self.context.get_or_insert_with(|| {
+            Box::new(Context {
+                name: name.to_owned(),
+                field: field.map(str::to_owned),
+            })
+        });
+        self
+    }
+
+    // whether reading can go on after the error when errors are collected,
+    // i.e. the document is well-formed but some of its values aren't
+    pub(crate) fn is_recoverable(&self) -> bool {
+        use ErrorDetail::*;
+        match &self.detail {
+            Utf8(_)
+            | MissingField { .. }
+            | UnknownField
+            | DuplicateField { .. }
+            | UnorderedField
+            | UnknownVariant { .. }
+            | UnexpectedValue { .. }
+            | UnexpectedOccurrences { .. }
+            | UnterminatedEntity { .. }
+            | UnrecognizedSymbol { .. }
+            | Unborrowable { .. }
+            | FromStr { .. } => true,
+            IO(_)
+            | Parser(_)
+            | UnexpectedEof
+            | UnexpectedToken { .. }
+            | TagMismatch { .. }
+            | DuplicateAttribute { .. }
+            | DepthLimitExceeded { .. } => false,
+        }
+    }
+}
+
 impl Error for XmlError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
-        use XmlError::*;
-        match self {
+        use ErrorDetail::*;
+        match &self.detail {
             IO(e) => Some(e),
             Parser(e) => Some(e),
             Utf8(e) => Some(e),
-            FromStr(e) => Some(e.as_ref()),
+            FromStr { source, .. } => Some(source.as_ref()),
             _ => None,
         }
     }
 }
 
-impl std::fmt::Display for XmlError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        use XmlError::*;
-        match self {
+impl fmt::Display for XmlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ErrorDetail::*;
+        let name = self.name().unwrap_or_default();
+        let field = self.field().unwrap_or_default();
+        match &self.detail {
             IO(e) => write!(f, "I/O error: {}", e),
             Parser(e) => write!(f, "XML parser error: {}", e),
             Utf8(e) => write!(f, "invalid UTF-8: {}", e),
             UnexpectedEof => f.write_str("unexpected end of file"),
-            UnexpectedToken { token } => write!(f, "unexpected token in XML: {:?}", token),
+            UnexpectedToken { token } => {
+                write!(f, "unexpected token in XML: {:?}", token)
+            }
             TagMismatch { expected, found } => write!(
                 f,
                 "mismatched XML tag; expected {:?}, found {:?}",
                 expected, found
             ),
-            MissingField { name, field } => {
-                write!(f, "missing field in XML of {:?}: {:?}", name, field)
+            MissingField { tag, expected } => {
+                write!(f, "missing field in XML of {:?}: {:?}", name, field)?;
+                match (expected.is_empty(), tag.is_empty()) {
+                    (false, false) => write!(f, " (expected {} in <{}>)", expected, tag),
+                    (false, true) => write!(f, " (expected {})", expected),
+                    (true, _) => Ok(()),
+                }
+            }
+            UnknownField => {
+                write!(f, "unknown field in XML of {:?}: {:?}", name, field)
+            }
+            DuplicateField { first } => {
+                write!(f, "duplicate field in XML of {:?}: {:?}", name, field)?;
+                match first.as_deref() {
+                    Some(first) if first.path.is_empty() => write!(f, " (first at {})", first),
+                    Some(first) => write!(f, " (first at {} in {})", first, first.path),
+                    None => Ok(()),
+                }
+            }
+            UnorderedField => {
+                write!(f, "field out of order in XML of {:?}: {:?}", name, field)
+            }
+            DuplicateAttribute { tag, attribute } => {
+                write!(
+                    f,
+                    "duplicate attribute in XML tag {:?}: {:?}",
+                    tag, attribute
+                )
+            }
+            UnknownVariant { value } => {
+                write!(f, "unknown variant in XML of {:?}: {:?}", name, value)
+            }
+            UnexpectedValue { expected, found } => write!(
+                f,
+                "unexpected value in XML of {:?}: {:?} is {:?}, expected {:?}",
+                name, field, found, expected
+            ),
+            UnexpectedOccurrences { count, min, max } => {
+                write!(
+                    f,
+                    "unexpected number of occurrences in XML of {:?}: {:?} occurs {} times, ",
+                    name, field, count
+                )?;
+                // names the violated bound
+                match max {
+                    Some(max) if count > max => write!(f, "expected at most {}", max),
+                    _ => write!(f, "expected at least {}", min),
+                }
+            }
+            UnterminatedEntity { entity } => {
+                write!(f, "unterminated XML entity: {}", entity)
+            }
+            UnrecognizedSymbol { symbol } => {
+                write!(f, "unrecognized XML symbol: {}", symbol)
+            }
+            DepthLimitExceeded { limit } => {
+                write!(f, "XML elements nested deeper than {} levels", limit)
+            }
+            Unborrowable { value } => write!(
+                f,
+                "XML value {:?} needs unescaping and can't be borrowed as &str",
+                value
+            ),
+            FromStr {
+                value,
+                type_name,
+                source,
+            } => {
+                f.write_str("error parsing XML value")?;
+                if !value.is_empty() {
+                    write!(f, " {:?}", value)?;
+                }
+                if !type_name.is_empty() {
+                    write!(f, " as {}", type_name)?;
+                }
+                if let Some(name) = self.name() {
+                    write!(f, " in {:?}: {:?}", name, field)?;
+                }
+                write!(f, " ({})", source)
+            }
+        }?;
+
+        if let Some(position) = self.position() {
+            // the parser reports its own line and column
+            if !matches!(self.detail, Parser(_)) {
+                write!(f, " at {}", position)?;
+            }
+            if !position.path.is_empty() {
+                write!(f, " in {}", position.path)?;
             }
-            UnterminatedEntity { entity } => write!(f, "unterminated XML entity: {}", entity),
-            UnrecognizedSymbol { symbol } => write!(f, "unrecognized XML symbol: {}", symbol),
-            FromStr(e) => write!(f, "error parsing XML value: {}", e),
         }
+        Ok(())
     }
 }
+
+#[cfg(feature = "miette")]
+mod miette_impls {
+    use miette::{Diagnostic, LabeledSpan};
+    use std::fmt::Display;
+
+    use super::{ErrorDetail, ErrorKind, XmlError};
+
+    // the source code isn't kept by errors, it's attached to reports of
+    // them with `Report::with_source_code`
+    impl Diagnostic for XmlError {
+        fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+            let code = match self.kind() {
+                ErrorKind::Io => "hard_xml::io",
+                ErrorKind::Syntax => "hard_xml::syntax",
+                ErrorKind::Mismatch => "hard_xml::mismatch",
+                ErrorKind::Missing => "hard_xml::missing",
+                ErrorKind::Duplicate => "hard_xml::duplicate",
+                ErrorKind::Conversion => "hard_xml::conversion",
+                ErrorKind::Limit => "hard_xml::limit",
+            };
+            Some(Box::new(code))
+        }
+
+        fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+            let position = self.position()?;
+            let label = match self.kind() {
+                ErrorKind::Io | ErrorKind::Syntax => "malformed here",
+                ErrorKind::Mismatch => "unexpected here",
+                ErrorKind::Missing => "missing before here",
+                ErrorKind::Duplicate => "repeated here",
+                ErrorKind::Conversion => "invalid value",
+                ErrorKind::Limit => "nested too deeply",
+            };
+            let mut labels = vec![LabeledSpan::at_offset(position.offset, label)];
+            if let ErrorDetail::DuplicateField { first: Some(first) } = self.detail() {
+                labels.push(LabeledSpan::at_offset(first.offset, "first here"));
+            }
+            Some(Box::new(labels.into_iter()))
+        }
+    }
+}
+
+#[test]
+fn test_position() {
+    let text = "<a>\n  <é b=\"c\"/>\r\n</a>";
+
+    assert_eq!(
+        Position::from_offset(text, 0),
+        Position {
+            offset: 0,
+            line: 1,
+            column: 1,
+            path: String::new(),
+        }
+    );
+    assert_eq!(Position::from_offset(text, 10).column, 6);
+    assert_eq!(Position::from_offset(text, 19).line, 3);
+    assert_eq!(Position::from_offset(text, 100).offset, text.len());
+}
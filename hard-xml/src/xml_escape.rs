@@ -1,6 +1,8 @@
 use jetscii::{bytes, BytesConst};
 use lazy_static::lazy_static;
 use std::borrow::Cow;
+use std::fmt::{self, Display};
+use std::io;
 
 pub fn xml_escape(raw: &str) -> Cow<'_, str> {
     lazy_static! {
@@ -48,6 +50,59 @@ pub fn xml_escape(raw: &str) -> Cow<'_, str> {
     }
 }
 
+// escapes text while it's being formatted, without buffering it
+struct EscapeWriter<'w, W> {
+    inner: &'w mut W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> fmt::Write for EscapeWriter<'_, W> {
+    fn write_str(&mut self, raw: &str) -> fmt::Result {
+        lazy_static! {
+            static ref ESCAPE_BYTES: BytesConst = bytes!(b'<', b'>', b'&', b'\'', b'"');
+        }
+
+        let bytes = raw.as_bytes();
+        let mut pos = 0;
+
+        while let Some(off) = ESCAPE_BYTES.find(&bytes[pos..]) {
+            let entity: &[u8] = match bytes[pos + off] {
+                b'<' => b"&lt;",
+                b'>' => b"&gt;",
+                b'&' => b"&amp;",
+                b'\'' => b"&apos;",
+                b'"' => b"&quot;",
+                _ => unreachable!(),
+            };
+            self.write_bytes(&bytes[pos..pos + off])?;
+            self.write_bytes(entity)?;
+            pos += off + 1;
+        }
+
+        self.write_bytes(&bytes[pos..])
+    }
+}
+
+impl<W: io::Write> EscapeWriter<'_, W> {
+    fn write_bytes(&mut self, bytes: &[u8]) -> fmt::Result {
+        self.inner.write_all(bytes).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
+
+/// Writes the escaped form of `value` to `inner`.
+pub(crate) fn write_escaped<W: io::Write>(inner: &mut W, value: impl Display) -> io::Result<()> {
+    let mut writer = EscapeWriter { inner, error: None };
+    match fmt::write(&mut writer, format_args!("{}", value)) {
+        Ok(()) => Ok(()),
+        Err(_) => Err(writer
+            .error
+            .unwrap_or_else(|| io::Error::other("formatter error"))),
+    }
+}
+
 #[test]
 fn test_escape() {
     assert_eq!(xml_escape("< < <"), "&lt; &lt; &lt;");
@@ -56,3 +111,14 @@ fn test_escape() {
         "&lt;script&gt;alert(&apos;Hello XSS&apos;)&lt;/script&gt;"
     );
 }
+
+#[test]
+fn test_write_escaped() {
+    let mut buf = Vec::new();
+    write_escaped(&mut buf, "<a href=\"x\">'&'</a>").unwrap();
+    write_escaped(&mut buf, 42).unwrap();
+    assert_eq!(
+        String::from_utf8(buf).unwrap(),
+        "&lt;a href=&quot;x&quot;&gt;&apos;&amp;&apos;&lt;/a&gt;42"
+    );
+}
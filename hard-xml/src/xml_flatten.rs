@@ -0,0 +1,48 @@
+use std::borrow::Cow;
+use std::io::Write;
+
+use crate::{XmlReader, XmlResult, XmlWriter};
+
+/// Fields read from the element of the struct they're flattened into.
+///
+/// It's implemented by `#[derive(XmlRead)]` for structs without `tag`
+/// attribute, which are embedded with `#[xml(flatten)]`.
+pub trait XmlReadFlatten<'a>: Sized {
+    /// Values of the fields read so far.
+    type State;
+
+    fn init_state() -> Self::State;
+
+    /// Reads an attribute of the element, or gives its value back if no
+    /// field is read from it.
+    fn read_attribute(
+        state: &mut Self::State,
+        reader: &mut XmlReader<'a>,
+        key: &'a str,
+        value: Cow<'a, str>,
+    ) -> XmlResult<Option<Cow<'a, str>>>;
+
+    /// Reads a child element starting with `tag`, and returns whether a
+    /// field is read from it. The reader is left untouched otherwise.
+    fn read_child(
+        state: &mut Self::State,
+        reader: &mut XmlReader<'a>,
+        tag: &'a str,
+    ) -> XmlResult<bool>;
+
+    /// Builds the struct once the element is read.
+    fn finish(state: Self::State) -> XmlResult<Self>;
+}
+
+/// Fields written to the element of the struct they're flattened into.
+///
+/// It's implemented by `#[derive(XmlWrite)]` for structs without `tag`
+/// attribute, which are embedded with `#[xml(flatten)]`.
+pub trait XmlWriteFlatten {
+    fn write_attributes<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()>;
+
+    fn write_children<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()>;
+
+    /// Whether `write_children` writes nothing.
+    fn is_content_empty(&self) -> bool;
+}
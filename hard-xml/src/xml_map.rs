@@ -0,0 +1,44 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+
+/// Map children are read into with `#[xml(child = "", key = "")]`.
+///
+/// It's implemented for `HashMap` and `BTreeMap`, for `IndexMap` with the
+/// `indexmap` feature, and can be implemented for other maps.
+pub trait XmlMap<K, V>: Default {
+    fn contains_key(&self, key: &K) -> bool;
+
+    /// Inserts an entry, replacing the value of an existing key.
+    fn insert(&mut self, key: K, value: V);
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher + Default> XmlMap<K, V> for HashMap<K, V, S> {
+    fn contains_key(&self, key: &K) -> bool {
+        HashMap::contains_key(self, key)
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        HashMap::insert(self, key, value);
+    }
+}
+
+impl<K: Ord, V> XmlMap<K, V> for BTreeMap<K, V> {
+    fn contains_key(&self, key: &K) -> bool {
+        BTreeMap::contains_key(self, key)
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        BTreeMap::insert(self, key, value);
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<K: Hash + Eq, V, S: BuildHasher + Default> XmlMap<K, V> for indexmap::IndexMap<K, V, S> {
+    fn contains_key(&self, key: &K) -> bool {
+        indexmap::IndexMap::contains_key(self, key)
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        indexmap::IndexMap::insert(self, key, value);
+    }
+}
@@ -0,0 +1,272 @@
+/// Strategy used by `XmlReader` to compare the names of elements and
+/// attributes in the document with the ones a type expects.
+///
+/// `expected` is the name given in `#[xml(...)]` and `found` is the
+/// qualified name as written in the document (e.g. `w:p`). Matchers
+/// should be reflexive, i.e. a name always matches itself.
+pub trait NameMatcher {
+    fn matches(&self, expected: &str, found: &str) -> bool;
+
+    /// Like `matches`, with the namespace URI `found` is in, resolved from
+    /// the `xmlns` declarations in scope, or `None` if it isn't in any.
+    ///
+    /// It is only called if `uses_namespaces` returns `true`, and defaults
+    /// to `matches`.
+    fn matches_namespace(
+        &self,
+        expected: &str,
+        found: &str,
+        namespace: Option<&str>,
+        is_attribute: bool,
+    ) -> bool {
+        let _ = (namespace, is_attribute);
+        self.matches(expected, found)
+    }
+
+    /// Whether the reader has to keep track of the `xmlns` declarations
+    /// for `matches_namespace`.
+    fn uses_namespaces(&self) -> bool {
+        false
+    }
+}
+
+/// Namespace bound to the `xml` prefix.
+pub const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+
+fn local_name(name: &str) -> &str {
+    match name.find(':') {
+        Some(i) => &name[i + 1..],
+        None => name,
+    }
+}
+
+/// Compares the local part of names, ignoring any prefix.
+///
+/// This is the default strategy.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalName;
+
+impl NameMatcher for LocalName {
+    #[inline]
+    fn matches(&self, expected: &str, found: &str) -> bool {
+        local_name(expected) == local_name(found)
+    }
+}
+
+/// Compares qualified names byte by byte, so `w:p` only matches `w:p`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Exact;
+
+impl NameMatcher for Exact {
+    #[inline]
+    fn matches(&self, expected: &str, found: &str) -> bool {
+        expected == found
+    }
+}
+
+/// Compares the local part of names, ignoring any prefix and ASCII case.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IgnoreAsciiCase;
+
+impl NameMatcher for IgnoreAsciiCase {
+    #[inline]
+    fn matches(&self, expected: &str, found: &str) -> bool {
+        local_name(expected).eq_ignore_ascii_case(local_name(found))
+    }
+}
+
+/// Accepts the names from a table of aliases in addition to the ones
+/// accepted by the inner matcher.
+///
+/// ```rust
+/// use hard_xml::matcher::{AliasTable, LocalName};
+/// use hard_xml::NameMatcher;
+///
+/// let matcher = AliasTable::new(LocalName).alias("creator", "author");
+///
+/// assert!(matcher.matches("author", "author"));
+/// assert!(matcher.matches("author", "dc:creator"));
+/// assert!(!matcher.matches("creator", "author"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AliasTable<M> {
+    inner: M,
+    // (found, expected)
+    aliases: Vec<(String, String)>,
+}
+
+impl<M: NameMatcher> AliasTable<M> {
+    pub fn new(inner: M) -> Self {
+        AliasTable {
+            inner,
+            aliases: Vec::new(),
+        }
+    }
+
+    /// Accepts `alias` in the document wherever `name` is expected.
+    pub fn alias(mut self, alias: impl Into<String>, name: impl Into<String>) -> Self {
+        self.aliases.push((alias.into(), name.into()));
+        self
+    }
+}
+
+impl<M: NameMatcher> NameMatcher for AliasTable<M> {
+    fn matches(&self, expected: &str, found: &str) -> bool {
+        self.inner.matches(expected, found)
+            || self.aliases.iter().any(|(alias, name)| {
+                self.inner.matches(alias, found) && self.inner.matches(expected, name)
+            })
+    }
+
+    fn matches_namespace(
+        &self,
+        expected: &str,
+        found: &str,
+        namespace: Option<&str>,
+        is_attribute: bool,
+    ) -> bool {
+        self.inner
+            .matches_namespace(expected, found, namespace, is_attribute)
+            || self.aliases.iter().any(|(alias, name)| {
+                self.inner
+                    .matches_namespace(alias, found, namespace, is_attribute)
+                    && self.inner.matches(expected, name)
+            })
+    }
+
+    fn uses_namespaces(&self) -> bool {
+        self.inner.uses_namespaces()
+    }
+}
+
+/// Compares names by their namespace URI and local part, resolving
+/// prefixes with the `xmlns` declarations of the document, so `w:p`
+/// matches `x:p` when both prefixes are bound to the same namespace.
+///
+/// Expected names are either written as `{uri}local` or use the prefixes
+/// bound with [`Namespaces::bind`]. Unprefixed ones are in the namespace
+/// bound to `""`, except for attributes, which like in the document are
+/// never in the default namespace. Names with prefixes which aren't bound
+/// only match themselves and, as with any matcher, a name always matches
+/// itself whatever its prefix is bound to.
+///
+/// Declarations made before the text given to `XmlReader::resume` are
+/// unknown to the resumed reader.
+///
+/// ```rust
+/// use hard_xml::matcher::Namespaces;
+/// use hard_xml::NameMatcher;
+///
+/// const W: &str = "http://schemas.openxmlformats.org/wordprocessingml/2006/main";
+///
+/// let matcher = Namespaces::new().bind("w", W);
+///
+/// assert!(matcher.matches_namespace("w:p", "x:p", Some(W), false));
+/// assert!(matcher.matches_namespace("{http://schemas.openxmlformats.org/wordprocessingml/2006/main}p", "p", Some(W), false));
+/// assert!(!matcher.matches_namespace("w:p", "v:p", Some("urn:other"), false));
+/// assert!(!matcher.matches_namespace("w:p", "p", None, false));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Namespaces {
+    // (prefix, uri)
+    prefixes: Vec<(String, String)>,
+}
+
+impl Namespaces {
+    pub fn new() -> Self {
+        Namespaces::default()
+    }
+
+    /// Binds `prefix` to `uri` in expected names, `""` binding the
+    /// namespace of unprefixed element names.
+    pub fn bind(mut self, prefix: impl Into<String>, uri: impl Into<String>) -> Self {
+        self.prefixes.push((prefix.into(), uri.into()));
+        self
+    }
+
+    // the namespace and local part of an expected name, or `None` if its
+    // prefix isn't bound
+    fn expand<'n>(
+        &'n self,
+        name: &'n str,
+        is_attribute: bool,
+    ) -> Option<(Option<&'n str>, &'n str)> {
+        if let Some((uri, local)) = name.strip_prefix('{').and_then(|name| name.split_once('}')) {
+            return Some((Some(uri).filter(|uri| !uri.is_empty()), local));
+        }
+        let (prefix, local) = name.split_once(':').unwrap_or(("", name));
+        if prefix.is_empty() && is_attribute {
+            return Some((None, local));
+        }
+        if prefix == "xml" {
+            return Some((Some(XML_NAMESPACE), local));
+        }
+        match self.prefixes.iter().rev().find(|(p, _)| p == prefix) {
+            Some((_, uri)) => Some((Some(uri.as_str()).filter(|uri| !uri.is_empty()), local)),
+            None if prefix.is_empty() => Some((None, local)),
+            None => None,
+        }
+    }
+}
+
+impl NameMatcher for Namespaces {
+    // compares names using the bound prefixes only
+    fn matches(&self, expected: &str, found: &str) -> bool {
+        match (self.expand(expected, false), self.expand(found, false)) {
+            (Some(expected), Some(found)) => expected == found,
+            _ => expected == found,
+        }
+    }
+
+    fn matches_namespace(
+        &self,
+        expected: &str,
+        found: &str,
+        namespace: Option<&str>,
+        is_attribute: bool,
+    ) -> bool {
+        match self.expand(expected, is_attribute) {
+            Some((uri, local)) => {
+                expected == found || (uri == namespace && local == local_name(found))
+            }
+            None => expected == found,
+        }
+    }
+
+    fn uses_namespaces(&self) -> bool {
+        true
+    }
+}
+
+#[test]
+fn test_matchers() {
+    assert!(LocalName.matches("p", "p"));
+    assert!(LocalName.matches("p", "w:p"));
+    assert!(LocalName.matches("w:p", "x:p"));
+    assert!(!LocalName.matches("p", "P"));
+
+    assert!(Exact.matches("w:p", "w:p"));
+    assert!(!Exact.matches("p", "w:p"));
+
+    assert!(IgnoreAsciiCase.matches("item", "Item"));
+    assert!(IgnoreAsciiCase.matches("item", "x:ITEM"));
+    assert!(!IgnoreAsciiCase.matches("item", "items"));
+
+    let ns = Namespaces::new().bind("", "urn:a").bind("b", "urn:b");
+    assert!(ns.matches_namespace("item", "a:item", Some("urn:a"), false));
+    assert!(ns.matches_namespace("{urn:b}item", "item", Some("urn:b"), false));
+    assert!(ns.matches_namespace("b:id", "x:id", Some("urn:b"), true));
+    assert!(ns.matches_namespace("id", "id", None, true));
+    assert!(!ns.matches_namespace("id", "a:id", Some("urn:a"), true));
+    assert!(!ns.matches_namespace("item", "x:item", None, false));
+    assert!(ns.matches_namespace("xml:lang", "xml:lang", Some(XML_NAMESPACE), true));
+    assert!(!ns.matches_namespace("c:item", "item", Some("urn:a"), false));
+    assert!(ns.matches_namespace("c:item", "c:item", None, false));
+    assert!(ns.matches("{urn:b}item", "b:item"));
+    assert!(!ns.matches("b:item", "item"));
+
+    let aliases = AliasTable::new(ns).alias("b:entry", "item");
+    assert!(aliases.uses_namespaces());
+    assert!(aliases.matches_namespace("item", "e:entry", Some("urn:b"), false));
+    assert!(!aliases.matches_namespace("item", "e:entry", Some("urn:a"), false));
+}
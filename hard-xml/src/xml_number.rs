@@ -0,0 +1,307 @@
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use crate::XmlValue;
+
+/// Direction in which a number exceeded the range of its type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    Positive,
+    Negative,
+}
+
+/// Numeric types whose parsing can tell overflows apart from other errors
+pub trait Bounded: FromStr {
+    const MIN: Self;
+    const MAX: Self;
+
+    /// Returns `Ok(Err(_))` if `s` is a valid number which doesn't fit
+    /// into `Self`.
+    fn parse_bounded(s: &str) -> Result<Result<Self, Overflow>, Self::Err>;
+}
+
+// returns whether `s` is a negative number which is zero, or `None` if it
+// isn't a negative number
+fn negative_is_zero(s: &str) -> Option<bool> {
+    let digits = s.strip_prefix('-')?;
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some(digits.bytes().all(|b| b == b'0'))
+}
+
+macro_rules! impl_int {
+    ($($ty:ty),* ; $negative:expr) => {$(
+        impl Bounded for $ty {
+            const MIN: Self = <$ty>::MIN;
+            const MAX: Self = <$ty>::MAX;
+
+            fn parse_bounded(s: &str) -> Result<Result<Self, Overflow>, Self::Err> {
+                use std::num::IntErrorKind;
+
+                match s.parse::<$ty>() {
+                    Ok(value) => Ok(Ok(value)),
+                    Err(err) => match err.kind() {
+                        IntErrorKind::PosOverflow => Ok(Err(Overflow::Positive)),
+                        IntErrorKind::NegOverflow => Ok(Err(Overflow::Negative)),
+                        IntErrorKind::InvalidDigit if $negative => match negative_is_zero(s) {
+                            Some(true) => Ok(Ok(0)),
+                            Some(false) => Ok(Err(Overflow::Negative)),
+                            None => Err(err),
+                        },
+                        _ => Err(err),
+                    },
+                }
+            }
+        }
+    )*};
+}
+
+impl_int!(i8, i16, i32, i64, i128, isize; false);
+// std reports negative numbers as invalid digits for unsigned types, so
+// they're told apart here, and `-0` is read as zero like in XML Schema
+impl_int!(u8, u16, u32, u64, u128, usize; true);
+
+macro_rules! impl_float {
+    ($($ty:ty),*) => {$(
+        impl Bounded for $ty {
+            const MIN: Self = <$ty>::MIN;
+            const MAX: Self = <$ty>::MAX;
+
+            fn parse_bounded(s: &str) -> Result<Result<Self, Overflow>, Self::Err> {
+                let value = s.parse::<$ty>()?;
+                // too large values are parsed as infinity, unlike the
+                // literal `inf` and `infinity`
+                if value.is_infinite() && !s.to_ascii_lowercase().contains("inf") {
+                    if value > 0.0 {
+                        Ok(Err(Overflow::Positive))
+                    } else {
+                        Ok(Err(Overflow::Negative))
+                    }
+                } else {
+                    Ok(Ok(value))
+                }
+            }
+        }
+    )*};
+}
+
+impl_float!(f32, f64);
+
+// infinities are written as the xs:float and xs:double forms `INF` and
+// `-INF`, which `FromStr` reads as well
+macro_rules! impl_float_value {
+    ($($ty:ty),*) => {$(
+        impl XmlValue for $ty {
+            fn from_xml_str(s: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+                Ok(s.parse()?)
+            }
+
+            fn fmt_xml(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                if self.is_infinite() {
+                    f.write_str(if *self > 0.0 { "INF" } else { "-INF" })
+                } else {
+                    fmt::Display::fmt(self, f)
+                }
+            }
+        }
+    )*};
+}
+
+impl_float_value!(f32, f64);
+
+/// Integer types which can be read and written in a radix other than 10
+pub trait RadixInt: Sized {
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseIntError>;
+
+    /// Writes the sign, the prefix and the digits in the radix, which is 2,
+    /// 8 or 16.
+    fn fmt_radix(&self, radix: u32, prefix: &str, f: &mut fmt::Formatter) -> fmt::Result;
+}
+
+macro_rules! impl_radix_int {
+    ($($ty:ty => $abs:path),*) => {$(
+        impl RadixInt for $ty {
+            fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseIntError> {
+                <$ty>::from_str_radix(s, radix)
+            }
+
+            #[allow(unused_comparisons)]
+            fn fmt_radix(&self, radix: u32, prefix: &str, f: &mut fmt::Formatter) -> fmt::Result {
+                if *self < 0 {
+                    f.write_str("-")?;
+                }
+                f.write_str(prefix)?;
+                let abs = $abs(*self);
+                match radix {
+                    2 => write!(f, "{:b}", abs),
+                    8 => write!(f, "{:o}", abs),
+                    _ => write!(f, "{:X}", abs),
+                }
+            }
+        }
+    )*};
+}
+
+impl_radix_int!(
+    i8 => i8::unsigned_abs,
+    i16 => i16::unsigned_abs,
+    i32 => i32::unsigned_abs,
+    i64 => i64::unsigned_abs,
+    i128 => i128::unsigned_abs,
+    isize => isize::unsigned_abs,
+    u8 => u8::from,
+    u16 => u16::from,
+    u32 => u32::from,
+    u64 => u64::from,
+    u128 => u128::from,
+    usize => usize::from
+);
+
+/// Parses an integer in a radix, after an optional sign and prefix.
+///
+/// Used by `#[xml(radix = ..)]` fields. The prefix is matched in any case.
+pub fn parse_radix<T: RadixInt>(
+    s: &str,
+    radix: u32,
+    prefix: &str,
+) -> Result<T, Box<dyn Error + Send + Sync>> {
+    let (sign, rest) = match s.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", s.strip_prefix('+').unwrap_or(s)),
+    };
+    let digits = match rest.get(..prefix.len()) {
+        Some(p) if p.eq_ignore_ascii_case(prefix) => &rest[prefix.len()..],
+        _ => rest,
+    };
+    // the sign comes before the prefix only
+    if digits.starts_with(['+', '-']) {
+        return Err(format!("invalid number {:?}", s).into());
+    }
+    Ok(T::from_str_radix(&format!("{}{}", sign, digits), radix)?)
+}
+
+/// Displays an integer in a radix, after its sign and a prefix.
+///
+/// Used by `#[xml(radix = ..)]` fields. Digits above 9 are written in upper
+/// case.
+pub struct RadixDisplay<'a, T>(pub &'a T, pub u32, pub &'a str);
+
+impl<T: RadixInt> fmt::Display for RadixDisplay<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt_radix(self.1, self.2, f)
+    }
+}
+
+/// Parses a number, clamping values out of range to the `MIN` or `MAX` of
+/// the type.
+///
+/// Used by `#[xml(overflow = "saturate")]` fields.
+pub fn parse_saturating<T: Bounded>(s: &str) -> Result<T, T::Err> {
+    Ok(match T::parse_bounded(s)? {
+        Ok(value) => value,
+        Err(Overflow::Positive) => T::MAX,
+        Err(Overflow::Negative) => T::MIN,
+    })
+}
+
+/// Number which keeps the original text when it doesn't fit into `T`
+///
+/// ```rust
+/// use hard_xml::Numeric;
+///
+/// assert_eq!("42".parse(), Ok(Numeric::Value(42u8)));
+/// assert_eq!("420".parse(), Ok(Numeric::<u8>::OutOfRange("420".into())));
+/// assert!("foo".parse::<Numeric<u8>>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Numeric<T> {
+    Value(T),
+    OutOfRange(String),
+}
+
+impl<T: Bounded> FromStr for Numeric<T> {
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match T::parse_bounded(s)? {
+            Ok(value) => Numeric::Value(value),
+            Err(_) => Numeric::OutOfRange(s.to_owned()),
+        })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Numeric<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Numeric::Value(value) => value.fmt(f),
+            Numeric::OutOfRange(text) => f.write_str(text),
+        }
+    }
+}
+
+#[test]
+fn test_parse_saturating() {
+    assert_eq!(parse_saturating::<u8>("42"), Ok(42));
+    assert_eq!(parse_saturating::<u8>("256"), Ok(255));
+    assert_eq!(parse_saturating::<i8>("-1000"), Ok(-128));
+    assert_eq!(parse_saturating::<u8>("-1"), Ok(0));
+    assert_eq!(parse_saturating::<u64>("-99999999999999999999999"), Ok(0));
+    assert_eq!(parse_saturating::<u8>("-0"), Ok(0));
+    assert_eq!("-00".parse::<Numeric<u16>>(), Ok(Numeric::Value(0)));
+    assert_eq!(
+        "-1".parse::<Numeric<u8>>(),
+        Ok(Numeric::OutOfRange("-1".into()))
+    );
+    assert!(parse_saturating::<u8>("-").is_err());
+    assert!(parse_saturating::<u8>("-+1").is_err());
+    assert!(parse_saturating::<u8>("").is_err());
+
+    assert_eq!(parse_saturating::<f32>("1e39"), Ok(f32::MAX));
+    assert_eq!(parse_saturating::<f32>("-1e39"), Ok(f32::MIN));
+    assert_eq!(parse_saturating::<f32>("-inf"), Ok(f32::NEG_INFINITY));
+    assert!(parse_saturating::<f32>("x").is_err());
+}
+
+#[test]
+fn test_radix() {
+    assert_eq!(parse_radix::<u32>("0x1F", 16, "0x").unwrap(), 31);
+    assert_eq!(parse_radix::<u32>("0X1f", 16, "0x").unwrap(), 31);
+    assert_eq!(parse_radix::<u32>("1F", 16, "0x").unwrap(), 31);
+    assert_eq!(parse_radix::<u32>("#FF00FF", 16, "#").unwrap(), 0xFF00FF);
+    assert_eq!(parse_radix::<i8>("-0x80", 16, "0x").unwrap(), -128);
+    assert_eq!(parse_radix::<u8>("+101", 2, "").unwrap(), 5);
+    assert!(parse_radix::<u8>("0x100", 16, "0x").is_err());
+    assert!(parse_radix::<i8>("0x-1", 16, "0x").is_err());
+    assert!(parse_radix::<i8>("--1", 16, "").is_err());
+    assert!(parse_radix::<u8>("0x", 16, "0x").is_err());
+
+    assert_eq!(RadixDisplay(&31u32, 16, "0x").to_string(), "0x1F");
+    assert_eq!(RadixDisplay(&-128i8, 16, "0x").to_string(), "-0x80");
+    assert_eq!(RadixDisplay(&8u64, 8, "0o").to_string(), "0o10");
+    assert_eq!(RadixDisplay(&5usize, 2, "").to_string(), "101");
+}
+
+#[test]
+fn test_float_value() {
+    struct Xml<T>(T);
+
+    impl<T: XmlValue> fmt::Display for Xml<T> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            self.0.fmt_xml(f)
+        }
+    }
+
+    assert_eq!(f64::from_xml_str("INF").unwrap(), f64::INFINITY);
+    assert_eq!(f64::from_xml_str("-INF").unwrap(), f64::NEG_INFINITY);
+    assert!(f32::from_xml_str("NaN").unwrap().is_nan());
+    assert!(f64::from_xml_str("IN").is_err());
+
+    assert_eq!(Xml(f64::INFINITY).to_string(), "INF");
+    assert_eq!(Xml(f32::NEG_INFINITY).to_string(), "-INF");
+    assert_eq!(Xml(f64::NAN).to_string(), "NaN");
+    assert_eq!(Xml(-0.5f64).to_string(), "-0.5");
+    assert_eq!(format!("{:.2}", Xml(1.0f64 / 3.0)), "0.33");
+}
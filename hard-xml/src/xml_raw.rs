@@ -0,0 +1,121 @@
+use std::borrow::Cow;
+use std::io::Write;
+use std::ops::Range;
+
+use xmlparser::Token;
+
+use crate::{ErrorDetail, XmlRead, XmlReader, XmlResult, XmlWrite, XmlWriter};
+
+/// Element kept as its source markup
+///
+/// It can be read from any element, e.g. for `xs:any` content, and is
+/// written back untouched, including its nested markup.
+///
+/// ```rust
+/// use hard_xml::{RawXml, XmlRead, XmlWrite};
+///
+/// #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+/// #[xml(tag = "parent")]
+/// struct Parent<'a> {
+///     #[xml(child = "extension")]
+///     extension: RawXml<'a>,
+/// }
+///
+/// let xml = r#"<parent><extension a="1"><x:b>&amp;</x:b></extension></parent>"#;
+///
+/// let parent = Parent::from_str(xml)?;
+///
+/// assert_eq!(parent.extension.tag(), "extension");
+/// assert_eq!(parent.extension.inner(), "<x:b>&amp;</x:b>");
+/// assert_eq!(parent.to_string()?, xml);
+/// # Ok::<(), hard_xml::XmlError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawXml<'a> {
+    xml: Cow<'a, str>,
+    tag: Range<usize>,
+    inner: Range<usize>,
+}
+
+impl<'a> RawXml<'a> {
+    /// Returns the markup of the whole element.
+    pub fn as_str(&self) -> &str {
+        &self.xml
+    }
+
+    /// Returns the qualified name of the element.
+    pub fn tag(&self) -> &str {
+        &self.xml[self.tag.clone()]
+    }
+
+    /// Returns the markup between the start and end tag.
+    pub fn inner(&self) -> &str {
+        &self.xml[self.inner.clone()]
+    }
+
+    pub fn into_owned(self) -> RawXml<'static> {
+        RawXml {
+            xml: Cow::Owned(self.xml.into_owned()),
+            tag: self.tag,
+            inner: self.inner,
+        }
+    }
+}
+
+impl<'i: 'a, 'a> XmlRead<'i> for RawXml<'a> {
+    fn from_reader(reader: &mut XmlReader<'i>) -> XmlResult<Self> {
+        reader.find_element_start(None)?;
+
+        let (tag, start) = match reader.next() {
+            Some(Ok(Token::ElementStart {
+                prefix,
+                local,
+                span,
+            })) => {
+                let tag_start = if prefix.is_empty() {
+                    local.start()
+                } else {
+                    prefix.start()
+                };
+                (tag_start..local.end(), span.start())
+            }
+            _ => return Err(ErrorDetail::UnexpectedEof.into()),
+        };
+
+        let (text, _) = reader.source();
+        let inner = reader.read_inner(&text[tag.clone()])?;
+        let inner_start = inner.as_ptr() as usize - text.as_ptr() as usize;
+        let (_, end) = reader.source();
+
+        Ok(RawXml {
+            xml: Cow::Borrowed(&text[start..end]),
+            tag: tag.start - start..tag.end - start,
+            inner: inner_start - start..inner_start + inner.len() - start,
+        })
+    }
+}
+
+impl XmlWrite for RawXml<'_> {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        writer.write_raw(&self.xml)?;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_raw_xml() -> XmlResult<()> {
+    let raw = RawXml::from_str(r#"<w:p a="1"><w:r>text</w:r><w:r/></w:p>"#)?;
+
+    assert_eq!(raw.as_str(), r#"<w:p a="1"><w:r>text</w:r><w:r/></w:p>"#);
+    assert_eq!(raw.tag(), "w:p");
+    assert_eq!(raw.inner(), "<w:r>text</w:r><w:r/>");
+
+    let raw = RawXml::from_str(r#"<?xml version="1.0"?><p/>"#)?.into_owned();
+
+    assert_eq!(raw.as_str(), "<p/>");
+    assert_eq!(raw.tag(), "p");
+    assert_eq!(raw.inner(), "");
+    assert_eq!(raw.to_string()?, "<p/>");
+
+    Ok(())
+}
@@ -1,3 +1,6 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
 use crate::{XmlReader, XmlResult};
 
 pub trait XmlRead<'a>: Sized {
@@ -12,3 +15,15 @@ pub trait XmlRead<'a>: Sized {
 pub trait XmlReadOwned: for<'s> XmlRead<'s> {}
 
 impl<T> XmlReadOwned for T where T: for<'s> XmlRead<'s> {}
+
+macro_rules! impl_pointer {
+    ($($pointer:ident),*) => {$(
+        impl<'a, T: XmlRead<'a>> XmlRead<'a> for $pointer<T> {
+            fn from_reader(reader: &mut XmlReader<'a>) -> XmlResult<Self> {
+                T::from_reader(reader).map($pointer::new)
+            }
+        }
+    )*};
+}
+
+impl_pointer!(Box, Rc, Arc);
@@ -1,38 +1,1031 @@
+use std::any::Any;
 use std::borrow::Cow;
-use std::iter::{Iterator, Peekable};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
 
 use xmlparser::ElementEnd;
 use xmlparser::Error;
+use xmlparser::StrSpan;
 use xmlparser::Token;
 use xmlparser::Tokenizer;
 
+use crate::xml_duplicate::DuplicateAttrs;
+use crate::xml_name_matcher::{LocalName, NameMatcher, XML_NAMESPACE};
+use crate::xml_token_filter::TokenFilter;
 use crate::xml_unescape::xml_unescape;
-use crate::{XmlError, XmlResult};
+use crate::xml_whitespace::{read_attr_value, AttrWhitespace};
+use crate::{ErrorDetail, Position, XmlError, XmlResult};
 
 /// Xml Reader
 ///
 /// It behaves almost exactly like `xmlparser::Tokenizer::from("...").peekable()`
 /// but with some helper functions.
+///
+/// Names of elements and attributes are returned as qualified names
+/// (e.g. `w:p`) and compared with the expected ones using its
+/// [`NameMatcher`], which defaults to [`LocalName`].
+///
+/// Tokens can be transformed before they are read by installing
+/// [`TokenFilter`]s.
 pub struct XmlReader<'a> {
-    tokenizer: Peekable<Tokenizer<'a>>,
+    text: &'a str,
+    tokenizer: Tokenizer<'a>,
+    peeked: Option<Option<Result<Token<'a>, Error>>>,
+    matcher: Box<dyn NameMatcher + Send + Sync>,
+    // whether the `xmlns` declarations are kept in `namespaces` for the
+    // name matcher
+    uses_namespaces: bool,
+    deprecation_hook: Option<Box<DeprecationHook>>,
+    filters: Vec<Box<dyn TokenFilter<'a> + Send + Sync + 'a>>,
+    // offset of `text` in the original document
+    base: usize,
+    // line and column of the start of `text` in the original document
+    first_line: u32,
+    first_column: u32,
+    // end of the last consumed token
+    offset: usize,
+    // start of the last consumed token
+    start: usize,
+    // line of `start`, and where that line starts
+    line: u32,
+    line_start: usize,
+    // names of the open elements, with their index among the siblings of
+    // the same name and where the counts of their own children start
+    elements: Vec<(&'a str, u32, usize)>,
+    // number of children of each name of the open elements
+    children: Vec<(&'a str, u32)>,
+    // `xmlns` declarations of the open elements, with the number of
+    // elements open when they were made
+    namespaces: Vec<(usize, &'a str, &'a str)>,
+    // name of the last consumed token if it is an attribute
+    attribute: Option<&'a str>,
+    // whether the last consumed token closed the innermost open element,
+    // which stays in the path of its errors until the next token
+    closing: bool,
+    depth: usize,
+    // whether we are inside a start tag or a DTD
+    in_markup: bool,
+    // whether names are compared ignoring ASCII case
+    ignore_case: bool,
+    attr_whitespace: AttrWhitespace,
+    duplicate_attrs: DuplicateAttrs,
+    // tokens read ahead of the peeked one, which already passed the filters
+    pending: VecDeque<Result<Token<'a>, Error>>,
+    // whether each attribute of the current start tag is read under the
+    // duplicate attributes policy, only kept when it isn't `Allow`
+    attrs: Vec<bool>,
+    // number of attributes of the current start tag consumed so far
+    attr_index: usize,
+    // number of elements which can be open at once
+    max_depth: Option<usize>,
+    // whether recoverable errors are recorded in `errors` instead of
+    // being returned
+    collect_errors: bool,
+    errors: Vec<XmlError>,
+}
+
+// called with the element, the field and the deprecated name found
+type DeprecationHook = dyn Fn(&str, &str, &str) + Send + Sync;
+
+/// Position of an [`XmlReader`] at an element boundary.
+///
+/// It can be persisted, using its fields or its `Display` and `FromStr`
+/// implementations, and passed to [`XmlReader::resume`] to continue
+/// parsing later on, e.g. after a process restart or when more input
+/// has arrived.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct XmlReaderState {
+    /// Byte offset in the document where parsing continues
+    pub offset: usize,
+    /// Number of elements open at `offset`
+    pub depth: usize,
+    /// Line of `offset`, starting at 1
+    pub line: u32,
+    /// Column of `offset` in characters, starting at 1
+    pub column: u32,
+}
+
+impl Default for XmlReaderState {
+    fn default() -> Self {
+        XmlReaderState {
+            offset: 0,
+            depth: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+}
+
+// what an element's reading started from, see `XmlReader::recover_element`
+pub(crate) struct Mark {
+    offset: usize,
+    depth: usize,
+    errors: usize,
+}
+
+/// Saved position of an [`XmlReader`], returned by
+/// [`XmlReader::checkpoint`].
+#[derive(Clone)]
+pub struct Checkpoint<'a> {
+    tokenizer: Tokenizer<'a>,
+    peeked: Option<Option<Result<Token<'a>, Error>>>,
+    offset: usize,
+    start: usize,
+    line: u32,
+    line_start: usize,
+    elements: Vec<(&'a str, u32, usize)>,
+    children: Vec<(&'a str, u32)>,
+    namespaces: Vec<(usize, &'a str, &'a str)>,
+    attribute: Option<&'a str>,
+    closing: bool,
+    pending: VecDeque<Result<Token<'a>, Error>>,
+    attrs: Vec<bool>,
+    attr_index: usize,
+    depth: usize,
+    in_markup: bool,
+    // states of the filters, see `TokenFilter::save`
+    filters: Vec<Option<Arc<dyn Any + Send + Sync>>>,
 }
 
 impl<'a> XmlReader<'a> {
     #[inline]
     pub fn new(text: &'a str) -> XmlReader<'a> {
         XmlReader {
-            tokenizer: Tokenizer::from(text).peekable(),
+            text,
+            tokenizer: Tokenizer::from(text),
+            peeked: None,
+            matcher: Box::new(LocalName),
+            uses_namespaces: false,
+            deprecation_hook: None,
+            filters: Vec::new(),
+            base: 0,
+            first_line: 1,
+            first_column: 1,
+            offset: 0,
+            start: 0,
+            line: 1,
+            line_start: 0,
+            elements: Vec::new(),
+            children: Vec::new(),
+            namespaces: Vec::new(),
+            attribute: None,
+            closing: false,
+            depth: 0,
+            in_markup: false,
+            ignore_case: false,
+            attr_whitespace: AttrWhitespace::Preserve,
+            duplicate_attrs: DuplicateAttrs::Allow,
+            pending: VecDeque::new(),
+            attrs: Vec::new(),
+            attr_index: 0,
+            max_depth: None,
+            collect_errors: false,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Continues parsing from a state returned by [`XmlReader::state`].
+    ///
+    /// `text` is the rest of the document, starting at `state.offset`.
+    /// Closing tags of the elements which are still open are accepted.
+    ///
+    /// ```rust
+    /// use hard_xml::{XmlReader, XmlReaderState};
+    ///
+    /// let xml = "<feed><item/><item/></feed>";
+    ///
+    /// let mut reader = XmlReader::new(xml);
+    /// reader.read_till_element_start("feed")?;
+    /// reader.next(); // ">"
+    /// assert_eq!(reader.find_element_start(Some("feed"))?, Some("item"));
+    /// reader.read_till_element_start("item")?;
+    /// reader.next(); // "/>"
+    ///
+    /// let state: XmlReaderState = reader.state().unwrap().to_string().parse()?;
+    /// assert_eq!(
+    ///     state,
+    ///     XmlReaderState { offset: 13, depth: 1, line: 1, column: 14 }
+    /// );
+    ///
+    /// let mut reader = XmlReader::resume(&xml[state.offset..], state);
+    /// assert_eq!(reader.find_element_start(Some("feed"))?, Some("item"));
+    /// # Ok::<(), hard_xml::XmlError>(())
+    /// ```
+    #[inline]
+    pub fn resume(text: &'a str, state: XmlReaderState) -> XmlReader<'a> {
+        XmlReader {
+            text,
+            tokenizer: Tokenizer::from_fragment(text, 0..text.len()),
+            peeked: None,
+            matcher: Box::new(LocalName),
+            uses_namespaces: false,
+            deprecation_hook: None,
+            filters: Vec::new(),
+            base: state.offset,
+            first_line: state.line,
+            first_column: state.column,
+            offset: 0,
+            start: 0,
+            line: state.line,
+            line_start: 0,
+            elements: Vec::new(),
+            children: Vec::new(),
+            namespaces: Vec::new(),
+            attribute: None,
+            closing: false,
+            depth: state.depth,
+            in_markup: false,
+            ignore_case: false,
+            attr_whitespace: AttrWhitespace::Preserve,
+            duplicate_attrs: DuplicateAttrs::Allow,
+            pending: VecDeque::new(),
+            attrs: Vec::new(),
+            attr_index: 0,
+            max_depth: None,
+            collect_errors: false,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Returns the current state if the reader is at an element boundary,
+    /// i.e. not inside a start tag or a DTD.
+    ///
+    /// Tokens which were peeked but not consumed yet are read again
+    /// after resuming.
+    #[inline]
+    pub fn state(&self) -> Option<XmlReaderState> {
+        if self.in_markup {
+            None
+        } else {
+            let (line, column) = self.line_column(self.offset);
+            Some(XmlReaderState {
+                offset: self.base + self.offset,
+                depth: self.depth,
+                line,
+                column,
+            })
+        }
+    }
+
+    /// Returns the position of the token being read, which is the one
+    /// peeked if there is one, or else the last one read.
+    ///
+    /// Offsets, lines and columns of resumed readers are counted from the
+    /// start of the document, but paths from where they resumed.
+    ///
+    /// ```rust
+    /// use hard_xml::XmlReader;
+    ///
+    /// let mut reader = XmlReader::new("<a>\n  <b/><b>\n    <c/></b></a>");
+    /// reader.read_till_element_start("a")?;
+    /// reader.next(); // ">"
+    /// assert_eq!(reader.find_element_start(Some("a"))?, Some("b"));
+    /// reader.next(); // "<b"
+    /// reader.read_to_end("b")?;
+    /// assert_eq!(reader.find_element_start(Some("a"))?, Some("b"));
+    /// reader.next(); // "<b"
+    /// reader.next(); // ">"
+    /// assert_eq!(reader.find_element_start(Some("b"))?, Some("c"));
+    ///
+    /// let position = reader.position();
+    /// assert_eq!((position.line, position.column), (3, 5));
+    /// assert_eq!(position.path, "a > b[2] > c");
+    /// # Ok::<(), hard_xml::XmlError>(())
+    /// ```
+    pub fn position(&self) -> Position {
+        let (start, element) = match &self.peeked {
+            Some(Some(Ok(token))) => match *token {
+                Token::ElementStart { prefix, local, .. } => (
+                    token_span(token).start(),
+                    Some(Some(qualified_name(self.text, prefix, local))),
+                ),
+                _ => (token_span(token).start(), Some(None)),
+            },
+            _ => (self.start, None),
+        };
+        let (line, column) = self.line_column(start);
+        Position {
+            offset: self.base + start,
+            line,
+            column,
+            path: self.path(element),
+        }
+    }
+
+    fn position_at(&self, offset: usize) -> Position {
+        let mut position = Position::from_offset(self.text, offset);
+        position.offset += self.base;
+        if position.line == 1 {
+            position.column += self.first_column - 1;
+        }
+        position.line += self.first_line - 1;
+        position.path = self.path(None);
+        position
+    }
+
+    // returns the line and column of `offset`, which isn't before the last
+    // consumed token
+    fn line_column(&self, offset: usize) -> (u32, u32) {
+        let (line, line_start) = self.line_at(offset);
+        let before = self.text.as_bytes().get(line_start..offset).unwrap_or(&[]);
+        // continuation bytes aren't characters of their own
+        let column = before.iter().filter(|b| **b & 0xC0 != 0x80).count() as u32 + 1;
+        // the first line of a resumed reader doesn't start with `text`
+        if line_start == 0 {
+            (line, column + self.first_column - 1)
+        } else {
+            (line, column)
+        }
+    }
+
+    // returns the line of `offset`, which isn't before the last consumed
+    // token, and where that line starts
+    #[inline]
+    fn line_at(&self, offset: usize) -> (u32, usize) {
+        let skipped = match self.text.as_bytes().get(self.start..offset) {
+            Some(skipped) => skipped,
+            None => return (self.line, self.line_start),
+        };
+        skipped
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| **b == b'\n')
+            .fold((self.line, self.line_start), |(line, _), (index, _)| {
+                (line + 1, self.start + index + 1)
+            })
+    }
+
+    // returns the path of the open elements at the last consumed token,
+    // or at the peeked one if `peeked` is set, with the name of the
+    // element it opens
+    fn path(&self, peeked: Option<Option<&str>>) -> String {
+        // an element closed by the last consumed token isn't open anymore
+        let open = match peeked {
+            Some(_) if self.closing => self.elements.len().saturating_sub(1),
+            _ => self.elements.len(),
+        };
+        let mut path = String::new();
+        let mut push = |name: &str, index: u32| {
+            if !path.is_empty() {
+                path.push_str(" > ");
+            }
+            path.push_str(name);
+            if index > 1 {
+                path.push_str(&format!("[{}]", index));
+            }
+        };
+        for (name, index, _) in &self.elements[..open] {
+            push(name, *index);
+        }
+        if let Some(Some(name)) = peeked {
+            let from = open.checked_sub(1).map_or(0, |i| self.elements[i].2);
+            let to = self.elements.get(open).map_or(self.children.len(), |e| e.2);
+            let index = self.children[from..to].iter().find(|(n, _)| *n == name);
+            push(name, index.map_or(1, |(_, count)| count + 1));
         }
+        path
     }
 
+    /// Sets the position of `err` to the one of the token being read, or to
+    /// the one the parser failed at, unless it's already known.
+    ///
+    /// Derived `XmlRead` implementations position their errors this way.
+    pub fn locate(&self, err: XmlError) -> XmlError {
+        let pos = match err.detail() {
+            ErrorDetail::Parser(error) if err.position().is_none() => error.pos(),
+            _ => return err.with_position(self.position()),
+        };
+        // the parser counts lines and characters
+        let line_start: usize = self
+            .text
+            .split_inclusive('\n')
+            .take(pos.row.saturating_sub(1) as usize)
+            .map(str::len)
+            .sum();
+        let column: usize = self.text[line_start..]
+            .chars()
+            .take(pos.col.saturating_sub(1) as usize)
+            .map(char::len_utf8)
+            .sum();
+        err.with_position(self.position_at(line_start + column))
+    }
+
+    /// Saves the current position, so the reader can go back to it
+    /// with [`XmlReader::rewind`] after reading ahead.
+    ///
+    /// The state of token filters is saved with `TokenFilter::save`.
+    ///
+    /// ```rust
+    /// use hard_xml::XmlReader;
+    ///
+    /// let mut reader = XmlReader::new("<a><b/></a>");
+    /// let checkpoint = reader.checkpoint();
+    ///
+    /// reader.read_till_element_start("a")?;
+    /// reader.read_to_end("a")?;
+    /// assert!(reader.peek().is_none());
+    ///
+    /// reader.rewind(checkpoint);
+    /// assert_eq!(reader.find_element_start(None)?, Some("a"));
+    /// # Ok::<(), hard_xml::XmlError>(())
+    /// ```
+    #[inline]
+    pub fn checkpoint(&self) -> Checkpoint<'a> {
+        Checkpoint {
+            tokenizer: self.tokenizer.clone(),
+            peeked: self.peeked,
+            offset: self.offset,
+            start: self.start,
+            line: self.line,
+            line_start: self.line_start,
+            elements: self.elements.clone(),
+            children: self.children.clone(),
+            namespaces: self.namespaces.clone(),
+            attribute: self.attribute,
+            closing: self.closing,
+            pending: self.pending.clone(),
+            attrs: self.attrs.clone(),
+            attr_index: self.attr_index,
+            depth: self.depth,
+            in_markup: self.in_markup,
+            filters: self.filters.iter().map(|filter| filter.save()).collect(),
+        }
+    }
+
+    /// Goes back to a position saved by [`XmlReader::checkpoint`].
     #[inline]
+    pub fn rewind(&mut self, checkpoint: Checkpoint<'a>) {
+        self.tokenizer = checkpoint.tokenizer;
+        self.peeked = checkpoint.peeked;
+        self.offset = checkpoint.offset;
+        self.start = checkpoint.start;
+        self.line = checkpoint.line;
+        self.line_start = checkpoint.line_start;
+        self.elements = checkpoint.elements;
+        self.children = checkpoint.children;
+        self.namespaces = checkpoint.namespaces;
+        self.attribute = checkpoint.attribute;
+        self.closing = checkpoint.closing;
+        self.pending = checkpoint.pending;
+        self.attrs = checkpoint.attrs;
+        self.attr_index = checkpoint.attr_index;
+        self.depth = checkpoint.depth;
+        self.in_markup = checkpoint.in_markup;
+        // filters added after the checkpoint was taken keep their state
+        for (filter, state) in self.filters.iter_mut().zip(&checkpoint.filters) {
+            if let Some(state) = state {
+                filter.restore(&**state);
+            }
+        }
+    }
+
+    /// Replaces the strategy used to compare names.
+    pub fn with_name_matcher<M>(mut self, matcher: M) -> Self
+    where
+        M: NameMatcher + Send + Sync + 'static,
+    {
+        self.uses_namespaces = matcher.uses_namespaces();
+        self.matcher = Box::new(matcher);
+        self
+    }
+
+    /// Calls `hook` with the name of the element, the name of the field
+    /// and the name found whenever a field is read from one of its
+    /// `deprecated` names.
+    ///
+    /// ```rust
+    /// use std::sync::{Arc, Mutex};
+    /// use hard_xml::{XmlRead, XmlReader};
+    ///
+    /// #[derive(XmlRead, PartialEq, Debug)]
+    /// #[xml(tag = "server")]
+    /// struct Server {
+    ///     #[xml(attr = "host", deprecated = "hostname")]
+    ///     host: String,
+    /// }
+    ///
+    /// let found = Arc::new(Mutex::new(Vec::new()));
+    /// let hook_found = found.clone();
+    ///
+    /// let mut reader = XmlReader::new(r#"<server hostname="a"/>"#).with_deprecation_hook(
+    ///     move |element, field, name| {
+    ///         hook_found.lock().unwrap().push(format!("{}.{}: {}", element, field, name));
+    ///     },
+    /// );
+    ///
+    /// Server::from_reader(&mut reader)?;
+    /// assert_eq!(*found.lock().unwrap(), vec!["Server.host: hostname"]);
+    /// # Ok::<(), hard_xml::XmlError>(())
+    /// ```
+    pub fn with_deprecation_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&str, &str, &str) + Send + Sync + 'static,
+    {
+        self.deprecation_hook = Some(Box::new(hook));
+        self
+    }
+
+    /// Reports that a field was read from one of its deprecated names,
+    /// logging a warning and calling the hook of the reader.
+    pub fn report_deprecated(&self, element: &str, field: &str, name: &str) {
+        #[cfg(feature = "log")]
+        crate::lib::log::warn!(
+            "[{}] Read field `{}` from deprecated name `{}`",
+            element,
+            field,
+            name
+        );
+        if let Some(hook) = &self.deprecation_hook {
+            hook(element, field, name);
+        }
+    }
+
+    /// Sets how whitespace in attribute values is read, which is preserved
+    /// by default.
+    pub fn with_attr_whitespace(mut self, whitespace: AttrWhitespace) -> Self {
+        self.attr_whitespace = whitespace;
+        self
+    }
+
+    /// Sets how attributes written more than once in a start tag are read,
+    /// which are all read by default.
+    pub fn with_duplicate_attrs(mut self, duplicate_attrs: DuplicateAttrs) -> Self {
+        self.duplicate_attrs = duplicate_attrs;
+        self
+    }
+
+    /// Limits how deeply elements read with `read_till_element_start` can be
+    /// nested, e.g. to read recursive types from untrusted documents without
+    /// overflowing the stack. There's no limit by default.
+    ///
+    /// ```rust
+    /// use hard_xml::{ErrorDetail, XmlRead, XmlReader};
+    ///
+    /// #[derive(XmlRead, PartialEq, Debug)]
+    /// #[xml(tag = "node")]
+    /// struct Node {
+    ///     #[xml(child = "node")]
+    ///     child: Option<Box<Node>>,
+    /// }
+    ///
+    /// let mut reader = XmlReader::new("<node><node><node/></node></node>").with_max_depth(2);
+    ///
+    /// assert!(matches!(
+    ///     Node::from_reader(&mut reader),
+    ///     Err(err) if matches!(err.detail(), ErrorDetail::DepthLimitExceeded { limit: 2 })
+    /// ));
+    /// ```
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Adds a filter applied to every token after the filters added before.
+    ///
+    /// ```rust
+    /// use hard_xml::filter::{Rename, UnwrapElement};
+    /// use hard_xml::matcher::Exact;
+    /// use hard_xml::{XmlRead, XmlReader};
+    ///
+    /// #[derive(XmlRead, PartialEq, Debug)]
+    /// #[xml(tag = "item")]
+    /// struct Item {
+    ///     #[xml(attr = "id")]
+    ///     id: usize,
+    /// }
+    ///
+    /// let mut reader = XmlReader::new(r#"<legacy><old:item id="1"/></legacy>"#)
+    ///     .with_name_matcher(Exact)
+    ///     .with_filter(UnwrapElement::new("legacy"))
+    ///     .with_filter(Rename::new("old:item", "item"));
+    ///
+    /// assert_eq!(Item::from_reader(&mut reader)?, Item { id: 1 });
+    /// # Ok::<(), hard_xml::XmlError>(())
+    /// ```
+    pub fn with_filter<F>(mut self, filter: F) -> Self
+    where
+        F: TokenFilter<'a> + Send + Sync + 'a,
+    {
+        self.filters.push(Box::new(filter));
+        self
+    }
+
+    /// Goes on reading after errors in the values of fields, or in child
+    /// elements, to report every error of the document at once.
+    ///
+    /// Values which can't be read are left out, and child elements which
+    /// can't be read are skipped. Errors of the document structure, like
+    /// mismatched tags, still stop reading. An element missing a field is
+    /// only reported if nothing else is wrong with it, since the field
+    /// is usually missing because it couldn't be read.
+    ///
+    /// The errors are returned by [`XmlReader::finish`].
+    ///
+    /// ```rust
+    /// use hard_xml::{ErrorKind, XmlRead, XmlReader};
+    ///
+    /// #[derive(XmlRead, PartialEq, Debug)]
+    /// #[xml(tag = "items")]
+    /// struct Items {
+    ///     #[xml(child = "item")]
+    ///     items: Vec<Item>,
+    /// }
+    ///
+    /// #[derive(XmlRead, PartialEq, Debug)]
+    /// #[xml(tag = "item")]
+    /// struct Item {
+    ///     #[xml(attr = "id")]
+    ///     id: u32,
+    ///     #[xml(attr = "count")]
+    ///     count: Option<u32>,
+    /// }
+    ///
+    /// let xml = r#"<items><item id="x" count="y"/><item id="2"/><item/></items>"#;
+    ///
+    /// let mut reader = XmlReader::new(xml).with_error_collection();
+    /// let res = Items::from_reader(&mut reader);
+    /// let errors = reader.finish(res).unwrap_err();
+    ///
+    /// assert_eq!(errors.len(), 3);
+    /// assert_eq!(errors[0].kind(), ErrorKind::Conversion);
+    /// assert_eq!(errors[1].kind(), ErrorKind::Conversion);
+    /// assert_eq!(
+    ///     errors[2].to_string(),
+    ///     r#"missing field in XML of "Item": "id" (expected attribute "id" in <item>) at 1:51 in items > item[3]"#
+    /// );
+    /// ```
+    pub fn with_error_collection(mut self) -> Self {
+        self.collect_errors = true;
+        self
+    }
+
+    /// Turns collecting errors on or off, e.g. while trying to read an
+    /// element in different ways, returning the previous setting.
+    #[inline]
+    pub fn set_collect_errors(&mut self, collect: bool) -> bool {
+        std::mem::replace(&mut self.collect_errors, collect)
+    }
+
+    /// Records `err` at the position of the token being read if errors are
+    /// collected and reading can go on after it, or returns it otherwise.
+    ///
+    /// Derived `XmlRead` implementations recover from errors this way.
+    #[doc(hidden)]
+    pub fn recover(&mut self, err: XmlError) -> XmlResult<()> {
+        if !self.collect_errors || !err.is_recoverable() {
+            return Err(err);
+        }
+        let err = self.locate(err);
+        self.errors.push(err);
+        Ok(())
+    }
+
+    // saves what `recover_element` needs to know before reading an element
+    #[inline]
+    pub(crate) fn mark(&self) -> Mark {
+        Mark {
+            offset: self.offset,
+            depth: self.depth,
+            errors: self.errors.len(),
+        }
+    }
+
+    // records the error an element read from `mark` failed with and skips
+    // the rest of it, unless errors recorded while reading it already
+    // explain why it failed
+    pub(crate) fn recover_element(&mut self, err: XmlError, mark: Mark) -> XmlResult<()> {
+        if !self.collect_errors || !err.is_recoverable() {
+            return Err(err);
+        }
+        if self.errors.len() == mark.errors {
+            self.recover(err)?;
+        }
+        // the start tag wasn't read yet
+        if self.offset == mark.offset {
+            self.next();
+        }
+        while self.in_markup || self.depth > mark.depth {
+            self.next().ok_or_else(|| {
+                XmlError::new(ErrorDetail::UnexpectedEof).with_position(self.position())
+            })??;
+        }
+        Ok(())
+    }
+
+    /// Takes the errors collected while reading `res`, returning the value
+    /// read if there are none.
+    ///
+    /// An error `res` failed with is added to them, unless it's explained
+    /// by the errors collected before, like a field missing because it
+    /// couldn't be read.
+    pub fn finish<T>(&mut self, res: XmlResult<T>) -> Result<T, Vec<XmlError>> {
+        let mut errors = std::mem::take(&mut self.errors);
+        match res {
+            Ok(value) if errors.is_empty() => Ok(value),
+            Ok(_) => Err(errors),
+            Err(err) => {
+                if errors.is_empty() || !err.is_recoverable() {
+                    errors.push(err);
+                }
+                Err(errors)
+            }
+        }
+    }
+
+    /// Makes the name matcher ignore ASCII case, e.g. while reading an
+    /// element with `#[xml(case_insensitive)]` attribute, returning the
+    /// previous setting.
+    #[inline]
+    pub fn set_ignore_ascii_case(&mut self, ignore_case: bool) -> bool {
+        std::mem::replace(&mut self.ignore_case, ignore_case)
+    }
+
+    #[inline]
+    fn matcher_matches(&self, expected: &str, found: &str) -> bool {
+        if self.uses_namespaces {
+            let is_attribute = self.attribute == Some(found);
+            let namespace = self.namespace_of(found, is_attribute);
+            if self.ignore_case {
+                self.matcher.matches_namespace(
+                    &expected.to_ascii_lowercase(),
+                    &found.to_ascii_lowercase(),
+                    namespace,
+                    is_attribute,
+                )
+            } else {
+                self.matcher
+                    .matches_namespace(expected, found, namespace, is_attribute)
+            }
+        } else if self.ignore_case {
+            self.matcher
+                .matches(&expected.to_ascii_lowercase(), &found.to_ascii_lowercase())
+        } else {
+            self.matcher.matches(expected, found)
+        }
+    }
+
+    // resolves the namespace of the name of the peeked start tag, of an
+    // open element or of the last consumed attribute
+    fn namespace_of(&self, found: &str, is_attribute: bool) -> Option<&'a str> {
+        let prefix = found.split_once(':').map_or("", |(prefix, _)| prefix);
+        if prefix == "xml" {
+            return Some(XML_NAMESPACE);
+        }
+        if prefix.is_empty() && is_attribute {
+            return None;
+        }
+
+        let mut open = self.elements.len();
+        if let Some(Some(Ok(Token::ElementStart {
+            prefix: p,
+            local,
+            span,
+        }))) = self.peeked
+        {
+            if !is_attribute && qualified_name(self.text, p, local) == found {
+                // the declarations of the peeked start tag aren't tracked yet
+                if let Some((_, uri)) = self.declarations(span.start()).find(|(p, _)| *p == prefix)
+                {
+                    return Some(uri).filter(|uri| !uri.is_empty());
+                }
+                // nor is the element closed by the last token
+                if self.closing {
+                    open -= 1;
+                }
+            }
+        }
+        self.namespaces
+            .iter()
+            .rev()
+            .filter(|(depth, ..)| *depth <= open)
+            .find(|(_, p, _)| *p == prefix)
+            .map(|(_, _, uri)| *uri)
+            .filter(|uri| !uri.is_empty())
+    }
+
+    // `xmlns` declarations of the start tag at `start`, as (prefix, uri)
+    fn declarations(&self, start: usize) -> impl Iterator<Item = (&'a str, &'a str)> {
+        Tokenizer::from_fragment(self.text, start..self.text.len())
+            .skip(1)
+            .map_while(|token| match token {
+                Ok(Token::Attribute {
+                    prefix,
+                    local,
+                    value,
+                    ..
+                }) => Some((prefix.as_str(), local.as_str(), value.as_str())),
+                _ => None,
+            })
+            .filter_map(|(prefix, local, value)| match (prefix, local) {
+                ("xmlns", prefix) => Some((prefix, value)),
+                ("", "xmlns") => Some(("", value)),
+                _ => None,
+            })
+    }
+
+    /// Compares names using the name matcher. The expected name `*`
+    /// matches any name.
+    #[inline]
+    pub fn name_matches(&self, expected: &str, found: &str) -> bool {
+        expected == found || expected == "*" || self.matcher_matches(expected, found)
+    }
+
+    /// Returns the name in `expected` matching `found`, or `found` itself
+    /// if there is none.
+    ///
+    /// `*` is only returned if no other name matches.
+    #[inline]
+    pub fn resolve_name<'n>(&self, found: &'n str, expected: &[&'n str]) -> &'n str {
+        if expected.contains(&found) {
+            return found;
+        }
+        expected
+            .iter()
+            .find(|expected| **expected != "*" && self.matcher_matches(expected, found))
+            .or_else(|| expected.iter().find(|expected| **expected == "*"))
+            .unwrap_or(&found)
+    }
+
+    #[inline]
+    #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> Option<Result<Token<'a>, Error>> {
-        self.tokenizer.next()
+        let token = match self.peeked.take() {
+            Some(token) => token,
+            None => self.pull(),
+        };
+        if let Some(Ok(token)) = &token {
+            self.track(token);
+        }
+        token
+    }
+
+    // reads the next token passing every filter
+    #[inline]
+    fn pull(&mut self) -> Option<Result<Token<'a>, Error>> {
+        match self.pending.pop_front() {
+            Some(token) => Some(token),
+            None => self.pull_filtered(),
+        }
+    }
+
+    #[inline]
+    fn pull_filtered(&mut self) -> Option<Result<Token<'a>, Error>> {
+        'tokens: loop {
+            let mut token = match self.tokenizer.next()? {
+                Ok(token) => token,
+                Err(err) => return Some(Err(err)),
+            };
+            for filter in &mut self.filters {
+                match filter.filter(token) {
+                    Some(filtered) => token = filtered,
+                    None => continue 'tokens,
+                }
+            }
+            return Some(Ok(token));
+        }
+    }
+
+    // keeps track of the position to be reported by `state` and `position`
+    #[inline]
+    fn track(&mut self, token: &Token<'a>) {
+        if self.closing {
+            self.closing = false;
+            self.close_element();
+        }
+        self.attribute = None;
+        match *token {
+            Token::ElementStart {
+                prefix,
+                local,
+                span,
+            } => {
+                self.in_markup = true;
+                self.attr_index = 0;
+                self.open_element(qualified_name(self.text, prefix, local));
+                if self.uses_namespaces {
+                    let open = self.elements.len();
+                    let declarations: Vec<_> = self.declarations(span.start()).collect();
+                    self.namespaces.extend(
+                        declarations
+                            .into_iter()
+                            .map(|(prefix, uri)| (open, prefix, uri)),
+                    );
+                }
+                self.buffer_attrs();
+            }
+            Token::Attribute { prefix, local, .. } => {
+                self.attr_index += 1;
+                self.attribute = Some(qualified_name(self.text, prefix, local));
+            }
+            Token::DtdStart { .. } => self.in_markup = true,
+            Token::ElementEnd {
+                end: ElementEnd::Open,
+                ..
+            } => {
+                self.in_markup = false;
+                self.depth += 1;
+            }
+            Token::ElementEnd {
+                end: ElementEnd::Empty,
+                ..
+            } => {
+                self.in_markup = false;
+                self.closing = true;
+            }
+            Token::DtdEnd { .. } => self.in_markup = false,
+            Token::ElementEnd {
+                end: ElementEnd::Close(..),
+                ..
+            } => {
+                self.depth = self.depth.saturating_sub(1);
+                self.closing = true;
+            }
+            _ => (),
+        }
+        let span = token_span(token);
+        // tokens made up by filters aren't part of the text
+        if span.start() >= self.start {
+            (self.line, self.line_start) = self.line_at(span.start());
+            self.start = span.start();
+        }
+        self.offset = span.end();
+    }
+
+    // reads the attributes of the start tag which was just consumed ahead,
+    // so which occurrence of a repeated one is read is known before the
+    // first one is
+    fn buffer_attrs(&mut self) {
+        self.attrs.clear();
+        if self.duplicate_attrs == DuplicateAttrs::Allow {
+            return;
+        }
+
+        let mut names = Vec::new();
+        while let Some(token) = self.pull_filtered() {
+            let name = match token {
+                Ok(Token::Attribute { prefix, local, .. }) => {
+                    Some(qualified_name(self.text, prefix, local))
+                }
+                _ => None,
+            };
+            self.pending.push_back(token);
+            match name {
+                Some(name) => names.push(name),
+                None => break,
+            }
+        }
+
+        self.attrs = match self.duplicate_attrs {
+            DuplicateAttrs::Last => {
+                let last: HashMap<_, _> = names.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+                names
+                    .iter()
+                    .enumerate()
+                    .map(|(index, name)| last[name] == index)
+                    .collect()
+            }
+            _ => {
+                let mut seen = HashSet::new();
+                names.iter().map(|name| seen.insert(*name)).collect()
+            }
+        };
+    }
+
+    #[inline]
+    fn open_element(&mut self, name: &'a str) {
+        let from = self.elements.last().map_or(0, |(_, _, from)| *from);
+        let index = match self.children[from..].iter_mut().find(|(n, _)| *n == name) {
+            Some((_, count)) => {
+                *count += 1;
+                *count
+            }
+            None => {
+                self.children.push((name, 1));
+                1
+            }
+        };
+        self.elements.push((name, index, self.children.len()));
+    }
+
+    #[inline]
+    fn close_element(&mut self) {
+        if let Some((_, _, from)) = self.elements.pop() {
+            self.children.truncate(from);
+        }
+        let open = self.elements.len();
+        while matches!(self.namespaces.last(), Some((depth, ..)) if *depth > open) {
+            self.namespaces.pop();
+        }
     }
 
     #[inline]
     pub fn peek(&mut self) -> Option<&Result<Token<'a>, Error>> {
-        self.tokenizer.peek()
+        if self.peeked.is_none() {
+            self.peeked = Some(self.pull());
+        }
+        self.peeked.as_ref().unwrap().as_ref()
     }
 
     #[inline]
@@ -46,23 +1039,27 @@ impl<'a> XmlReader<'a> {
                 }
                 | Token::Attribute { .. } => (),
                 Token::Text { text } => {
-                    res = Some(xml_unescape(text.as_str())?);
+                    append_text(&mut res, xml_unescape(text.as_str())?);
                 }
                 Token::Cdata { text, .. } => {
-                    res = Some(Cow::Borrowed(text.as_str()));
+                    append_text(&mut res, Cow::Borrowed(text.as_str()));
                 }
+                // comments and processing instructions split the text into
+                // multiple tokens, but aren't part of its value
+                Token::Comment { .. } | Token::ProcessingInstruction { .. } => (),
                 Token::ElementEnd {
-                    end: ElementEnd::Close(_, local),
-                    span,
+                    end: ElementEnd::Close(prefix, local),
+                    ..
                 } => {
-                    let tag = local.as_str();
-                    if end_tag == tag {
+                    let tag = qualified_name(self.text, prefix, local);
+                    if self.name_matches(end_tag, tag) {
                         break;
                     } else {
-                        return Err(XmlError::TagMismatch {
+                        return Err(XmlError::new(ErrorDetail::TagMismatch {
                             expected: end_tag.to_owned(),
                             found: tag.to_owned(),
-                        });
+                        })
+                        .with_position(self.position()));
                     }
                 }
                 Token::ElementEnd {
@@ -72,9 +1069,10 @@ impl<'a> XmlReader<'a> {
                     break;
                 }
                 token => {
-                    return Err(XmlError::UnexpectedToken {
+                    return Err(XmlError::new(ErrorDetail::UnexpectedToken {
                         token: format!("{:?}", token),
-                    });
+                    })
+                    .with_position(self.position()));
                 }
             }
         }
@@ -82,14 +1080,31 @@ impl<'a> XmlReader<'a> {
         Ok(res.unwrap_or_default())
     }
 
+    /// Skips everything before the start tag of the element `end_tag`,
+    /// returning the element's name as written in the document.
+    #[inline]
+    pub fn read_till_element_start(&mut self, end_tag: &str) -> XmlResult<Option<&'a str>> {
+        self.read_till_element_start_of(&[end_tag])
+    }
+
+    /// Skips everything before the start tag of an element matching one
+    /// of `tags`, returning the element's name as written in the document.
     #[inline]
-    pub fn read_till_element_start(&mut self, end_tag: &str) -> XmlResult<()> {
+    pub fn read_till_element_start_of(&mut self, tags: &[&str]) -> XmlResult<Option<&'a str>> {
         while let Some(token) = self.next() {
             match token? {
-                Token::ElementStart { local, .. } => {
-                    let token = local.as_str();
-                    if end_tag == token {
-                        break;
+                Token::ElementStart { prefix, local, .. } => {
+                    let token = qualified_name(self.text, prefix, local);
+                    if tags.iter().any(|tag| self.name_matches(tag, token)) {
+                        match self.max_depth {
+                            Some(limit) if self.depth >= limit => {
+                                return Err(XmlError::new(ErrorDetail::DepthLimitExceeded {
+                                    limit,
+                                })
+                                .with_position(self.position()))
+                            }
+                            _ => return Ok(Some(token)),
+                        }
                     } else {
                         self.read_to_end(token)?;
                     }
@@ -98,25 +1113,36 @@ impl<'a> XmlReader<'a> {
                 | Token::Attribute { .. }
                 | Token::Text { .. }
                 | Token::Cdata { .. } => {
-                    return Err(XmlError::UnexpectedToken {
+                    return Err(XmlError::new(ErrorDetail::UnexpectedToken {
                         token: format!("{:?}", token),
-                    });
+                    })
+                    .with_position(self.position()));
                 }
                 _ => (),
             }
         }
-        Ok(())
+        Ok(None)
     }
 
     #[inline]
     pub fn find_attribute(&mut self) -> XmlResult<Option<(&'a str, Cow<'a, str>)>> {
-        if let Some(token) = self.tokenizer.peek() {
+        while let Some(token) = self.peek().copied() {
             match token {
-                Ok(Token::Attribute { local, value, .. }) => {
+                Ok(Token::Attribute {
+                    prefix,
+                    local,
+                    value,
+                    ..
+                }) => {
                     let value = value.as_str();
-                    let key = local.as_str();
+                    let key = qualified_name(self.text, prefix, local);
 
-                    let value = Cow::Borrowed(value);
+                    if !self.is_attr_read(key)? {
+                        self.next();
+                        continue;
+                    }
+
+                    let value = read_attr_value(value, self.attr_whitespace)?;
                     self.next();
                     return Ok(Some((key, value)));
                 }
@@ -129,9 +1155,10 @@ impl<'a> XmlReader<'a> {
                     ..
                 }) => return Ok(None),
                 Ok(token) => {
-                    return Err(XmlError::UnexpectedToken {
+                    return Err(XmlError::new(ErrorDetail::UnexpectedToken {
                         token: format!("{:?}", token),
                     })
+                    .with_position(self.position()))
                 }
                 Err(_) => {
                     // we have call .peek() above, and it's safe to use unwrap
@@ -140,36 +1167,55 @@ impl<'a> XmlReader<'a> {
             }
         }
 
-        Err(XmlError::UnexpectedEof)
+        Err(XmlError::new(ErrorDetail::UnexpectedEof).with_position(self.position()))
+    }
+
+    // whether the attribute `key`, which is the peeked token, is read under
+    // the duplicate attributes policy
+    fn is_attr_read(&self, key: &'a str) -> XmlResult<bool> {
+        let read = self.attrs.get(self.attr_index).copied().unwrap_or(true);
+        match self.duplicate_attrs {
+            DuplicateAttrs::Error if !read => Err(XmlError::new(ErrorDetail::DuplicateAttribute {
+                tag: self
+                    .elements
+                    .last()
+                    .map_or_else(String::new, |(name, _, _)| name.to_string()),
+                attribute: key.to_owned(),
+            })
+            .with_position(self.position())),
+            _ => Ok(read),
+        }
     }
 
     #[inline]
     pub fn find_element_start(&mut self, end_tag: Option<&str>) -> XmlResult<Option<&'a str>> {
-        while let Some(token) = self.tokenizer.peek() {
+        while let Some(token) = self.peek().copied() {
             match token {
-                Ok(Token::ElementStart { local, .. }) => {
-                    return Ok(Some(local.as_str()));
+                Ok(Token::ElementStart { prefix, local, .. }) => {
+                    return Ok(Some(qualified_name(self.text, prefix, local)));
                 }
                 Ok(Token::ElementEnd {
-                    end: ElementEnd::Close(_, local),
-                    span,
+                    end: ElementEnd::Close(prefix, local),
+                    ..
                 }) if end_tag.is_some() => {
                     let end_tag = end_tag.unwrap();
-                    let tag = local.as_str();
-                    if tag == end_tag {
+                    let tag = qualified_name(self.text, prefix, local);
+                    if self.name_matches(end_tag, tag) {
                         self.next();
                         return Ok(None);
                     } else {
-                        return Err(XmlError::TagMismatch {
+                        return Err(XmlError::new(ErrorDetail::TagMismatch {
                             expected: end_tag.to_owned(),
                             found: tag.to_owned(),
-                        });
+                        })
+                        .with_position(self.position()));
                     }
                 }
                 Ok(Token::ElementEnd { .. }) | Ok(Token::Attribute { .. }) => {
-                    return Err(XmlError::UnexpectedToken {
+                    return Err(XmlError::new(ErrorDetail::UnexpectedToken {
                         token: format!("{:?}", token),
                     })
+                    .with_position(self.position()))
                 }
                 _ => {
                     // we have call .peek() above, and it's safe to use unwrap
@@ -178,7 +1224,84 @@ impl<'a> XmlReader<'a> {
             }
         }
 
-        Err(XmlError::UnexpectedEof)
+        Err(XmlError::new(ErrorDetail::UnexpectedEof).with_position(self.position()))
+    }
+
+    /// Skips the rest of an element like `read_to_end`, returning the
+    /// source text between its start and end tag verbatim.
+    ///
+    /// ```rust
+    /// use hard_xml::XmlReader;
+    ///
+    /// let mut reader = XmlReader::new(r#"<any a="1">text<b>&amp;</b></any>"#);
+    ///
+    /// reader.next(); // "<any"
+    /// assert_eq!(reader.read_inner("any")?, "text<b>&amp;</b>");
+    /// # Ok::<(), hard_xml::XmlError>(())
+    /// ```
+    #[inline]
+    pub fn read_inner(&mut self, end_tag: &str) -> XmlResult<&'a str> {
+        let start = loop {
+            match self.next().ok_or_else(|| {
+                XmlError::new(ErrorDetail::UnexpectedEof).with_position(self.position())
+            })?? {
+                Token::ElementEnd {
+                    end: ElementEnd::Empty,
+                    span,
+                } => return Ok(&self.text[span.end()..span.end()]),
+                Token::ElementEnd {
+                    end: ElementEnd::Open,
+                    span,
+                } => break span.end(),
+                Token::Attribute { .. } => (),
+                // there shouldn't have any token but Attribute between ElementStart and ElementEnd
+                token => {
+                    return Err(XmlError::new(ErrorDetail::UnexpectedToken {
+                        token: format!("{:?}", token),
+                    })
+                    .with_position(self.position()))
+                }
+            }
+        };
+
+        let mut depth = 0;
+
+        while let Some(token) = self.next() {
+            match token? {
+                Token::ElementEnd {
+                    end: ElementEnd::Open,
+                    ..
+                } => depth += 1,
+                Token::ElementEnd {
+                    end: ElementEnd::Close(prefix, local),
+                    span,
+                } => {
+                    if depth > 0 {
+                        depth -= 1;
+                        continue;
+                    }
+                    let tag = qualified_name(self.text, prefix, local);
+                    if self.name_matches(end_tag, tag) {
+                        return Ok(&self.text[start..span.start()]);
+                    } else {
+                        return Err(XmlError::new(ErrorDetail::TagMismatch {
+                            expected: end_tag.to_owned(),
+                            found: tag.to_owned(),
+                        })
+                        .with_position(self.position()));
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        Err(XmlError::new(ErrorDetail::UnexpectedEof).with_position(self.position()))
+    }
+
+    // source text and the end of the last consumed token in it
+    #[inline]
+    pub(crate) fn source(&self) -> (&'a str, usize) {
+        (self.text, self.offset)
     }
 
     #[inline]
@@ -197,9 +1320,10 @@ impl<'a> XmlReader<'a> {
                 Token::Attribute { .. } => (),
                 // there shouldn't have any token but Attribute between ElementStart and ElementEnd
                 token => {
-                    return Err(XmlError::UnexpectedToken {
+                    return Err(XmlError::new(ErrorDetail::UnexpectedToken {
                         token: format!("{:?}", token),
                     })
+                    .with_position(self.position()))
                 }
             }
         }
@@ -208,7 +1332,9 @@ impl<'a> XmlReader<'a> {
 
         while let Some(token) = self.next() {
             match token? {
-                Token::ElementStart { local, .. } if end_tag == local.as_str() => {
+                Token::ElementStart { prefix, local, .. }
+                    if self.name_matches(end_tag, qualified_name(self.text, prefix, local)) =>
+                {
                     while let Some(token) = self.next() {
                         match token? {
                             Token::ElementEnd {
@@ -232,29 +1358,114 @@ impl<'a> XmlReader<'a> {
                             Token::Attribute { .. } => (),
                             // there shouldn't have any token but Attribute between ElementStart and ElementEnd
                             token => {
-                                return Err(XmlError::UnexpectedToken {
+                                return Err(XmlError::new(ErrorDetail::UnexpectedToken {
                                     token: format!("{:?}", token),
-                                });
+                                })
+                                .with_position(self.position()));
                             }
                         }
                     }
                 }
                 Token::ElementEnd {
-                    end: ElementEnd::Close(_, local),
-                    span,
-                } => {
-                    if end_tag == local.as_str() {
-                        depth -= 1;
-                        if depth == 0 {
-                            return Ok(());
-                        }
+                    end: ElementEnd::Close(prefix, local),
+                    ..
+                } if self.name_matches(end_tag, qualified_name(self.text, prefix, local)) => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(());
                     }
                 }
                 _ => (),
             }
         }
 
-        Err(XmlError::UnexpectedEof)
+        Err(XmlError::new(ErrorDetail::UnexpectedEof).with_position(self.position()))
+    }
+}
+
+impl fmt::Display for XmlReaderState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}:{}",
+            self.offset, self.depth, self.line, self.column
+        )
+    }
+}
+
+impl FromStr for XmlReaderState {
+    type Err = XmlError;
+
+    fn from_str(s: &str) -> XmlResult<Self> {
+        let invalid = |source: Box<dyn std::error::Error + Send + Sync>| {
+            XmlError::new(ErrorDetail::FromStr {
+                value: s.to_owned(),
+                type_name: "XmlReaderState".to_owned(),
+                source,
+            })
+        };
+        let mut parts = s.split(':');
+        let (offset, depth, line, column) =
+            match (parts.next(), parts.next(), parts.next(), parts.next()) {
+                (Some(offset), Some(depth), Some(line), Some(column)) if parts.next().is_none() => {
+                    (offset, depth, line, column)
+                }
+                _ => return Err(invalid("expected offset:depth:line:column".into())),
+            };
+        Ok(XmlReaderState {
+            offset: offset.parse().map_err(|e| invalid(Box::new(e)))?,
+            depth: depth.parse().map_err(|e| invalid(Box::new(e)))?,
+            line: line.parse().map_err(|e| invalid(Box::new(e)))?,
+            column: column.parse().map_err(|e| invalid(Box::new(e)))?,
+        })
+    }
+}
+
+// returns `prefix:local`, borrowed from the source text, or only `local`
+// if the names weren't taken from it (e.g. they were replaced by a filter)
+#[inline]
+pub(crate) fn qualified_name<'a>(
+    text: &'a str,
+    prefix: StrSpan<'a>,
+    local: StrSpan<'a>,
+) -> &'a str {
+    let (prefix, local) = (prefix.as_str(), local.as_str());
+    if prefix.is_empty() {
+        return local;
+    }
+    let start = prefix.as_ptr() as usize;
+    let end = local.as_ptr() as usize + local.len();
+    let text_start = text.as_ptr() as usize;
+    if start >= text_start && end <= text_start + text.len() && start + prefix.len() < end {
+        &text[start - text_start..end - text_start]
+    } else {
+        local
+    }
+}
+
+fn token_span<'a>(token: &Token<'a>) -> StrSpan<'a> {
+    match *token {
+        Token::Text { text } => text,
+        Token::Declaration { span, .. }
+        | Token::ProcessingInstruction { span, .. }
+        | Token::Comment { span, .. }
+        | Token::DtdStart { span, .. }
+        | Token::EmptyDtd { span, .. }
+        | Token::EntityDeclaration { span, .. }
+        | Token::DtdEnd { span }
+        | Token::ElementStart { span, .. }
+        | Token::Attribute { span, .. }
+        | Token::ElementEnd { span, .. }
+        | Token::Cdata { span, .. } => span,
+    }
+}
+
+// appends a text segment, only allocating if there is more than one segment
+#[inline]
+fn append_text<'a>(res: &mut Option<Cow<'a, str>>, text: Cow<'a, str>) {
+    match res {
+        Some(prev) => prev.to_mut().push_str(&text),
+        None => *res = Some(text),
     }
 }
 
@@ -315,6 +1526,36 @@ fn read_text() -> XmlResult<()> {
     assert_eq!(reader.read_text("parent")?, "&quot;&apos;&lt;&gt;&amp;");
     assert!(reader.next().is_none());
 
+    reader = XmlReader::new("<parent>foo<![CDATA[bar]]>baz</parent>");
+
+    assert!(reader.next().is_some()); // "<parent"
+    assert_eq!(reader.read_text("parent")?, "foobarbaz");
+    assert!(reader.next().is_none());
+
+    reader = XmlReader::new("<parent><![CDATA[foo]]><![CDATA[bar]]></parent>");
+
+    assert!(reader.next().is_some()); // "<parent"
+    assert_eq!(reader.read_text("parent")?, "foobar");
+    assert!(reader.next().is_none());
+
+    reader = XmlReader::new("<parent>text</parent>");
+
+    assert!(reader.next().is_some()); // "<parent"
+    assert!(matches!(reader.read_text("parent")?, Cow::Borrowed("text")));
+    assert!(reader.next().is_none());
+
+    reader = XmlReader::new("<parent>AT&amp;T</parent>");
+
+    assert!(reader.next().is_some()); // "<parent"
+    assert_eq!(reader.read_text("parent")?, "AT&T");
+    assert!(reader.next().is_none());
+
+    reader = XmlReader::new("<parent>AT<!-- comment -->&amp;<?pi?>T</parent>");
+
+    assert!(reader.next().is_some()); // "<parent"
+    assert_eq!(reader.read_text("parent")?, "AT&T");
+    assert!(reader.next().is_none());
+
     Ok(())
 }
 
@@ -405,3 +1646,148 @@ fn read_to_end() -> XmlResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn read_inner() -> XmlResult<()> {
+    let mut reader = XmlReader::new("<parent><child a=\"1\"><child/>text</child></parent>");
+
+    assert!(reader.next().is_some()); // "<parent"
+    assert!(reader.next().is_some()); // ">"
+    assert!(reader.next().is_some()); // "<child"
+    assert_eq!(reader.read_inner("child")?, "<child/>text");
+    assert!(reader.next().is_some()); // "</parent>"
+    assert!(reader.next().is_none());
+
+    reader = XmlReader::new("<parent><child/></parent>");
+
+    assert!(reader.next().is_some()); // "<parent"
+    assert!(reader.next().is_some()); // ">"
+    assert!(reader.next().is_some()); // "<child"
+    assert_eq!(reader.read_inner("child")?, "");
+    assert!(reader.next().is_some()); // "</parent>"
+    assert!(reader.next().is_none());
+
+    reader = XmlReader::new("<parent><child><a></a></b></parent>");
+
+    assert!(reader.next().is_some()); // "<parent"
+    assert!(reader.next().is_some()); // ">"
+    assert!(reader.next().is_some()); // "<child"
+    assert!(reader.read_inner("child").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn find_attribute() -> XmlResult<()> {
+    let mut reader = XmlReader::new(r#"<parent attr="AT&amp;T" raw="text"/>"#);
+
+    assert!(reader.next().is_some()); // "<parent"
+    assert_eq!(
+        reader.find_attribute()?,
+        Some(("attr", Cow::Owned("AT&T".into())))
+    );
+    assert!(matches!(
+        reader.find_attribute()?,
+        Some(("raw", Cow::Borrowed("text")))
+    ));
+    assert_eq!(reader.find_attribute()?, None);
+
+    Ok(())
+}
+
+#[test]
+fn name_matcher() -> XmlResult<()> {
+    use crate::xml_name_matcher::{Exact, IgnoreAsciiCase};
+
+    let mut reader = XmlReader::new(r#"<w:p w:val="1"><w:r/></w:p>"#);
+
+    assert_eq!(reader.read_till_element_start("p")?, Some("w:p"));
+    assert_eq!(
+        reader.find_attribute()?,
+        Some(("w:val", Cow::Borrowed("1")))
+    );
+    assert_eq!(reader.find_attribute()?, None);
+    assert!(reader.next().is_some()); // ">"
+    assert_eq!(reader.find_element_start(Some("p"))?, Some("w:r"));
+    assert_eq!(reader.resolve_name("w:r", &["p", "r"]), "r");
+    assert_eq!(reader.resolve_name("w:s", &["p", "r"]), "w:s");
+    assert!(reader.next().is_some()); // "<w:r"
+    reader.read_to_end("w:r")?;
+    assert_eq!(reader.find_element_start(Some("w:p"))?, None);
+    assert!(reader.next().is_none());
+
+    assert_eq!(reader.resolve_name("w:s", &["*", "s"]), "s");
+    assert_eq!(reader.resolve_name("w:t", &["*", "s"]), "*");
+
+    reader = XmlReader::new("<w:p/>").with_name_matcher(Exact);
+
+    assert_eq!(reader.resolve_name("w:p", &["p"]), "w:p");
+    assert_eq!(reader.resolve_name("w:p", &["w:p"]), "w:p");
+
+    reader = XmlReader::new("<Parent>text</PARENT>").with_name_matcher(IgnoreAsciiCase);
+
+    reader.read_till_element_start("parent")?;
+    assert_eq!(reader.read_text("parent")?, "text");
+    assert!(reader.next().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn state() -> XmlResult<()> {
+    let xml = r#"<?xml version="1.0"?><feed><item id="1"/><item>2</item></feed>"#;
+
+    let mut reader = XmlReader::new(xml);
+
+    assert_eq!(reader.state(), Some(XmlReaderState::default()));
+    assert_eq!(reader.find_element_start(None)?, Some("feed"));
+    assert!(reader.next().is_some()); // "<feed"
+    assert_eq!(reader.state(), None);
+    assert!(reader.next().is_some()); // ">"
+    assert_eq!(reader.find_element_start(Some("feed"))?, Some("item"));
+    assert!(reader.next().is_some()); // "<item"
+    assert_eq!(reader.state(), None);
+    reader.read_to_end("item")?;
+
+    let state = reader.state().unwrap();
+    assert_eq!(
+        state,
+        XmlReaderState {
+            offset: 41,
+            depth: 1,
+            line: 1,
+            column: 42
+        }
+    );
+    assert_eq!(state.to_string().parse::<XmlReaderState>()?, state);
+    assert!("41:1".parse::<XmlReaderState>().is_err());
+
+    // restart from the rest of the document
+    let mut reader = XmlReader::resume(&xml[state.offset..], state);
+
+    assert_eq!(reader.find_element_start(Some("feed"))?, Some("item"));
+    assert!(reader.next().is_some()); // "<item"
+    assert_eq!(reader.read_text("item")?, "2");
+    assert_eq!(
+        reader.state(),
+        Some(XmlReaderState {
+            offset: 55,
+            depth: 1,
+            line: 1,
+            column: 56
+        })
+    );
+    assert_eq!(reader.find_element_start(Some("feed"))?, None);
+    assert_eq!(
+        reader.state(),
+        Some(XmlReaderState {
+            offset: xml.len(),
+            depth: 0,
+            line: 1,
+            column: xml.len() as u32 + 1
+        })
+    );
+    assert!(reader.next().is_none());
+
+    Ok(())
+}
@@ -0,0 +1,161 @@
+use std::io::Write;
+use std::sync::{Arc, RwLock};
+
+use crate::{ErrorDetail, XmlError, XmlReadOwned, XmlReader, XmlResult, XmlWrite, XmlWriter};
+
+type ReadFn<T> = Box<dyn Fn(&mut XmlReader<'_>) -> XmlResult<Box<T>> + Send + Sync>;
+
+/// Table of the types elements are read as, by tag, for children whose
+/// types are only known at runtime, e.g. `Vec<Box<dyn Node>>` fields.
+///
+/// Types are registered with a function boxing them into the trait
+/// object, and `impl_registry!` implements `XmlRead` and `XmlWrite` for
+/// the box using the registry. The trait should have `DynXmlWrite` as a
+/// supertrait, so every node can be written back.
+///
+/// ```rust
+/// use hard_xml::registry::{DynXmlWrite, Registry};
+/// use hard_xml::{impl_registry, XmlRead, XmlWrite};
+///
+/// trait Node: DynXmlWrite + std::fmt::Debug {}
+///
+/// static NODES: Registry<dyn Node> = Registry::new();
+///
+/// impl_registry!(dyn Node, NODES);
+///
+/// #[derive(XmlWrite, XmlRead, Debug)]
+/// #[xml(tag = "image")]
+/// struct Image {
+///     #[xml(attr = "src")]
+///     src: String,
+/// }
+///
+/// impl Node for Image {}
+///
+/// #[derive(XmlWrite, XmlRead, Debug)]
+/// #[xml(tag = "page")]
+/// struct Page {
+///     #[xml(child = "*")]
+///     nodes: Vec<Box<dyn Node>>,
+/// }
+///
+/// NODES.register("image", |image: Image| Box::new(image));
+///
+/// let xml = r#"<page><image src="a.png"/></page>"#;
+/// let page = Page::from_str(xml)?;
+///
+/// assert_eq!(page.nodes.len(), 1);
+/// assert_eq!(page.to_string()?, xml);
+/// # Ok::<(), hard_xml::XmlError>(())
+/// ```
+pub struct Registry<T: ?Sized + 'static> {
+    // (tag, reader)
+    readers: RwLock<Vec<(String, Arc<ReadFn<T>>)>>,
+}
+
+impl<T: ?Sized + 'static> Registry<T> {
+    pub const fn new() -> Self {
+        Registry {
+            readers: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Reads elements with the given tag as `U`, converted with `into`.
+    ///
+    /// Types registered later take precedence.
+    pub fn register<U>(&self, tag: impl Into<String>, into: fn(U) -> Box<T>)
+    where
+        U: XmlReadOwned + 'static,
+    {
+        let read: ReadFn<T> = Box::new(move |reader| U::from_reader(reader).map(into));
+        self.readers
+            .write()
+            .unwrap()
+            .push((tag.into(), Arc::new(read)));
+    }
+
+    /// Returns whether a type is registered for the tag.
+    pub fn contains(&self, tag: &str) -> bool {
+        self.readers
+            .read()
+            .unwrap()
+            .iter()
+            .any(|(expected, _)| expected == tag)
+    }
+
+    /// Reads the next element with the type registered for its tag.
+    pub fn read(&self, reader: &mut XmlReader<'_>) -> XmlResult<Box<T>> {
+        let tag = reader.find_element_start(None)?.ok_or_else(|| {
+            XmlError::new(ErrorDetail::UnexpectedEof).with_position(reader.position())
+        })?;
+
+        // the lock is released before reading, so types can be registered
+        // while elements are read, e.g. by the readers themselves
+        let read = self
+            .readers
+            .read()
+            .unwrap()
+            .iter()
+            .rev()
+            .find(|(expected, _)| reader.name_matches(expected, tag))
+            .map(|(_, read)| read.clone());
+
+        match read {
+            Some(read) => read(reader),
+            None => Err(XmlError::new(ErrorDetail::UnknownVariant {
+                value: tag.to_owned(),
+            })
+            .with_context(std::any::type_name::<T>(), None)
+            .with_position(reader.position())),
+        }
+    }
+}
+
+impl<T: ?Sized + 'static> Default for Registry<T> {
+    fn default() -> Self {
+        Registry::new()
+    }
+}
+
+/// Object safe version of `XmlWrite`, implemented for every `XmlWrite`
+/// type.
+pub trait DynXmlWrite {
+    fn to_dyn_writer(&self, writer: &mut XmlWriter<&mut dyn Write>) -> XmlResult<()>;
+}
+
+impl<T: XmlWrite> DynXmlWrite for T {
+    fn to_dyn_writer(&self, writer: &mut XmlWriter<&mut dyn Write>) -> XmlResult<()> {
+        self.to_writer(writer)
+    }
+}
+
+/// Writes a trait object with `DynXmlWrite` to any writer.
+pub fn write_dyn<T, W>(value: &T, writer: &mut XmlWriter<W>) -> XmlResult<()>
+where
+    T: DynXmlWrite + ?Sized,
+    W: Write,
+{
+    writer.with_dyn(|writer| value.to_dyn_writer(writer))
+}
+
+/// Implements `XmlRead` and `XmlWrite` for `Box<$ty>`, reading it with
+/// the `Registry` given as the second argument.
+#[macro_export]
+macro_rules! impl_registry {
+    ($ty:ty, $registry:expr) => {
+        impl<'__input> $crate::XmlRead<'__input> for Box<$ty> {
+            fn from_reader(reader: &mut $crate::XmlReader<'__input>) -> $crate::XmlResult<Self> {
+                $registry.read(reader)
+            }
+        }
+
+        impl $crate::XmlWrite for Box<$ty> {
+            fn to_writer<W: std::io::Write>(
+                &self,
+                writer: &mut $crate::XmlWriter<W>,
+            ) -> $crate::XmlResult<()> {
+                $crate::registry::write_dyn(&**self, writer)
+            }
+        }
+    };
+}
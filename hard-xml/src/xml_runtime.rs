@@ -0,0 +1,218 @@
+use std::borrow::Cow;
+use std::error::Error;
+
+use xmlparser::{ElementEnd, Token};
+
+use crate::{ErrorDetail, Position, XmlError, XmlRead, XmlReader, XmlResult};
+
+// Steps shared by the derived implementations, which call these instead of
+// inlining the same code into each of them.
+
+/// Skips the element whose start tag is the next token, with its content.
+#[doc(hidden)]
+pub fn skip_element(reader: &mut XmlReader, tag: &str) -> XmlResult<()> {
+    // skip the start tag
+    reader.next();
+    reader.read_to_end(tag)
+}
+
+/// Reads the text of the element whose start tag is the next token,
+/// ignoring its attributes.
+#[doc(hidden)]
+pub fn read_element_text<'a>(reader: &mut XmlReader<'a>, tag: &str) -> XmlResult<Cow<'a, str>> {
+    // skip the start tag
+    reader.next();
+    reader.read_text(tag)
+}
+
+/// Skips the start tag and the attributes of the element whose start tag is
+/// the next token, returning whether it has content to read.
+#[doc(hidden)]
+pub fn skip_start_tag(reader: &mut XmlReader) -> XmlResult<bool> {
+    reader.next();
+    while reader.find_attribute()?.is_some() {}
+    has_content(reader)
+}
+
+/// Consumes the end of a start tag whose attributes were read, returning
+/// whether the element has content to read.
+#[doc(hidden)]
+pub fn has_content(reader: &mut XmlReader) -> XmlResult<bool> {
+    match reader.next().ok_or_else(|| {
+        XmlError::new(ErrorDetail::UnexpectedEof).with_position(reader.position())
+    })?? {
+        Token::ElementEnd {
+            end: ElementEnd::Empty,
+            ..
+        } => Ok(false),
+        Token::ElementEnd {
+            end: ElementEnd::Open,
+            ..
+        } => Ok(true),
+        token => Err(XmlError::new(ErrorDetail::UnexpectedToken {
+            token: format!("{:?}", token),
+        })
+        .with_position(reader.position())),
+    }
+}
+
+/// Calls `read` with the name and the value of every attribute of the
+/// element whose start tag was read.
+#[doc(hidden)]
+pub fn read_attributes<'a>(
+    reader: &mut XmlReader<'a>,
+    mut read: impl FnMut(&mut XmlReader<'a>, &'a str, Cow<'a, str>) -> XmlResult<()>,
+) -> XmlResult<()> {
+    while let Some((key, value)) = reader.find_attribute()? {
+        read(reader, key, value)?;
+    }
+    Ok(())
+}
+
+/// Calls `read` with the tag of every child of the element `end_tag`, whose
+/// start tag is the next token when `read` is called. `read` must consume
+/// the child.
+#[doc(hidden)]
+pub fn read_children<'a>(
+    reader: &mut XmlReader<'a>,
+    end_tag: &str,
+    mut read: impl FnMut(&mut XmlReader<'a>, &'a str) -> XmlResult<()>,
+) -> XmlResult<()> {
+    while let Some(tag) = reader.find_element_start(Some(end_tag))? {
+        read(reader, tag)?;
+    }
+    Ok(())
+}
+
+/// Reads a child element, or skips it and returns `None` if it can't be
+/// read and errors are collected.
+#[doc(hidden)]
+#[inline]
+pub fn read_child<'a, T: XmlRead<'a>>(reader: &mut XmlReader<'a>) -> XmlResult<Option<T>> {
+    let mark = reader.mark();
+    match T::from_reader(reader) {
+        Ok(value) => Ok(Some(value)),
+        Err(err) => {
+            reader.recover_element(err, mark)?;
+            Ok(None)
+        }
+    }
+}
+
+/// Returns the error of `field` of `name` missing from the element `tag`,
+/// where it's read from what `expected` describes.
+#[doc(hidden)]
+#[cold]
+pub fn missing_field(name: &str, field: &str, tag: &str, expected: &str) -> XmlError {
+    XmlError::new(ErrorDetail::MissingField {
+        tag: tag.to_owned(),
+        expected: expected.to_owned(),
+    })
+    .with_context(name, Some(field))
+}
+
+/// Returns the error of `value`, read into `field` of `name`, which can't
+/// be converted into `type_name`.
+///
+/// Conversion errors raised by hard-xml, like the ones of `hex_decode`, are
+/// completed instead of being wrapped again.
+#[doc(hidden)]
+#[cold]
+pub fn from_str_error<E: Into<Box<dyn Error + Send + Sync>>>(
+    name: &str,
+    field: &str,
+    value: &str,
+    type_name: &str,
+    err: E,
+) -> XmlError {
+    let source = match err.into().downcast::<XmlError>() {
+        Ok(err) => match err.into_detail() {
+            ErrorDetail::FromStr { source, .. } => source,
+            detail => return XmlError::new(detail).with_context(name, Some(field)),
+        },
+        Err(source) => source,
+    };
+    XmlError::new(ErrorDetail::FromStr {
+        value: value.to_owned(),
+        type_name: type_name.to_owned(),
+        source,
+    })
+    .with_context(name, Some(field))
+}
+
+/// Returns the error of `field` of `name` occurring again, after its
+/// first occurrence at `first`.
+#[doc(hidden)]
+#[cold]
+pub fn duplicate_field(name: &str, field: &str, first: Position) -> XmlError {
+    XmlError::new(ErrorDetail::DuplicateField {
+        first: Some(Box::new(first)),
+    })
+    .with_context(name, Some(field))
+}
+
+/// Checks that a repeated field occurs within its bounds.
+#[doc(hidden)]
+pub fn check_occurs(
+    name: &str,
+    field: &str,
+    count: usize,
+    min: usize,
+    max: Option<usize>,
+) -> XmlResult<()> {
+    if count < min || max.is_some_and(|max| count > max) {
+        return Err(
+            XmlError::new(ErrorDetail::UnexpectedOccurrences { count, min, max })
+                .with_context(name, Some(field)),
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_skip_element() {
+    let mut reader = XmlReader::new("<a><b x=\"1\"><b/></b><c>text</c></a>");
+
+    reader.next();
+    assert!(has_content(&mut reader).unwrap());
+    assert_eq!(reader.find_element_start(Some("a")).unwrap(), Some("b"));
+    skip_element(&mut reader, "b").unwrap();
+    assert_eq!(reader.find_element_start(Some("a")).unwrap(), Some("c"));
+    assert_eq!(read_element_text(&mut reader, "c").unwrap(), "text");
+    assert_eq!(reader.find_element_start(Some("a")).unwrap(), None);
+}
+
+#[test]
+fn test_read_children() {
+    let mut reader = XmlReader::new("<a x=\"1\" y=\"2\"><b/><c>text</c></a>");
+
+    reader.next();
+    let mut attrs = Vec::new();
+    read_attributes(&mut reader, |_, key, value| {
+        attrs.push((key, value));
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(attrs, [("x", "1".into()), ("y", "2".into())]);
+    assert!(has_content(&mut reader).unwrap());
+
+    let mut tags = Vec::new();
+    read_children(&mut reader, "a", |reader, tag| {
+        tags.push(tag);
+        skip_element(reader, tag)
+    })
+    .unwrap();
+    assert_eq!(tags, ["b", "c"]);
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn test_check_occurs() {
+    assert!(check_occurs("a", "b", 1, 1, Some(2)).is_ok());
+    assert!(check_occurs("a", "b", 3, 0, None).is_ok());
+    assert!(matches!(
+        check_occurs("a", "b", 0, 1, None).map_err(XmlError::into_detail),
+        Err(ErrorDetail::UnexpectedOccurrences { count: 0, .. })
+    ));
+    assert!(check_occurs("a", "b", 3, 1, Some(2)).is_err());
+}
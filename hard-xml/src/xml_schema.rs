@@ -0,0 +1,103 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Structural description of a type, used to validate documents
+/// before they are serialized.
+///
+/// It can be derived with `#[derive(XmlSchemaInfo)]` using the same
+/// `#[xml(...)]` attributes as `XmlRead` and `XmlWrite`.
+pub trait XmlSchemaInfo {
+    /// Returns every element this type can be read from or written as.
+    ///
+    /// Structs have exactly one element, enums have one for each tag
+    /// their variants accept.
+    fn elements() -> Vec<ElementInfo>;
+}
+
+macro_rules! impl_pointer {
+    ($($pointer:ident),*) => {$(
+        impl<T: XmlSchemaInfo> XmlSchemaInfo for $pointer<T> {
+            fn elements() -> Vec<ElementInfo> {
+                T::elements()
+            }
+        }
+    )*};
+}
+
+impl_pointer!(Box, Rc, Arc);
+
+#[derive(Debug, Clone)]
+pub struct ElementInfo {
+    /// Name of the struct or enum variant
+    pub name: &'static str,
+    pub tag: &'static str,
+    /// Other tags the element is read from
+    pub aliases: Vec<&'static str>,
+    pub fields: Vec<FieldInfo>,
+    /// Whether children must appear in the order of `fields`
+    pub ordered: bool,
+    /// Doc comment or `#[xml(doc = "")]` of the struct or enum variant
+    pub doc: Option<&'static str>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldInfo {
+    /// Name of the struct field
+    pub name: &'static str,
+    pub kind: FieldKind,
+    /// Whether the field can be absent from the document
+    pub optional: bool,
+    /// Whether the field can occur more than once
+    pub multiple: bool,
+    /// Minimum number of occurrences, like `minOccurs` in XSD
+    pub min_occurs: usize,
+    /// Maximum number of occurrences, if bounded, like `maxOccurs` in XSD
+    pub max_occurs: Option<usize>,
+    /// Doc comment or `#[xml(doc = "")]` of the field
+    pub doc: Option<&'static str>,
+}
+
+#[derive(Debug, Clone)]
+pub enum FieldKind {
+    Attribute {
+        name: &'static str,
+    },
+    Child {
+        tags: Vec<&'static str>,
+        elements: fn() -> Vec<ElementInfo>,
+        /// Element the children are nested in
+        wrapper: Option<&'static str>,
+    },
+    Text,
+    FlattenText {
+        tag: &'static str,
+    },
+    /// Entries of a map, read from `<tag key="" value=""/>` elements, or
+    /// from `<key>value</key>` elements if `tag` is `*`
+    Map {
+        tag: &'static str,
+        key: &'static str,
+        value: &'static str,
+    },
+    /// Attributes no other field is read from, with `#[xml(attrs)]`
+    AnyAttributes,
+    /// Child elements no other field is read from, with `#[xml(children)]`
+    AnyChildren,
+}
+
+impl FieldKind {
+    // describes what the field is read from in errors, like `child <title>`
+    pub(crate) fn expected(&self) -> String {
+        match self {
+            FieldKind::Attribute { name } => format!("attribute {:?}", name),
+            FieldKind::Child {
+                wrapper: Some(tag), ..
+            }
+            | FieldKind::FlattenText { tag }
+            | FieldKind::Map { tag, .. } => format!("child <{}>", tag),
+            FieldKind::Child { tags, .. } => format!("child <{}>", tags.join("> or <")),
+            FieldKind::Text => "text".to_owned(),
+            FieldKind::AnyAttributes | FieldKind::AnyChildren => String::new(),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use std::borrow::Cow;
+
+use crate::{ErrorDetail, XmlError, XmlResult};
+
+/// String field types, built from the unescaped text.
+pub trait FromCowStr<'a>: Sized {
+    fn from_cow_str(text: Cow<'a, str>) -> XmlResult<Self>;
+}
+
+impl<'a> FromCowStr<'a> for Cow<'a, str> {
+    fn from_cow_str(text: Cow<'a, str>) -> XmlResult<Self> {
+        Ok(text)
+    }
+}
+
+// only text which is borrowed from the document, i.e. which didn't need
+// unescaping, can be read into `&str`
+impl<'a> FromCowStr<'a> for &'a str {
+    fn from_cow_str(text: Cow<'a, str>) -> XmlResult<Self> {
+        match text {
+            Cow::Borrowed(text) => Ok(text),
+            Cow::Owned(text) => Err(XmlError::new(ErrorDetail::Unborrowable { value: text })),
+        }
+    }
+}
+
+pub fn from_cow_str<'a, T: FromCowStr<'a>>(text: Cow<'a, str>) -> XmlResult<T> {
+    T::from_cow_str(text)
+}
+
+/// Like `from_cow_str`, but copies the text, so it doesn't have to outlive
+/// the field.
+pub fn from_cow_str_owned<'a, T: FromCowStr<'a>>(text: Cow<'_, str>) -> XmlResult<T> {
+    T::from_cow_str(Cow::Owned(text.into_owned()))
+}
+
+#[test]
+fn test_from_cow_str() {
+    assert_eq!(from_cow_str::<&str>(Cow::Borrowed("a")).unwrap(), "a");
+    assert_eq!(
+        from_cow_str::<Cow<str>>(Cow::Owned("&".into())).unwrap(),
+        "&"
+    );
+    assert!(matches!(
+        from_cow_str::<&str>(Cow::Owned("&".into())).map_err(XmlError::into_detail),
+        Err(ErrorDetail::Unborrowable { value }) if value == "&"
+    ));
+}
@@ -0,0 +1,273 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use xmlparser::{ElementEnd, StrSpan, Token};
+
+/// Transformation applied by `XmlReader` to every token before it is
+/// handed to the reading code.
+///
+/// Returning `None` drops the token. Replaced tokens should keep the
+/// `span` of the original one, and names which aren't taken from the
+/// document should be given as a `local` name with an empty `prefix`.
+///
+/// Filters which keep state between tokens must implement `save` and
+/// `restore`: tokens read after a [checkpoint](crate::XmlReader::checkpoint)
+/// are filtered again after rewinding to it, e.g. when a variant of an
+/// `untagged` enum doesn't match.
+///
+/// It's implemented for closures, so simple filters don't need a type:
+///
+/// ```rust
+/// use hard_xml::XmlReader;
+/// use hard_xml::xmlparser::Token;
+///
+/// let mut reader = XmlReader::new(r#"<a><!-- comment --></a>"#)
+///     .with_filter(|token| match token {
+///         Token::Comment { .. } => None,
+///         token => Some(token),
+///     });
+/// ```
+pub trait TokenFilter<'a> {
+    fn filter(&mut self, token: Token<'a>) -> Option<Token<'a>>;
+
+    /// Returns the state of the filter, which is passed to `restore` when
+    /// the reader rewinds to a checkpoint taken at that time.
+    fn save(&self) -> Option<Arc<dyn Any + Send + Sync>> {
+        None
+    }
+
+    /// Goes back to a state returned by `save`.
+    fn restore(&mut self, _state: &(dyn Any + Send + Sync)) {}
+}
+
+impl<'a, F> TokenFilter<'a> for F
+where
+    F: FnMut(Token<'a>) -> Option<Token<'a>>,
+{
+    #[inline]
+    fn filter(&mut self, token: Token<'a>) -> Option<Token<'a>> {
+        self(token)
+    }
+}
+
+fn name<'a>(prefix: StrSpan<'a>, local: StrSpan<'a>) -> (&'a str, &'a str) {
+    (prefix.as_str(), local.as_str())
+}
+
+// only the local part is compared unless `expected` has a prefix
+fn matches(expected: &str, (prefix, local): (&str, &str)) -> bool {
+    match expected.split_once(':') {
+        Some((p, l)) => p == prefix && l == local,
+        None => expected == local,
+    }
+}
+
+/// Removes the start and end tags of an element, keeping its content,
+/// e.g. to strip a legacy wrapper element.
+#[derive(Debug, Clone)]
+pub struct UnwrapElement {
+    tag: String,
+    // whether every element currently open is unwrapped
+    stack: Vec<bool>,
+    in_start_tag: Option<bool>,
+}
+
+impl UnwrapElement {
+    pub fn new(tag: impl Into<String>) -> Self {
+        UnwrapElement {
+            tag: tag.into(),
+            stack: Vec::new(),
+            in_start_tag: None,
+        }
+    }
+}
+
+impl<'a> TokenFilter<'a> for UnwrapElement {
+    fn filter(&mut self, token: Token<'a>) -> Option<Token<'a>> {
+        let unwrapped = match token {
+            Token::ElementStart { prefix, local, .. } => {
+                let unwrapped = matches(&self.tag, name(prefix, local));
+                self.in_start_tag = Some(unwrapped);
+                unwrapped
+            }
+            Token::Attribute { .. } => self.in_start_tag.unwrap_or(false),
+            Token::ElementEnd { end, .. } => match end {
+                ElementEnd::Open => {
+                    let unwrapped = self.in_start_tag.take().unwrap_or(false);
+                    self.stack.push(unwrapped);
+                    unwrapped
+                }
+                ElementEnd::Empty => self.in_start_tag.take().unwrap_or(false),
+                ElementEnd::Close(..) => self.stack.pop().unwrap_or(false),
+            },
+            _ => false,
+        };
+
+        if unwrapped {
+            None
+        } else {
+            Some(token)
+        }
+    }
+
+    fn save(&self) -> Option<Arc<dyn Any + Send + Sync>> {
+        Some(Arc::new((self.stack.clone(), self.in_start_tag)))
+    }
+
+    fn restore(&mut self, state: &(dyn Any + Send + Sync)) {
+        if let Some((stack, in_start_tag)) = state.downcast_ref::<(Vec<bool>, Option<bool>)>() {
+            self.stack.clone_from(stack);
+            self.in_start_tag = *in_start_tag;
+        }
+    }
+}
+
+/// Drops attributes with the given name.
+#[derive(Debug, Clone)]
+pub struct DropAttribute {
+    name: String,
+}
+
+impl DropAttribute {
+    pub fn new(name: impl Into<String>) -> Self {
+        DropAttribute { name: name.into() }
+    }
+}
+
+impl<'a> TokenFilter<'a> for DropAttribute {
+    fn filter(&mut self, token: Token<'a>) -> Option<Token<'a>> {
+        match token {
+            Token::Attribute { prefix, local, .. } if matches(&self.name, name(prefix, local)) => {
+                None
+            }
+            token => Some(token),
+        }
+    }
+}
+
+/// Renames elements and attributes, e.g. to move them into another
+/// namespace prefix.
+///
+/// ```rust
+/// use hard_xml::filter::Rename;
+/// use hard_xml::XmlReader;
+///
+/// let mut reader = XmlReader::new(r#"<old:item old:id="1"/>"#)
+///     .with_filter(Rename::new("old:item", "new:item").rename("old:id", "new:id"));
+///
+/// assert_eq!(reader.find_element_start(None)?, Some("new:item"));
+/// reader.next();
+/// assert_eq!(reader.find_attribute()?.unwrap().0, "new:id");
+/// # Ok::<(), hard_xml::XmlError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rename<'a> {
+    // (from, to)
+    names: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Rename<'a> {
+    pub fn new(from: &'a str, to: &'a str) -> Self {
+        Rename {
+            names: vec![(from, to)],
+        }
+    }
+
+    pub fn rename(mut self, from: &'a str, to: &'a str) -> Self {
+        self.names.push((from, to));
+        self
+    }
+
+    fn find(&self, prefix: StrSpan<'a>, local: StrSpan<'a>) -> Option<(StrSpan<'a>, StrSpan<'a>)> {
+        let (_, to) = self
+            .names
+            .iter()
+            .find(|(from, _)| matches(from, name(prefix, local)))?;
+        Some((StrSpan::from(""), StrSpan::from(*to)))
+    }
+}
+
+impl<'a> TokenFilter<'a> for Rename<'a> {
+    fn filter(&mut self, token: Token<'a>) -> Option<Token<'a>> {
+        let token = match token {
+            Token::ElementStart {
+                prefix,
+                local,
+                span,
+            } => match self.find(prefix, local) {
+                Some((prefix, local)) => Token::ElementStart {
+                    prefix,
+                    local,
+                    span,
+                },
+                None => token,
+            },
+            Token::ElementEnd {
+                end: ElementEnd::Close(prefix, local),
+                span,
+            } => match self.find(prefix, local) {
+                Some((prefix, local)) => Token::ElementEnd {
+                    end: ElementEnd::Close(prefix, local),
+                    span,
+                },
+                None => token,
+            },
+            Token::Attribute {
+                prefix,
+                local,
+                value,
+                span,
+            } => match self.find(prefix, local) {
+                Some((prefix, local)) => Token::Attribute {
+                    prefix,
+                    local,
+                    value,
+                    span,
+                },
+                None => token,
+            },
+            token => token,
+        };
+        Some(token)
+    }
+}
+
+#[test]
+fn test_filters() {
+    use xmlparser::Tokenizer;
+
+    fn apply<'a>(text: &'a str, filter: &mut dyn TokenFilter<'a>) -> Vec<String> {
+        Tokenizer::from(text)
+            .filter_map(|token| filter.filter(token.unwrap()))
+            .map(|token| match token {
+                Token::ElementStart { prefix, local, .. } => format!("<{}{}", prefix, local),
+                Token::Attribute { prefix, local, .. } => format!("@{}{}", prefix, local),
+                Token::ElementEnd { end, .. } => match end {
+                    ElementEnd::Open => ">".to_owned(),
+                    ElementEnd::Empty => "/>".to_owned(),
+                    ElementEnd::Close(prefix, local) => format!("</{}{}>", prefix, local),
+                },
+                Token::Text { text } => text.to_string(),
+                token => format!("{:?}", token),
+            })
+            .collect()
+    }
+
+    assert_eq!(
+        apply(
+            "<a><w x=\"1\"><b/><w/></w></a>",
+            &mut UnwrapElement::new("w")
+        ),
+        vec!["<a", ">", "<b", "/>", "</a>"]
+    );
+
+    assert_eq!(
+        apply("<a x=\"1\" y=\"2\"/>", &mut DropAttribute::new("x")),
+        vec!["<a", "@y", "/>"]
+    );
+
+    assert_eq!(
+        apply("<a:b><b/></a:b>", &mut Rename::new("a:b", "c:b")),
+        vec!["<c:b", ">", "<b", "/>", "</c:b>"]
+    );
+}
@@ -0,0 +1,268 @@
+use std::borrow::Cow;
+use std::io::Write;
+
+use xmlparser::{ElementEnd, Token};
+
+use crate::xml_reader::qualified_name;
+use crate::xml_unescape::xml_unescape;
+use crate::{ErrorDetail, XmlError, XmlRead, XmlReader, XmlResult, XmlWrite, XmlWriter};
+
+/// Untyped element, read and written like any other type
+///
+/// It borrows from the source text whenever possible, so small sections of
+/// a document can be materialized cheaply while the rest is streamed,
+/// e.g. with [`Subtrees`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Element<'a> {
+    /// Qualified name, e.g. `w:p`
+    pub name: Cow<'a, str>,
+    /// Attributes with unescaped values, in document order
+    pub attributes: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+    pub children: Vec<Node<'a>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node<'a> {
+    Element(Element<'a>),
+    /// Unescaped text
+    Text(Cow<'a, str>),
+    Cdata(Cow<'a, str>),
+    Comment(Cow<'a, str>),
+}
+
+impl<'a> Element<'a> {
+    pub fn new(name: impl Into<Cow<'a, str>>) -> Self {
+        Element {
+            name: name.into(),
+            attributes: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Returns the value of the first attribute named `name`.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| &**value)
+    }
+
+    /// Returns the child elements named `name`.
+    pub fn children<'e>(&'e self, name: &'e str) -> impl Iterator<Item = &'e Element<'a>> + 'e {
+        self.children.iter().filter_map(move |node| match node {
+            Node::Element(element) if element.name == name => Some(element),
+            _ => None,
+        })
+    }
+
+    /// Returns the concatenated text and CDATA of the element, excluding
+    /// its descendants.
+    pub fn text(&self) -> Cow<'_, str> {
+        let mut res: Option<Cow<str>> = None;
+        for node in &self.children {
+            if let Node::Text(text) | Node::Cdata(text) = node {
+                match &mut res {
+                    Some(prev) => prev.to_mut().push_str(text),
+                    None => res = Some(Cow::Borrowed(text)),
+                }
+            }
+        }
+        res.unwrap_or_default()
+    }
+
+    pub fn into_owned(self) -> Element<'static> {
+        Element {
+            name: Cow::Owned(self.name.into_owned()),
+            attributes: self
+                .attributes
+                .into_iter()
+                .map(|(key, value)| (Cow::Owned(key.into_owned()), Cow::Owned(value.into_owned())))
+                .collect(),
+            children: self.children.into_iter().map(Node::into_owned).collect(),
+        }
+    }
+}
+
+impl Node<'_> {
+    pub fn into_owned(self) -> Node<'static> {
+        match self {
+            Node::Element(element) => Node::Element(element.into_owned()),
+            Node::Text(text) => Node::Text(Cow::Owned(text.into_owned())),
+            Node::Cdata(text) => Node::Cdata(Cow::Owned(text.into_owned())),
+            Node::Comment(text) => Node::Comment(Cow::Owned(text.into_owned())),
+        }
+    }
+}
+
+impl<'i: 'a, 'a> XmlRead<'i> for Element<'a> {
+    fn from_reader(reader: &mut XmlReader<'i>) -> XmlResult<Self> {
+        let name = reader.find_element_start(None)?.ok_or_else(|| {
+            XmlError::new(ErrorDetail::UnexpectedEof).with_position(reader.position())
+        })?;
+        // the start tag is read like the one of any element, within the
+        // depth limit of the reader
+        reader.read_till_element_start(name)?;
+
+        let mut element = Element::new(name);
+
+        while let Some((key, value)) = reader.find_attribute()? {
+            element.attributes.push((Cow::Borrowed(key), value));
+        }
+
+        if let Token::ElementEnd {
+            end: ElementEnd::Empty,
+            ..
+        } = reader.next().ok_or_else(|| {
+            XmlError::new(ErrorDetail::UnexpectedEof).with_position(reader.position())
+        })?? {
+            return Ok(element);
+        }
+
+        loop {
+            if let Some(Ok(Token::ElementStart { .. })) = reader.peek() {
+                let child = Element::from_reader(reader)?;
+                element.children.push(Node::Element(child));
+                continue;
+            }
+
+            match reader.next().ok_or_else(|| {
+                XmlError::new(ErrorDetail::UnexpectedEof).with_position(reader.position())
+            })?? {
+                Token::Text { text } => {
+                    let text = xml_unescape(text.as_str())?;
+                    element.children.push(Node::Text(text));
+                }
+                Token::Cdata { text, .. } => {
+                    element.children.push(Node::Cdata(text.as_str().into()));
+                }
+                Token::Comment { text, .. } => {
+                    element.children.push(Node::Comment(text.as_str().into()));
+                }
+                Token::ElementEnd {
+                    end: ElementEnd::Close(prefix, local),
+                    ..
+                } => {
+                    let (text, _) = reader.source();
+                    let tag = qualified_name(text, prefix, local);
+                    if reader.name_matches(name, tag) {
+                        return Ok(element);
+                    } else {
+                        return Err(XmlError::new(ErrorDetail::TagMismatch {
+                            expected: name.to_owned(),
+                            found: tag.to_owned(),
+                        })
+                        .with_position(reader.position()));
+                    }
+                }
+                // processing instructions aren't kept
+                _ => (),
+            }
+        }
+    }
+}
+
+impl XmlWrite for Element<'_> {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        writer.write_element_start(&self.name)?;
+
+        for (key, value) in &self.attributes {
+            writer.write_attribute(key, value)?;
+        }
+
+        if self.children.is_empty() {
+            writer.write_element_end_empty()?;
+            return Ok(());
+        }
+
+        writer.write_element_end_open()?;
+
+        for node in &self.children {
+            match node {
+                Node::Element(element) => element.to_writer(writer)?,
+                Node::Text(text) => writer.write_text(text)?,
+                Node::Cdata(text) => writer.write_cdata_text(text)?,
+                Node::Comment(text) => writer.write_comment(text)?,
+            }
+        }
+
+        writer.write_element_end_close(&self.name)?;
+
+        Ok(())
+    }
+}
+
+/// Iterator materializing every element named `tag` into an [`Element`],
+/// while skipping the rest of the document token by token.
+///
+/// Nested elements named `tag` are part of the outer element.
+///
+/// ```rust
+/// use hard_xml::tree::Subtrees;
+/// use hard_xml::{XmlReader, XmlWrite};
+///
+/// let mut reader = XmlReader::new(
+///     r#"<feed><meta>...</meta><item id="1"/><group><item id="2">text</item></group></feed>"#,
+/// );
+///
+/// let items = Subtrees::new(&mut reader, "item").collect::<Result<Vec<_>, _>>()?;
+///
+/// assert_eq!(items.len(), 2);
+/// assert_eq!(items[0].attribute("id"), Some("1"));
+/// assert_eq!(items[1].to_string()?, r#"<item id="2">text</item>"#);
+/// # Ok::<(), hard_xml::XmlError>(())
+/// ```
+pub struct Subtrees<'r, 'a> {
+    reader: &'r mut XmlReader<'a>,
+    tag: String,
+}
+
+impl<'r, 'a> Subtrees<'r, 'a> {
+    pub fn new(reader: &'r mut XmlReader<'a>, tag: impl Into<String>) -> Self {
+        Subtrees {
+            reader,
+            tag: tag.into(),
+        }
+    }
+}
+
+impl<'a> Iterator for Subtrees<'_, 'a> {
+    type Item = XmlResult<Element<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let token = match self.reader.peek()? {
+                Ok(token) => *token,
+                Err(_) => return Some(Err(self.reader.next()?.unwrap_err().into())),
+            };
+            if let Token::ElementStart { prefix, local, .. } = token {
+                let (text, _) = self.reader.source();
+                if self
+                    .reader
+                    .name_matches(&self.tag, qualified_name(text, prefix, local))
+                {
+                    return Some(Element::from_reader(self.reader));
+                }
+            }
+            self.reader.next();
+        }
+    }
+}
+
+#[test]
+fn test_element() -> XmlResult<()> {
+    let xml = r#"<w:p a="1" b="&amp;"><w:r>foo<!--c--><![CDATA[<bar>]]></w:r><w:r/>baz</w:p>"#;
+
+    let element = Element::from_str(xml)?;
+
+    assert_eq!(element.name, "w:p");
+    assert_eq!(element.attribute("b"), Some("&"));
+    assert_eq!(element.children("w:r").count(), 2);
+    assert_eq!(element.children("w:r").next().unwrap().text(), "foo<bar>");
+    assert_eq!(element.text(), "baz");
+    assert_eq!(element.to_string()?, xml);
+    assert_eq!(element.clone().into_owned(), element);
+
+    assert!(Element::from_str("<a><b></a>").is_err());
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ use memchr::memchr;
 use std::borrow::Cow;
 use std::char;
 
-use crate::{XmlError, XmlResult};
+use crate::{ErrorDetail, XmlError, XmlResult};
 
 pub fn xml_unescape<'a>(raw: &'a str) -> XmlResult<Cow<'a, str>> {
     let bytes = raw.as_bytes();
@@ -19,9 +19,9 @@ pub fn xml_unescape<'a>(raw: &'a str) -> XmlResult<Cow<'a, str>> {
 
             pos += i + 1;
         } else {
-            return Err(XmlError::UnterminatedEntity {
+            return Err(XmlError::new(ErrorDetail::UnterminatedEntity {
                 entity: String::from(&raw[pos - 1..]),
-            });
+            }));
         }
 
         while let Some(i) = memchr(b'&', &bytes[pos..]) {
@@ -34,9 +34,9 @@ pub fn xml_unescape<'a>(raw: &'a str) -> XmlResult<Cow<'a, str>> {
 
                 pos += i + 1;
             } else {
-                return Err(XmlError::UnterminatedEntity {
+                return Err(XmlError::new(ErrorDetail::UnterminatedEntity {
                     entity: String::from(&raw[pos - 1..]),
-                });
+                }));
             }
         }
 
@@ -56,19 +56,19 @@ fn recognize(entity: &str, result: &mut String) -> XmlResult<()> {
         "lt" => result.push('<'),
         "amp" => result.push('&'),
         _ => {
-            let val = if entity.starts_with("#x") {
-                u32::from_str_radix(&entity[2..], 16).ok()
-            } else if entity.starts_with('#') {
-                u32::from_str_radix(&entity[1..], 10).ok()
+            let val = if let Some(hex) = entity.strip_prefix("#x") {
+                u32::from_str_radix(hex, 16).ok()
+            } else if let Some(dec) = entity.strip_prefix('#') {
+                dec.parse::<u32>().ok()
             } else {
                 None
             };
             match val.and_then(char::from_u32) {
                 Some(c) => result.push(c),
                 None => {
-                    return Err(XmlError::UnrecognizedSymbol {
+                    return Err(XmlError::new(ErrorDetail::UnrecognizedSymbol {
                         symbol: String::from(entity),
-                    })
+                    }))
                 }
             }
         }
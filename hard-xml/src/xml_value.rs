@@ -0,0 +1,373 @@
+use std::error::Error;
+use std::fmt;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Values whose lexical form in XML differs from their `FromStr` and
+/// `Display` implementations, like `xs:dateTime`.
+///
+/// Attribute and text fields of these types are read and written with it
+/// instead of `FromStr` and `Display`. It's implemented for `f32` and `f64`,
+/// for `Rc<str>` and `Arc<str>`, for `std::time::Duration`, for `Xs` with the
+/// `chrono` feature, for the types of `rust_decimal`, `time`, `url` and `uuid`
+/// with the features of the same name, and can be implemented for others.
+///
+/// ```rust
+/// use std::fmt;
+/// use hard_xml::{XmlRead, XmlValue, XmlWrite};
+///
+/// #[derive(PartialEq, Debug)]
+/// struct Flag(bool);
+///
+/// impl XmlValue for Flag {
+///     fn from_xml_str(s: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+///         Ok(Flag(s == "Y"))
+///     }
+///
+///     fn fmt_xml(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         f.write_str(if self.0 { "Y" } else { "N" })
+///     }
+/// }
+///
+/// #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+/// #[xml(tag = "option")]
+/// struct Opt {
+///     #[xml(attr = "enabled")]
+///     enabled: Flag,
+/// }
+///
+/// assert_eq!(Opt::from_str(r#"<option enabled="Y"/>"#).unwrap().enabled, Flag(true));
+/// assert_eq!(Opt { enabled: Flag(false) }.to_string().unwrap(), r#"<option enabled="N"/>"#);
+/// ```
+pub trait XmlValue: Sized {
+    fn from_xml_str(s: &str) -> Result<Self, Box<dyn Error + Send + Sync>>;
+
+    fn fmt_xml(&self, f: &mut fmt::Formatter) -> fmt::Result;
+}
+
+// Generated code picks `XmlValue` over `FromStr` and `Display` by calling
+// these through two references: the impls for `&Parse<T>` and `&Format<T>`
+// are found first, and only apply to `XmlValue` types.
+
+#[doc(hidden)]
+pub struct Parse<T>(PhantomData<T>);
+
+impl<T> Parse<T> {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Parse(PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait ParseXmlValue<T> {
+    fn parse_value(&self, s: &str) -> Result<T, Box<dyn Error + Send + Sync>>;
+}
+
+impl<T: XmlValue> ParseXmlValue<T> for &Parse<T> {
+    fn parse_value(&self, s: &str) -> Result<T, Box<dyn Error + Send + Sync>> {
+        T::from_xml_str(s)
+    }
+}
+
+#[doc(hidden)]
+pub trait ParseFromStr<T> {
+    fn parse_value(&self, s: &str) -> Result<T, Box<dyn Error + Send + Sync>>;
+}
+
+impl<T> ParseFromStr<T> for Parse<T>
+where
+    T: FromStr,
+    T::Err: Into<Box<dyn Error + Send + Sync>>,
+{
+    fn parse_value(&self, s: &str) -> Result<T, Box<dyn Error + Send + Sync>> {
+        T::from_str(s).map_err(Into::into)
+    }
+}
+
+#[doc(hidden)]
+pub struct Format<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub trait FormatXmlValue<'a, T> {
+    fn format_value(&self) -> XmlValueDisplay<'a, T>;
+}
+
+impl<'a, T: XmlValue> FormatXmlValue<'a, T> for &Format<'a, T> {
+    fn format_value(&self) -> XmlValueDisplay<'a, T> {
+        XmlValueDisplay(self.0)
+    }
+}
+
+#[doc(hidden)]
+pub trait FormatDisplay<'a, T> {
+    fn format_value(&self) -> &'a T;
+}
+
+impl<'a, T: fmt::Display> FormatDisplay<'a, T> for Format<'a, T> {
+    fn format_value(&self) -> &'a T {
+        self.0
+    }
+}
+
+/// Displays a value in its XML lexical form.
+#[doc(hidden)]
+pub struct XmlValueDisplay<'a, T>(&'a T);
+
+impl<T: XmlValue> fmt::Display for XmlValueDisplay<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt_xml(f)
+    }
+}
+
+// shared strings, which can't be parsed with `FromStr`
+macro_rules! impl_shared_str {
+    ($($pointer:ident),*) => {$(
+        impl XmlValue for $pointer<str> {
+            fn from_xml_str(s: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+                Ok(s.into())
+            }
+
+            fn fmt_xml(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(self)
+            }
+        }
+    )*};
+}
+
+impl_shared_str!(Rc, Arc);
+
+/// Reads and writes the wrapped `chrono` type in its XML Schema form, like
+/// `xs:dateTime`, instead of the one of its `Display` implementation.
+///
+/// Fields of the `chrono` types themselves keep being read with `FromStr` and
+/// written with `Display`, so enabling the feature doesn't change documents
+/// written without it.
+///
+/// ```rust
+/// use chrono::{DateTime, TimeZone, Utc};
+/// use hard_xml::{XmlRead, XmlWrite, Xs};
+///
+/// #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+/// #[xml(tag = "event")]
+/// struct Event {
+///     #[xml(attr = "created")]
+///     created: Xs<DateTime<Utc>>,
+/// }
+///
+/// let event = Event::from_str(r#"<event created="2024-01-02T05:04:05+02:00"/>"#).unwrap();
+/// assert_eq!(*event.created, Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap());
+/// assert_eq!(event.to_string().unwrap(), r#"<event created="2024-01-02T03:04:05Z"/>"#);
+/// ```
+#[cfg(feature = "chrono")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Xs<T>(pub T);
+
+#[cfg(feature = "chrono")]
+impl<T> std::ops::Deref for Xs<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<T> std::ops::DerefMut for Xs<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<T> From<T> for Xs<T> {
+    fn from(value: T) -> Self {
+        Xs(value)
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, SecondsFormat, Utc};
+    use std::error::Error;
+    use std::fmt;
+
+    use super::{XmlValue, Xs};
+
+    impl XmlValue for Xs<DateTime<Utc>> {
+        fn from_xml_str(s: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+            Ok(Xs(Xs::<DateTime<FixedOffset>>::from_xml_str(s)?
+                .0
+                .with_timezone(&Utc)))
+        }
+
+        fn fmt_xml(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(&self.0.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+        }
+    }
+
+    impl XmlValue for Xs<DateTime<FixedOffset>> {
+        fn from_xml_str(s: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+            let s = s.trim();
+            // the `Display` form, written for fields without `Xs`, is read
+            // as well
+            DateTime::parse_from_rfc3339(s)
+                .or_else(|err| s.parse().map_err(|_| err))
+                .map(Xs)
+                .map_err(Into::into)
+        }
+
+        fn fmt_xml(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(&self.0.to_rfc3339_opts(SecondsFormat::AutoSi, false))
+        }
+    }
+
+    macro_rules! impl_format {
+        ($($ty:ty => $format:expr, $display:expr),*) => {$(
+            impl XmlValue for Xs<$ty> {
+                fn from_xml_str(s: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+                    let s = s.trim();
+                    // the `Display` form is read as well
+                    <$ty>::parse_from_str(s, $format)
+                        .or_else(|err| <$ty>::parse_from_str(s, $display).map_err(|_| err))
+                        .map(Xs)
+                        .map_err(Into::into)
+                }
+
+                fn fmt_xml(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    write!(f, "{}", self.0.format($format))
+                }
+            }
+        )*};
+    }
+
+    // xs:dateTime separates the date and the time with a `T`, unlike
+    // the `Display` implementation of `NaiveDateTime`
+    impl_format!(
+        NaiveDate => "%Y-%m-%d", "%Y-%m-%d",
+        NaiveTime => "%H:%M:%S%.f", "%H:%M:%S%.f",
+        NaiveDateTime => "%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"
+    );
+}
+
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal_impls {
+    use rust_decimal::Decimal;
+    use std::error::Error;
+    use std::fmt;
+
+    use super::XmlValue;
+
+    fn is_xs_decimal(s: &str) -> bool {
+        let s = s.strip_prefix(['+', '-']).unwrap_or(s);
+        let (int, frac) = s.split_once('.').unwrap_or((s, ""));
+        (!int.is_empty() || !frac.is_empty())
+            && int.bytes().all(|b| b.is_ascii_digit())
+            && frac.bytes().all(|b| b.is_ascii_digit())
+    }
+
+    // only reads the xs:decimal form, and fails instead of rounding values
+    // with too many digits, so the scale written back is the one read
+    impl XmlValue for Decimal {
+        fn from_xml_str(s: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+            let s = s.trim();
+            if !is_xs_decimal(s) {
+                return Err(format!("invalid decimal {:?}", s).into());
+            }
+            Ok(Decimal::from_str_exact(s)?)
+        }
+
+        fn fmt_xml(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            fmt::Display::fmt(self, f)
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use std::error::Error;
+    use std::fmt;
+    use time::format_description::well_known::Rfc3339;
+    use time::format_description::BorrowedFormatItem;
+    use time::macros::format_description;
+    use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+
+    use super::XmlValue;
+
+    const DATE: &[BorrowedFormatItem] = format_description!("[year]-[month]-[day]");
+    const TIME: &[BorrowedFormatItem] =
+        format_description!("[hour]:[minute]:[second][optional [.[subsecond]]]");
+    const DATE_TIME: &[BorrowedFormatItem] = format_description!(
+        "[year]-[month]-[day]T[hour]:[minute]:[second][optional [.[subsecond]]]"
+    );
+
+    impl XmlValue for OffsetDateTime {
+        fn from_xml_str(s: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+            Ok(OffsetDateTime::parse(s.trim(), &Rfc3339)?)
+        }
+
+        fn fmt_xml(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(&self.format(&Rfc3339).map_err(|_| fmt::Error)?)
+        }
+    }
+
+    macro_rules! impl_format {
+        ($($ty:ty => $format:expr),*) => {$(
+            impl XmlValue for $ty {
+                fn from_xml_str(s: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+                    Ok(<$ty>::parse(s.trim(), $format)?)
+                }
+
+                fn fmt_xml(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    f.write_str(&self.format($format).map_err(|_| fmt::Error)?)
+                }
+            }
+        )*};
+    }
+
+    impl_format!(Date => DATE, Time => TIME, PrimitiveDateTime => DATE_TIME);
+}
+
+#[cfg(feature = "url")]
+mod url_impls {
+    use std::error::Error;
+    use std::fmt;
+    use url::Url;
+
+    use super::XmlValue;
+
+    impl XmlValue for Url {
+        fn from_xml_str(s: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+            // xs:anyURI allows surrounding whitespace
+            let s = s.trim();
+            Url::parse(s).map_err(|err| format!("invalid URL {:?}: {}", s, err).into())
+        }
+
+        fn fmt_xml(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(self.as_str())
+        }
+    }
+}
+
+#[cfg(feature = "uuid")]
+mod uuid_impls {
+    use std::error::Error;
+    use std::fmt;
+    use uuid::Uuid;
+
+    use super::XmlValue;
+
+    // reads the hyphenated, simple, braced and urn forms, and writes the
+    // hyphenated one
+    impl XmlValue for Uuid {
+        fn from_xml_str(s: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+            Ok(Uuid::try_parse(s.trim())?)
+        }
+
+        fn fmt_xml(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            fmt::Display::fmt(&self.hyphenated(), f)
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::borrow::Cow;
+
+use crate::xml_unescape::xml_unescape;
+use crate::XmlResult;
+
+/// How an [`XmlReader`](crate::XmlReader) treats whitespace in attribute
+/// values
+///
+/// ```rust
+/// use hard_xml::{AttrWhitespace, XmlRead, XmlReader};
+///
+/// #[derive(XmlRead, PartialEq, Debug)]
+/// #[xml(tag = "link")]
+/// struct Link {
+///     #[xml(attr = "rel")]
+///     rel: String,
+/// }
+///
+/// let xml = "<link rel=\"\n  next\tprev&#10;\"/>";
+///
+/// let mut reader = XmlReader::new(xml).with_attr_whitespace(AttrWhitespace::Normalize);
+/// assert_eq!(Link::from_reader(&mut reader)?.rel, "   next prev\n");
+///
+/// let mut reader = XmlReader::new(xml).with_attr_whitespace(AttrWhitespace::Collapse);
+/// assert_eq!(Link::from_reader(&mut reader)?.rel, "next prev\n");
+/// # Ok::<(), hard_xml::XmlError>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AttrWhitespace {
+    /// Values are read as they are written
+    #[default]
+    Preserve,
+    /// Tabs, newlines and carriage returns are read as spaces, as described
+    /// in section 3.3.3 of XML 1.0. Those written as character references
+    /// are kept.
+    Normalize,
+    /// Values are normalized, then leading and trailing spaces are removed
+    /// and other runs of spaces are read as one, like values of tokenized
+    /// attribute types.
+    Collapse,
+}
+
+/// Unescapes an attribute value, normalizing its whitespace.
+pub(crate) fn read_attr_value(raw: &str, whitespace: AttrWhitespace) -> XmlResult<Cow<'_, str>> {
+    let value = if whitespace != AttrWhitespace::Preserve && raw.contains(['\t', '\n', '\r']) {
+        // a `\r\n` line break is a single space
+        let raw = raw.replace("\r\n", " ").replace(['\t', '\n', '\r'], " ");
+        Cow::Owned(xml_unescape(&raw)?.into_owned())
+    } else {
+        xml_unescape(raw)?
+    };
+
+    // only spaces are collapsed, not other characters written as references
+    if whitespace == AttrWhitespace::Collapse
+        && (value.starts_with(' ') || value.ends_with(' ') || value.contains("  "))
+    {
+        let tokens: Vec<&str> = value.split(' ').filter(|token| !token.is_empty()).collect();
+        Ok(Cow::Owned(tokens.join(" ")))
+    } else {
+        Ok(value)
+    }
+}
+
+/// Trims leading and trailing whitespace off a text.
+///
+/// Used by `#[xml(trim)]` fields. Borrowed text stays borrowed.
+pub fn trim_text(text: Cow<str>) -> Cow<str> {
+    match text {
+        Cow::Borrowed(text) => Cow::Borrowed(text.trim()),
+        Cow::Owned(text) if text.trim().len() == text.len() => Cow::Owned(text),
+        Cow::Owned(text) => Cow::Owned(text.trim().to_owned()),
+    }
+}
+
+#[test]
+fn test_trim_text() {
+    assert!(matches!(
+        trim_text(Cow::Borrowed("\n  a b\n")),
+        Cow::Borrowed("a b")
+    ));
+    assert_eq!(trim_text(Cow::Owned(" a ".to_owned())), "a");
+    assert_eq!(trim_text(Cow::Owned("a".to_owned())), "a");
+    assert_eq!(trim_text(Cow::Borrowed(" \t ")), "");
+}
+
+#[test]
+fn test_read_attr_value() {
+    let read = |raw, whitespace| read_attr_value(raw, whitespace).unwrap();
+
+    assert_eq!(read("a\tb\n", AttrWhitespace::Preserve), "a\tb\n");
+    assert_eq!(read("a\tb\r\nc\rd", AttrWhitespace::Normalize), "a b c d");
+    assert_eq!(read("a&#9;b&#10;", AttrWhitespace::Normalize), "a\tb\n");
+    assert_eq!(read("  a \n b  ", AttrWhitespace::Collapse), "a b");
+    assert_eq!(
+        read(" a&#32;&#32;b&#10; ", AttrWhitespace::Collapse),
+        "a b\n"
+    );
+    assert_eq!(read("&lt; a", AttrWhitespace::Collapse), "< a");
+    assert_eq!(read("a&#32;&#32;b", AttrWhitespace::Collapse), "a b");
+    assert_eq!(read("a  b", AttrWhitespace::Normalize), "a  b");
+    assert!(matches!(
+        read("plain", AttrWhitespace::Collapse),
+        Cow::Borrowed("plain")
+    ));
+}
@@ -1,4 +1,7 @@
-use std::io::Write;
+use std::fmt;
+use std::io::{self, Write};
+use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::{XmlResult, XmlWriter};
 
@@ -12,4 +15,132 @@ pub trait XmlWrite {
 
         Ok(String::from_utf8(writer.inner)?)
     }
+
+    /// Writes the element to a formatter directly, without building a
+    /// `String` first.
+    fn write_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut writer = XmlWriter::new(FmtWriter { f });
+
+        self.to_writer(&mut writer).map_err(|_| fmt::Error)
+    }
+
+    /// Returns a value whose `Display` implementation writes the element,
+    /// e.g. to log it without allocating.
+    ///
+    /// ```rust
+    /// use hard_xml::XmlWrite;
+    ///
+    /// #[derive(XmlWrite)]
+    /// #[xml(tag = "ping")]
+    /// struct Ping {
+    ///     #[xml(attr = "seq")]
+    ///     seq: u32,
+    /// }
+    ///
+    /// assert_eq!(format!("{}", Ping { seq: 1 }.display()), r#"<ping seq="1"/>"#);
+    /// ```
+    fn display(&self) -> XmlDisplay<'_, Self> {
+        XmlDisplay(self)
+    }
+}
+
+impl<T: XmlWrite + ?Sized> XmlWrite for &T {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        (**self).to_writer(writer)
+    }
+}
+
+macro_rules! impl_pointer {
+    ($($pointer:ident),*) => {$(
+        impl<T: XmlWrite> XmlWrite for $pointer<T> {
+            fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+                (**self).to_writer(writer)
+            }
+        }
+    )*};
+}
+
+impl_pointer!(Box, Rc, Arc);
+
+/// Writes the elements of an iterator one after another, without collecting
+/// them into a `Vec`.
+///
+/// The iterator is cloned every time it's written, so it can be used as a
+/// `child` field or written on its own.
+///
+/// ```rust
+/// use hard_xml::{XmlIter, XmlWrite};
+///
+/// #[derive(XmlWrite)]
+/// #[xml(tag = "n")]
+/// struct N {
+///     #[xml(text)]
+///     value: u32,
+/// }
+///
+/// #[derive(XmlWrite)]
+/// #[xml(tag = "squares")]
+/// struct Squares<I>
+/// where
+///     I: Iterator<Item = N> + Clone,
+/// {
+///     #[xml(child = "n")]
+///     values: XmlIter<I>,
+/// }
+///
+/// let values = XmlIter::new((1..4).map(|i| N { value: i * i }));
+///
+/// assert_eq!(values.to_string().unwrap(), "<n>1</n><n>4</n><n>9</n>");
+/// assert_eq!(
+///     (Squares { values }).to_string().unwrap(),
+///     "<squares><n>1</n><n>4</n><n>9</n></squares>"
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct XmlIter<I>(I);
+
+impl<I> XmlIter<I> {
+    pub fn new(iter: I) -> Self {
+        XmlIter(iter)
+    }
+
+    pub fn into_inner(self) -> I {
+        self.0
+    }
+}
+
+impl<I> XmlWrite for XmlIter<I>
+where
+    I: IntoIterator + Clone,
+    I::Item: XmlWrite,
+{
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        writer.write_elements(self.0.clone())
+    }
+}
+
+/// Displays an element as XML, returned by [`XmlWrite::display`].
+pub struct XmlDisplay<'a, T: ?Sized>(&'a T);
+
+impl<T: XmlWrite + ?Sized> fmt::Display for XmlDisplay<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.write_to(f)
+    }
+}
+
+// everything written by `XmlWriter` is made of complete `str`s
+struct FmtWriter<'a, 'f> {
+    f: &'a mut fmt::Formatter<'f>,
+}
+
+impl Write for FmtWriter<'_, '_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let s = std::str::from_utf8(buf).map_err(io::Error::other)?;
+        self.f.write_str(s).map_err(io::Error::other)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
 }
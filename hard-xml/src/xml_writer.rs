@@ -1,42 +1,181 @@
+use std::fmt::Display;
+use std::io::Error;
 use std::io::Result;
 use std::io::Write;
 
-use crate::xml_escape::xml_escape;
+use crate::xml_escape::{write_escaped, xml_escape};
+use crate::{XmlResult, XmlWrite};
 
+const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// Xml Writer
+///
+/// Besides writing names as they are given, it can write namespaced
+/// elements and attributes using `start_element_ns`, `attr_ns` and
+/// `end_element_ns`, which declare a prefix for every namespace the
+/// first time it's used in a scope.
+///
+/// ```rust
+/// use hard_xml::XmlWriter;
+///
+/// let mut writer = XmlWriter::new(Vec::new());
+///
+/// writer.register_namespace("w", "urn:word");
+/// writer.start_element_ns("urn:word", "p")?;
+/// writer.attr_ns("urn:other", "id", "1")?;
+/// writer.start_element_ns("urn:word", "r")?;
+/// writer.write_text("text")?;
+/// writer.end_element_ns()?;
+/// writer.end_element_ns()?;
+///
+/// assert_eq!(
+///     String::from_utf8(writer.into_inner()).unwrap(),
+///     r#"<w:p xmlns:w="urn:word" ns0:id="1" xmlns:ns0="urn:other"><w:r>text</w:r></w:p>"#
+/// );
+/// # Ok::<(), std::io::Error>(())
+/// ```
 pub struct XmlWriter<W: Write> {
     pub inner: W,
+    ns: Namespaces,
+    // attributes written with the next start tag
+    pending: Vec<(String, String)>,
+}
+
+#[derive(Default)]
+struct Namespaces {
+    // prefixes in scope: (prefix, uri, depth of the declaring element)
+    bindings: Vec<(String, String, usize)>,
+    // qualified names of the open namespaced elements
+    elements: Vec<String>,
+    // (uri, prefix)
+    preferred: Vec<(String, String)>,
+    // whether the start tag of the innermost namespaced element is still open
+    in_start_tag: bool,
+    next: usize,
+}
+
+impl Namespaces {
+    // returns the prefix of `uri`, and whether it has to be declared
+    fn prefix(&mut self, uri: &str) -> (Option<String>, bool) {
+        if uri.is_empty() {
+            return (None, false);
+        }
+        if uri == XML_NAMESPACE {
+            return (Some("xml".to_owned()), false);
+        }
+        if let Some((prefix, _, _)) = self.bindings.iter().rev().find(|(_, u, _)| u == uri) {
+            return (Some(prefix.clone()), false);
+        }
+
+        let preferred = self
+            .preferred
+            .iter()
+            .find(|(u, p)| u == uri && !self.in_scope(p))
+            .map(|(_, p)| p.clone());
+
+        let prefix = match preferred {
+            Some(prefix) => prefix,
+            None => loop {
+                let prefix = format!("ns{}", self.next);
+                self.next += 1;
+                if !self.in_scope(&prefix) {
+                    break prefix;
+                }
+            },
+        };
+
+        self.bindings
+            .push((prefix.clone(), uri.to_owned(), self.elements.len()));
+
+        (Some(prefix), true)
+    }
+
+    fn in_scope(&self, prefix: &str) -> bool {
+        prefix == "xml" || self.bindings.iter().any(|(p, _, _)| p == prefix)
+    }
+}
+
+fn qualify(prefix: Option<&str>, local: &str) -> String {
+    match prefix {
+        Some(prefix) => format!("{}:{}", prefix, local),
+        None => local.to_owned(),
+    }
 }
 
 impl<W: Write> XmlWriter<W> {
     pub fn new(inner: W) -> Self {
-        XmlWriter { inner }
+        XmlWriter {
+            inner,
+            ns: Namespaces::default(),
+            pending: Vec::new(),
+        }
     }
 
     pub fn into_inner(self) -> W {
         self.inner
     }
 
+    // runs `f` with a writer over `&mut dyn Write` sharing the state of
+    // this one, for `XmlWrite` implementations behind a trait object
+    pub(crate) fn with_dyn<R>(&mut self, f: impl FnOnce(&mut XmlWriter<&mut dyn Write>) -> R) -> R {
+        let mut writer = XmlWriter {
+            inner: &mut self.inner as &mut dyn Write,
+            ns: std::mem::take(&mut self.ns),
+            pending: std::mem::take(&mut self.pending),
+        };
+        let res = f(&mut writer);
+        self.ns = writer.ns;
+        self.pending = writer.pending;
+        res
+    }
+
     pub fn write_element_start(&mut self, tag: &str) -> Result<()> {
-        write!(self.inner, "<{}", tag)
+        self.close_start_tag()?;
+        write!(self.inner, "<{}", tag)?;
+        self.write_pending_attributes()
+    }
+
+    /// Adds an attribute to the next element started, e.g. to mark the
+    /// type of an element written by another `XmlWrite` implementation.
+    pub fn push_attribute(&mut self, key: &str, value: &str) {
+        self.pending.push((key.to_owned(), value.to_owned()));
+    }
+
+    fn write_pending_attributes(&mut self) -> Result<()> {
+        for (key, value) in std::mem::take(&mut self.pending) {
+            self.write_attribute(&key, &value)?;
+        }
+        Ok(())
     }
 
-    pub fn write_attribute(&mut self, key: &str, value: &str) -> Result<()> {
-        write!(self.inner, r#" {}="{}""#, key, xml_escape(value))
+    /// Writes an attribute, escaping the formatted value as it's written.
+    pub fn write_attribute(&mut self, key: &str, value: impl Display) -> Result<()> {
+        write!(self.inner, r#" {}=""#, key)?;
+        write_escaped(&mut self.inner, value)?;
+        write!(self.inner, "\"")
     }
 
-    pub fn write_text(&mut self, content: &str) -> Result<()> {
-        write!(self.inner, "{}", xml_escape(content))
+    pub fn write_text(&mut self, content: impl Display) -> Result<()> {
+        self.close_start_tag()?;
+        write_escaped(&mut self.inner, content)
     }
 
-    pub fn write_cdata_text(&mut self, content: &str) -> Result<()> {
+    pub fn write_cdata_text(&mut self, content: impl Display) -> Result<()> {
+        self.close_start_tag()?;
         write!(self.inner, "<![CDATA[{}]]>", content)
     }
 
     pub fn write_element_end_open(&mut self) -> Result<()> {
+        self.ns.in_start_tag = false;
         write!(self.inner, ">")
     }
 
-    pub fn write_flatten_text(&mut self, tag: &str, content: &str, is_cdata: bool) -> Result<()> {
+    pub fn write_flatten_text(
+        &mut self,
+        tag: &str,
+        content: impl Display,
+        is_cdata: bool,
+    ) -> Result<()> {
         self.write_element_start(tag)?;
         self.write_element_end_open()?;
         if is_cdata {
@@ -52,7 +191,147 @@ impl<W: Write> XmlWriter<W> {
         write!(self.inner, "</{}>", tag)
     }
 
+    /// Writes a comment, failing if `text` contains `--` or ends with `-`,
+    /// which can't be escaped in comments.
+    pub fn write_comment(&mut self, text: &str) -> Result<()> {
+        if text.contains("--") || text.ends_with('-') {
+            return Err(Error::other("comment text contains `--` or ends with `-`"));
+        }
+        self.close_start_tag()?;
+        write!(self.inner, "<!--{}-->", text)
+    }
+
+    /// Writes markup as is, without escaping.
+    pub fn write_raw(&mut self, xml: &str) -> Result<()> {
+        self.close_start_tag()?;
+        write!(self.inner, "{}", xml)
+    }
+
+    /// Writes every element of a sequence as it's iterated, so it doesn't
+    /// have to be collected first.
+    pub fn write_elements<I>(&mut self, elements: I) -> XmlResult<()>
+    where
+        I: IntoIterator,
+        I::Item: XmlWrite,
+    {
+        for element in elements {
+            element.to_writer(self)?;
+        }
+        Ok(())
+    }
+
     pub fn write_element_end_empty(&mut self) -> Result<()> {
         write!(self.inner, "/>")
     }
+
+    /// Sets the prefix used when `uri` is declared, if it's not already
+    /// bound to another namespace.
+    pub fn register_namespace(&mut self, prefix: &str, uri: &str) {
+        self.ns.preferred.push((uri.to_owned(), prefix.to_owned()));
+    }
+
+    /// Starts an element in the namespace `uri`, or in no namespace if
+    /// it's empty, which is closed by `end_element_ns`.
+    pub fn start_element_ns(&mut self, uri: &str, local: &str) -> Result<()> {
+        self.close_start_tag()?;
+        // bindings declared by the element are scoped by its depth
+        self.ns.elements.push(String::new());
+        let (prefix, declare) = self.ns.prefix(uri);
+        let name = qualify(prefix.as_deref(), local);
+        write!(self.inner, "<{}", name)?;
+        if declare {
+            self.write_declaration(prefix.as_deref(), uri)?;
+        }
+        self.write_pending_attributes()?;
+        *self.ns.elements.last_mut().unwrap() = name;
+        self.ns.in_start_tag = true;
+        Ok(())
+    }
+
+    /// Writes an attribute of the element started by `start_element_ns`.
+    pub fn attr_ns(&mut self, uri: &str, local: &str, value: &str) -> Result<()> {
+        if !self.ns.in_start_tag {
+            return Err(Error::other("attribute written outside of a start tag"));
+        }
+        let (prefix, declare) = self.ns.prefix(uri);
+        write!(
+            self.inner,
+            r#" {}="{}""#,
+            qualify(prefix.as_deref(), local),
+            xml_escape(value)
+        )?;
+        if declare {
+            self.write_declaration(prefix.as_deref(), uri)?;
+        }
+        Ok(())
+    }
+
+    /// Closes the innermost element started by `start_element_ns`.
+    pub fn end_element_ns(&mut self) -> Result<()> {
+        let depth = self.ns.elements.len();
+        let name = self
+            .ns
+            .elements
+            .pop()
+            .ok_or_else(|| Error::other("no element to close"))?;
+        if self.ns.in_start_tag {
+            self.ns.in_start_tag = false;
+            write!(self.inner, "/>")?;
+        } else {
+            write!(self.inner, "</{}>", name)?;
+        }
+        self.ns.bindings.retain(|(_, _, d)| *d < depth);
+        Ok(())
+    }
+
+    fn write_declaration(&mut self, prefix: Option<&str>, uri: &str) -> Result<()> {
+        match prefix {
+            Some(prefix) => write!(self.inner, r#" xmlns:{}="{}""#, prefix, xml_escape(uri)),
+            None => write!(self.inner, r#" xmlns="{}""#, xml_escape(uri)),
+        }
+    }
+
+    // closes the start tag of a namespaced element before its content
+    #[inline]
+    fn close_start_tag(&mut self) -> Result<()> {
+        if self.ns.in_start_tag {
+            self.ns.in_start_tag = false;
+            write!(self.inner, ">")?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_namespaces() -> Result<()> {
+    let mut writer = XmlWriter::new(Vec::new());
+
+    writer.start_element_ns("urn:a", "root")?;
+    writer.attr_ns("", "id", "1")?;
+    writer.attr_ns(XML_NAMESPACE, "lang", "en")?;
+    writer.start_element_ns("urn:b", "child")?;
+    writer.attr_ns("urn:a", "x", "&")?;
+    writer.end_element_ns()?;
+    writer.start_element_ns("urn:b", "child")?;
+    writer.write_text("text")?;
+    writer.end_element_ns()?;
+    writer.write_element_start("plain")?;
+    writer.write_element_end_empty()?;
+    writer.end_element_ns()?;
+
+    assert!(writer.end_element_ns().is_err());
+    assert!(writer.attr_ns("", "id", "1").is_err());
+
+    assert_eq!(
+        String::from_utf8(writer.into_inner()).unwrap(),
+        concat!(
+            r#"<ns0:root xmlns:ns0="urn:a" id="1" xml:lang="en">"#,
+            r#"<ns1:child xmlns:ns1="urn:b" ns0:x="&amp;"/>"#,
+            r#"<ns2:child xmlns:ns2="urn:b">text</ns2:child>"#,
+            r#"<plain/>"#,
+            r#"</ns0:root>"#,
+        )
+    );
+
+    Ok(())
 }
@@ -0,0 +1,68 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "user")]
+struct User<'a> {
+    #[xml(attr = "id", read_only)]
+    id: u32,
+    #[xml(attr = "name")]
+    name: Cow<'a, str>,
+    #[xml(attr = "version", write_only, default = "first_version")]
+    version: u32,
+    #[xml(child = "session", read_only)]
+    sessions: Vec<Session>,
+    #[xml(flatten_text = "note", write_only)]
+    note: Option<Cow<'a, str>>,
+}
+
+fn first_version() -> u32 {
+    1
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "session")]
+struct Session {
+    #[xml(attr = "id")]
+    id: u32,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let user = User::from_str(
+        r#"<user id="1" name="foo" version="5"><session id="2"/><note>bar</note></user>"#,
+    )?;
+
+    assert_eq!(
+        user,
+        User {
+            id: 1,
+            name: "foo".into(),
+            version: 1,
+            sessions: vec![Session { id: 2 }],
+            note: None,
+        }
+    );
+
+    assert_eq!(user.to_string()?, r#"<user name="foo" version="1"/>"#);
+
+    let user = User {
+        note: Some("bar".into()),
+        ..user
+    };
+
+    assert_eq!(
+        user.to_string()?,
+        r#"<user name="foo" version="1"><note>bar</note></user>"#
+    );
+
+    // required read only fields still have to be present
+    assert!(User::from_str(r#"<user name="foo"/>"#).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use hard_xml::{XmlRead, XmlResult, XmlSchemaInfo, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "author", alias = "Author", alias = "creator")]
+struct Author<'a> {
+    #[xml(attr = "id")]
+    id: Option<usize>,
+    #[xml(text)]
+    name: Cow<'a, str>,
+}
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "note")]
+struct Note<'a> {
+    #[xml(flatten_text = "body")]
+    body: Cow<'a, str>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "link")]
+struct Link<'a> {
+    #[xml(attr = "id", alias = "ID", alias = "Id")]
+    id: usize,
+    #[xml(attr = "href", alias = "url")]
+    href: Option<Cow<'a, str>>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+enum Entry<'a> {
+    #[xml(tag = "author", alias = "creator")]
+    Author(Author<'a>),
+    #[xml(tag = "note", alias = "comment")]
+    Note {
+        #[xml(flatten_text = "body")]
+        body: Cow<'a, str>,
+    },
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "book")]
+struct Book<'a> {
+    #[xml(child = "author", child = "creator", child = "note", child = "comment")]
+    entries: Vec<Entry<'a>>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let author = Author {
+        id: None,
+        name: "foo".into(),
+    };
+
+    assert_eq!(Author::from_str(r#"<author>foo</author>"#)?, author);
+    assert_eq!(Author::from_str(r#"<Author>foo</Author>"#)?, author);
+    assert_eq!(Author::from_str(r#"<creator>foo</creator>"#)?, author);
+    assert_eq!(Author::from_str(r#"<creator/>"#)?.name, "");
+
+    // the element must be closed with the name it was opened with
+    assert!(Author::from_str(r#"<creator>foo</author>"#).is_err());
+
+    // written with the canonical tag
+    assert_eq!(author.to_string()?, r#"<author>foo</author>"#);
+
+    assert_eq!(Author::elements()[0].aliases, vec!["Author", "creator"]);
+    assert!(Note::elements()[0].aliases.is_empty());
+
+    let link = Link {
+        id: 1,
+        href: Some("a".into()),
+    };
+
+    assert_eq!(Link::from_str(r#"<link id="1" href="a"/>"#)?, link);
+    assert_eq!(Link::from_str(r#"<link ID="1" url="a"/>"#)?, link);
+    assert_eq!(Link::from_str(r#"<link Id="1" href="a"/>"#)?, link);
+    assert!(Link::from_str(r#"<link iD="1"/>"#).is_err());
+
+    // written with the canonical name
+    assert_eq!(link.to_string()?, r#"<link id="1" href="a"/>"#);
+
+    let book = Book::from_str(
+        r#"<book><creator id="1">foo</creator><comment><body>bar</body></comment></book>"#,
+    )?;
+
+    assert_eq!(
+        book,
+        Book {
+            entries: vec![
+                Entry::Author(Author {
+                    id: Some(1),
+                    name: "foo".into(),
+                }),
+                Entry::Note { body: "bar".into() },
+            ],
+        }
+    );
+
+    assert_eq!(
+        book.to_string()?,
+        r#"<book><author id="1">foo</author><note><body>bar</body></note></book>"#
+    );
+
+    Ok(())
+}
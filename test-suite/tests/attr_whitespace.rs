@@ -0,0 +1,44 @@
+use hard_xml::{AttrWhitespace, XmlRead, XmlReader, XmlResult};
+use std::borrow::Cow;
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "image")]
+struct Image<'a> {
+    #[xml(attr = "alt")]
+    alt: Cow<'a, str>,
+    #[xml(attr = "classes", list)]
+    classes: Vec<Cow<'a, str>>,
+    #[xml(attr = "width")]
+    width: Option<u32>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = "<image alt=\"A\r\n  red\tball&#10;\" classes=\"\n  big\n  round\n\"/>";
+
+    let image = Image::from_str(xml)?;
+    assert_eq!(image.alt, "A\r\n  red\tball\n");
+
+    let mut reader = XmlReader::new(xml).with_attr_whitespace(AttrWhitespace::Normalize);
+    let image = Image::from_reader(&mut reader)?;
+    assert_eq!(image.alt, "A   red ball\n");
+    assert_eq!(image.classes, vec!["big", "round"]);
+
+    let mut reader = XmlReader::new(xml).with_attr_whitespace(AttrWhitespace::Collapse);
+    let image = Image::from_reader(&mut reader)?;
+    assert_eq!(image.alt, "A red ball\n");
+    assert_eq!(image.classes, vec!["big", "round"]);
+
+    // numbers can't be read with surrounding whitespace unless it's collapsed
+    let xml = "<image alt=\"\" classes=\"\" width=\"\n  42\n\"/>";
+    assert!(Image::from_str(xml).is_err());
+    let mut reader = XmlReader::new(xml).with_attr_whitespace(AttrWhitespace::Collapse);
+    assert_eq!(Image::from_reader(&mut reader)?.width, Some(42));
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "div")]
+struct Div<'a> {
+    #[xml(attr = "id")]
+    id: Cow<'a, str>,
+    #[xml(attrs)]
+    data: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+    #[xml(child = "div")]
+    children: Vec<Div<'a>>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "span")]
+struct Span {
+    #[xml(attrs)]
+    attrs: BTreeMap<String, String>,
+    #[xml(text)]
+    text: String,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "input")]
+struct Input {
+    #[xml(attr = "type")]
+    ty: String,
+    #[xml(attrs)]
+    attrs: HashMap<String, String>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+struct Common {
+    #[xml(attr = "class")]
+    class: Option<String>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "p")]
+struct Paragraph {
+    #[xml(flatten)]
+    common: Common,
+    #[xml(attrs)]
+    attrs: Vec<(String, String)>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<div id="a" data-x="1" data-y="&amp;"><div id="b"/></div>"#;
+    let div = Div::from_str(xml)?;
+    assert_eq!(div.id, "a");
+    assert_eq!(
+        div.data,
+        vec![("data-x".into(), "1".into()), ("data-y".into(), "&".into())]
+    );
+    assert!(div.children[0].data.is_empty());
+    assert_eq!(div.to_string()?, xml);
+
+    let xml = r#"<span lang="en" dir="ltr">foo</span>"#;
+    let span = Span::from_str(xml)?;
+    assert_eq!(span.attrs["lang"], "en");
+    assert_eq!(span.attrs["dir"], "ltr");
+    assert_eq!(span.to_string()?, r#"<span dir="ltr" lang="en">foo</span>"#);
+
+    let input = Input::from_str(r#"<input type="text" name="q"/>"#)?;
+    assert_eq!(input.ty, "text");
+    assert_eq!(input.attrs.len(), 1);
+    assert_eq!(input.attrs["name"], "q");
+
+    // flattened fields read their attributes first
+    let xml = r#"<p class="note" hidden="true"/>"#;
+    let paragraph = Paragraph::from_str(xml)?;
+    assert_eq!(paragraph.common.class.as_deref(), Some("note"));
+    assert_eq!(paragraph.attrs, vec![("hidden".into(), "true".into())]);
+    assert_eq!(paragraph.to_string()?, xml);
+
+    Ok(())
+}
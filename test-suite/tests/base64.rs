@@ -0,0 +1,48 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "message")]
+struct Message {
+    #[xml(attr = "signature", base64)]
+    signature: Option<Vec<u8>>,
+    #[xml(flatten_text = "payload", base64)]
+    payload: Vec<u8>,
+    #[xml(flatten_text = "attachment", encoding = "base64")]
+    attachments: Vec<Vec<u8>>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "blob")]
+struct Blob {
+    #[xml(text, base64)]
+    data: Vec<u8>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let message = Message {
+        signature: Some(vec![0xff, 0x00]),
+        payload: b"hello".to_vec(),
+        attachments: vec![b"a".to_vec(), vec![]],
+    };
+    let xml = r#"<message signature="/wA="><payload>aGVsbG8=</payload><attachment>YQ==</attachment><attachment></attachment></message>"#;
+    assert_eq!(message.to_string()?, xml);
+    assert_eq!(Message::from_str(xml)?, message);
+
+    let message = Message::from_str(r#"<message><payload/></message>"#)?;
+    assert_eq!(message.signature, None);
+    assert!(message.payload.is_empty());
+
+    let blob = Blob::from_str("<blob>\n  AAEC\n  AwQ=\n</blob>")?;
+    assert_eq!(blob.data, vec![0, 1, 2, 3, 4]);
+    assert_eq!(blob.to_string()?, "<blob>AAECAwQ=</blob>");
+
+    assert!(Blob::from_str("<blob>!</blob>").is_err());
+
+    Ok(())
+}
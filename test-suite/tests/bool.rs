@@ -21,6 +21,30 @@ struct Text {
     text: bool,
 }
 
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "flags")]
+struct Flags {
+    #[xml(attr = "default")]
+    default: Option<bool>,
+    #[xml(attr = "strict", bool = "strict")]
+    strict: Option<bool>,
+    #[xml(attr = "lenient", bool = "lenient")]
+    lenient: Option<bool>,
+    #[xml(flatten_text = "flag", bool = "lenient")]
+    flags: Vec<bool>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "switch")]
+struct Switch {
+    #[xml(attr = "enabled", true = "yes", false = "no")]
+    enabled: bool,
+    #[xml(attr = "visible", true = "1", false = "0")]
+    visible: Option<bool>,
+    #[xml(flatten_text = "state", true = "On", false = "Off", bool = "lenient")]
+    states: Vec<bool>,
+}
+
 #[test]
 fn test() -> XmlResult<()> {
     let _ = env_logger::builder()
@@ -72,5 +96,32 @@ fn test() -> XmlResult<()> {
         r#"<text><foo>false</foo><foo>true</foo></text>"#,
     );
 
+    let flags = Flags::from_str(
+        r#"<flags default="yes" strict=" 1 " lenient="YES"><flag>On</flag><flag> False </flag><flag>n</flag></flags>"#,
+    )?;
+    assert_eq!(flags.default, Some(true));
+    assert_eq!(flags.strict, Some(true));
+    assert_eq!(flags.lenient, Some(true));
+    assert_eq!(flags.flags, vec![true, false, false]);
+
+    assert!(Flags::from_str(r#"<flags default="YES"/>"#).is_err());
+    assert!(Flags::from_str(r#"<flags strict="yes"/>"#).is_err());
+    assert!(Flags::from_str(r#"<flags strict="True"/>"#).is_err());
+    assert!(Flags::from_str(r#"<flags lenient="maybe"/>"#).is_err());
+
+    let switch = Switch {
+        enabled: true,
+        visible: Some(false),
+        states: vec![true, false],
+    };
+    let xml = r#"<switch enabled="yes" visible="0"><state>On</state><state>Off</state></switch>"#;
+    assert_eq!(switch.to_string()?, xml);
+    assert_eq!(Switch::from_str(xml)?, switch);
+
+    let switch = Switch::from_str(r#"<switch enabled="true"><state> ON </state></switch>"#)?;
+    assert!(switch.enabled);
+    assert_eq!(switch.visible, None);
+    assert_eq!(switch.states, vec![true]);
+
     Ok(())
 }
@@ -0,0 +1,77 @@
+use hard_xml::{ErrorDetail, XmlRead, XmlResult, XmlWrite};
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "user")]
+struct User<'a> {
+    #[xml(attr = "id")]
+    id: &'a str,
+    #[xml(attr = "nick")]
+    nick: Option<&'a str>,
+    #[xml(flatten_text = "email")]
+    emails: Vec<&'a str>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "note")]
+struct Note<'a> {
+    #[xml(text)]
+    text: &'a str,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "*")]
+struct Any<'a> {
+    #[xml(tag_name)]
+    tag: &'a str,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<user id="u1"><email>a@b.c</email><email>d@e.f</email></user>"#;
+    let user = User::from_str(xml)?;
+    assert_eq!(
+        user,
+        User {
+            id: "u1",
+            nick: None,
+            emails: vec!["a@b.c", "d@e.f"],
+        }
+    );
+
+    let user = User {
+        id: "u2",
+        nick: Some("<b>"),
+        emails: vec!["<a>"],
+    };
+    assert_eq!(
+        user.to_string()?,
+        r#"<user id="u2" nick="&lt;b&gt;"><email>&lt;a&gt;</email></user>"#
+    );
+
+    assert!(matches!(
+        User::from_str(r#"<user id="a&amp;b"/>"#),
+        Err(err) if matches!(err.detail(), ErrorDetail::Unborrowable { value } if value == "a&b")
+    ));
+
+    let note = Note::from_str(r#"<note>hi</note>"#)?;
+    assert_eq!(note.text, "hi");
+    assert_eq!(
+        Note { text: "A & B" }.to_string()?,
+        r#"<note>A &amp; B</note>"#
+    );
+    assert!(matches!(
+        Note::from_str(r#"<note>A &amp; B</note>"#),
+        Err(err) if matches!(err.detail(), ErrorDetail::Unborrowable { .. })
+    ));
+
+    let any = Any::from_str(r#"<item/>"#)?;
+    assert_eq!(any.tag, "item");
+    assert_eq!(any.to_string()?, r#"<item/>"#);
+
+    Ok(())
+}
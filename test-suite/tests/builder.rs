@@ -0,0 +1,132 @@
+use hard_xml::{ErrorDetail, ErrorKind, XmlBuilder, XmlRead, XmlResult, XmlSchemaInfo, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "a")]
+struct A;
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "b")]
+struct B<'a> {
+    #[xml(text)]
+    text: Cow<'a, str>,
+}
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+enum AB<'a> {
+    #[xml(tag = "a")]
+    A(A),
+    #[xml(tag = "b")]
+    B(B<'a>),
+}
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "root")]
+struct Root<'a> {
+    #[xml(attr = "id")]
+    id: usize,
+    #[xml(attr = "name")]
+    name: Option<Cow<'a, str>>,
+    #[xml(child = "a", child = "b")]
+    children: Vec<AB<'a>>,
+    #[xml(flatten_text = "note")]
+    note: Cow<'a, str>,
+}
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "seq", ordered)]
+struct Seq<'a> {
+    #[xml(flatten_text = "x")]
+    x: Vec<Cow<'a, str>>,
+    #[xml(flatten_text = "y")]
+    y: Option<Cow<'a, str>>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let document = XmlBuilder::new::<Root>("root")?
+        .attr("id", "42")?
+        .child("b", |b| b.text("text"))?
+        .child("a", Ok)?
+        .flatten_text("note", "note")?
+        .finish()?
+        .to_string()?;
+
+    assert_eq!(
+        document,
+        r#"<root id="42"><b>text</b><a/><note>note</note></root>"#
+    );
+
+    assert_eq!(
+        Root::from_str(&document)?,
+        Root {
+            id: 42,
+            name: None,
+            children: vec![
+                AB::B(B {
+                    text: "text".into()
+                }),
+                AB::A(A)
+            ],
+            note: "note".into(),
+        }
+    );
+
+    assert!(matches!(
+        XmlBuilder::new::<Root>("root")?.attr("unknown", ""),
+        Err(err) if matches!(err.detail(), ErrorDetail::UnknownField)
+    ));
+
+    assert!(matches!(
+        XmlBuilder::new::<Root>("root")?
+            .attr("id", "1")?
+            .attr("id", "2"),
+        Err(err) if matches!(err.detail(), ErrorDetail::DuplicateField { .. })
+    ));
+
+    assert!(matches!(
+        XmlBuilder::new::<Root>("root")?.child("c", Ok),
+        Err(err) if matches!(err.detail(), ErrorDetail::UnknownField)
+    ));
+
+    assert!(matches!(
+        XmlBuilder::new::<Root>("root")?.child("a", |a| a.text("text")),
+        Err(err) if matches!(err.detail(), ErrorDetail::UnknownField)
+    ));
+
+    assert!(matches!(
+        XmlBuilder::new::<Root>("root")?.attr("id", "1")?.finish(),
+        Err(err) if matches!(err.detail(), ErrorDetail::MissingField { tag, expected }
+            if tag == "root" && expected == "child <note>")
+    ));
+
+    assert!(matches!(
+        XmlBuilder::new::<AB>("c"),
+        Err(err) if matches!(err.detail(), ErrorDetail::UnknownVariant { value } if value == "c")
+            && err.kind() == ErrorKind::Mismatch
+    ));
+
+    assert_eq!(
+        XmlBuilder::new::<Seq>("seq")?
+            .flatten_text("x", "1")?
+            .flatten_text("x", "2")?
+            .flatten_text("y", "3")?
+            .finish()?
+            .to_string()?,
+        r#"<seq><x>1</x><x>2</x><y>3</y></seq>"#
+    );
+
+    assert!(matches!(
+        XmlBuilder::new::<Seq>("seq")?
+            .flatten_text("y", "3")?
+            .flatten_text("x", "1"),
+        Err(err) if matches!(err.detail(), ErrorDetail::UnorderedField)
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "root")]
+struct Root<'a> {
+    #[xml(attr = "digest", encoding = "hex")]
+    digest: Cow<'a, [u8]>,
+    #[xml(attr = "raw", encoding = "utf8")]
+    raw: Option<Cow<'a, [u8]>>,
+    #[xml(flatten_text = "chunk", encoding = "base64")]
+    chunks: Vec<Cow<'a, [u8]>>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "payload")]
+struct Payload<'a> {
+    #[xml(text, encoding = "utf8")]
+    content: Cow<'a, [u8]>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let root = Root {
+        digest: Cow::Borrowed(&[0xde, 0xad, 0xbe, 0xef]),
+        raw: Some(Cow::Borrowed(b"a b")),
+        chunks: vec![Cow::Borrowed(b"hello"), Cow::Borrowed(b"")],
+    };
+
+    assert_eq!(
+        root.to_string()?,
+        r#"<root digest="DEADBEEF" raw="a b"><chunk>aGVsbG8=</chunk><chunk></chunk></root>"#
+    );
+
+    assert_eq!(
+        Root::from_str(
+            r#"<root digest="deadbeef" raw="a b"><chunk>aGVs
+            bG8=</chunk><chunk></chunk></root>"#
+        )?,
+        root
+    );
+
+    let payload = Payload::from_str("<payload>binary</payload>")?;
+    assert!(matches!(payload.content, Cow::Borrowed(b"binary")));
+    assert_eq!(payload.to_string()?, "<payload>binary</payload>");
+
+    assert!(Root::from_str(r#"<root digest="xyz"/>"#).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use hard_xml::{XmlRead, XmlReader, XmlResult, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "item")]
+struct Item<'a> {
+    #[xml(attr = "id")]
+    id: usize,
+    #[xml(flatten_text = "name")]
+    name: Cow<'a, str>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "feed", case_insensitive)]
+struct Feed<'a> {
+    #[xml(attr = "version")]
+    version: usize,
+    #[xml(child = "item")]
+    items: Vec<Item<'a>>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(case_insensitive)]
+enum Entry<'a> {
+    #[xml(tag = "item")]
+    Item(Item<'a>),
+    #[xml(tag = "note")]
+    Note {
+        #[xml(text)]
+        text: Cow<'a, str>,
+    },
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let feed = Feed::from_str(
+        r#"<Feed VERSION="2"><Item Id="1"><NAME>foo</NAME></Item><item id="2"><name>bar</name></item></Feed>"#,
+    )?;
+
+    assert_eq!(
+        feed,
+        Feed {
+            version: 2,
+            items: vec![
+                Item {
+                    id: 1,
+                    name: "foo".into(),
+                },
+                Item {
+                    id: 2,
+                    name: "bar".into(),
+                },
+            ],
+        }
+    );
+
+    // written with the declared casing
+    assert_eq!(
+        feed.to_string()?,
+        r#"<feed version="2"><item id="1"><name>foo</name></item><item id="2"><name>bar</name></item></feed>"#
+    );
+
+    // only elements read inside the flagged one ignore case
+    assert!(Item::from_str(r#"<Item id="1"><name>foo</name></Item>"#).is_err());
+
+    assert_eq!(
+        Entry::from_str(r#"<NOTE>foo</NOTE>"#)?,
+        Entry::Note { text: "foo".into() }
+    );
+
+    // the reader is restored afterwards
+    let mut reader = XmlReader::new(r#"<root><NOTE>foo</NOTE><Item id="1"><name/></Item></root>"#);
+    reader.read_till_element_start("root")?;
+    reader.next();
+    Entry::from_reader(&mut reader)?;
+    assert!(Item::from_reader(&mut reader).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use hard_xml::{XmlRead, XmlResult, XmlSchemaInfo, XmlWrite};
+
+// `not(any())` and `any()` stand for an enabled and a disabled feature
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "root")]
+struct Root {
+    #[xml(attr = "id")]
+    id: String,
+    #[cfg(not(any()))]
+    #[xml(attr = "enabled")]
+    enabled: Option<String>,
+    #[cfg(any())]
+    #[xml(attr = "disabled")]
+    disabled: Option<String>,
+    // the same attribute can be read by fields guarded by exclusive features
+    #[cfg(any())]
+    #[xml(attr = "value")]
+    value: u32,
+    #[cfg(not(any()))]
+    #[xml(attr = "value")]
+    value: String,
+    #[cfg_attr(not(any()), xml(child = "item"))]
+    items: Vec<Item>,
+    #[cfg_attr(any(), xml(child = "other"))]
+    #[cfg_attr(not(any()), xml(flatten_text = "other"))]
+    other: Option<String>,
+    #[cfg(any())]
+    #[xml(child = "extra")]
+    extra: Vec<Item>,
+}
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "item")]
+struct Item(
+    #[cfg(any())]
+    #[xml(attr = "name")]
+    String,
+    #[xml(text)] String,
+);
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+enum Node {
+    #[cfg(any())]
+    #[xml(tag = "a")]
+    A(Item),
+    #[xml(tag = "b")]
+    B {
+        #[cfg(any())]
+        #[xml(attr = "c")]
+        c: String,
+        #[cfg(not(any()))]
+        #[xml(attr = "d")]
+        d: String,
+    },
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<root id="1" enabled="yes" disabled="no" value="v"><item>foo</item><extra>bar</extra><other>baz</other></root>"#;
+    let root = Root::from_str(xml)?;
+    assert_eq!(
+        root,
+        Root {
+            id: "1".into(),
+            enabled: Some("yes".into()),
+            value: "v".into(),
+            items: vec![Item("foo".into())],
+            other: Some("baz".into()),
+        }
+    );
+    assert_eq!(
+        root.to_string()?,
+        r#"<root id="1" enabled="yes" value="v"><item>foo</item><other>baz</other></root>"#
+    );
+
+    // disabled fields are missing from the schema
+    let fields = &Root::elements()[0].fields;
+    assert_eq!(fields.len(), 5);
+    assert!(fields.iter().all(|field| field.name != "disabled"));
+
+    assert_eq!(
+        Item::from_str(r#"<item name="a">b</item>"#)?,
+        Item("b".into())
+    );
+
+    assert!(Node::from_str(r#"<a><item>foo</item></a>"#).is_err());
+    assert_eq!(
+        Node::from_str(r#"<b c="c" d="d"/>"#)?,
+        Node::B { d: "d".into() }
+    );
+    assert_eq!(Node::B { d: "d".into() }.to_string()?, r#"<b d="d"/>"#);
+
+    Ok(())
+}
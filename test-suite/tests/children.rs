@@ -0,0 +1,83 @@
+use hard_xml::tree::Element;
+use hard_xml::{RawXml, XmlRead, XmlResult, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "package")]
+struct Package<'a> {
+    #[xml(attr = "version")]
+    version: Cow<'a, str>,
+    #[xml(flatten_text = "name")]
+    name: Cow<'a, str>,
+    #[xml(child = "dependency")]
+    dependencies: Vec<Dependency<'a>>,
+    #[xml(children)]
+    extensions: Vec<RawXml<'a>>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "dependency")]
+struct Dependency<'a> {
+    #[xml(attr = "name")]
+    name: Cow<'a, str>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "list")]
+struct List<'a> {
+    #[xml(children)]
+    items: Vec<Element<'a>>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "doc")]
+struct Doc<'a> {
+    #[xml(order)]
+    order: Vec<&'static str>,
+    #[xml(flatten_text = "title")]
+    titles: Vec<Cow<'a, str>>,
+    #[xml(children)]
+    other: Vec<RawXml<'a>>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<package version="1"><name>foo</name><x:lock hash="ab"/><dependency name="bar"/><x:build><x:step/></x:build></package>"#;
+    let package = Package::from_str(xml)?;
+    assert_eq!(package.name, "foo");
+    assert_eq!(package.dependencies.len(), 1);
+    assert_eq!(package.extensions.len(), 2);
+    assert_eq!(package.extensions[0].tag(), "x:lock");
+    assert_eq!(package.extensions[1].inner(), "<x:step/>");
+
+    // unknown children are written after the others
+    assert_eq!(
+        package.to_string()?,
+        r#"<package version="1"><dependency name="bar"/><name>foo</name><x:lock hash="ab"/><x:build><x:step/></x:build></package>"#
+    );
+
+    let list = List::from_str(r#"<list/>"#)?;
+    assert!(list.items.is_empty());
+    assert_eq!(list.to_string()?, r#"<list/>"#);
+
+    let xml = r#"<list><a>1</a><b c="2"/></list>"#;
+    let list = List::from_str(xml)?;
+    assert_eq!(list.items.len(), 2);
+    assert_eq!(list.to_string()?, xml);
+
+    let xml = r#"<doc><title>a</title><note/><title>b</title></doc>"#;
+    let doc = Doc::from_str(xml)?;
+    assert_eq!(doc.order, vec!["titles", "titles"]);
+    assert_eq!(doc.other.len(), 1);
+    assert_eq!(
+        doc.to_string()?,
+        r#"<doc><title>a</title><title>b</title><note/></doc>"#
+    );
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use hard_xml::{XmlRead, XmlResult, XmlWrite, Xs};
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "event")]
+struct Event {
+    #[xml(attr = "created")]
+    created: Xs<DateTime<Utc>>,
+    #[xml(attr = "local")]
+    local: Option<Xs<DateTime<FixedOffset>>>,
+    #[xml(attr = "date")]
+    date: Xs<NaiveDate>,
+    #[xml(flatten_text = "start")]
+    start: Xs<NaiveDateTime>,
+    #[xml(flatten_text = "time")]
+    times: Vec<Xs<NaiveTime>>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "stamp")]
+struct Stamp {
+    #[xml(text)]
+    at: Xs<DateTime<Utc>>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "plain")]
+struct Plain {
+    #[xml(attr = "created")]
+    created: DateTime<Utc>,
+    #[xml(attr = "start")]
+    start: NaiveDateTime,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let date = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+    let event = Event {
+        created: Xs(Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap()),
+        local: Some(Xs(FixedOffset::east_opt(2 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 2, 5, 4, 5)
+            .unwrap())),
+        date: Xs(date),
+        start: Xs(date.and_hms_milli_opt(9, 30, 0, 250).unwrap()),
+        times: vec![Xs(NaiveTime::from_hms_opt(12, 0, 0).unwrap())],
+    };
+
+    let xml = r#"<event created="2024-01-02T03:04:05Z" local="2024-01-02T05:04:05+02:00" date="2024-02-29"><start>2024-02-29T09:30:00.250</start><time>12:00:00</time></event>"#;
+    assert_eq!(event.to_string()?, xml);
+    assert_eq!(Event::from_str(xml)?, event);
+
+    // any offset is converted to UTC
+    let event = Event::from_str(
+        r#"<event created="2024-01-02T05:04:05+02:00" date="2024-02-29"><start>2024-02-29T09:30:00</start></event>"#,
+    )?;
+    assert_eq!(
+        *event.created,
+        Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap()
+    );
+    assert_eq!(event.local, None);
+
+    // dates and times are read without their surrounding whitespace
+    let event = Event::from_str(
+        "<event created=\"2024-01-02T03:04:05Z\" date=\" 2024-02-29\n\"><start>2024-02-29T09:30:00</start><time> 12:00:00.5 </time></event>",
+    )?;
+    assert_eq!(*event.date, date);
+    assert_eq!(
+        event.times,
+        [Xs(NaiveTime::from_hms_milli_opt(12, 0, 0, 500).unwrap())]
+    );
+    assert_eq!(
+        event.to_string()?,
+        r#"<event created="2024-01-02T03:04:05Z" date="2024-02-29"><start>2024-02-29T09:30:00</start><time>12:00:00.500</time></event>"#
+    );
+
+    // the `Display` forms are read as well
+    let event = Event::from_str(
+        r#"<event created="2024-01-02 03:04:05 UTC" date="2024-02-29"><start>2018-01-26 18:30:09.453829</start></event>"#,
+    )?;
+    assert_eq!(
+        *event.created,
+        Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap()
+    );
+    assert_eq!(
+        *event.start,
+        NaiveDate::from_ymd_opt(2018, 1, 26)
+            .unwrap()
+            .and_hms_micro_opt(18, 30, 9, 453_829)
+            .unwrap()
+    );
+
+    assert!(matches!(
+        Event::from_str(
+            r#"<event created="2024-01-02 03:04" date="2024-02-29"><start>2024-02-29T09:30:00</start></event>"#
+        ),
+        Err(err) if matches!(err.detail(), hard_xml::ErrorDetail::FromStr { .. })
+    ));
+
+    let xml = "<stamp>1970-01-01T00:00:00.5Z</stamp>";
+    let stamp = Stamp::from_str(xml)?;
+    assert_eq!(*stamp.at, Utc.timestamp_opt(0, 500_000_000).unwrap());
+    assert_eq!(
+        stamp.to_string()?,
+        "<stamp>1970-01-01T00:00:00.500Z</stamp>"
+    );
+
+    // fields without `Xs` keep their `FromStr` and `Display` forms
+    let plain = Plain::from_str(
+        r#"<plain created="2018-01-26 18:30:09 UTC" start="2018-01-26T18:30:09.453829"/>"#,
+    )?;
+    assert_eq!(
+        plain.to_string()?,
+        r#"<plain created="2018-01-26 18:30:09 UTC" start="2018-01-26 18:30:09.453829"/>"#
+    );
+
+    Ok(())
+}
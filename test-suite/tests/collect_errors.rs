@@ -0,0 +1,157 @@
+use hard_xml::{ErrorDetail, XmlError, XmlRead, XmlReader, XmlResult};
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "catalog")]
+struct Catalog {
+    #[xml(attr = "version")]
+    version: u32,
+    #[xml(child = "book")]
+    books: Vec<Book>,
+    #[xml(flatten_text = "count")]
+    count: Option<u32>,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "book")]
+struct Book {
+    #[xml(attr = "id")]
+    id: u32,
+    #[xml(attr = "tags", list)]
+    tags: Vec<u32>,
+    #[xml(child = "author")]
+    author: Author,
+    #[xml(flatten_text = "price")]
+    price: Option<f64>,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "author")]
+struct Author {
+    #[xml(text)]
+    name: String,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(untagged)]
+enum Value {
+    #[xml(tag = "value")]
+    Number {
+        #[xml(attr = "n")]
+        n: i32,
+    },
+    #[xml(tag = "value")]
+    Text {
+        #[xml(text)]
+        text: String,
+    },
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "values")]
+struct Values {
+    #[xml(child = "value")]
+    values: Vec<Value>,
+}
+
+fn read_all<'a, T: XmlRead<'a>>(xml: &'a str) -> Result<T, Vec<XmlError>> {
+    let mut reader = XmlReader::new(xml).with_error_collection();
+    let res = T::from_reader(&mut reader);
+    reader.finish(res)
+}
+
+fn paths(errors: &[XmlError]) -> Vec<&str> {
+    errors
+        .iter()
+        .map(|err| err.position().map_or("", |position| &position.path))
+        .collect()
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<catalog version="1"><book id="1"><author>a</author></book></catalog>"#;
+    assert_eq!(
+        read_all::<Catalog>(xml).unwrap(),
+        Catalog {
+            version: 1,
+            books: vec![Book {
+                id: 1,
+                tags: vec![],
+                author: Author { name: "a".into() },
+                price: None,
+            }],
+            count: None,
+        }
+    );
+
+    // every book is read, whatever is wrong with the ones before
+    let xml = r#"<catalog version="1">
+<book id="x" tags="1 y 3"><author>a</author><price>z</price></book>
+<book id="2"/>
+<book id="3"><author>c</author></book>
+<count>w</count>
+</catalog>"#;
+    let errors = read_all::<Catalog>(xml).unwrap_err();
+    assert_eq!(errors.len(), 5);
+    assert!(matches!(errors[0].detail(), ErrorDetail::FromStr { .. }));
+    assert!(matches!(errors[1].detail(), ErrorDetail::FromStr { .. }));
+    assert!(matches!(errors[2].detail(), ErrorDetail::FromStr { .. }));
+    assert!(matches!(
+        errors[3].detail(),
+        ErrorDetail::MissingField { .. }
+    ));
+    assert_eq!(
+        errors[3].to_string(),
+        r#"missing field in XML of "Book": "author" (expected child <author> in <book>) at 3:13 in catalog > book[2]"#
+    );
+    assert!(matches!(errors[4].detail(), ErrorDetail::FromStr { .. }));
+    assert_eq!(
+        errors[1].to_string(),
+        r#"error parsing XML value "y" as u32 in "Book": "tags" (invalid digit found in string) at 2:14 in catalog > book"#
+    );
+    assert_eq!(
+        errors[4].to_string(),
+        r#"error parsing XML value "w" as u32 in "Catalog": "count" (invalid digit found in string) at 5:9 in catalog > count"#
+    );
+
+    // the first error is still returned without collecting them
+    assert!(
+        matches!(Catalog::from_str(xml), Err(err) if matches!(err.detail(), ErrorDetail::FromStr { .. }))
+    );
+
+    // elements failing because of errors found inside them aren't reported
+    // again, unlike the ones they're missing
+    let xml = r#"<catalog version="x"><book><author>a</author></book></catalog>"#;
+    let errors = read_all::<Catalog>(xml).unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(errors[0].detail(), ErrorDetail::FromStr { .. }));
+    assert!(matches!(
+        errors[1].detail(),
+        ErrorDetail::MissingField { .. }
+    ));
+    assert_eq!(paths(&errors), ["catalog", "catalog > book"]);
+
+    // errors of the document structure stop reading
+    let xml = r#"<catalog version="x"><book id="1"></boo></catalog>"#;
+    let errors = read_all::<Catalog>(xml).unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(
+        errors[1].detail(),
+        ErrorDetail::TagMismatch { .. }
+    ));
+
+    // variants of untagged enums are tried without collecting errors
+    let xml = r#"<values><value n="1"/><value>a</value></values>"#;
+    assert_eq!(
+        read_all::<Values>(xml).unwrap(),
+        Values {
+            values: vec![Value::Number { n: 1 }, Value::Text { text: "a".into() }]
+        }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use hard_xml::{ErrorDetail, ErrorKind, XmlError, XmlRead, XmlResult, XmlWrite};
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(
+    tag = "config",
+    const_attr("version", "1.2", validate),
+    const_attr("xmlns", "urn:example")
+)]
+struct Config {
+    #[xml(attr = "name")]
+    name: String,
+    #[xml(child = "item")]
+    items: Vec<Item>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+enum Item {
+    #[xml(tag = "item", const_attr("kind", "plain"))]
+    Plain,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let config = Config {
+        name: "foo".into(),
+        items: vec![Item::Plain],
+    };
+
+    let xml =
+        r#"<config version="1.2" xmlns="urn:example" name="foo"><item kind="plain"/></config>"#;
+    assert_eq!(config.to_string()?, xml);
+    assert_eq!(Config::from_str(xml)?, config);
+
+    // attributes which aren't validated can be anything or absent
+    assert_eq!(
+        Config::from_str(r#"<config name="foo" version="1.2" xmlns="other"><item/></config>"#)?,
+        config
+    );
+
+    let err = Config::from_str(r#"<config version="2.0" name="foo"/>"#).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Mismatch);
+    assert_eq!(err.name(), Some("Config"));
+    assert_eq!(err.field(), Some("version"));
+    match err.detail() {
+        ErrorDetail::UnexpectedValue { expected, found } => {
+            assert_eq!(expected, "1.2");
+            assert_eq!(found, "2.0");
+        }
+        detail => panic!("unexpected error: {:?}", detail),
+    }
+
+    assert!(matches!(
+        Config::from_str(r#"<config name="foo"/>"#),
+        Err(err) if err.field() == Some("version") && matches!(err.detail(), ErrorDetail::MissingField { tag, expected }
+            if tag == "config" && expected == r#"attribute "version""#)
+    ));
+
+    assert_eq!(
+        XmlError::new(ErrorDetail::UnexpectedValue {
+            expected: "1.2".into(),
+            found: "2.0".into(),
+        })
+        .with_context("Config", Some("version"))
+        .to_string(),
+        r#"unexpected value in XML of "Config": "version" is "2.0", expected "1.2""#
+    );
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use hard_xml::{XmlRead, XmlResult, XmlSchemaInfo, XmlWrite};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "matrix")]
+struct Matrix<const N: usize> {
+    #[xml(child = "row")]
+    rows: Vec<Row<N>>,
+}
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "row")]
+struct Row<const N: usize> {
+    #[xml(attr = "values")]
+    values: Values<N>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "grid")]
+struct Grid<'a, T, const W: usize = 2> {
+    #[xml(attr = "name")]
+    name: std::borrow::Cow<'a, str>,
+    #[xml(child = "row")]
+    rows: Vec<T>,
+}
+
+#[derive(PartialEq, Debug)]
+struct Values<const N: usize>([f64; N]);
+
+impl<const N: usize> FromStr for Values<N> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let values = s
+            .split_whitespace()
+            .map(|value| value.parse::<f64>().map_err(|err| err.to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        values
+            .try_into()
+            .map(Values)
+            .map_err(|values: Vec<f64>| format!("expected {} values, got {}", N, values.len()))
+    }
+}
+
+impl<const N: usize> fmt::Display for Values<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, value) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}", value)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<matrix><row values="1 2"/><row values="3 4.5"/></matrix>"#;
+    let matrix = Matrix::<2>::from_str(xml)?;
+    assert_eq!(matrix.rows[1].values, Values([3.0, 4.5]));
+    assert_eq!(matrix.to_string()?, xml);
+    assert_eq!(Matrix::<2>::elements()[0].tag, "matrix");
+
+    assert!(Matrix::<3>::from_str(xml).is_err());
+
+    let xml = r#"<grid name="g"><row values="1 2"/></grid>"#;
+    let grid = Grid::<Row<2>>::from_str(xml)?;
+    assert_eq!(grid.rows.len(), 1);
+    assert_eq!(grid.to_string()?, xml);
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+mod facade {
+    pub use hard_xml as xml;
+}
+
+mod app {
+    // shadows the crate, so only the re-exported path resolves
+    #[allow(dead_code)]
+    mod hard_xml {}
+
+    use crate::facade::xml::{XmlRead, XmlSchemaInfo, XmlWrite};
+    use std::borrow::Cow;
+
+    #[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+    #[xml(tag = "note", crate = "crate::facade::xml")]
+    pub struct Note<'a> {
+        #[xml(attr = "id")]
+        pub id: u32,
+        #[xml(flatten_text = "body")]
+        pub body: Cow<'a, str>,
+        #[xml(child = "tag")]
+        pub tags: Vec<Tag>,
+    }
+
+    #[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+    #[xml(crate = "crate::facade::xml")]
+    pub enum Tag {
+        #[xml(tag = "tag")]
+        Tag(#[xml(text)] String),
+    }
+}
+
+use app::{Note, Tag};
+use hard_xml::{XmlRead, XmlResult, XmlSchemaInfo, XmlWrite};
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<note id="1"><tag>a</tag><body>hi</body></note>"#;
+    let note = Note::from_str(xml)?;
+    assert_eq!(note.body, "hi");
+    assert_eq!(note.tags, vec![Tag::Tag("a".into())]);
+    assert_eq!(note.to_string()?, xml);
+    assert_eq!(Note::elements()[0].tag, "note");
+
+    Ok(())
+}
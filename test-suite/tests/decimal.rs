@@ -0,0 +1,44 @@
+use hard_xml::{ErrorDetail, XmlRead, XmlResult, XmlWrite};
+use rust_decimal::Decimal;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "line")]
+struct Line {
+    #[xml(attr = "price")]
+    price: Decimal,
+    #[xml(attr = "discount")]
+    discount: Option<Decimal>,
+    #[xml(flatten_text = "tax")]
+    taxes: Vec<Decimal>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    // the scale is kept
+    let xml = r#"<line price="19.90" discount="-0.10"><tax>0.2000</tax><tax>12345678901234567890.123456789</tax></line>"#;
+    let line = Line::from_str(xml)?;
+    assert_eq!(line.price, Decimal::new(1990, 2));
+    assert_eq!(line.price.scale(), 2);
+    assert_eq!(line.to_string()?, xml);
+
+    let line = Line::from_str(r#"<line price=" +5. "><tax>.5</tax></line>"#)?;
+    assert_eq!(line.price, Decimal::new(5, 0));
+    assert_eq!(line.taxes, vec![Decimal::new(5, 1)]);
+
+    for price in ["1e3", "1_000", "", "-", "."] {
+        let xml = format!(r#"<line price="{}"/>"#, price);
+        assert!(
+            matches!(Line::from_str(&xml), Err(err) if matches!(err.detail(), ErrorDetail::FromStr { .. }))
+        );
+    }
+
+    // too many digits to be read exactly
+    assert!(Line::from_str(r#"<line price="0.12345678901234567890123456789012"/>"#).is_err());
+
+    Ok(())
+}
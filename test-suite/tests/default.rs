@@ -1,4 +1,4 @@
-use hard_xml::{XmlRead, XmlResult, XmlWrite};
+use hard_xml::{XmlRead, XmlReader, XmlResult, XmlWrite};
 use std::borrow::Cow;
 
 #[derive(Default, XmlWrite, XmlRead, PartialEq, Debug)]
@@ -19,6 +19,35 @@ struct Child<'a> {
     att2: bool,
 }
 
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "server", partial)]
+struct Server<'a> {
+    #[xml(attr = "host", default = "default_host")]
+    host: Cow<'a, str>,
+    #[xml(attr = "port", default = "default_port")]
+    port: u16,
+    #[xml(flatten_text = "retries", default = "default_retries")]
+    retries: Option<u8>,
+    #[xml(flatten_text = "path", default = "default_paths")]
+    paths: Vec<Cow<'a, str>>,
+}
+
+fn default_host() -> Cow<'static, str> {
+    "localhost".into()
+}
+
+fn default_port() -> u16 {
+    443
+}
+
+fn default_retries() -> Option<u8> {
+    Some(3)
+}
+
+fn default_paths() -> Vec<Cow<'static, str>> {
+    vec!["/".into()]
+}
+
 #[test]
 fn test() -> XmlResult<()> {
     let _ = env_logger::builder()
@@ -68,3 +97,43 @@ fn test() -> XmlResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_default_fn() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let defaults = Server {
+        host: "localhost".into(),
+        port: 443,
+        retries: Some(3),
+        paths: vec!["/".into()],
+    };
+
+    assert_eq!(Server::from_str(r#"<server/>"#)?, defaults);
+
+    assert_eq!(
+        Server::from_str(
+            r#"<server host="a" port="80"><retries>1</retries><path>/b</path></server>"#
+        )?,
+        Server {
+            host: "a".into(),
+            port: 80,
+            retries: Some(1),
+            paths: vec!["/b".into()],
+        }
+    );
+
+    // fields which aren't selected get their default too
+    assert_eq!(
+        Server::read_fields(&mut XmlReader::new(r#"<server port="80"/>"#), &["port"])?,
+        Server {
+            port: 80,
+            ..defaults
+        }
+    );
+
+    Ok(())
+}
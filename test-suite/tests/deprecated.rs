@@ -0,0 +1,84 @@
+use hard_xml::{XmlRead, XmlReader, XmlResult, XmlWrite};
+use std::sync::{Arc, Mutex};
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "server")]
+struct Server {
+    #[xml(attr = "host", deprecated = "hostname", deprecated = "addr")]
+    host: String,
+    #[xml(attr = "ports", deprecated = "port")]
+    ports: Vec<u16>,
+    #[xml(flatten_text = "timeout", deprecated = "timeout-secs")]
+    timeout: Option<u32>,
+    #[xml(flatten_text = "tag", deprecated = "label")]
+    tags: Vec<String>,
+}
+
+fn read(xml: &str) -> XmlResult<(Server, Vec<String>)> {
+    let found = Arc::new(Mutex::new(Vec::new()));
+    let hook_found = found.clone();
+
+    let mut reader = XmlReader::new(xml).with_deprecation_hook(move |element, field, name| {
+        hook_found
+            .lock()
+            .unwrap()
+            .push(format!("{}.{}: {}", element, field, name));
+    });
+
+    let server = Server::from_reader(&mut reader)?;
+    let found = found.lock().unwrap().clone();
+    Ok((server, found))
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let server = Server {
+        host: "a".into(),
+        ports: vec![80, 443],
+        timeout: Some(5),
+        tags: vec!["x".into(), "y".into()],
+    };
+
+    let (read_server, found) = read(
+        r#"<server host="a" ports="80" ports="443"><timeout>5</timeout><tag>x</tag><tag>y</tag></server>"#,
+    )?;
+    assert_eq!(read_server, server);
+    assert!(found.is_empty());
+
+    let (read_server, found) = read(
+        r#"<server addr="a" port="80" ports="443"><timeout-secs>5</timeout-secs><label>x</label><tag>y</tag></server>"#,
+    )?;
+    assert_eq!(read_server, server);
+    assert_eq!(
+        found,
+        vec![
+            "Server.host: addr",
+            "Server.ports: port",
+            "Server.timeout: timeout-secs",
+            "Server.tags: label",
+        ]
+    );
+
+    assert_eq!(
+        server.to_string()?,
+        r#"<server host="a" ports="80" ports="443"><timeout>5</timeout><tag>x</tag><tag>y</tag></server>"#
+    );
+
+    // without a hook the old names are still read
+    assert_eq!(
+        Server::from_str(r#"<server hostname="a"><label>x</label></server>"#)?,
+        Server {
+            host: "a".into(),
+            ports: vec![],
+            timeout: None,
+            tags: vec!["x".into()],
+        }
+    );
+
+    Ok(())
+}
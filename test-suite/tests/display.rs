@@ -45,5 +45,18 @@ fn test() -> XmlResult<()> {
         r#"<root foo="foo" bar="bar"/>"#
     );
 
+    // written to the formatter directly, escaping values on the way
+    assert_eq!(
+        format!(
+            "{}",
+            (Root {
+                foo: Some(Foo),
+                bar: "<&>".into()
+            })
+            .display()
+        ),
+        r#"<root foo="foo" bar="&lt;&amp;&gt;"/>"#
+    );
+
     Ok(())
 }
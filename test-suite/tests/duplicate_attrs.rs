@@ -0,0 +1,87 @@
+use hard_xml::filter::Rename;
+use hard_xml::{DuplicateAttrs, ErrorDetail, XmlRead, XmlReader, XmlResult};
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "entry")]
+struct Entry {
+    #[xml(attr = "id")]
+    id: usize,
+    #[xml(attr = "rank")]
+    ranks: Vec<u8>,
+    #[xml(child = "entry")]
+    entries: Vec<Entry>,
+}
+
+fn read(xml: &str, duplicate_attrs: DuplicateAttrs) -> XmlResult<Entry> {
+    let mut reader = XmlReader::new(xml).with_duplicate_attrs(duplicate_attrs);
+    Entry::from_reader(&mut reader)
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<entry id="1" rank="2" id="3" rank="4"><entry id="5" rank="6"/></entry>"#;
+
+    let entry = read(xml, DuplicateAttrs::Allow)?;
+    assert_eq!((entry.id, entry.ranks), (3, vec![2, 4]));
+
+    let entry = read(xml, DuplicateAttrs::First)?;
+    assert_eq!((entry.id, entry.ranks), (1, vec![2]));
+
+    let entry = read(xml, DuplicateAttrs::Last)?;
+    assert_eq!((entry.id, entry.ranks), (3, vec![4]));
+
+    // names are only compared within a start tag
+    assert_eq!(entry.entries[0].id, 5);
+    assert_eq!(entry.entries[0].ranks, vec![6]);
+
+    let err = read(xml, DuplicateAttrs::Error).unwrap_err();
+    assert!(matches!(
+        err.detail(),
+        ErrorDetail::DuplicateAttribute { tag, attribute } if tag == "entry" && attribute == "id"
+    ));
+    assert_eq!(
+        err.to_string(),
+        r#"duplicate attribute in XML tag "entry": "id" at 1:24 in entry"#
+    );
+
+    // names are compared once the filters are applied
+    let xml = r#"<entry id="1" rank="2" position="4"/>"#;
+    for (duplicate_attrs, ranks) in [
+        (DuplicateAttrs::Allow, vec![2, 4]),
+        (DuplicateAttrs::First, vec![2]),
+        (DuplicateAttrs::Last, vec![4]),
+    ] {
+        let mut reader = XmlReader::new(xml)
+            .with_filter(Rename::new("position", "rank"))
+            .with_duplicate_attrs(duplicate_attrs);
+        assert_eq!(Entry::from_reader(&mut reader)?.ranks, ranks);
+    }
+    let mut reader = XmlReader::new(xml)
+        .with_filter(Rename::new("position", "rank"))
+        .with_duplicate_attrs(DuplicateAttrs::Error);
+    let err = Entry::from_reader(&mut reader).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        r#"duplicate attribute in XML tag "entry": "rank" at 1:24 in entry"#
+    );
+
+    // a single occurrence of every attribute is read under any policy
+    let xml = r#"<entry id="1" rank="2"><entry id="3"/></entry>"#;
+    for duplicate_attrs in [
+        DuplicateAttrs::Allow,
+        DuplicateAttrs::Error,
+        DuplicateAttrs::First,
+        DuplicateAttrs::Last,
+    ] {
+        let entry = read(xml, duplicate_attrs)?;
+        assert_eq!((entry.id, entry.ranks), (1, vec![2]));
+        assert_eq!(entry.entries[0].id, 3);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use hard_xml::{ErrorDetail, XmlRead, XmlReader, XmlResult};
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "name")]
+struct Name {
+    #[xml(text)]
+    value: String,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "person")]
+struct Person {
+    #[xml(child = "name")]
+    last: Name,
+    #[xml(child = "nick", duplicate = "first")]
+    first: Option<Nick>,
+    #[xml(child = "id", wrapper = "ids", duplicate = "error")]
+    id: Option<Id>,
+    #[xml(flatten_text = "alias", duplicate = "first")]
+    alias: Option<String>,
+    #[xml(flatten_text = "age", duplicate = "error")]
+    age: Option<u8>,
+    #[xml(flatten)]
+    contact: Contact,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "nick")]
+struct Nick {
+    #[xml(text)]
+    value: String,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "id")]
+struct Id {
+    #[xml(attr = "value")]
+    value: u32,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+struct Contact {
+    #[xml(child = "email", duplicate = "error")]
+    email: Option<Email>,
+    #[xml(flatten_text = "phone", duplicate = "error")]
+    phone: Option<String>,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "email")]
+struct Email {
+    #[xml(text)]
+    address: String,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let person = Person::from_str(
+        "<person><name>a</name><nick>b</nick><name>c</name><nick>d</nick></person>",
+    )?;
+    assert_eq!(person.last.value, "c");
+    assert_eq!(person.first.unwrap().value, "b");
+
+    // duplicates in the same wrapper or in another one are errors
+    let xml = r#"<person><name>a</name><ids><id value="1"/><id value="2"/></ids></person>"#;
+    let err = Person::from_str(xml).unwrap_err();
+    assert_eq!(err.field(), Some("id"));
+    match (err.detail(), err.position()) {
+        (ErrorDetail::DuplicateField { first: Some(first) }, Some(position)) => {
+            assert_eq!((first.column, position.column), (28, 43));
+            assert_eq!(first.path, "person > ids > id");
+            assert_eq!(position.path, "person > ids > id[2]");
+        }
+        err => panic!("unexpected error: {:?}", err),
+    }
+    assert_eq!(
+        err.to_string(),
+        r#"duplicate field in XML of "Person": "id" (first at 1:28 in person > ids > id) at 1:43 in person > ids > id[2]"#
+    );
+
+    let xml =
+        r#"<person><name>a</name><ids><id value="1"/></ids><ids><id value="2"/></ids></person>"#;
+    assert!(matches!(
+        Person::from_str(xml),
+        Err(err) if matches!(err.detail(), ErrorDetail::DuplicateField { .. })
+    ));
+
+    // text of the children too
+    let person = Person::from_str(
+        "<person><name>a</name><alias>b</alias><age>3</age><alias>c</alias></person>",
+    )?;
+    assert_eq!(person.alias.as_deref(), Some("b"));
+    assert_eq!(person.age, Some(3));
+
+    let xml = "<person><name>a</name><age>3</age><age>x</age></person>";
+    let err = Person::from_str(xml).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        r#"duplicate field in XML of "Person": "age" (first at 1:23 in person > age) at 1:35 in person > age[2]"#
+    );
+
+    // flattened fields too
+    let xml = "<person><name>a</name><email>b</email><email>c</email></person>";
+    let err = Person::from_str(xml).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        r#"duplicate field in XML of "Contact": "email" (first at 1:23 in person > email) at 1:39 in person > email[2]"#
+    );
+
+    // the first one is kept when errors are collected
+    let mut reader = XmlReader::new(xml).with_error_collection();
+    let person = Person::from_reader(&mut reader)?;
+    assert_eq!(person.contact.email.unwrap().address, "b");
+    let errors = reader.finish(Ok(())).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0].detail(),
+        ErrorDetail::DuplicateField { .. }
+    ));
+
+    let xml = "<person><name>a</name><phone>1</phone><phone>2</phone></person>";
+    let mut reader = XmlReader::new(xml).with_error_collection();
+    let person = Person::from_reader(&mut reader)?;
+    assert_eq!(person.contact.phone.as_deref(), Some("1"));
+    let errors = reader.finish(Ok(())).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].field(), Some("phone"));
+
+    Ok(())
+}
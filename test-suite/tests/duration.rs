@@ -0,0 +1,48 @@
+use hard_xml::{ErrorDetail, XmlRead, XmlResult, XmlWrite, XsdDuration};
+use std::time::Duration;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "podcast")]
+struct Podcast {
+    #[xml(attr = "ttl")]
+    ttl: Option<Duration>,
+    #[xml(attr = "period")]
+    period: XsdDuration,
+    #[xml(flatten_text = "length")]
+    lengths: Vec<Duration>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<podcast ttl="PT1H" period="-P1Y2M"><length>PT45M30.5S</length><length>P1D</length></podcast>"#;
+    let podcast = Podcast::from_str(xml)?;
+    assert_eq!(podcast.ttl, Some(Duration::from_secs(3600)));
+    assert!(podcast.period.negative);
+    assert_eq!((podcast.period.years, podcast.period.months), (1, 2));
+    assert_eq!(
+        podcast.lengths,
+        vec![Duration::from_millis(2_730_500), Duration::from_secs(86400)]
+    );
+    assert_eq!(podcast.to_string()?, xml);
+
+    // normalized when written
+    let podcast = Podcast::from_str(r#"<podcast ttl="PT90M" period="PT0M"/>"#)?;
+    assert_eq!(
+        podcast.to_string()?,
+        r#"<podcast ttl="PT1H30M" period="PT0S"/>"#
+    );
+
+    for ttl in ["P1M", "-PT1S", "1H", "PT"] {
+        let xml = format!(r#"<podcast ttl="{}" period="P1D"/>"#, ttl);
+        assert!(
+            matches!(Podcast::from_str(&xml), Err(err) if matches!(err.detail(), ErrorDetail::FromStr { .. }))
+        );
+    }
+
+    Ok(())
+}
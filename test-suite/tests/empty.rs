@@ -4,6 +4,46 @@ use hard_xml::{XmlRead, XmlResult, XmlWrite};
 #[xml(tag = "empty")]
 struct Empty;
 
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "item")]
+struct Item {
+    #[xml(attr = "id")]
+    id: usize,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "feed")]
+struct Feed {
+    #[xml(child = "item", wrapper = "items")]
+    omitted: Vec<Item>,
+    #[xml(child = "item", wrapper = "expanded", empty = "expanded")]
+    expanded: Vec<Item>,
+    #[xml(child = "item", wrapper = "self_closing", empty = "self_closing")]
+    self_closing: Vec<Item>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "feed", empty = "self_closing")]
+struct DefaultFeed {
+    #[xml(child = "item", wrapper = "items")]
+    items: Vec<Item>,
+    #[xml(child = "item", wrapper = "others", empty = "omit")]
+    others: Vec<Item>,
+    #[xml(child = "item", wrapper = "first")]
+    first: Option<Item>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "point", empty = "expanded")]
+struct Point {
+    #[xml(flatten_text = "tags", list)]
+    tags: Vec<String>,
+    #[xml(flatten_text = "coords", list, empty = "self_closing")]
+    coords: Vec<f64>,
+    #[xml(flatten_text = "ids", list, empty = "omit")]
+    ids: Vec<usize>,
+}
+
 #[test]
 fn test() -> XmlResult<()> {
     let _ = env_logger::builder()
@@ -15,5 +55,50 @@ fn test() -> XmlResult<()> {
 
     assert_eq!(Empty::from_str("<empty/>")?, Empty);
 
+    let feed = Feed {
+        omitted: vec![],
+        expanded: vec![],
+        self_closing: vec![],
+    };
+    let xml = r#"<feed><expanded></expanded><self_closing/></feed>"#;
+    assert_eq!(feed.to_string()?, xml);
+    assert_eq!(Feed::from_str(xml)?, feed);
+
+    let feed = Feed {
+        omitted: vec![Item { id: 1 }],
+        expanded: vec![Item { id: 2 }],
+        self_closing: vec![Item { id: 3 }],
+    };
+    let xml = r#"<feed><items><item id="1"/></items><expanded><item id="2"/></expanded><self_closing><item id="3"/></self_closing></feed>"#;
+    assert_eq!(feed.to_string()?, xml);
+    assert_eq!(Feed::from_str(xml)?, feed);
+
+    let feed = DefaultFeed {
+        items: vec![],
+        others: vec![],
+        first: None,
+    };
+    let xml = r#"<feed><items/><first/></feed>"#;
+    assert_eq!(feed.to_string()?, xml);
+    assert_eq!(DefaultFeed::from_str(xml)?, feed);
+
+    let point = Point {
+        tags: vec![],
+        coords: vec![],
+        ids: vec![],
+    };
+    let xml = r#"<point><tags></tags><coords/></point>"#;
+    assert_eq!(point.to_string()?, xml);
+    assert_eq!(Point::from_str(xml)?, point);
+
+    let point = Point {
+        tags: vec!["a".into()],
+        coords: vec![1.5, 2.0],
+        ids: vec![3],
+    };
+    let xml = r#"<point><tags>a</tags><coords>1.5 2</coords><ids>3</ids></point>"#;
+    assert_eq!(point.to_string()?, xml);
+    assert_eq!(Point::from_str(xml)?, point);
+
     Ok(())
 }
@@ -0,0 +1,48 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "field")]
+struct Field<'a> {
+    #[xml(attr = "value")]
+    value: Option<Cow<'a, str>>,
+    #[xml(attr = "label", empty = "omit")]
+    label: Option<Cow<'a, str>>,
+    #[xml(attr = "note", empty = "expanded")]
+    note: String,
+    #[xml(attr = "hint", empty = "omit")]
+    hint: String,
+    #[xml(attr = "tags", list, empty = "omit")]
+    tags: Vec<String>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let field = Field::from_str(r#"<field note="" hint=""/>"#)?;
+    assert_eq!(field.value, None);
+    assert_eq!(field.label, None);
+    assert_eq!(field.to_string()?, r#"<field note=""/>"#);
+
+    let field = Field::from_str(r#"<field value="" label="" note="" hint="" tags=""/>"#)?;
+    assert_eq!(field.value.as_deref(), Some(""));
+    assert_eq!(field.label.as_deref(), Some(""));
+    assert_eq!(field.to_string()?, r#"<field value="" note=""/>"#);
+
+    let field = Field {
+        value: Some("a".into()),
+        label: Some("b".into()),
+        note: "c".into(),
+        hint: "d".into(),
+        tags: vec!["e".into()],
+    };
+    let xml = r#"<field value="a" label="b" note="c" hint="d" tags="e"/>"#;
+    assert_eq!(field.to_string()?, xml);
+    assert_eq!(Field::from_str(xml)?, field);
+
+    Ok(())
+}
@@ -33,6 +33,7 @@ enum CD {
 }
 
 #[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[allow(clippy::upper_case_acronyms)]
 enum ABCDEFG {
     #[xml(tag = "a", tag = "b")]
     AB(AB),
@@ -54,6 +55,19 @@ enum ABCDEFG {
     },
 }
 
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+enum Shape {
+    #[xml(tag = "group")]
+    Group {
+        #[xml(attr = "id")]
+        id: u32,
+        #[xml(child = "a", child = "b")]
+        children: Vec<AB>,
+    },
+    #[xml(tag = "label")]
+    Label(#[xml(text)] String),
+}
+
 #[test]
 fn test() -> XmlResult<()> {
     let _ = env_logger::builder()
@@ -79,5 +93,24 @@ fn test() -> XmlResult<()> {
         ABCDEFG::F { foo: "foo".into() }
     );
 
+    let xml = r#"<group id="1"><a/><b/></group>"#;
+
+    let group = Shape::from_str(xml)?;
+
+    assert_eq!(
+        group,
+        Shape::Group {
+            id: 1,
+            children: vec![AB::A(A), AB::B(B)],
+        }
+    );
+
+    assert_eq!(group.to_string()?, xml);
+
+    assert_eq!(
+        Shape::from_str("<label>foo</label>")?,
+        Shape::Label("foo".into())
+    );
+
     Ok(())
 }
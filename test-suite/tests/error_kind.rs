@@ -0,0 +1,64 @@
+use hard_xml::{ErrorDetail, ErrorKind, XmlRead, XmlReader, XmlResult};
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "item")]
+struct Item {
+    #[xml(attr = "id")]
+    id: u32,
+    #[xml(child = "name", duplicate = "error")]
+    name: Option<Name>,
+    #[xml(flatten_text = "tag", max = 2)]
+    tags: Vec<String>,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "name")]
+struct Name {
+    #[xml(text)]
+    value: String,
+}
+
+fn kind(xml: &str) -> ErrorKind {
+    Item::from_str(xml).unwrap_err().kind()
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    assert_eq!(kind(r#"<item id="1"></name>"#), ErrorKind::Syntax);
+    assert_eq!(kind(r#"<item id="1">"#), ErrorKind::Syntax);
+    assert_eq!(
+        kind(r#"<item id="1"><name><b/></name></item>"#),
+        ErrorKind::Mismatch
+    );
+    assert_eq!(kind(r#"<item/>"#), ErrorKind::Missing);
+    assert_eq!(kind(r#"<item id="x"/>"#), ErrorKind::Conversion);
+    assert_eq!(
+        kind(r#"<item id="1"><name>a</name><name>b</name></item>"#),
+        ErrorKind::Duplicate
+    );
+    assert_eq!(
+        kind(r#"<item id="1"><tag>a</tag><tag>b</tag><tag>c</tag></item>"#),
+        ErrorKind::Duplicate
+    );
+
+    let mut reader = XmlReader::new(r#"<item id="1"/>"#).with_max_depth(0);
+    let err = Item::from_reader(&mut reader).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Limit);
+
+    // the type and field being read are known apart from the detail
+    let err = Item::from_str(r#"<item id="1"><name>a</name><name>b</name></item>"#).unwrap_err();
+    assert_eq!(err.name(), Some("Item"));
+    assert_eq!(err.field(), Some("name"));
+    assert!(matches!(
+        err.detail(),
+        ErrorDetail::DuplicateField { first: Some(_) }
+    ));
+    assert_eq!(err.position().map(|pos| pos.column), Some(28));
+
+    Ok(())
+}
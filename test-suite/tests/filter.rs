@@ -0,0 +1,75 @@
+use hard_xml::filter::{DropAttribute, Rename, UnwrapElement};
+use hard_xml::matcher::Exact;
+use hard_xml::xmlparser::Token;
+use hard_xml::{XmlRead, XmlReader, XmlResult, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "item")]
+struct Item<'a> {
+    #[xml(attr = "id")]
+    id: usize,
+    #[xml(attr = "name")]
+    name: Option<Cow<'a, str>>,
+    #[xml(text)]
+    text: Cow<'a, str>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "n:feed")]
+struct Feed<'a> {
+    #[xml(child = "item")]
+    items: Vec<Item<'a>>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<o:feed><wrapper><item id="1" name="a">foo</item></wrapper><item id="2" name="b">b<!-- c -->ar</item></o:feed>"#;
+
+    let mut reader = XmlReader::new(xml)
+        .with_name_matcher(Exact)
+        .with_filter(Rename::new("o:feed", "n:feed"))
+        .with_filter(UnwrapElement::new("wrapper"))
+        .with_filter(DropAttribute::new("name"))
+        .with_filter(|token| match token {
+            Token::Comment { .. } => None,
+            token => Some(token),
+        });
+
+    assert_eq!(
+        Feed::from_reader(&mut reader)?,
+        Feed {
+            items: vec![
+                Item {
+                    id: 1,
+                    name: None,
+                    text: "foo".into()
+                },
+                Item {
+                    id: 2,
+                    name: None,
+                    text: "bar".into()
+                }
+            ]
+        }
+    );
+
+    // without filters
+    assert_eq!(
+        Feed::from_str(xml)?,
+        Feed {
+            items: vec![Item {
+                id: 2,
+                name: Some("b".into()),
+                text: "bar".into()
+            }]
+        }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use hard_xml::{XmlRead, XmlResult, XmlSchemaInfo, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+struct Common<'a> {
+    #[xml(attr = "id")]
+    id: Cow<'a, str>,
+    #[xml(attr = "created")]
+    created: Option<u64>,
+    #[xml(child = "tag")]
+    tags: Vec<Tag<'a>>,
+    #[xml(flatten)]
+    audit: Audit,
+}
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+struct Audit {
+    #[xml(attr = "modified", default)]
+    modified: u64,
+    #[xml(flatten_text = "by")]
+    by: Option<String>,
+    #[xml(skip)]
+    dirty: bool,
+}
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "tag")]
+struct Tag<'a> {
+    #[xml(text)]
+    name: Cow<'a, str>,
+}
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "document")]
+struct Document<'a> {
+    #[xml(attr = "title")]
+    title: Cow<'a, str>,
+    #[xml(flatten)]
+    common: Common<'a>,
+    #[xml(flatten_text = "body")]
+    body: Cow<'a, str>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+enum Item<'a> {
+    #[xml(tag = "file")]
+    File {
+        #[xml(flatten)]
+        common: Common<'a>,
+    },
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "ordered", ordered)]
+struct Ordered {
+    #[xml(flatten_text = "a")]
+    a: String,
+    #[xml(flatten)]
+    audit: Audit,
+    #[xml(flatten_text = "b")]
+    b: String,
+}
+
+// `modified` is read by both, which isn't caught by the derive
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "shadowed")]
+struct Shadowed {
+    #[xml(attr = "modified")]
+    modified: Option<u64>,
+    #[xml(flatten)]
+    audit: Audit,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let document = Document {
+        title: "foo".into(),
+        common: Common {
+            id: "1".into(),
+            created: Some(10),
+            tags: vec![Tag { name: "a".into() }, Tag { name: "b".into() }],
+            audit: Audit {
+                modified: 20,
+                by: Some("bar".into()),
+                dirty: false,
+            },
+        },
+        body: "baz".into(),
+    };
+
+    let xml = r#"<document title="foo" id="1" created="10" modified="20"><body>baz</body><tag>a</tag><tag>b</tag><by>bar</by></document>"#;
+
+    assert_eq!(document.to_string()?, xml);
+    assert_eq!(Document::from_str(xml)?, document);
+
+    // flattened fields are read in any order, unknown ones are skipped
+    assert_eq!(
+        Document::from_str(
+            r#"<document id="1" unknown="" title="foo" modified="20" created="10"><tag>a</tag><by>bar</by><other/><body>baz</body><tag>b</tag></document>"#
+        )?,
+        document
+    );
+
+    assert_eq!(
+        Item::from_str(r#"<file id="2"/>"#)?,
+        Item::File {
+            common: Common {
+                id: "2".into(),
+                created: None,
+                tags: vec![],
+                audit: Audit {
+                    modified: 0,
+                    by: None,
+                    dirty: false,
+                },
+            },
+        }
+    );
+    assert_eq!(
+        Item::from_str(r#"<file id="2"/>"#)?.to_string()?,
+        r#"<file id="2" modified="0"/>"#
+    );
+
+    // missing fields of flattened structs are reported
+    assert!(Item::from_str(r#"<file/>"#).is_err());
+
+    // flattened children of ordered elements are written in place
+    let ordered = Ordered {
+        a: "x".into(),
+        audit: Audit {
+            modified: 1,
+            by: Some("y".into()),
+            dirty: true,
+        },
+        b: "z".into(),
+    };
+    let xml = r#"<ordered modified="1"><a>x</a><by>y</by><b>z</b></ordered>"#;
+    assert_eq!(ordered.to_string()?, xml);
+    assert_eq!(
+        Ordered::from_str(xml)?,
+        Ordered {
+            audit: Audit {
+                dirty: false,
+                ..ordered.audit
+            },
+            ..ordered
+        }
+    );
+
+    // the field of the embedding struct takes the name
+    assert_eq!(
+        Shadowed::from_str(r#"<shadowed modified="5"/>"#)?,
+        Shadowed {
+            modified: Some(5),
+            audit: Audit {
+                modified: 0,
+                by: None,
+                dirty: false,
+            },
+        }
+    );
+
+    let fields = &Document::elements()[0].fields;
+    let names = fields.iter().map(|field| field.name).collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        vec!["title", "id", "created", "tags", "modified", "by", "body"]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "selection")]
+struct Selection {
+    #[xml(flatten_text = "id")]
+    ids: Vec<u64>,
+    #[xml(flatten_text = "weight")]
+    weights: Vec<f32>,
+    #[xml(flatten_text = "flag")]
+    flags: Vec<bool>,
+    #[xml(flatten_text = "initial", cdata)]
+    initials: Vec<char>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<selection><id>1</id><weight>0.5</weight><id>20</id><flag>1</flag><id>300</id><initial>a</initial></selection>"#;
+    let selection = Selection::from_str(xml)?;
+    assert_eq!(
+        selection,
+        Selection {
+            ids: vec![1, 20, 300],
+            weights: vec![0.5],
+            flags: vec![true],
+            initials: vec!['a'],
+        }
+    );
+    assert_eq!(
+        selection.to_string()?,
+        r#"<selection><id>1</id><id>20</id><id>300</id><weight>0.5</weight><flag>true</flag><initial><![CDATA[a]]></initial></selection>"#
+    );
+
+    let selection = Selection::from_str(r#"<selection/>"#)?;
+    assert!(selection.ids.is_empty());
+    assert_eq!(selection.to_string()?, r#"<selection/>"#);
+
+    assert!(Selection::from_str(r#"<selection><id>1</id><id>x</id></selection>"#).is_err());
+    assert!(Selection::from_str(r#"<selection><id/></selection>"#).is_err());
+
+    Ok(())
+}
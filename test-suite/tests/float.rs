@@ -0,0 +1,47 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "range")]
+struct Range {
+    #[xml(attr = "min")]
+    min: f64,
+    #[xml(attr = "max", precision = 2)]
+    max: f32,
+    #[xml(attr = "steps", list)]
+    steps: Vec<f64>,
+    #[xml(flatten_text = "default")]
+    default: Option<f64>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<range min="-INF" max="INF" steps="0.5 NaN -INF"><default>1.5</default></range>"#;
+    let range = Range::from_str(xml)?;
+    assert_eq!(range.min, f64::NEG_INFINITY);
+    assert_eq!(range.max, f32::INFINITY);
+    assert_eq!(range.steps[0], 0.5);
+    assert!(range.steps[1].is_nan());
+    assert_eq!(range.steps[2], f64::NEG_INFINITY);
+    assert_eq!(range.default, Some(1.5));
+    assert_eq!(range.to_string()?, xml);
+
+    let range = Range {
+        min: 0.0,
+        max: f32::NAN,
+        steps: vec![],
+        default: Some(f64::INFINITY),
+    };
+    assert_eq!(
+        range.to_string()?,
+        r#"<range min="0" max="NaN"><default>INF</default></range>"#
+    );
+
+    assert!(Range::from_str(r#"<range min="-IN" max="0"/>"#).is_err());
+
+    Ok(())
+}
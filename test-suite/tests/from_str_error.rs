@@ -0,0 +1,88 @@
+use hard_xml::{ErrorDetail, ErrorKind, XmlRead, XmlResult};
+use std::net::IpAddr;
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "host")]
+struct Host {
+    #[xml(attr = "port")]
+    port: u16,
+    #[xml(attr = "mac", hex)]
+    mac: Option<[u8; 6]>,
+    #[xml(flatten_text = "address")]
+    addresses: Vec<std::net::IpAddr>,
+    #[xml(flatten_text = "enabled")]
+    enabled: Option<bool>,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "peer")]
+struct Peer {
+    #[xml(text)]
+    address: IpAddr,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let err = Host::from_str(r#"<host port="80000"/>"#).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Conversion);
+    assert_eq!((err.name(), err.field()), (Some("Host"), Some("port")));
+    match err.detail() {
+        ErrorDetail::FromStr {
+            value,
+            type_name,
+            source,
+        } => {
+            assert_eq!((value.as_str(), type_name.as_str()), ("80000", "u16"));
+            assert_eq!(source.to_string(), "number too large to fit in target type");
+        }
+        detail => panic!("unexpected error: {:?}", detail),
+    }
+    assert_eq!(
+        err.to_string(),
+        r#"error parsing XML value "80000" as u16 in "Host": "port" (number too large to fit in target type) at 1:7 in host"#
+    );
+
+    // types are named as they're written
+    let xml =
+        "<host port=\"80\">\n  <address>::1</address>\n  <address>localhost</address>\n</host>";
+    assert_eq!(
+        Host::from_str(xml).unwrap_err().to_string(),
+        r#"error parsing XML value "localhost" as std::net::IpAddr in "Host": "addresses" (invalid IP address syntax) at 3:21 in host > address[2]"#
+    );
+    assert_eq!(
+        Peer::from_str("<peer>localhost</peer>")
+            .unwrap_err()
+            .to_string(),
+        r#"error parsing XML value "localhost" as IpAddr in "Peer": "address" (invalid IP address syntax) at 1:16 in peer"#
+    );
+
+    // errors of decoding the value aren't wrapped again
+    let err = Host::from_str(r#"<host port="80" mac="00:11"/>"#).unwrap_err();
+    assert!(matches!(
+        err.detail(),
+        ErrorDetail::FromStr { value, type_name, .. } if value == "00:11" && type_name == "hexBinary"
+    ));
+    assert_eq!(
+        err.to_string(),
+        r#"error parsing XML value "00:11" as hexBinary in "Host": "mac" (hex encoded value has an odd number of digits) at 1:17 in host"#
+    );
+    let err = Host::from_str(r#"<host port="80" mac="0011"/>"#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        r#"error parsing XML value "0011" as hexBinary in "Host": "mac" (expected 6 bytes, found 2) at 1:17 in host"#
+    );
+
+    assert_eq!(
+        Host::from_str(r#"<host port="80"><enabled>maybe</enabled></host>"#)
+            .unwrap_err()
+            .to_string(),
+        r#"error parsing XML value "maybe" as bool in "Host": "enabled" (provided string was not `true` or `false`) at 1:31 in host > enabled"#
+    );
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use hard_xml::{XmlRead, XmlReadOwned, XmlResult, XmlSchemaInfo, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "envelope")]
+struct Envelope<T> {
+    #[xml(child = "body")]
+    body: T,
+}
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "body")]
+struct Body<'a> {
+    #[xml(text)]
+    text: Cow<'a, str>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "note")]
+struct Note {
+    #[xml(text)]
+    text: String,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "list")]
+struct List<T, V> {
+    #[xml(attr = "total")]
+    total: Option<V>,
+    #[xml(child = "body")]
+    items: Vec<T>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+enum Message<T> {
+    #[xml(tag = "body")]
+    Body(T),
+    #[xml(tag = "empty")]
+    Empty,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "owned", bound = "T: XmlReadOwned + XmlWrite")]
+struct Owned<T> {
+    #[xml(child = "note")]
+    note: Option<T>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<envelope><body>hi</body></envelope>"#;
+    let envelope = Envelope::<Body>::from_str(xml)?;
+    assert_eq!(envelope.body.text, "hi");
+    assert_eq!(envelope.to_string()?, xml);
+    assert_eq!(Envelope::<Body>::elements()[0].tag, "envelope");
+
+    let xml = r#"<list total="2"><body>a</body><body>b</body></list>"#;
+    let list = List::<Body, u32>::from_str(xml)?;
+    assert_eq!(list.total, Some(2));
+    assert_eq!(list.items.len(), 2);
+    assert_eq!(list.to_string()?, xml);
+
+    assert_eq!(
+        Message::<Body>::from_str(r#"<body>x</body>"#)?,
+        Message::Body(Body { text: "x".into() })
+    );
+    assert_eq!(Message::<Body>::Empty.to_string()?, r#"<empty/>"#);
+
+    let xml = r#"<owned><note>y</note></owned>"#;
+    let owned = Owned::<Note>::from_str(xml)?;
+    assert_eq!(owned.note.as_ref().unwrap().text, "y");
+    assert_eq!(owned.to_string()?, xml);
+
+    Ok(())
+}
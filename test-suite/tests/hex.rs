@@ -0,0 +1,49 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "file")]
+struct File {
+    #[xml(attr = "digest", hex)]
+    digest: [u8; 4],
+    #[xml(attr = "mac", hex)]
+    mac: Option<Vec<u8>>,
+    #[xml(flatten_text = "key", hex)]
+    keys: Vec<[u8; 2]>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "salt")]
+struct Salt {
+    #[xml(text, hex)]
+    value: Vec<u8>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let file = File {
+        digest: [0xde, 0xad, 0xbe, 0xef],
+        mac: None,
+        keys: vec![[0x01, 0x02], [0xab, 0xcd]],
+    };
+    let xml = r#"<file digest="DEADBEEF"><key>0102</key><key>ABCD</key></file>"#;
+    assert_eq!(file.to_string()?, xml);
+    assert_eq!(File::from_str(xml)?, file);
+    assert_eq!(
+        File::from_str(r#"<file digest="deadbeef" mac="00ff"/>"#)?.mac,
+        Some(vec![0x00, 0xff])
+    );
+
+    // the length of an array is checked
+    assert!(File::from_str(r#"<file digest="dead"/>"#).is_err());
+
+    let salt = Salt::from_str("<salt> 0a0B </salt>")?;
+    assert_eq!(salt.value, vec![0x0a, 0x0b]);
+    assert_eq!(salt.to_string()?, "<salt>0A0B</salt>");
+
+    Ok(())
+}
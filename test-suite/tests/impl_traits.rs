@@ -0,0 +1,76 @@
+use hard_xml::{XmlRead, XmlReadOwned, XmlResult, XmlWrite};
+use std::borrow::Cow;
+use std::str::FromStr;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "point", impl_display, impl_from_str)]
+struct Point {
+    #[xml(attr = "x")]
+    x: i32,
+    #[xml(attr = "y")]
+    y: i32,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(impl_display, impl_from_str)]
+enum Shape {
+    #[xml(tag = "point")]
+    Point(Point),
+    #[xml(tag = "line")]
+    Line {
+        #[xml(child = "point")]
+        points: Vec<Point>,
+    },
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "label", owned, impl_display, impl_from_str)]
+struct Label<'a> {
+    #[xml(text)]
+    text: Cow<'a, str>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "wrapper", impl_display, impl_from_str)]
+struct Wrapper<T>
+where
+    T: XmlReadOwned + XmlWrite,
+{
+    #[xml(child = "point")]
+    inner: T,
+}
+
+// only takes the standard traits
+fn round_trip<T: FromStr + ToString>(s: &str) -> String
+where
+    T::Err: std::fmt::Debug,
+{
+    s.parse::<T>().unwrap().to_string()
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<point x="1" y="2"/>"#;
+    assert_eq!(xml.parse::<Point>()?, Point { x: 1, y: 2 });
+    assert_eq!(format!("{}", Point { x: 1, y: 2 }), xml);
+    assert_eq!(round_trip::<Point>(xml), xml);
+
+    let xml = r#"<line><point x="1" y="2"/><point x="3" y="4"/></line>"#;
+    assert_eq!(round_trip::<Shape>(xml), xml);
+
+    let xml = r#"<label>a &amp; b</label>"#;
+    assert_eq!(xml.parse::<Label>()?.text, "a & b");
+    assert_eq!(round_trip::<Label>(xml), xml);
+
+    let xml = r#"<wrapper><point x="1" y="2"/></wrapper>"#;
+    assert_eq!(round_trip::<Wrapper<Point>>(xml), xml);
+
+    assert!("<other/>".parse::<Point>().is_err());
+
+    Ok(())
+}
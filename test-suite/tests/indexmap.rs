@@ -0,0 +1,38 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+use indexmap::IndexMap;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "root")]
+struct Root {
+    #[xml(attrs)]
+    attrs: IndexMap<String, String>,
+    #[xml(child = "item", key = "id")]
+    items: IndexMap<String, Item>,
+    #[xml(map = "*")]
+    vars: IndexMap<String, String>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "item")]
+struct Item {
+    #[xml(attr = "id")]
+    id: String,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    // entries keep the order they're read in
+    let xml = r#"<root z="1" a="2"><item id="b"/><item id="a"/><Z>1</Z><A>2</A></root>"#;
+    let root = Root::from_str(xml)?;
+    assert_eq!(root.attrs.keys().collect::<Vec<_>>(), ["z", "a"]);
+    assert_eq!(root.items.keys().collect::<Vec<_>>(), ["b", "a"]);
+    assert_eq!(root.vars.keys().collect::<Vec<_>>(), ["Z", "A"]);
+    assert_eq!(root.to_string()?, xml);
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use hard_xml::{XmlIter, XmlRead, XmlResult, XmlWrite, XmlWriter};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "row")]
+struct Row<'a> {
+    #[xml(attr = "id")]
+    id: usize,
+    #[xml(text)]
+    name: Cow<'a, str>,
+}
+
+#[derive(XmlWrite)]
+#[xml(tag = "table")]
+struct Table<I>
+where
+    I: IntoIterator + Clone,
+    I::Item: XmlWrite,
+{
+    #[xml(attr = "name")]
+    name: &'static str,
+    #[xml(child = "row")]
+    rows: XmlIter<I>,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "table")]
+struct OwnedTable<'a> {
+    #[xml(attr = "name")]
+    name: Cow<'a, str>,
+    #[xml(child = "row")]
+    rows: Vec<Row<'a>>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let names = ["a", "b", "c"];
+    let table = Table {
+        name: "t",
+        rows: XmlIter::new(names.iter().enumerate().map(|(id, name)| Row {
+            id,
+            name: Cow::Borrowed(*name),
+        })),
+    };
+    let xml = table.to_string()?;
+    assert_eq!(
+        xml,
+        r#"<table name="t"><row id="0">a</row><row id="1">b</row><row id="2">c</row></table>"#
+    );
+    // written again from a fresh clone of the iterator
+    assert_eq!(table.to_string()?, xml);
+    assert_eq!(OwnedTable::from_str(&xml)?.rows.len(), 3);
+
+    // borrowed elements
+    let rows = OwnedTable::from_str(&xml)?.rows;
+    let table = Table {
+        name: "t",
+        rows: XmlIter::new(&rows),
+    };
+    assert_eq!(table.to_string()?, xml);
+
+    // top-level sequence
+    let mut writer = XmlWriter::new(Vec::new());
+    writer.write_elements(rows.iter().filter(|row| row.id != 1))?;
+    assert_eq!(
+        String::from_utf8(writer.into_inner())?,
+        r#"<row id="0">a</row><row id="2">c</row>"#
+    );
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use hard_xml::{ErrorDetail, XmlRead, XmlResult, XmlWrite};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "users")]
+struct Users<'a> {
+    #[xml(child = "user", key = "id")]
+    users: BTreeMap<Cow<'a, str>, User<'a>>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "user")]
+struct User<'a> {
+    #[xml(attr = "id")]
+    id: Cow<'a, str>,
+    #[xml(text)]
+    name: Cow<'a, str>,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "users")]
+struct UserIds<'a> {
+    #[xml(child = "user", key = "id")]
+    users: BTreeMap<Cow<'a, str>, UserName<'a>>,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "user")]
+struct UserName<'a> {
+    #[xml(text)]
+    name: Cow<'a, str>,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "ports")]
+struct Ports {
+    #[xml(child = "port", key = "number", duplicate_key = "first")]
+    first: HashMap<u16, Port>,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "ports")]
+struct LastPorts {
+    #[xml(child = "port", key = "number", duplicate_key = "last")]
+    last: HashMap<u16, Port>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "port")]
+struct Port {
+    #[xml(attr = "number")]
+    number: u16,
+    #[xml(attr = "protocol")]
+    protocol: String,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<users><user id="bob">Bob</user><user id="alice">Alice</user></users>"#;
+    let users = Users::from_str(xml)?;
+    assert_eq!(users.users["alice"].name, "Alice");
+    assert_eq!(users.users["bob"].name, "Bob");
+    assert_eq!(
+        users.to_string()?,
+        r#"<users><user id="alice">Alice</user><user id="bob">Bob</user></users>"#
+    );
+
+    assert_eq!(Users::from_str(r#"<users/>"#)?.to_string()?, r#"<users/>"#);
+
+    assert!(matches!(
+        Users::from_str(r#"<users><user id="a">A</user><user id="a">B</user></users>"#),
+        Err(err) if matches!(err.detail(), ErrorDetail::DuplicateField { .. })
+    ));
+
+    assert!(matches!(
+        Users::from_str(r#"<users><user>A</user></users>"#),
+        Err(err) if matches!(err.detail(), ErrorDetail::MissingField { .. })
+    ));
+
+    assert_eq!(
+        UserIds::from_str(r#"<users><user>A</user></users>"#)
+            .unwrap_err()
+            .to_string(),
+        r#"missing field in XML of "UserIds": "users" (expected attribute "id" in <user>) at 1:15 in users > user"#
+    );
+
+    let xml =
+        r#"<ports><port number="80" protocol="tcp"/><port number="80" protocol="udp"/></ports>"#;
+    assert_eq!(Ports::from_str(xml)?.first[&80].protocol, "tcp");
+    assert_eq!(LastPorts::from_str(xml)?.last[&80].protocol, "udp");
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "rect")]
+struct Rect<'a> {
+    #[xml(attr = "class", list)]
+    class: Vec<Cow<'a, str>>,
+    #[xml(attr = "viewBox", list)]
+    view_box: Vec<f64>,
+    #[xml(attr = "flags", list)]
+    flags: Vec<bool>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let rect = Rect::from_str(
+        r#"<rect class=" big  red&#10;box " viewBox="0 0 10.5 20" flags="true 0"/>"#,
+    )?;
+    assert_eq!(rect.class, vec!["big", "red", "box"]);
+    assert_eq!(rect.view_box, vec![0.0, 0.0, 10.5, 20.0]);
+    assert_eq!(rect.flags, vec![true, false]);
+    assert_eq!(
+        rect.to_string()?,
+        r#"<rect class="big red box" viewBox="0 0 10.5 20" flags="true false"/>"#
+    );
+
+    // empty lists are written as missing attributes
+    let rect = Rect::from_str(r#"<rect class=""/>"#)?;
+    assert!(rect.class.is_empty());
+    assert!(rect.view_box.is_empty());
+    assert_eq!(rect.to_string()?, r#"<rect/>"#);
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use hard_xml::schema::FieldKind;
+use hard_xml::{XmlRead, XmlResult, XmlSchemaInfo, XmlWrite};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "config")]
+struct Config<'a> {
+    #[xml(attr = "name")]
+    name: Cow<'a, str>,
+    #[xml(map = "entry")]
+    entries: BTreeMap<Cow<'a, str>, Cow<'a, str>>,
+    #[xml(map = "limit", key = "name", value = "max")]
+    limits: BTreeMap<String, u32>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "env")]
+struct Env {
+    #[xml(flatten_text = "shell")]
+    shell: String,
+    #[xml(map = "*")]
+    vars: BTreeMap<String, String>,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "flags")]
+struct Flags {
+    #[xml(map = "flag")]
+    flags: HashMap<String, bool>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<config name="app"><entry key="b" value="2"/><entry key="a" value="&amp;"/><limit name="cpu" max="4"/></config>"#;
+    let config = Config::from_str(xml)?;
+    assert_eq!(config.entries["a"], "&");
+    assert_eq!(config.entries["b"], "2");
+    assert_eq!(config.limits["cpu"], 4);
+    assert_eq!(
+        config.to_string()?,
+        r#"<config name="app"><entry key="a" value="&amp;"/><entry key="b" value="2"/><limit name="cpu" max="4"/></config>"#
+    );
+
+    let config = Config::from_str(r#"<config name="app"/>"#)?;
+    assert!(config.entries.is_empty());
+    assert_eq!(config.to_string()?, r#"<config name="app"/>"#);
+
+    assert!(matches!(
+        Config::from_str(r#"<config name="app"><entry key="a"/></config>"#),
+        Err(err) if matches!(err.detail(), hard_xml::ErrorDetail::MissingField { .. })
+    ));
+
+    assert!(matches!(
+        Config::elements()[0].fields[1].kind,
+        FieldKind::Map {
+            tag: "entry",
+            key: "key",
+            value: "value"
+        }
+    ));
+
+    let xml = r#"<env><shell>sh</shell><PATH>/bin</PATH><HOME>/root</HOME></env>"#;
+    let env = Env::from_str(xml)?;
+    assert_eq!(env.shell, "sh");
+    assert_eq!(env.vars["PATH"], "/bin");
+    assert_eq!(env.vars["HOME"], "/root");
+    assert_eq!(
+        env.to_string()?,
+        r#"<env><HOME>/root</HOME><PATH>/bin</PATH><shell>sh</shell></env>"#
+    );
+
+    let flags = Flags::from_str(r#"<flags><flag key="debug" value="yes"/></flags>"#)?;
+    assert!(flags.flags["debug"]);
+
+    Ok(())
+}
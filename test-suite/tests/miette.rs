@@ -0,0 +1,80 @@
+use hard_xml::{XmlRead, XmlResult};
+use miette::{Diagnostic, GraphicalReportHandler, GraphicalTheme, Report};
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "server")]
+struct Server {
+    #[xml(attr = "host")]
+    host: String,
+    #[xml(child = "port", duplicate = "error")]
+    port: Option<Port>,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "port")]
+struct Port {
+    #[xml(text)]
+    value: u16,
+}
+
+fn render(xml: &str) -> String {
+    let report = Report::new(Server::from_str(xml).unwrap_err()).with_source_code(xml.to_owned());
+    let mut out = String::new();
+    GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor())
+        .with_width(100)
+        .render_report(&mut out, report.as_ref())
+        .unwrap();
+    out
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = "<server host=\"a\">\n  <port>80</port>\n  <port>81</port>\n</server>";
+    assert_eq!(
+        render(xml),
+        r#"hard_xml::duplicate
+
+  × duplicate field in XML of "Server": "port" (first at 2:3 in server > port) at 3:3 in server >
+  │ port[2]
+   ╭─[2:3]
+ 1 │ <server host="a">
+ 2 │   <port>80</port>
+   ·   ▲
+   ·   ╰── first here
+ 3 │   <port>81</port>
+   ·   ▲
+   ·   ╰── repeated here
+ 4 │ </server>
+   ╰────
+"#
+    );
+
+    let xml = "<server host=\"a\">\n  <port>80</port>\n</srv>";
+    assert_eq!(
+        render(xml),
+        r#"hard_xml::syntax
+
+  × mismatched XML tag; expected "server", found "srv" at 3:1 in server
+   ╭─[3:1]
+ 2 │   <port>80</port>
+ 3 │ </srv>
+   · ▲
+   · ╰── malformed here
+   ╰────
+"#
+    );
+
+    let err = Server::from_str("<server><port>80</port></server>").unwrap_err();
+    assert_eq!(err.code().unwrap().to_string(), "hard_xml::missing");
+    let labels: Vec<_> = err.labels().unwrap().collect();
+    assert_eq!(labels.len(), 1);
+    assert_eq!(labels[0].offset(), 23);
+    assert_eq!(labels[0].label(), Some("missing before here"));
+
+    Ok(())
+}
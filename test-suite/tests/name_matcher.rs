@@ -0,0 +1,122 @@
+use hard_xml::matcher::{AliasTable, Exact, IgnoreAsciiCase, LocalName, Namespaces};
+use hard_xml::{XmlRead, XmlReader, XmlResult, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "w:p")]
+struct Paragraph<'a> {
+    #[xml(attr = "w:val")]
+    val: Option<Cow<'a, str>>,
+    #[xml(flatten_text = "w:t")]
+    text: Vec<Cow<'a, str>>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "item")]
+struct Item<'a> {
+    #[xml(attr = "id")]
+    id: usize,
+    #[xml(flatten_text = "author")]
+    author: Cow<'a, str>,
+}
+
+const W: &str = "http://schemas.openxmlformats.org/wordprocessingml/2006/main";
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let paragraph = Paragraph {
+        val: Some("1".into()),
+        text: vec!["foo".into(), "bar".into()],
+    };
+
+    assert_eq!(
+        paragraph.to_string()?,
+        r#"<w:p w:val="1"><w:t>foo</w:t><w:t>bar</w:t></w:p>"#
+    );
+
+    assert_eq!(Paragraph::from_str(&paragraph.to_string()?)?, paragraph);
+
+    // prefixes are ignored by default
+    assert_eq!(
+        Paragraph::from_str(r#"<x:p x:val="1"><t>foo</t><x:t>bar</x:t></x:p>"#)?,
+        paragraph
+    );
+
+    assert_eq!(
+        Paragraph::from_reader(
+            &mut XmlReader::new(
+                r#"<w:p w:val="1" x:val="2"><w:t>foo</w:t><x:t>baz</x:t><w:t>bar</w:t></w:p>"#
+            )
+            .with_name_matcher(Exact)
+        )?,
+        paragraph
+    );
+
+    let item = Item {
+        id: 1,
+        author: "foo".into(),
+    };
+
+    assert!(Item::from_str(r#"<Item ID="1"><Author>foo</Author></Item>"#).is_err());
+
+    assert_eq!(
+        Item::from_reader(
+            &mut XmlReader::new(r#"<Item ID="1"><Author>foo</Author></Item>"#)
+                .with_name_matcher(IgnoreAsciiCase)
+        )?,
+        item
+    );
+
+    assert_eq!(
+        Item::from_reader(
+            &mut XmlReader::new(r#"<item id="1"><dc:creator>foo</dc:creator></item>"#)
+                .with_name_matcher(AliasTable::new(LocalName).alias("creator", "author"))
+        )?,
+        item
+    );
+
+    // prefixes are resolved with the declarations in scope
+    let namespaces = || Namespaces::new().bind("w", W);
+
+    let xml = format!(
+        r#"<x:p xmlns:x="{W}" x:val="1"><x:t>foo</x:t><v:t xmlns:v="urn:other">baz</v:t><n xmlns:y="{W}"/><y:t>qux</y:t><t xmlns="{W}">bar</t></x:p>"#
+    );
+    assert_eq!(
+        Paragraph::from_reader(&mut XmlReader::new(&xml).with_name_matcher(namespaces()))?,
+        paragraph
+    );
+
+    // unprefixed attributes aren't in the default namespace
+    let xml = format!(r#"<p xmlns="{W}" val="1"><t>foo</t></p>"#);
+    assert_eq!(
+        Paragraph::from_reader(&mut XmlReader::new(&xml).with_name_matcher(namespaces()))?,
+        Paragraph {
+            val: None,
+            text: vec!["foo".into()],
+        }
+    );
+
+    assert!(Paragraph::from_reader(
+        &mut XmlReader::new(r#"<v:p xmlns:v="urn:other"/>"#).with_name_matcher(namespaces())
+    )
+    .is_err());
+
+    assert_eq!(
+        Item::from_reader(
+            &mut XmlReader::new(
+                r#"<item id="1" xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:creator>foo</dc:creator></item>"#
+            )
+            .with_name_matcher(
+                AliasTable::new(Namespaces::new()).alias("{http://purl.org/dc/elements/1.1/}creator", "author")
+            )
+        )?,
+        item
+    );
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "distance")]
+struct Meters(f64);
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "name")]
+struct Name<'a>(Cow<'a, str>);
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "enabled")]
+struct Enabled(bool);
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+enum Value {
+    #[xml(tag = "count")]
+    Count(u32),
+    #[xml(tag = "label")]
+    Label(String),
+    #[xml(tag = "distance")]
+    Distance(Meters),
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "route")]
+struct Route<'a> {
+    #[xml(child = "name")]
+    name: Name<'a>,
+    #[xml(child = "distance")]
+    distance: Vec<Meters>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    assert_eq!(
+        Meters::from_str(r#"<distance>1.5</distance>"#)?,
+        Meters(1.5)
+    );
+    assert_eq!(Meters(1.5).to_string()?, r#"<distance>1.5</distance>"#);
+    assert!(Meters::from_str(r#"<distance>far</distance>"#).is_err());
+
+    assert_eq!(
+        Name::from_str(r#"<name>a &amp; b</name>"#)?,
+        Name("a & b".into())
+    );
+    assert_eq!(
+        Name("a & b".into()).to_string()?,
+        r#"<name>a &amp; b</name>"#
+    );
+
+    assert_eq!(
+        Enabled::from_str(r#"<enabled>true</enabled>"#)?,
+        Enabled(true)
+    );
+    assert_eq!(Enabled(false).to_string()?, r#"<enabled>false</enabled>"#);
+
+    assert_eq!(Value::from_str(r#"<count>3</count>"#)?, Value::Count(3));
+    assert_eq!(Value::Label("x".into()).to_string()?, r#"<label>x</label>"#);
+    assert_eq!(
+        Value::from_str(r#"<distance>2</distance>"#)?,
+        Value::Distance(Meters(2.0))
+    );
+
+    let xml = r#"<route><name>loop</name><distance>1</distance><distance>2.5</distance></route>"#;
+    let route = Route::from_str(xml)?;
+    assert_eq!(route.name, Name("loop".into()));
+    assert_eq!(route.distance, vec![Meters(1.0), Meters(2.5)]);
+    assert_eq!(route.to_string()?, xml);
+
+    Ok(())
+}
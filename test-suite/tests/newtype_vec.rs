@@ -0,0 +1,64 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "item")]
+struct Item {
+    #[xml(attr = "id")]
+    id: usize,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+enum Content {
+    #[xml(tag = "item")]
+    Item(Item),
+    #[xml(tag = "items", tag = "list")]
+    Items(Vec<Item>),
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "ids")]
+struct Ids(Vec<Item>);
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "root")]
+struct Root {
+    #[xml(child = "item", child = "items", child = "list")]
+    content: Vec<Content>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<root><item id="1"/><items><item id="2"/><item id="3"/></items><items/></root>"#;
+
+    let root = Root::from_str(xml)?;
+
+    assert_eq!(
+        root,
+        Root {
+            content: vec![
+                Content::Item(Item { id: 1 }),
+                Content::Items(vec![Item { id: 2 }, Item { id: 3 }]),
+                Content::Items(vec![]),
+            ],
+        }
+    );
+
+    assert_eq!(root.to_string()?, xml);
+
+    assert_eq!(
+        Content::from_str(r#"<list n="2"><item id="4"/></list>"#)?,
+        Content::Items(vec![Item { id: 4 }])
+    );
+
+    let ids = Ids(vec![Item { id: 1 }]);
+
+    assert_eq!(ids.to_string()?, r#"<ids><item id="1"/></ids>"#);
+    assert_eq!(Ids::from_str(r#"<ids><item id="1"/></ids>"#)?, ids);
+
+    Ok(())
+}
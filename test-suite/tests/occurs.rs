@@ -0,0 +1,89 @@
+use hard_xml::{ErrorDetail, XmlBuilder, XmlRead, XmlResult, XmlSchemaInfo, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "item")]
+struct Item;
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "list")]
+struct List<'a> {
+    #[xml(child = "item", min = 1, max = 2)]
+    items: Vec<Item>,
+    #[xml(flatten_text = "tag", max = 1)]
+    tags: Vec<Cow<'a, str>>,
+    #[xml(flatten_text = "note", min = 1)]
+    notes: Vec<Cow<'a, str>>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    assert_eq!(
+        List::from_str(r#"<list><item/><item/><note>foo</note></list>"#)?,
+        List {
+            items: vec![Item, Item],
+            tags: vec![],
+            notes: vec!["foo".into()],
+        }
+    );
+
+    assert!(matches!(
+        List::from_str(r#"<list><note>foo</note></list>"#),
+        Err(err) if err.field() == Some("items") && matches!(err.detail(), ErrorDetail::UnexpectedOccurrences { count: 0, min: 1, max: Some(2) })
+    ));
+
+    assert!(matches!(
+        List::from_str(r#"<list><item/><item/><item/><note>foo</note></list>"#),
+        Err(err) if err.field() == Some("items") && matches!(err.detail(), ErrorDetail::UnexpectedOccurrences { count: 3, .. })
+    ));
+
+    assert!(matches!(
+        List::from_str(r#"<list><item/><tag>a</tag><tag>b</tag><note>foo</note></list>"#),
+        Err(err) if err.field() == Some("tags") && matches!(err.detail(), ErrorDetail::UnexpectedOccurrences { count: 2, min: 0, max: Some(1) })
+    ));
+
+    assert!(matches!(
+        List::from_str(r#"<list/>"#),
+        Err(err) if matches!(err.detail(), ErrorDetail::UnexpectedOccurrences { .. })
+    ));
+
+    assert_eq!(
+        List::from_str(r#"<list><item/></list>"#)
+            .unwrap_err()
+            .to_string(),
+        r#"unexpected number of occurrences in XML of "List": "notes" occurs 0 times, expected at least 1 at 1:14 in list"#
+    );
+
+    assert_eq!(
+        List::from_str(r#"<list><item/><item/><item/><note>foo</note></list>"#)
+            .unwrap_err()
+            .to_string(),
+        r#"unexpected number of occurrences in XML of "List": "items" occurs 3 times, expected at most 2 at 1:44 in list"#
+    );
+
+    let items = &List::elements()[0].fields[0];
+    assert_eq!((items.min_occurs, items.max_occurs), (1, Some(2)));
+
+    // the builder enforces the same bounds
+    assert!(matches!(
+        XmlBuilder::new::<List>("list")?
+            .child("item", Ok)?
+            .child("item", Ok)?
+            .child("item", Ok),
+        Err(err) if err.field() == Some("items") && matches!(err.detail(), ErrorDetail::UnexpectedOccurrences { count: 3, .. })
+    ));
+
+    assert!(matches!(
+        XmlBuilder::new::<List>("list")?
+            .flatten_text("note", "foo")?
+            .finish(),
+        Err(err) if err.field() == Some("items") && matches!(err.detail(), ErrorDetail::UnexpectedOccurrences { count: 0, min: 1, .. })
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use hard_xml::{XmlRead, XmlResult, XmlSchemaInfo, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "project")]
+struct Project<'a> {
+    #[xml(child = "tag", wrapper = "tags")]
+    tags: Option<Vec<Tag<'a>>>,
+}
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "tag")]
+struct Tag<'a> {
+    #[xml(text)]
+    name: Cow<'a, str>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let project = Project::from_str(r#"<project/>"#)?;
+    assert_eq!(project.tags, None);
+    assert_eq!(project.to_string()?, r#"<project/>"#);
+
+    let project = Project::from_str(r#"<project><tags/></project>"#)?;
+    assert_eq!(project.tags, Some(vec![]));
+    assert_eq!(project.to_string()?, r#"<project><tags/></project>"#);
+
+    let project = Project::from_str(r#"<project><tags></tags></project>"#)?;
+    assert_eq!(project.tags, Some(vec![]));
+
+    let xml = r#"<project><tags><tag>a</tag><tag>b</tag></tags></project>"#;
+    let project = Project::from_str(xml)?;
+    assert_eq!(
+        project.tags,
+        Some(vec![Tag { name: "a".into() }, Tag { name: "b".into() }])
+    );
+    assert_eq!(project.to_string()?, xml);
+
+    let field = &Project::elements()[0].fields[0];
+    assert_eq!(field.min_occurs, 0);
+    assert_eq!(field.max_occurs, None);
+
+    Ok(())
+}
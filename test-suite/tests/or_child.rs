@@ -0,0 +1,77 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "item")]
+struct Item<'a> {
+    #[xml(attr = "id", or_child = "id")]
+    id: u32,
+    #[xml(attr = "name", or_child = "name")]
+    name: Option<Cow<'a, str>>,
+    #[xml(flatten_text = "price", or_attr = "price")]
+    price: f64,
+    #[xml(flatten_text = "tag", or_attr = "tags", list)]
+    tags: Vec<String>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "order")]
+struct Order<'a> {
+    #[xml(flatten)]
+    item: ItemFields<'a>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+struct ItemFields<'a> {
+    #[xml(attr = "id", or_child = "id")]
+    id: u32,
+    #[xml(flatten_text = "note", or_attr = "note")]
+    note: Option<Cow<'a, str>>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let expected = Item {
+        id: 1,
+        name: Some("pen".into()),
+        price: 1.5,
+        tags: vec!["a".into(), "b".into()],
+    };
+
+    let item = Item::from_str(r#"<item id="1" name="pen" price="1.5" tags="a b"/>"#)?;
+    assert_eq!(item, expected);
+
+    let item = Item::from_str(
+        r#"<item><id>1</id><name>pen</name><price>1.5</price><tag>a</tag><tag>b</tag></item>"#,
+    )?;
+    assert_eq!(item, expected);
+
+    let item = Item::from_str(r#"<item id="1" price="1.5"><name>pen</name><tag>a b</tag></item>"#)?;
+    assert_eq!(item, expected);
+
+    // written in the form of the main attribute
+    assert_eq!(
+        item.to_string()?,
+        r#"<item id="1" name="pen"><price>1.5</price><tag>a b</tag></item>"#
+    );
+
+    assert!(matches!(
+        Item::from_str(r#"<item price="1"/>"#),
+        Err(err) if matches!(err.detail(), hard_xml::ErrorDetail::MissingField { .. })
+    ));
+
+    let order = Order::from_str(r#"<order note="x"><id>2</id></order>"#)?;
+    assert_eq!(order.item.id, 2);
+    assert_eq!(order.item.note.as_deref(), Some("x"));
+    assert_eq!(
+        order.to_string()?,
+        r#"<order id="2"><note>x</note></order>"#
+    );
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "a")]
+struct A {
+    #[xml(attr = "n")]
+    n: usize,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "b")]
+struct B;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "root")]
+struct Root<'a> {
+    #[xml(child = "a")]
+    a: Vec<A>,
+    #[xml(child = "b")]
+    b: Option<B>,
+    #[xml(flatten_text = "c")]
+    c: Vec<Cow<'a, str>>,
+    #[xml(order)]
+    order: Vec<&'static str>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<root><a n="1"/><c>foo</c><b/><a n="2"/><c>bar</c></root>"#;
+
+    let root = Root::from_str(xml)?;
+
+    assert_eq!(
+        root,
+        Root {
+            a: vec![A { n: 1 }, A { n: 2 }],
+            b: Some(B),
+            c: vec!["foo".into(), "bar".into()],
+            order: vec!["a", "c", "b", "a", "c"],
+        }
+    );
+
+    assert_eq!(root.to_string()?, xml);
+
+    // children missing from the order are written afterwards
+    assert_eq!(
+        (Root {
+            a: vec![A { n: 1 }, A { n: 2 }],
+            b: None,
+            c: vec!["foo".into()],
+            order: vec!["c", "a"],
+        })
+        .to_string()?,
+        r#"<root><c>foo</c><a n="1"/><a n="2"/></root>"#
+    );
+
+    assert_eq!(
+        (Root {
+            a: vec![],
+            b: None,
+            c: vec![],
+            order: vec!["a", "b"],
+        })
+        .to_string()?,
+        r#"<root/>"#
+    );
+
+    Ok(())
+}
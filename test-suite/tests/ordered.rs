@@ -0,0 +1,97 @@
+use hard_xml::{ErrorDetail, XmlRead, XmlResult, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "a")]
+struct A;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "root", ordered)]
+struct Root<'a> {
+    #[xml(attr = "id")]
+    id: Option<usize>,
+    #[xml(flatten_text = "title")]
+    title: Cow<'a, str>,
+    #[xml(child = "a")]
+    a: Vec<A>,
+    #[xml(flatten_text = "note")]
+    note: Option<Cow<'a, str>>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+enum Either<'a> {
+    #[xml(tag = "ordered", ordered)]
+    Ordered {
+        #[xml(flatten_text = "x")]
+        x: Cow<'a, str>,
+        #[xml(flatten_text = "y")]
+        y: Cow<'a, str>,
+    },
+    #[xml(tag = "unordered")]
+    Unordered {
+        #[xml(flatten_text = "x")]
+        x: Cow<'a, str>,
+        #[xml(flatten_text = "y")]
+        y: Cow<'a, str>,
+    },
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<root id="1"><title>foo</title><a/><a/><note>bar</note></root>"#;
+
+    let root = Root::from_str(xml)?;
+
+    assert_eq!(
+        root,
+        Root {
+            id: Some(1),
+            title: "foo".into(),
+            a: vec![A, A],
+            note: Some("bar".into()),
+        }
+    );
+
+    assert_eq!(root.to_string()?, xml);
+
+    // absent fields and unknown children are fine
+    assert_eq!(
+        Root::from_str(r#"<root><title>foo</title><b/><note>bar</note><c/></root>"#)?,
+        Root {
+            id: None,
+            title: "foo".into(),
+            a: vec![],
+            note: Some("bar".into()),
+        }
+    );
+
+    assert!(matches!(
+        Root::from_str(r#"<root><a/><title>foo</title></root>"#),
+        Err(err) if err.field() == Some("title") && matches!(err.detail(), ErrorDetail::UnorderedField)
+    ));
+
+    assert!(matches!(
+        Root::from_str(r#"<root><title>foo</title><a/><note>bar</note><a/></root>"#),
+        Err(err) if err.field() == Some("a") && matches!(err.detail(), ErrorDetail::UnorderedField)
+    ));
+
+    assert!(matches!(
+        Either::from_str(r#"<ordered><y>2</y><x>1</x></ordered>"#),
+        Err(err) if matches!(err.detail(), ErrorDetail::UnorderedField)
+    ));
+
+    assert_eq!(
+        Either::from_str(r#"<unordered><y>2</y><x>1</x></unordered>"#)?,
+        Either::Unordered {
+            x: "1".into(),
+            y: "2".into()
+        }
+    );
+
+    Ok(())
+}
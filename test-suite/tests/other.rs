@@ -0,0 +1,93 @@
+use hard_xml::{RawXml, XmlRead, XmlResult, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "p")]
+struct Paragraph<'a> {
+    #[xml(text)]
+    text: Cow<'a, str>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+enum Block<'a> {
+    #[xml(tag = "p")]
+    Paragraph(Paragraph<'a>),
+    #[xml(tag = "hr")]
+    Rule,
+    #[xml(other)]
+    Unknown(RawXml<'a>),
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "body")]
+struct Body<'a> {
+    #[xml(child = "p", child = "hr", child = "*")]
+    blocks: Vec<Block<'a>>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+enum Inline {
+    #[xml(tag = "br")]
+    Break,
+    #[xml(other)]
+    Unknown,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "span")]
+struct Span {
+    #[xml(child = "br", child = "*")]
+    inlines: Vec<Inline>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "shape", type_attr = "type")]
+enum Shape {
+    #[xml(type = "circle")]
+    Circle {
+        #[xml(attr = "r")]
+        r: u32,
+    },
+    #[xml(other)]
+    Unknown,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<body><p>foo</p><table><tr/></table><hr/></body>"#;
+
+    let body = Body::from_str(xml)?;
+
+    assert_eq!(body.blocks.len(), 3);
+    assert_eq!(
+        body.blocks[0],
+        Block::Paragraph(Paragraph { text: "foo".into() })
+    );
+    assert!(
+        matches!(&body.blocks[1], Block::Unknown(raw) if raw.as_str() == "<table><tr/></table>")
+    );
+    assert_eq!(body.blocks[2], Block::Rule);
+
+    // captured elements are written back
+    assert_eq!(body.to_string()?, xml);
+
+    let span = Span::from_str(r#"<span><br/><b>bold</b></span>"#)?;
+
+    assert_eq!(span.inlines, vec![Inline::Break, Inline::Unknown]);
+
+    // others which weren't captured are dropped
+    assert_eq!(span.to_string()?, r#"<span><br/></span>"#);
+
+    assert_eq!(
+        Shape::from_str(r#"<shape type="square" size="1"/>"#)?,
+        Shape::Unknown
+    );
+    assert_eq!(Shape::from_str(r#"<shape/>"#)?, Shape::Unknown);
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use hard_xml::{Numeric, XmlRead, XmlResult, XmlWrite};
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "reading")]
+struct Reading {
+    #[xml(attr = "level", overflow = "saturate")]
+    level: u8,
+    #[xml(attr = "offset", overflow = "saturate")]
+    offset: Option<i16>,
+    #[xml(attr = "raw")]
+    raw: Option<Numeric<u32>>,
+    #[xml(flatten_text = "value", overflow = "saturate")]
+    values: Vec<f32>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "count")]
+struct Count {
+    #[xml(text, overflow = "error")]
+    count: u8,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    assert_eq!(
+        Reading::from_str(
+            r#"<reading level="300" offset="-40000" raw="99999999999"><value>1e50</value><value>-1e50</value><value>1.5</value></reading>"#
+        )?,
+        Reading {
+            level: 255,
+            offset: Some(i16::MIN),
+            raw: Some(Numeric::OutOfRange("99999999999".into())),
+            values: vec![f32::MAX, f32::MIN, 1.5],
+        }
+    );
+
+    // negative numbers are below the range of unsigned types
+    assert_eq!(
+        Reading::from_str(r#"<reading level="-1" raw="-5"/>"#)?,
+        Reading {
+            level: 0,
+            offset: None,
+            raw: Some(Numeric::OutOfRange("-5".into())),
+            values: vec![],
+        }
+    );
+
+    let reading = Reading::from_str(r#"<reading level="1" raw="2"/>"#)?;
+
+    assert_eq!(reading.raw, Some(Numeric::Value(2)));
+    assert_eq!(reading.to_string()?, r#"<reading level="1" raw="2"/>"#);
+
+    // invalid numbers are still errors
+    assert!(Reading::from_str(r#"<reading level="x"/>"#).is_err());
+    assert!(Reading::from_str(r#"<reading level="1" raw="x"/>"#).is_err());
+
+    // overflows are errors by default
+    assert_eq!(Count::from_str(r#"<count>7</count>"#)?, Count { count: 7 });
+    assert!(Count::from_str(r#"<count>300</count>"#).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use hard_xml::{XmlRead, XmlReadOwned, XmlResult, XmlWrite};
+use std::borrow::Cow;
+use std::thread;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "book", owned)]
+struct Book<'a> {
+    #[xml(attr = "title")]
+    title: Cow<'a, str>,
+    #[xml(attr = "tags")]
+    tags: Vec<Cow<'a, str>>,
+    #[xml(flatten_text = "isbn", encoding = "utf8")]
+    isbn: Option<Cow<'a, [u8]>>,
+    #[xml(child = "author")]
+    authors: Vec<Author<'a>>,
+    #[xml(flatten)]
+    meta: Meta<'a>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "author", owned)]
+struct Author<'a> {
+    #[xml(text)]
+    name: Cow<'a, str>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(owned)]
+struct Meta<'a> {
+    #[xml(attr = "lang")]
+    lang: Option<Cow<'a, str>>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "*", owned)]
+struct Any<'a> {
+    #[xml(tag_name)]
+    tag: Cow<'a, str>,
+}
+
+fn load(xml: String) -> XmlResult<Book<'static>> {
+    Book::from_str(&xml)
+}
+
+fn assert_owned<T: XmlReadOwned>() {}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    assert_owned::<Book<'static>>();
+
+    let xml =
+        r#"<book title="Dune" tags="sf" lang="en"><author>Frank</author><isbn>123</isbn></book>"#;
+    let book = load(xml.to_owned())?;
+    assert!(matches!(book.title, Cow::Owned(_)));
+    assert_eq!(book.isbn.as_deref(), Some(&b"123"[..]));
+
+    let book = thread::spawn(move || book).join().unwrap();
+    assert_eq!(book.authors[0].name, "Frank");
+    assert_eq!(book.meta.lang.as_deref(), Some("en"));
+    assert_eq!(book.to_string()?, xml);
+
+    let any: Any<'static> = Any::from_str(&String::from("<item/>"))?;
+    assert_eq!(any.tag, "item");
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use hard_xml::{XmlRead, XmlReader, XmlResult, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug, Default)]
+#[xml(tag = "author")]
+struct Author<'a> {
+    #[xml(text)]
+    name: Cow<'a, str>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug, Default)]
+#[xml(tag = "doc", partial)]
+struct Doc<'a> {
+    #[xml(attr = "id")]
+    id: u32,
+    #[xml(attr = "lang")]
+    lang: Option<Cow<'a, str>>,
+    #[xml(flatten_text = "title")]
+    title: Cow<'a, str>,
+    #[xml(child = "author", min = 1)]
+    authors: Vec<Author<'a>>,
+    #[xml(flatten_text = "p")]
+    body: Vec<Cow<'a, str>>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<doc id="1" lang="en"><title>t</title><author>a</author><p>1</p><p>2</p></doc>"#;
+
+    assert_eq!(
+        Doc::read_fields(&mut XmlReader::new(xml), &["id", "title"])?,
+        Doc {
+            id: 1,
+            title: "t".into(),
+            ..Default::default()
+        }
+    );
+
+    // fields which aren't selected aren't parsed or checked either
+    assert_eq!(
+        Doc::read_fields(
+            &mut XmlReader::new(r#"<doc id="x"><author>a</author><p>1</p></doc>"#),
+            &["body"]
+        )?,
+        Doc {
+            body: vec!["1".into()],
+            ..Default::default()
+        }
+    );
+
+    // selected fields are still required
+    assert!(Doc::read_fields(
+        &mut XmlReader::new(r#"<doc><title>t</title></doc>"#),
+        &["id"]
+    )
+    .is_err());
+    assert!(Doc::read_fields(&mut XmlReader::new(r#"<doc id="1"/>"#), &["authors"]).is_err());
+
+    // selecting every field is the same as reading the whole element
+    assert_eq!(
+        Doc::read_fields(
+            &mut XmlReader::new(xml),
+            &["id", "lang", "title", "authors", "body"]
+        )?,
+        Doc::from_str(xml)?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+use std::marker::PhantomData;
+
+struct Meters;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "length")]
+struct Length<U> {
+    #[xml(attr = "value")]
+    value: f64,
+    unit: PhantomData<U>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "id")]
+struct Id<T>(#[xml(text)] u32, PhantomData<fn() -> T>, ());
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "marker")]
+struct Marker<T>(PhantomData<T>);
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<length value="1.5"/>"#;
+    let length = Length::<Meters>::from_str(xml)?;
+    assert_eq!(length.value, 1.5);
+    assert_eq!(length.to_string()?, xml);
+
+    let xml = r#"<id>7</id>"#;
+    let id = Id::<Meters>::from_str(xml)?;
+    assert_eq!(id.0, 7);
+    assert_eq!(id.to_string()?, xml);
+
+    let marker = Marker::<Meters>::from_str(r#"<marker/>"#)?;
+    assert_eq!(marker.to_string()?, r#"<marker/>"#);
+
+    Ok(())
+}
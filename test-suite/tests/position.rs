@@ -0,0 +1,206 @@
+use hard_xml::tree::Element;
+use hard_xml::{ErrorDetail, Position, XmlError, XmlRead, XmlReader, XmlReaderState, XmlResult};
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "channel")]
+struct Channel {
+    #[xml(child = "item")]
+    items: Vec<Item>,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "item")]
+struct Item {
+    #[xml(attr = "id")]
+    id: u32,
+    #[xml(flatten_text = "title")]
+    title: String,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "item", ordered)]
+struct Ordered {
+    #[xml(flatten_text = "a")]
+    a: String,
+    #[xml(flatten_text = "b")]
+    b: String,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "rss")]
+struct Rss {
+    #[xml(child = "channel")]
+    channel: RssChannel,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "channel")]
+struct RssChannel {
+    #[xml(flatten_text = "title")]
+    title: String,
+    #[xml(child = "item")]
+    items: Vec<RssItem>,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "item")]
+struct RssItem {
+    #[xml(child = "pubDate")]
+    pub_date: Option<PubDate>,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "pubDate")]
+struct PubDate {
+    #[xml(attr = "zone")]
+    zone: String,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(untagged)]
+enum Value {
+    #[xml(tag = "value")]
+    Number {
+        #[xml(attr = "n")]
+        n: i32,
+    },
+    #[xml(tag = "value")]
+    Range {
+        #[xml(attr = "min")]
+        min: i32,
+    },
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "values")]
+struct Values {
+    #[xml(child = "value")]
+    values: Vec<Value>,
+}
+
+fn position(err: &XmlError) -> (usize, u32, u32, &str) {
+    let Position {
+        offset,
+        line,
+        column,
+        path,
+    } = err.position().unwrap();
+    (*offset, *line, *column, path)
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = "<channel>\n  <item id=\"1\"><title>a</title></item>\n  <item id=\"2\"/>\n</channel>";
+
+    // the end of the item missing its title
+    let err = Channel::from_str(xml).unwrap_err();
+    assert!(matches!(err.detail(), ErrorDetail::MissingField { .. }));
+    assert_eq!(position(&err), (63, 3, 15, "channel > item[2]"));
+    assert_eq!(
+        err.to_string(),
+        r#"missing field in XML of "Item": "title" (expected child <title> in <item>) at 3:15 in channel > item[2]"#
+    );
+
+    // the closing tag which doesn't match
+    let err = Item::from_str("<item id=\"1\">\n\t<title>a</titel></item>").unwrap_err();
+    assert!(matches!(err.detail(), ErrorDetail::TagMismatch { .. }));
+    assert_eq!(position(&err), (23, 2, 10, "item > title"));
+
+    // the token the parser fails at
+    let err = Item::from_str("<item id=\"1\">\n  <title>é</title>\n  <</item>").unwrap_err();
+    assert!(matches!(err.detail(), ErrorDetail::Parser(_)));
+    assert_eq!(position(&err), (36, 3, 3, "item"));
+
+    // the child out of order
+    let err = Ordered::from_str("<item>\n<b>b</b><a>a</a></item>").unwrap_err();
+    assert!(matches!(err.detail(), ErrorDetail::UnorderedField));
+    assert_eq!(position(&err), (15, 2, 9, "item > a"));
+
+    // the attribute which can't be converted
+    let err = Item::from_str(r#"<item id="x"><title>a</title></item>"#).unwrap_err();
+    assert!(matches!(err.detail(), ErrorDetail::FromStr { .. }));
+    assert_eq!(position(&err), (6, 1, 7, "item"));
+
+    // the last token read before the end of the document
+    let err = Item::from_str("<item id=\"1\">\n  <title>a</title>").unwrap_err();
+    assert!(matches!(err.detail(), ErrorDetail::UnexpectedEof));
+    assert_eq!(position(&err), (24, 2, 11, "item > title"));
+    let err = Element::from_str("<a>\n  <b>").unwrap_err();
+    assert!(matches!(err.detail(), ErrorDetail::UnexpectedEof));
+    assert_eq!(position(&err), (8, 2, 5, "a > b"));
+
+    // positions of resumed readers are counted from the start of the document
+    let mut reader = XmlReader::resume(
+        "<item/>",
+        XmlReaderState {
+            offset: 100,
+            depth: 0,
+            line: 4,
+            column: 10,
+        },
+    );
+    let err = Item::from_reader(&mut reader).unwrap_err();
+    assert_eq!(position(&err), (105, 4, 15, "item"));
+
+    // the state a reader stops at is where the next one starts
+    let xml = "<channel>\n  <item id=\"1\"><title>a</title></item>  <item id=\"x\">\n<title>b</title></item>\n</channel>";
+    let mut reader = XmlReader::new(xml);
+    reader.read_till_element_start("channel")?;
+    reader.next(); // ">"
+    assert_eq!(reader.find_element_start(Some("channel"))?, Some("item"));
+    Item::from_reader(&mut reader)?;
+    let state = reader.state().unwrap();
+    assert_eq!((state.offset, state.line, state.column), (48, 2, 39));
+
+    let mut reader = XmlReader::resume(&xml[state.offset..], state);
+    assert_eq!(reader.find_element_start(Some("channel"))?, Some("item"));
+    let err = Item::from_reader(&mut reader).unwrap_err();
+    assert!(matches!(err.detail(), ErrorDetail::FromStr { .. }));
+    assert_eq!(position(&err), (56, 2, 47, "item"));
+
+    // and lines after the first one of the rest start at their own column
+    let mut reader = XmlReader::resume(&xml[state.offset..], state);
+    assert_eq!(reader.find_element_start(Some("channel"))?, Some("item"));
+    reader.next(); // "<item"
+    reader.next(); // "id"
+    reader.next(); // ">"
+    assert_eq!(reader.find_element_start(Some("item"))?, Some("title"));
+    let position = reader.position();
+    assert_eq!(
+        (position.offset, position.line, position.column),
+        (64, 3, 1)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_path() {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    // elements are numbered among their siblings of the same name
+    let xml = r#"<rss><channel><title>a</title><item/><item><pubDate zone="z"/></item><item>
+  <pubDate/></item></channel></rss>"#;
+    let err = Rss::from_str(xml).unwrap_err();
+    assert_eq!(
+        position(&err),
+        (86, 2, 11, "rss > channel > item[3] > pubDate")
+    );
+    assert_eq!(
+        err.to_string(),
+        r#"missing field in XML of "PubDate": "zone" (expected attribute "zone" in <pubDate>) at 2:11 in rss > channel > item[3] > pubDate"#
+    );
+
+    // elements read again after rewinding are only counted once
+    let xml = r#"<values><value n="1"/><value min="2"/><value/></values>"#;
+    let err = Values::from_str(xml).unwrap_err();
+    assert_eq!(position(&err).3, "values > value[3]");
+}
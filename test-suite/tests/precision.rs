@@ -0,0 +1,60 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "trkpt", precision = 6)]
+struct TrackPoint {
+    #[xml(attr = "lat")]
+    lat: f64,
+    #[xml(attr = "lon")]
+    lon: f64,
+    #[xml(flatten_text = "ele", precision = 1)]
+    ele: Option<f32>,
+    #[xml(attr = "sat")]
+    sat: u8,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "values")]
+struct Values {
+    #[xml(attr = "list", list, precision = 2)]
+    list: Vec<f64>,
+    #[xml(text)]
+    shortest: f64,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let point = TrackPoint {
+        lat: 12.3456789,
+        lon: -1.5,
+        ele: Some(4.25),
+        sat: 7,
+    };
+    let xml = r#"<trkpt lat="12.345679" lon="-1.500000" sat="7"><ele>4.2</ele></trkpt>"#;
+    assert_eq!(point.to_string()?, xml);
+    assert_eq!(TrackPoint::from_str(xml)?.lat, 12.345679);
+
+    let values = Values {
+        list: vec![0.5, 1.0 / 3.0],
+        shortest: 1e21,
+    };
+    assert_eq!(
+        values.to_string()?,
+        r#"<values list="0.50 0.33">1000000000000000000000</values>"#
+    );
+    assert_eq!(
+        Values {
+            list: vec![],
+            shortest: 12.345678,
+        }
+        .to_string()?,
+        r#"<values>12.345678</values>"#
+    );
+
+    Ok(())
+}
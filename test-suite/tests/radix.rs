@@ -0,0 +1,57 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "register")]
+struct Register {
+    #[xml(attr = "mask", radix = 16, prefix = "0x")]
+    mask: u32,
+    #[xml(attr = "color", radix = 16, prefix = "#")]
+    color: Option<u32>,
+    #[xml(attr = "offset", radix = 16, prefix = "0x")]
+    offset: i16,
+    #[xml(attr = "bits", radix = 2, list)]
+    bits: Vec<u8>,
+    #[xml(flatten_text = "mode", radix = 8, prefix = "0o")]
+    modes: Vec<u16>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "value")]
+struct Value {
+    #[xml(text, radix = 16)]
+    value: u64,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r##"<register mask="0x1f" color="#ff00FF" offset="-0X10" bits="101 +11"><mode>755</mode><mode>0o644</mode></register>"##;
+    let register = Register::from_str(xml)?;
+    assert_eq!(
+        register,
+        Register {
+            mask: 0x1F,
+            color: Some(0xFF00FF),
+            offset: -0x10,
+            bits: vec![5, 3],
+            modes: vec![0o755, 0o644],
+        }
+    );
+    assert_eq!(
+        register.to_string()?,
+        r##"<register mask="0x1F" color="#FF00FF" offset="-0x10" bits="101 11"><mode>0o755</mode><mode>0o644</mode></register>"##
+    );
+
+    assert!(Register::from_str(r#"<register mask="0x1G" offset="0"/>"#).is_err());
+    assert!(Register::from_str(r#"<register mask="0x100000000" offset="0"/>"#).is_err());
+
+    let value = Value::from_str(r#"<value>DeadBeef</value>"#)?;
+    assert_eq!(value.value, 0xDEADBEEF);
+    assert_eq!(value.to_string()?, r#"<value>DEADBEEF</value>"#);
+
+    Ok(())
+}
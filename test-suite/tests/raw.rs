@@ -0,0 +1,35 @@
+use hard_xml::{RawXml, XmlRead, XmlResult, XmlWrite};
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "root")]
+struct Root<'a> {
+    #[xml(attr = "id")]
+    id: usize,
+    #[xml(child = "any", child = "other")]
+    content: Vec<RawXml<'a>>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<root id="1"><any a="&lt;"><b><![CDATA[<c/>]]></b><!-- d --></any><skip/><other/></root>"#;
+
+    let root = Root::from_str(xml)?;
+
+    assert_eq!(root.id, 1);
+    assert_eq!(root.content.len(), 2);
+    assert_eq!(root.content[0].tag(), "any");
+    assert_eq!(root.content[0].inner(), "<b><![CDATA[<c/>]]></b><!-- d -->");
+    assert_eq!(root.content[1].as_str(), "<other/>");
+
+    assert_eq!(
+        root.to_string()?,
+        r#"<root id="1"><any a="&lt;"><b><![CDATA[<c/>]]></b><!-- d --></any><other/></root>"#
+    );
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use hard_xml::{ErrorDetail, XmlRead, XmlReader, XmlResult, XmlSchemaInfo, XmlWrite};
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "menu")]
+struct Menu {
+    #[xml(attr = "label")]
+    label: String,
+    #[xml(child = "menu")]
+    #[allow(clippy::vec_box)]
+    items: Vec<Box<Menu>>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+enum Expr {
+    #[xml(tag = "num")]
+    Num(#[xml(attr = "value")] i64),
+    #[xml(tag = "neg")]
+    Neg(#[xml(child = "num", child = "neg", child = "add")] Box<Expr>),
+    #[xml(tag = "add")]
+    Add {
+        #[xml(child = "num", child = "neg", child = "add")]
+        operands: Vec<Expr>,
+    },
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "section")]
+struct Section {
+    #[xml(child = "section")]
+    next: Option<Box<Section>>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<menu label="File"><menu label="Open"/><menu label="Recent"><menu label="a.txt"/></menu></menu>"#;
+    let menu = Menu::from_str(xml)?;
+    assert_eq!(menu.items[1].items[0].label, "a.txt");
+    assert_eq!(menu.to_string()?, xml);
+    assert_eq!(Menu::elements()[0].tag, "menu");
+
+    let xml = r#"<add><num value="1"/><neg><num value="2"/></neg></add>"#;
+    let expr = Expr::from_str(xml)?;
+    assert_eq!(
+        expr,
+        Expr::Add {
+            operands: vec![Expr::Num(1), Expr::Neg(Box::new(Expr::Num(2)))]
+        }
+    );
+    assert_eq!(expr.to_string()?, xml);
+
+    let depth = 100;
+    let xml = "<section>".repeat(depth) + &"</section>".repeat(depth);
+    let mut section = Section::from_str(&xml)?;
+    let mut count = 1;
+    while let Some(next) = section.next {
+        section = *next;
+        count += 1;
+    }
+    assert_eq!(count, depth);
+
+    let mut reader = XmlReader::new(&xml).with_max_depth(10);
+    assert!(matches!(
+        Section::from_reader(&mut reader),
+        Err(err) if matches!(err.detail(), ErrorDetail::DepthLimitExceeded { limit: 10 })
+    ));
+
+    let xml = "<section>".repeat(10) + &"</section>".repeat(10);
+    let mut reader = XmlReader::new(&xml).with_max_depth(10);
+    assert!(Section::from_reader(&mut reader).is_ok());
+
+    Ok(())
+}
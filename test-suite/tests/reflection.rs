@@ -0,0 +1,99 @@
+use hard_xml::schema::{ElementInfo, FieldKind};
+use hard_xml::{RawXml, XmlRead, XmlResult, XmlSchemaInfo, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "form")]
+struct Form<'a> {
+    #[xml(attr = "action")]
+    action: Cow<'a, str>,
+    #[xml(attr = "method")]
+    method: Option<Cow<'a, str>>,
+    #[xml(flatten_text = "title")]
+    title: Cow<'a, str>,
+    #[xml(child = "input")]
+    inputs: Vec<Input<'a>>,
+    #[xml(flatten)]
+    meta: Meta<'a>,
+    /// Attributes of other tools
+    #[xml(attrs)]
+    extra: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+    #[xml(children)]
+    extensions: Vec<RawXml<'a>>,
+}
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "input")]
+struct Input<'a> {
+    #[xml(attr = "name")]
+    name: Cow<'a, str>,
+    #[xml(text)]
+    label: Cow<'a, str>,
+}
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+struct Meta<'a> {
+    #[xml(attr = "lang")]
+    lang: Option<Cow<'a, str>>,
+}
+
+// describes an element the way a generic tool would, without knowing its type
+fn describe(element: &ElementInfo) -> Vec<String> {
+    element
+        .fields
+        .iter()
+        .map(|field| {
+            let kind = match &field.kind {
+                FieldKind::Attribute { name } => format!("@{}", name),
+                FieldKind::Child { tags, .. } => format!("<{}>", tags.join("|")),
+                FieldKind::Text => "text".to_owned(),
+                FieldKind::FlattenText { tag } => format!("<{}>text", tag),
+                FieldKind::Map { tag, .. } => format!("<{}> map", tag),
+                FieldKind::AnyAttributes => "@*".to_owned(),
+                FieldKind::AnyChildren => "<*>".to_owned(),
+            };
+            let occurs = match (field.optional, field.multiple) {
+                (false, false) => "",
+                (true, false) => "?",
+                (false, true) => "+",
+                (true, true) => "*",
+            };
+            format!("{} {}{}", field.name, kind, occurs)
+        })
+        .collect()
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let form = &Form::elements()[0];
+    assert_eq!(form.name, "Form");
+    assert_eq!(form.tag, "form");
+    assert_eq!(
+        describe(form),
+        [
+            "action @action",
+            "method @method?",
+            "title <title>text",
+            "inputs <input>*",
+            "lang @lang?",
+            "extra @**",
+            "extensions <*>*",
+        ]
+    );
+    assert_eq!(form.fields[5].doc, Some("Attributes of other tools"));
+
+    // child elements are described by the type of the field
+    let input = match &form.fields[3].kind {
+        FieldKind::Child { elements, .. } => elements().remove(0),
+        _ => unreachable!(),
+    };
+    assert_eq!(input.tag, "input");
+    assert_eq!(describe(&input), ["name @name", "label text?"]);
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use hard_xml::registry::{DynXmlWrite, Registry};
+use hard_xml::{impl_registry, ErrorDetail, XmlRead, XmlResult, XmlWrite};
+use std::fmt::Debug;
+
+trait Node: DynXmlWrite + Debug {
+    fn name(&self) -> &str;
+}
+
+static NODES: Registry<dyn Node> = Registry::new();
+
+impl_registry!(dyn Node, NODES);
+
+#[derive(XmlWrite, XmlRead, Debug)]
+#[xml(tag = "image")]
+struct Image {
+    #[xml(attr = "src")]
+    src: String,
+}
+
+impl Node for Image {
+    fn name(&self) -> &str {
+        "image"
+    }
+}
+
+#[derive(XmlWrite, XmlRead, Debug)]
+#[xml(tag = "chart")]
+struct Chart {
+    #[xml(flatten_text = "title")]
+    title: String,
+    #[xml(child = "*")]
+    nodes: Vec<Box<dyn Node>>,
+}
+
+impl Node for Chart {
+    fn name(&self) -> &str {
+        "chart"
+    }
+}
+
+#[derive(XmlWrite, XmlRead, Debug)]
+#[xml(tag = "footer")]
+struct Footer;
+
+impl Node for Footer {
+    fn name(&self) -> &str {
+        "footer"
+    }
+}
+
+#[derive(XmlWrite, XmlRead, Debug)]
+#[xml(tag = "page")]
+struct Page {
+    #[xml(attr = "id")]
+    id: usize,
+    #[xml(child = "image", child = "chart")]
+    nodes: Vec<Box<dyn Node>>,
+    #[xml(child = "footer")]
+    footer: Option<Box<dyn Node>>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    NODES.register("image", |image: Image| Box::new(image));
+    NODES.register("chart", |chart: Chart| Box::new(chart));
+
+    assert!(NODES.contains("chart"));
+    assert!(!NODES.contains("footer"));
+
+    let xml = r#"<page id="1"><image src="a.png"/><chart><image src="b.png"/><title>foo</title></chart></page>"#;
+
+    let page = Page::from_str(xml)?;
+
+    assert_eq!(
+        page.nodes
+            .iter()
+            .map(|node| node.name())
+            .collect::<Vec<_>>(),
+        vec!["image", "chart"]
+    );
+    assert!(page.footer.is_none());
+
+    assert_eq!(page.to_string()?, xml);
+
+    // children the registry doesn't know fail
+    assert!(matches!(
+        Page::from_str(r#"<page id="1"><footer/></page>"#),
+        Err(err) if matches!(err.detail(), ErrorDetail::UnknownVariant { value } if value == "footer")
+    ));
+
+    // types can be registered while an element is read
+    NODES.register("footer", |footer: Footer| {
+        NODES.register("caption", |image: Image| Box::new(image));
+        Box::new(footer)
+    });
+
+    let page = Page::from_str(r#"<page id="1"><footer/></page>"#)?;
+    assert_eq!(
+        page.footer.map(|node| node.name().to_owned()),
+        Some("footer".into())
+    );
+    assert!(NODES.contains("caption"));
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "line-item")]
+struct LineItem {
+    #[xml(attr)]
+    sku: usize,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "order", rename_all = "camelCase")]
+struct Order<'a> {
+    #[xml(attr)]
+    order_id: usize,
+    #[xml(attr = "ref")]
+    reference: Option<Cow<'a, str>>,
+    #[xml(flatten_text)]
+    customer_name: Cow<'a, str>,
+    #[xml(child = "line-item")]
+    line_items: Vec<LineItem>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "Config", rename_all = "PascalCase")]
+struct Config {
+    #[xml(attr)]
+    max_size: usize,
+    #[xml(flatten_text)]
+    r#type: String,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+enum Event {
+    #[xml(tag = "click", rename_all = "kebab-case")]
+    Click {
+        #[xml(attr)]
+        pos_x: usize,
+        #[xml(attr)]
+        pos_y: usize,
+    },
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<order orderId="1" ref="a"><line-item sku="2"/><customerName>foo</customerName></order>"#;
+
+    let order = Order::from_str(xml)?;
+
+    assert_eq!(
+        order,
+        Order {
+            order_id: 1,
+            reference: Some("a".into()),
+            customer_name: "foo".into(),
+            line_items: vec![LineItem { sku: 2 }],
+        }
+    );
+
+    assert_eq!(order.to_string()?, xml);
+
+    let xml = r#"<Config MaxSize="3"><Type>foo</Type></Config>"#;
+
+    let config = Config::from_str(xml)?;
+
+    assert_eq!(
+        config,
+        Config {
+            max_size: 3,
+            r#type: "foo".into(),
+        }
+    );
+
+    assert_eq!(config.to_string()?, xml);
+
+    assert_eq!(
+        Event::from_str(r#"<click pos-x="1" pos-y="2"/>"#)?,
+        Event::Click { pos_x: 1, pos_y: 2 }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "entry")]
+struct Entry<'a> {
+    #[xml(attr = "id")]
+    id: usize,
+    #[xml(attr = "category")]
+    categories: Vec<Cow<'a, str>>,
+    #[xml(attr = "rank")]
+    ranks: Vec<u8>,
+    #[xml(attr = "hidden")]
+    hidden: Vec<bool>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<entry id="1" category="a" category="b &amp; c" rank="2" rank="1"/>"#;
+
+    let entry = Entry::from_str(xml)?;
+
+    assert_eq!(
+        entry,
+        Entry {
+            id: 1,
+            categories: vec!["a".into(), "b & c".into()],
+            ranks: vec![2, 1],
+            hidden: vec![],
+        }
+    );
+
+    assert_eq!(entry.to_string()?, xml);
+
+    assert_eq!(
+        Entry::from_str(r#"<entry rank="3" id="2" hidden="yes" rank="4"/>"#)?,
+        Entry {
+            id: 2,
+            categories: vec![],
+            ranks: vec![3, 4],
+            hidden: vec![true],
+        }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use hard_xml::{XmlRead, XmlReader, XmlReaderState, XmlResult};
+use std::borrow::Cow;
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "item")]
+struct Item<'a> {
+    #[xml(attr = "id")]
+    id: usize,
+    #[xml(text)]
+    text: Cow<'a, str>,
+}
+
+// reads as many items as possible, returning the state after the last one
+fn ingest<'a>(reader: &mut XmlReader<'a>, items: &mut Vec<Item<'a>>) -> XmlReaderState {
+    let mut state = reader.state().unwrap();
+    loop {
+        match reader.find_element_start(Some("feed")) {
+            Ok(Some(_)) => (),
+            Ok(None) => return reader.state().unwrap(),
+            Err(_) => return state,
+        }
+        match Item::from_reader(reader) {
+            Ok(item) => items.push(item),
+            Err(_) => return state,
+        }
+        state = reader.state().unwrap();
+    }
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml =
+        r#"<feed><item id="1">foo</item><item id="2">bar</item><item id="3">baz</item></feed>"#;
+
+    // only part of the stream has arrived
+    let mut reader = XmlReader::new(&xml[..40]);
+    reader.read_till_element_start("feed")?;
+    reader.next(); // ">"
+
+    let mut items = vec![];
+    let state = ingest(&mut reader, &mut items);
+    assert_eq!(
+        items,
+        vec![Item {
+            id: 1,
+            text: "foo".into()
+        }]
+    );
+
+    // persisted and restored, e.g. across a restart
+    let state: XmlReaderState = state.to_string().parse()?;
+
+    let mut reader = XmlReader::resume(&xml[state.offset..], state);
+    let mut items = vec![];
+    let state = ingest(&mut reader, &mut items);
+    assert_eq!(
+        items,
+        vec![
+            Item {
+                id: 2,
+                text: "bar".into()
+            },
+            Item {
+                id: 3,
+                text: "baz".into()
+            }
+        ]
+    );
+    assert_eq!(
+        state,
+        XmlReaderState {
+            offset: xml.len(),
+            depth: 0,
+            line: 1,
+            column: xml.len() as u32 + 1
+        }
+    );
+
+    Ok(())
+}
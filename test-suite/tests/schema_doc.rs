@@ -0,0 +1,54 @@
+use hard_xml::{XmlRead, XmlResult, XmlSchemaInfo, XmlWrite};
+use std::borrow::Cow;
+
+/// A book in the catalog.
+///
+/// Identified by its ISBN.
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "book")]
+struct Book<'a> {
+    /// ISBN-13 without dashes
+    #[xml(attr = "isbn")]
+    isbn: Cow<'a, str>,
+    #[xml(flatten_text = "title", doc = "Title as printed on the cover")]
+    title: Cow<'a, str>,
+    #[xml(flatten_text = "note")]
+    notes: Vec<Cow<'a, str>>,
+}
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+enum Entry<'a> {
+    /// A single book
+    #[xml(tag = "book")]
+    Book(Book<'a>),
+    #[xml(tag = "series", doc = "Books published together")]
+    Series {
+        #[xml(child = "book")]
+        books: Vec<Book<'a>>,
+    },
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let book = &Book::elements()[0];
+
+    assert_eq!(
+        book.doc,
+        Some("A book in the catalog.\n\nIdentified by its ISBN.")
+    );
+    assert_eq!(book.fields[0].doc, Some("ISBN-13 without dashes"));
+    assert_eq!(book.fields[1].doc, Some("Title as printed on the cover"));
+    assert_eq!(book.fields[2].doc, None);
+
+    // newtype variants use the elements of the inner type
+    let entries = Entry::elements();
+    assert_eq!(entries[0].doc, book.doc);
+    assert_eq!(entries[1].doc, Some("Books published together"));
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use hard_xml::{XmlRead, XmlResult, XmlSchemaInfo, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "query")]
+struct Query<'a> {
+    #[xml(attr = "ids", separator = ",")]
+    ids: Vec<u32>,
+    #[xml(flatten_text = "tags", separator = ";")]
+    tags: Vec<Cow<'a, str>>,
+    #[xml(flatten_text = "words", list)]
+    words: Vec<String>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "point")]
+struct Point {
+    #[xml(text, separator = ",")]
+    coords: Vec<f64>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let query = Query::from_str(
+        r#"<query ids="1, 2 ,3"><tags> a ;b&amp;c; </tags><words>x  y</words></query>"#,
+    )?;
+    assert_eq!(query.ids, vec![1, 2, 3]);
+    assert_eq!(query.tags, vec!["a", "b&c"]);
+    assert_eq!(query.words, vec!["x", "y"]);
+    assert_eq!(
+        query.to_string()?,
+        r#"<query ids="1,2,3"><tags>a;b&amp;c</tags><words>x y</words></query>"#
+    );
+
+    let query = Query::from_str(r#"<query/>"#)?;
+    assert!(query.ids.is_empty() && query.tags.is_empty());
+    assert_eq!(query.to_string()?, r#"<query/>"#);
+
+    // every field occurs at most once
+    assert!(Query::elements()[0]
+        .fields
+        .iter()
+        .all(|field| !field.multiple));
+
+    let point = Point::from_str(r#"<point>1.5,2</point>"#)?;
+    assert_eq!(point.coords, vec![1.5, 2.0]);
+    assert_eq!(point.to_string()?, r#"<point>1.5,2</point>"#);
+
+    assert!(Query::from_str(r#"<query ids="1,x"/>"#).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use hard_xml::{XmlRead, XmlResult, XmlSchemaInfo, XmlWrite};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::thread;
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "library")]
+struct Library {
+    #[xml(attr = "name")]
+    name: Arc<str>,
+    #[xml(child = "book")]
+    books: Vec<Arc<Book>>,
+    #[xml(child = "shelf")]
+    shelf: Option<Rc<Shelf>>,
+}
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "book")]
+struct Book {
+    #[xml(attr = "title")]
+    title: String,
+}
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "shelf")]
+struct Shelf {
+    #[xml(text)]
+    label: Rc<str>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<library name="a &amp; b"><book title="Dune"/><book title="Emma"/><shelf>top</shelf></library>"#;
+    let library = Library::from_str(xml)?;
+    assert_eq!(&*library.name, "a & b");
+    assert_eq!(&*library.shelf.as_ref().unwrap().label, "top");
+    assert_eq!(library.to_string()?, xml);
+
+    let book = Arc::clone(&library.books[1]);
+    let title = thread::spawn(move || book.title.clone()).join().unwrap();
+    assert_eq!(title, "Emma");
+
+    assert_eq!(Library::elements()[0].tag, "library");
+
+    Ok(())
+}
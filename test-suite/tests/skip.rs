@@ -0,0 +1,77 @@
+use hard_xml::{XmlRead, XmlResult, XmlSchemaInfo, XmlWrite};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "root")]
+struct Root<'a> {
+    #[xml(attr = "name")]
+    name: Cow<'a, str>,
+    #[xml(skip)]
+    cache: HashMap<String, usize>,
+    #[xml(skip, default = "initial_version")]
+    version: u32,
+}
+
+fn initial_version() -> u32 {
+    1
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "item")]
+struct Item<'a>(#[xml(text)] Cow<'a, str>, #[xml(skip)] bool);
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+enum Node {
+    #[xml(tag = "a")]
+    A {
+        #[xml(skip)]
+        visited: bool,
+        #[xml(flatten_text = "b")]
+        b: Option<String>,
+    },
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let mut root = Root::from_str(r#"<root name="foo"/>"#)?;
+    assert_eq!(root.name, "foo");
+    assert!(root.cache.is_empty());
+    assert_eq!(root.version, 1);
+
+    root.cache.insert("foo".into(), 1);
+    root.version = 2;
+    assert_eq!(root.to_string()?, r#"<root name="foo"/>"#);
+
+    // skipped fields are missing from the schema
+    assert_eq!(Root::elements()[0].fields.len(), 1);
+
+    assert_eq!(
+        Item::from_str(r#"<item>foo</item>"#)?,
+        Item("foo".into(), false)
+    );
+    assert_eq!(Item("foo".into(), true).to_string()?, r#"<item>foo</item>"#);
+
+    assert_eq!(
+        Node::from_str(r#"<a><b>foo</b></a>"#)?,
+        Node::A {
+            visited: false,
+            b: Some("foo".into())
+        }
+    );
+    assert_eq!(
+        Node::A {
+            visited: true,
+            b: None
+        }
+        .to_string()?,
+        r#"<a/>"#
+    );
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug, Default)]
+#[xml(tag = "child")]
+struct Child {
+    #[xml(attr = "name", default)]
+    name: String,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "parent")]
+struct Parent {
+    #[xml(attr = "count", default, skip_serializing_if = "is_zero")]
+    count: u32,
+    #[xml(attr = "ids", skip_serializing_if = "Vec::is_empty")]
+    ids: Vec<u32>,
+    #[xml(child = "child", default, skip_serializing_if = "is_default")]
+    child: Child,
+    #[xml(
+        child = "child2",
+        wrapper = "list",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    list: Vec<Child2>,
+    #[xml(
+        flatten_text = "note",
+        default,
+        skip_serializing_if = "String::is_empty"
+    )]
+    note: String,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "child2")]
+struct Child2;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "ordered", ordered)]
+struct Ordered {
+    #[xml(flatten_text = "a", default, skip_serializing_if = "is_zero")]
+    a: u32,
+    #[xml(flatten_text = "b", default, skip_serializing_if = "is_zero")]
+    b: u32,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let empty = Parent {
+        count: 0,
+        ids: vec![],
+        child: Child::default(),
+        list: vec![],
+        note: String::new(),
+    };
+
+    assert_eq!(empty.to_string()?, r#"<parent/>"#);
+    assert_eq!(Parent::from_str(r#"<parent/>"#)?, empty);
+
+    let full = Parent {
+        count: 3,
+        ids: vec![1, 2],
+        child: Child { name: "foo".into() },
+        list: vec![Child2],
+        note: "bar".into(),
+    };
+
+    let xml = r#"<parent count="3" ids="1" ids="2"><child name="foo"/><list><child2/></list><note>bar</note></parent>"#;
+    assert_eq!(full.to_string()?, xml);
+    assert_eq!(Parent::from_str(xml)?, full);
+
+    let partly = Parent {
+        count: 0,
+        ids: vec![],
+        child: Child::default(),
+        list: vec![],
+        note: "bar".into(),
+    };
+    assert_eq!(partly.to_string()?, r#"<parent><note>bar</note></parent>"#);
+
+    assert_eq!((Ordered { a: 0, b: 0 }).to_string()?, r#"<ordered/>"#);
+    assert_eq!(
+        (Ordered { a: 0, b: 2 }).to_string()?,
+        r#"<ordered><b>2</b></ordered>"#
+    );
+
+    Ok(())
+}
@@ -90,5 +90,17 @@ fn test() -> XmlResult<()> {
         })
     );
 
+    let tag2 = Tag2 {
+        att1: "AT&T".into(),
+        att2: r#"<"quoted">"#.into(),
+    };
+
+    assert_eq!(
+        tag2.to_string()?,
+        r#"<tag2 att1="AT&amp;T" att2="&lt;&quot;quoted&quot;&gt;"/>"#
+    );
+
+    assert_eq!(Tag2::from_str(&tag2.to_string()?)?, tag2);
+
     Ok(())
 }
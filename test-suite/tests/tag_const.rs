@@ -0,0 +1,47 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "item", alias = "entry")]
+struct Item {
+    #[xml(attr = "id")]
+    id: u32,
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "items", tag = "list")]
+struct Items(Vec<Item>);
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+enum Message {
+    #[xml(tag = "ping")]
+    Ping,
+    #[xml(tag = "item", tag = "entry")]
+    Item(Item),
+}
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "level")]
+enum Level {
+    #[xml(rename = "low")]
+    Low,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    assert_eq!(Item::TAG, "item");
+    assert_eq!(Item::ALIASES, &["entry"]);
+    assert_eq!(Items::TAG, "items");
+    assert_eq!(Items::ALIASES, &["list"]);
+    assert_eq!(Message::TAGS, &["ping", "item", "entry"]);
+    assert_eq!(Level::TAG, "level");
+
+    let xml = format!(r#"<{} id="1"/>"#, Item::TAG);
+    assert_eq!(Item::from_str(&xml)?, Item { id: 1 });
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use hard_xml::matcher::{AliasTable, LocalName};
+use hard_xml::{XmlRead, XmlReader, XmlResult, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "author")]
+struct Author<'a> {
+    #[xml(tag_name)]
+    tag: Cow<'a, str>,
+    #[xml(text)]
+    name: Cow<'a, str>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "item")]
+struct Item<'a> {
+    #[xml(tag_name)]
+    tag: Cow<'a, str>,
+    #[xml(attr = "id")]
+    id: usize,
+    #[xml(child = "author")]
+    authors: Vec<Author<'a>>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<x:item id="1"><author>foo</author><dc:creator>bar</dc:creator></x:item>"#;
+
+    let item = Item::from_reader(
+        &mut XmlReader::new(xml)
+            .with_name_matcher(AliasTable::new(LocalName).alias("creator", "author")),
+    )?;
+
+    assert_eq!(
+        item,
+        Item {
+            tag: "x:item".into(),
+            id: 1,
+            authors: vec![
+                Author {
+                    tag: "author".into(),
+                    name: "foo".into()
+                },
+                Author {
+                    tag: "dc:creator".into(),
+                    name: "bar".into()
+                }
+            ]
+        }
+    );
+
+    assert_eq!(item.to_string()?, xml);
+
+    // an empty tag name falls back to the declared tag
+    assert_eq!(
+        (Item {
+            tag: "".into(),
+            id: 2,
+            authors: vec![]
+        })
+        .to_string()?,
+        r#"<item id="2"/>"#
+    );
+
+    Ok(())
+}
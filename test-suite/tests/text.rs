@@ -46,5 +46,19 @@ fn test() -> XmlResult<()> {
         Root::from_str(r#"<root><![CDATA[</>]]></root>"#)?
     );
 
+    assert_eq!(
+        Root {
+            content: "foo<bar>baz".into()
+        },
+        Root::from_str(r#"<root>foo<![CDATA[<bar>]]>baz</root>"#)?
+    );
+
+    assert_eq!(
+        Root {
+            content: "AT&T".into()
+        },
+        Root::from_str(r#"<root>AT<!-- comment -->&amp;T</root>"#)?
+    );
+
     Ok(())
 }
@@ -0,0 +1,162 @@
+use hard_xml::{ErrorDetail, XmlRead, XmlResult, XmlWrite};
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "status")]
+enum Status {
+    #[xml(rename = "active")]
+    Active,
+    #[xml(rename = "inactive")]
+    Inactive,
+    Unknown,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+enum Level {
+    #[xml(rename = "low")]
+    Low,
+    #[xml(rename = "high")]
+    High,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+enum Kind {
+    #[xml(rename = "file")]
+    File,
+    #[xml(rename = "dir")]
+    Dir,
+    #[xml(other)]
+    Other(String),
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+enum Mode {
+    #[xml(rename = "r")]
+    Read,
+    #[xml(other)]
+    Unknown,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(rename_all = "kebab-case")]
+enum Color {
+    DarkRed,
+    LightBlue,
+    #[xml(rename = "green")]
+    PaleGreen,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(rename_all = "SCREAMING_SNAKE_CASE")]
+enum Priority {
+    VeryHigh,
+    Low,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "entry")]
+struct Entry {
+    #[xml(attr = "kind")]
+    kind: Kind,
+    #[xml(attr = "mode")]
+    mode: Option<Mode>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "account")]
+struct Account {
+    #[xml(child = "status")]
+    status: Status,
+    #[xml(child = "status", wrapper = "history")]
+    history: Vec<Status>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "alert")]
+struct Alert {
+    #[xml(attr = "level")]
+    level: Level,
+    #[xml(text)]
+    text: Level,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    assert_eq!("active".parse::<Status>()?, Status::Active);
+    assert_eq!("Unknown".parse::<Status>()?, Status::Unknown);
+    assert_eq!(format!("{}", Status::Inactive), "inactive");
+
+    assert!(matches!(
+        "Active".parse::<Status>(),
+        Err(err) if err.name() == Some("Status") && matches!(err.detail(), ErrorDetail::UnknownVariant { value } if value == "Active")
+    ));
+
+    let xml = r#"<account><status>active</status><history><status>inactive</status><status>Unknown</status></history></account>"#;
+
+    let account = Account::from_str(xml)?;
+
+    assert_eq!(
+        account,
+        Account {
+            status: Status::Active,
+            history: vec![Status::Inactive, Status::Unknown],
+        }
+    );
+
+    assert_eq!(account.to_string()?, xml);
+
+    assert!(matches!(
+        <Status as XmlRead>::from_str(r#"<status>deleted</status>"#),
+        Err(err) if matches!(err.detail(), ErrorDetail::UnknownVariant { .. })
+    ));
+
+    let xml = r#"<alert level="low">high</alert>"#;
+
+    let alert = Alert::from_str(xml)?;
+
+    assert_eq!(
+        alert,
+        Alert {
+            level: Level::Low,
+            text: Level::High,
+        }
+    );
+
+    assert_eq!(alert.to_string()?, xml);
+
+    assert_eq!("dark-red".parse::<Color>()?, Color::DarkRed);
+    assert_eq!(format!("{}", Color::LightBlue), "light-blue");
+    assert_eq!(format!("{}", Color::PaleGreen), "green");
+    assert!("pale-green".parse::<Color>().is_err());
+    assert_eq!(format!("{}", Priority::VeryHigh), "VERY_HIGH");
+    assert_eq!("LOW".parse::<Priority>()?, Priority::Low);
+
+    // other values are kept by the fallback variant
+    let xml = r#"<entry kind="link" mode="w"/>"#;
+
+    let entry = Entry::from_str(xml)?;
+
+    assert_eq!(
+        entry,
+        Entry {
+            kind: Kind::Other("link".into()),
+            mode: Some(Mode::Unknown),
+        }
+    );
+
+    assert_eq!(entry.to_string()?, r#"<entry kind="link" mode="Unknown"/>"#);
+
+    assert_eq!(
+        Entry::from_str(r#"<entry kind="dir" mode="r"/>"#)?,
+        Entry {
+            kind: Kind::Dir,
+            mode: Some(Mode::Read),
+        }
+    );
+
+    Ok(())
+}
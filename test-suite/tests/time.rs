@@ -0,0 +1,47 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+use time::macros::{date, datetime, time};
+use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "event")]
+struct Event {
+    #[xml(attr = "created")]
+    created: OffsetDateTime,
+    #[xml(attr = "date")]
+    date: Date,
+    #[xml(attr = "time")]
+    time: Option<Time>,
+    #[xml(flatten_text = "start")]
+    start: PrimitiveDateTime,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let event = Event {
+        created: datetime!(2024-01-02 03:04:05 UTC),
+        date: date!(2024 - 02 - 29),
+        time: Some(time!(12:30:00.5)),
+        start: datetime!(2024-02-29 09:30:00),
+    };
+    let xml = r#"<event created="2024-01-02T03:04:05Z" date="2024-02-29" time="12:30:00.5"><start>2024-02-29T09:30:00.0</start></event>"#;
+    assert_eq!(event.to_string()?, xml);
+    assert_eq!(Event::from_str(xml)?, event);
+
+    let event = Event::from_str(
+        r#"<event created="2024-01-02T05:04:05+02:00" date="2024-02-29"><start>2024-02-29T09:30:00</start></event>"#,
+    )?;
+    assert_eq!(event.created, datetime!(2024-01-02 03:04:05 UTC));
+    assert_eq!(event.time, None);
+
+    assert!(Event::from_str(
+        r#"<event created="2024-01-02T03:04:05Z" date="29/02/2024"><start>2024-02-29T09:30:00</start></event>"#
+    )
+    .is_err());
+
+    Ok(())
+}
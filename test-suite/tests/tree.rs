@@ -0,0 +1,67 @@
+use hard_xml::tree::{Element, Node, Subtrees};
+use hard_xml::{ErrorDetail, XmlRead, XmlReader, XmlResult, XmlWrite, XmlWriter};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "entry")]
+struct Entry<'a> {
+    #[xml(attr = "id")]
+    id: usize,
+    #[xml(child = "extension")]
+    extension: Option<Element<'a>>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<entry id="1"><extension kind="x"><a>1</a><b/></extension></entry>"#;
+
+    let entry = Entry::from_str(xml)?;
+
+    let extension = entry.extension.as_ref().unwrap();
+    assert_eq!(extension.attribute("kind"), Some("x"));
+    assert_eq!(extension.children("a").next().unwrap().text(), "1");
+    assert_eq!(entry.to_string()?, xml);
+
+    // materialize some subtrees of a large document and stream them back out
+    let mut reader = XmlReader::new(
+        r#"<feed><title>t</title><item n="1"/><skip><item n="2">x</item></skip></feed>"#,
+    );
+
+    let mut writer = XmlWriter::new(Vec::new());
+    for item in Subtrees::new(&mut reader, "item") {
+        let mut item = item?;
+        item.children.push(Node::Text(Cow::Borrowed("!")));
+        item.to_writer(&mut writer)?;
+    }
+
+    assert_eq!(
+        String::from_utf8(writer.inner).unwrap(),
+        r#"<item n="1">!</item><item n="2">x!</item>"#
+    );
+
+    // comments are written back, unless they would end early
+    let mut element = Element::from_str("<a><!-- b --></a>")?;
+    assert_eq!(element.to_string()?, "<a><!-- b --></a>");
+    for text in ["b -- c", "b-"] {
+        element.children = vec![Node::Comment(Cow::Borrowed(text))];
+        assert!(element.to_string().is_err());
+    }
+
+    // nested elements count towards the depth limit of the reader
+    let xml = "<a><b><c/></b></a>";
+    let mut reader = XmlReader::new(xml).with_max_depth(2);
+    let err = Element::from_reader(&mut reader).unwrap_err();
+    assert!(matches!(
+        err.detail(),
+        ErrorDetail::DepthLimitExceeded { limit: 2 }
+    ));
+    let mut reader = XmlReader::new(xml).with_max_depth(3);
+    assert_eq!(Element::from_reader(&mut reader)?.to_string()?, xml);
+
+    Ok(())
+}
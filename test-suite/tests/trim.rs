@@ -0,0 +1,60 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "title")]
+struct Title<'a> {
+    #[xml(text, trim)]
+    text: Cow<'a, str>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "book", trim)]
+struct Book<'a> {
+    #[xml(flatten_text = "name")]
+    name: Cow<'a, str>,
+    #[xml(flatten_text = "pages")]
+    pages: u32,
+    #[xml(flatten_text = "tag")]
+    tags: Vec<String>,
+    #[xml(flatten_text = "code", preserve)]
+    code: Cow<'a, str>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let title = Title::from_str("<title>\n    Dune\n</title>")?;
+    assert_eq!(title.text, "Dune");
+    assert!(matches!(title.text, Cow::Borrowed(_)));
+    assert_eq!(
+        Title::from_str("<title> A &amp; B <![CDATA[ ]]></title>")?.text,
+        "A & B"
+    );
+
+    let xml = r#"<book>
+    <name>
+        Dune
+    </name>
+    <pages> 412 </pages>
+    <tag> sf </tag>
+    <tag>classic</tag>
+    <code>  fn main() {}
+</code>
+</book>"#;
+    let book = Book::from_str(xml)?;
+    assert_eq!(book.name, "Dune");
+    assert_eq!(book.pages, 412);
+    assert_eq!(book.tags, vec!["sf", "classic"]);
+    assert_eq!(book.code, "  fn main() {}\n");
+    assert_eq!(
+        book.to_string()?,
+        "<book><name>Dune</name><pages>412</pages><tag>sf</tag><tag>classic</tag><code>  fn main() {}\n</code></book>"
+    );
+
+    Ok(())
+}
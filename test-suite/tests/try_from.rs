@@ -0,0 +1,71 @@
+use hard_xml::{ErrorDetail, XmlRead, XmlResult, XmlWrite};
+use std::borrow::Cow;
+use std::convert::{TryFrom, TryInto};
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "user", impl_try_from)]
+struct User<'a> {
+    #[xml(attr = "name")]
+    name: Cow<'a, str>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(impl_try_from)]
+enum Event<'a> {
+    #[xml(tag = "login")]
+    Login {
+        #[xml(child = "user")]
+        user: User<'a>,
+    },
+    #[xml(tag = "logout")]
+    Logout,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(impl_try_from)]
+enum Level {
+    #[xml(rename = "low")]
+    Low,
+    #[xml(rename = "high")]
+    High,
+}
+
+// only relies on the conversion traits
+fn convert<'a, T>(s: &'a str) -> String
+where
+    T: TryFrom<&'a str> + TryInto<String>,
+    <T as TryFrom<&'a str>>::Error: std::fmt::Debug,
+    <T as TryInto<String>>::Error: std::fmt::Debug,
+{
+    T::try_from(s).unwrap().try_into().unwrap()
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<user name="bob"/>"#;
+    let user = User::try_from(xml)?;
+    // borrowed from the string
+    assert!(matches!(user.name, Cow::Borrowed("bob")));
+    assert_eq!(String::try_from(&user)?, xml);
+    assert_eq!(convert::<User>(xml), xml);
+
+    let xml = r#"<login><user name="bob"/></login>"#;
+    assert_eq!(convert::<Event>(xml), xml);
+    assert_eq!(convert::<Event>("<logout/>"), "<logout/>");
+
+    assert_eq!(Level::try_from("high")?, Level::High);
+    assert_eq!(convert::<Level>("low"), "low");
+
+    assert!(matches!(
+        Level::try_from("medium"),
+        Err(err) if matches!(err.detail(), ErrorDetail::UnknownVariant { .. })
+    ));
+    assert!(User::try_from("<other/>").is_err());
+
+    Ok(())
+}
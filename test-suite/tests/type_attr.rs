@@ -0,0 +1,74 @@
+use hard_xml::{ErrorDetail, XmlRead, XmlResult, XmlWrite};
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "shape")]
+struct Rect {
+    #[xml(attr = "w")]
+    w: u32,
+    #[xml(attr = "h")]
+    h: u32,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "shape", type_attr = "xsi:type")]
+enum Shape {
+    #[xml(type = "circle")]
+    Circle {
+        #[xml(attr = "r")]
+        r: u32,
+    },
+    #[xml(type = "rect")]
+    Rect(Rect),
+    #[xml(type = "point")]
+    Point,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "drawing")]
+struct Drawing {
+    #[xml(child = "shape")]
+    shapes: Vec<Shape>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<drawing><shape xsi:type="circle" r="1"/><shape xsi:type="rect" w="2" h="3"/><shape xsi:type="point"/></drawing>"#;
+
+    let drawing = Drawing::from_str(xml)?;
+
+    assert_eq!(
+        drawing,
+        Drawing {
+            shapes: vec![
+                Shape::Circle { r: 1 },
+                Shape::Rect(Rect { w: 2, h: 3 }),
+                Shape::Point,
+            ]
+        }
+    );
+
+    assert_eq!(drawing.to_string()?, xml);
+
+    // the attribute can appear anywhere in the start tag
+    assert_eq!(
+        Shape::from_str(r#"<shape r="4" type="circle"/>"#)?,
+        Shape::Circle { r: 4 }
+    );
+
+    assert!(matches!(
+        Shape::from_str(r#"<shape type="polygon"/>"#),
+        Err(err) if matches!(err.detail(), ErrorDetail::UnknownVariant { .. })
+    ));
+
+    assert!(matches!(
+        Shape::from_str(r#"<shape r="4"/>"#),
+        Err(err) if matches!(err.detail(), ErrorDetail::MissingField { .. })
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "count")]
+struct Count {
+    #[xml(text)]
+    value: u32,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "enabled")]
+struct Enabled {
+    #[xml(text)]
+    value: bool,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "limit")]
+struct Limit {
+    #[xml(attr = "unit")]
+    unit: Option<String>,
+    #[xml(text)]
+    value: Option<f64>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "note")]
+struct Note<'a> {
+    #[xml(text)]
+    value: Option<Cow<'a, str>>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let count = Count::from_str(r#"<count>42</count>"#)?;
+    assert_eq!(count.value, 42);
+    assert_eq!(count.to_string()?, r#"<count>42</count>"#);
+    assert!(Count::from_str(r#"<count>x</count>"#).is_err());
+    assert!(Count::from_str(r#"<count/>"#).is_err());
+
+    let enabled = Enabled::from_str(r#"<enabled>yes</enabled>"#)?;
+    assert!(enabled.value);
+    assert_eq!(enabled.to_string()?, r#"<enabled>true</enabled>"#);
+
+    let limit = Limit::from_str(r#"<limit unit="s">1.5</limit>"#)?;
+    assert_eq!(limit.value, Some(1.5));
+    assert_eq!(limit.to_string()?, r#"<limit unit="s">1.5</limit>"#);
+
+    let limit = Limit::from_str(r#"<limit/>"#)?;
+    assert_eq!(limit.value, None);
+    assert_eq!(limit.to_string()?, r#"<limit></limit>"#);
+
+    assert_eq!(Note::from_str(r#"<note></note>"#)?.value, None);
+    let note = Note::from_str(r#"<note>hi</note>"#)?;
+    assert_eq!(note.value.as_deref(), Some("hi"));
+    assert_eq!(note.to_string()?, r#"<note>hi</note>"#);
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "enabled")]
+struct Enabled;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "feature")]
+struct Feature {
+    #[xml(attr = "name")]
+    name: String,
+    #[xml(child = "enabled")]
+    enabled: Option<Enabled>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    assert_eq!(Enabled::from_str(r#"<enabled/>"#)?, Enabled);
+    assert_eq!(Enabled::from_str(r#"<enabled></enabled>"#)?, Enabled);
+    assert_eq!(
+        Enabled::from_str(r#"<enabled a="1"><x/>y</enabled>"#)?,
+        Enabled
+    );
+    assert_eq!(Enabled.to_string()?, r#"<enabled/>"#);
+
+    let xml = r#"<feature name="a"><enabled/></feature>"#;
+    let feature = Feature::from_str(xml)?;
+    assert_eq!(feature.enabled, Some(Enabled));
+    assert_eq!(feature.to_string()?, xml);
+
+    let xml = r#"<feature name="a"/>"#;
+    let feature = Feature::from_str(xml)?;
+    assert_eq!(feature.enabled, None);
+    assert_eq!(feature.to_string()?, xml);
+
+    let feature = Feature::from_str(r#"<feature name="a"><enabled></enabled></feature>"#)?;
+    assert_eq!(feature.enabled, Some(Enabled));
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use hard_xml::filter::UnwrapElement;
+use hard_xml::{XmlRead, XmlReader, XmlResult, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "value")]
+struct Range {
+    #[xml(attr = "min")]
+    min: i32,
+    #[xml(attr = "max")]
+    max: i32,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(untagged)]
+enum Value<'a> {
+    #[xml(tag = "value")]
+    Number {
+        #[xml(attr = "n")]
+        n: i32,
+    },
+    #[xml(tag = "value")]
+    Range(Range),
+    #[xml(tag = "value")]
+    Text {
+        #[xml(text)]
+        text: Cow<'a, str>,
+    },
+    #[xml(tag = "none")]
+    None,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "values")]
+struct Values<'a> {
+    #[xml(child = "value", child = "none")]
+    values: Vec<Value<'a>>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "x")]
+struct Y {
+    #[xml(attr = "n")]
+    n: u8,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "x")]
+struct X {
+    #[xml(attr = "n")]
+    n: u32,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(untagged)]
+enum Val {
+    #[xml(tag = "v")]
+    N {
+        #[xml(child = "x")]
+        x: Y,
+    },
+    #[xml(tag = "v")]
+    T {
+        #[xml(attr = "k")]
+        k: String,
+        #[xml(child = "x")]
+        x: Vec<X>,
+    },
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "list")]
+struct VList {
+    #[xml(child = "v")]
+    values: Vec<Val>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml =
+        r#"<values><value n="1"/><value min="2" max="3"/><value>four</value><none/></values>"#;
+
+    let values = Values::from_str(xml)?;
+
+    assert_eq!(
+        values,
+        Values {
+            values: vec![
+                Value::Number { n: 1 },
+                Value::Range(Range { min: 2, max: 3 }),
+                Value::Text {
+                    text: "four".into()
+                },
+                Value::None,
+            ]
+        }
+    );
+
+    assert_eq!(values.to_string()?, xml);
+
+    // variants are tried in declaration order
+    assert_eq!(
+        Value::from_str(r#"<value n="1" min="2" max="3"/>"#)?,
+        Value::Number { n: 1 }
+    );
+
+    // the error of the last variant is returned if none matches
+    assert!(Value::from_str(r#"<value><child/></value>"#).is_err());
+
+    // the state of filters is restored when a variant fails
+    let mut reader =
+        XmlReader::new(r#"<list><v k="a"><legacy><x n="300"/></legacy></v><x n="9"/></list>"#)
+            .with_filter(UnwrapElement::new("legacy"));
+    assert_eq!(
+        VList::from_reader(&mut reader)?,
+        VList {
+            values: vec![Val::T {
+                k: "a".into(),
+                x: vec![X { n: 300 }],
+            }],
+        }
+    );
+
+    Ok(())
+}
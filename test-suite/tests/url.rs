@@ -0,0 +1,40 @@
+use hard_xml::{ErrorDetail, XmlError, XmlRead, XmlResult, XmlWrite};
+use url::Url;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "item")]
+struct Item {
+    #[xml(attr = "href")]
+    href: Url,
+    #[xml(flatten_text = "enclosure")]
+    enclosures: Vec<Url>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<item href="https://example.com/a?b=1&amp;c=2"><enclosure>https://example.com/ep1.mp3</enclosure></item>"#;
+    let item = Item::from_str(xml)?;
+    assert_eq!(item.href.query(), Some("b=1&c=2"));
+    assert_eq!(item.enclosures[0].path(), "/ep1.mp3");
+    assert_eq!(item.to_string()?, xml);
+
+    let item = Item::from_str(r#"<item href=" HTTPS://Example.COM "/>"#)?;
+    assert_eq!(item.href.as_str(), "https://example.com/");
+
+    match Item::from_str(r#"<item href="/relative"/>"#).map_err(XmlError::into_detail) {
+        Err(ErrorDetail::FromStr { source: err, .. }) => {
+            assert_eq!(
+                err.to_string(),
+                r#"invalid URL "/relative": relative URL without a base"#
+            )
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    Ok(())
+}
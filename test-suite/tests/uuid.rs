@@ -0,0 +1,44 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+use uuid::fmt::Braced;
+use uuid::Uuid;
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "item")]
+struct Item {
+    #[xml(attr = "id")]
+    id: Uuid,
+    #[xml(attr = "guid")]
+    guid: Option<Braced>,
+    #[xml(flatten_text = "ref")]
+    refs: Vec<Uuid>,
+}
+
+const ID: Uuid = Uuid::from_u128(0x67e55044_10b1_426f_9247_bb680e5fe0c8);
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let item = Item {
+        id: ID,
+        guid: Some(ID.braced()),
+        refs: vec![ID],
+    };
+    let xml = r#"<item id="67e55044-10b1-426f-9247-bb680e5fe0c8" guid="{67e55044-10b1-426f-9247-bb680e5fe0c8}"><ref>67e55044-10b1-426f-9247-bb680e5fe0c8</ref></item>"#;
+    assert_eq!(item.to_string()?, xml);
+    assert_eq!(Item::from_str(xml)?, item);
+
+    // any form is read
+    let item = Item::from_str(
+        r#"<item id="{67E55044-10B1-426F-9247-BB680E5FE0C8}"><ref> urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8 </ref><ref>67e5504410b1426f9247bb680e5fe0c8</ref></item>"#,
+    )?;
+    assert_eq!(item.id, ID);
+    assert_eq!(item.refs, vec![ID, ID]);
+
+    assert!(Item::from_str(r#"<item id="67e55044"/>"#).is_err());
+
+    Ok(())
+}
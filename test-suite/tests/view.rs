@@ -0,0 +1,116 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+
+// domain types, which aren't tied to hard-xml
+struct Library {
+    name: String,
+    tags: Vec<String>,
+    books: Vec<Book>,
+}
+
+struct Book {
+    title: String,
+    year: u16,
+}
+
+#[derive(XmlWrite)]
+#[xml(tag = "library")]
+struct LibraryView<'a> {
+    #[xml(attr = "name")]
+    name: &'a str,
+    #[xml(attr = "tags", list)]
+    tags: &'a [String],
+    #[xml(flatten_text = "tag")]
+    tag_elements: &'a [String],
+    #[xml(child = "book")]
+    books: &'a [BookView<'a>],
+    #[xml(child = "book")]
+    featured: Option<&'a BookView<'a>>,
+}
+
+#[derive(XmlWrite)]
+#[xml(tag = "book")]
+struct BookView<'a> {
+    #[xml(attr = "year")]
+    year: &'a u16,
+    #[xml(text)]
+    title: &'a str,
+}
+
+// read and written, with a field only borrowed when writing
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "shelf")]
+struct Shelf<'a> {
+    #[xml(attr = "id")]
+    id: u32,
+    #[xml(flatten_text = "label", write_only)]
+    labels: &'a [&'a str],
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let library = Library {
+        name: "city".into(),
+        tags: vec!["public".into(), "free".into()],
+        books: vec![
+            Book {
+                title: "Dune".into(),
+                year: 1965,
+            },
+            Book {
+                title: "Emma".into(),
+                year: 1815,
+            },
+        ],
+    };
+
+    let books = library
+        .books
+        .iter()
+        .map(|book| BookView {
+            year: &book.year,
+            title: &book.title,
+        })
+        .collect::<Vec<_>>();
+
+    let view = LibraryView {
+        name: &library.name,
+        tags: &library.tags,
+        tag_elements: &library.tags[..1],
+        books: &books,
+        featured: books.first(),
+    };
+
+    assert_eq!(
+        view.to_string()?,
+        r#"<library name="city" tags="public free"><book year="1965">Dune</book><book year="1815">Emma</book><book year="1965">Dune</book><tag>public</tag></library>"#
+    );
+
+    let view = LibraryView {
+        name: "empty",
+        tags: &[],
+        tag_elements: &[],
+        books: &[],
+        featured: None,
+    };
+    assert_eq!(view.to_string()?, r#"<library name="empty"/>"#);
+
+    let shelf = Shelf {
+        id: 1,
+        labels: &["a", "b"],
+    };
+    assert_eq!(
+        shelf.to_string()?,
+        r#"<shelf id="1"><label>a</label><label>b</label></shelf>"#
+    );
+    assert_eq!(
+        Shelf::from_str(r#"<shelf id="1"><label>a</label></shelf>"#)?,
+        Shelf { id: 1, labels: &[] }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use hard_xml::{XmlRead, XmlResult, XmlWrite};
+
+// a struct with an optional `flatten_text` field for every tag
+macro_rules! wide {
+    ($name:ident { $( $field:ident: $tag:literal, )* }) => {
+        #[derive(XmlWrite, XmlRead, PartialEq, Debug, Default)]
+        #[xml(tag = "record")]
+        struct $name {
+            #[xml(attr = "kind")]
+            kind: Option<String>,
+            $(
+                #[xml(flatten_text = $tag)]
+                $field: Option<String>,
+            )*
+        }
+    };
+}
+
+wide!(Record {
+    id: "id",
+    name: "name",
+    title: "title",
+    author: "author",
+    isbn: "isbn",
+    year: "year",
+    month: "month",
+    day: "day",
+    price: "price",
+    currency: "currency",
+    publisher: "publisher",
+    city: "city",
+    country: "country",
+    language: "language",
+    edition: "edition",
+    pages: "pages",
+    format: "format",
+    width: "width",
+    height: "height",
+    depth: "depth",
+    weight: "weight",
+    color: "color",
+    series: "series",
+    volume: "volume",
+    genre: "genre",
+    subject: "subject",
+    summary: "summary",
+    note: "note",
+    rating: "rating",
+    reviews: "reviews",
+    stock: "stock",
+    shelf: "shelf",
+    row: "row",
+    floor: "floor",
+    branch: "branch",
+    barcode: "barcode",
+    status: "status",
+    loaned: "loaned",
+    due: "due",
+    owner: "owner",
+    tag: "tag",
+    link: "link",
+});
+
+#[derive(XmlRead, PartialEq, Debug)]
+#[xml(tag = "record", case_insensitive)]
+struct Small {
+    #[xml(flatten_text = "title")]
+    title: String,
+    #[xml(flatten_text = "note")]
+    notes: Vec<String>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<record kind="book"><title>Dune</title><due>2024</due><row>3</row><link>x</link><unknown/><day>1</day></record>"#;
+    let record = Record::from_str(xml)?;
+    assert_eq!(record.kind.as_deref(), Some("book"));
+    assert_eq!(record.title.as_deref(), Some("Dune"));
+    assert_eq!(record.due.as_deref(), Some("2024"));
+    assert_eq!(record.row.as_deref(), Some("3"));
+    assert_eq!(record.link.as_deref(), Some("x"));
+    assert_eq!(record.day.as_deref(), Some("1"));
+    assert_eq!(record.id, None);
+
+    assert_eq!(
+        record.to_string()?,
+        r#"<record kind="book"><title>Dune</title><day>1</day><row>3</row><due>2024</due><link>x</link></record>"#
+    );
+
+    // names that aren't equal are still resolved by the reader
+    let small =
+        Small::from_str(r#"<RECORD><Title>a</Title><NOTE>b</NOTE><note>c</note></RECORD>"#)?;
+    assert_eq!(small.title, "a");
+    assert_eq!(small.notes, ["b", "c"]);
+
+    Ok(())
+}
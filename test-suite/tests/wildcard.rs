@@ -0,0 +1,99 @@
+use hard_xml::{ErrorDetail, XmlBuilder, XmlRead, XmlResult, XmlSchemaInfo, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "*")]
+struct Property<'a> {
+    #[xml(tag_name)]
+    key: Cow<'a, str>,
+    #[xml(text)]
+    value: Cow<'a, str>,
+}
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "size")]
+struct Size<'a> {
+    #[xml(attr = "unit")]
+    unit: Option<Cow<'a, str>>,
+    #[xml(child = "*")]
+    properties: Vec<Property<'a>>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "shape")]
+struct Shape<'a> {
+    #[xml(flatten_text = "name")]
+    name: Cow<'a, str>,
+    #[xml(child = "*")]
+    other: Vec<Property<'a>>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<size unit="px"><width>10</width><height>20</height></size>"#;
+
+    let size = Size::from_str(xml)?;
+
+    assert_eq!(
+        size,
+        Size {
+            unit: Some("px".into()),
+            properties: vec![
+                Property {
+                    key: "width".into(),
+                    value: "10".into()
+                },
+                Property {
+                    key: "height".into(),
+                    value: "20".into()
+                },
+            ]
+        }
+    );
+
+    assert_eq!(size.to_string()?, xml);
+
+    assert_eq!(
+        Property::from_str(r#"<depth>30</depth>"#)?,
+        Property {
+            key: "depth".into(),
+            value: "30".into()
+        }
+    );
+
+    // declared children are matched before the wildcard
+    assert_eq!(
+        Shape::from_str(r#"<shape><color>red</color><name>square</name></shape>"#)?,
+        Shape {
+            name: "square".into(),
+            other: vec![Property {
+                key: "color".into(),
+                value: "red".into()
+            }]
+        }
+    );
+
+    assert!(matches!(
+        (Property {
+            key: "".into(),
+            value: "".into()
+        })
+        .to_string(),
+        Err(err) if matches!(err.detail(), ErrorDetail::MissingField { .. })
+    ));
+
+    assert_eq!(
+        XmlBuilder::new::<Size>("size")?
+            .child("width", |b| b.text("10"))?
+            .finish()?
+            .to_string()?,
+        r#"<size><width>10</width></size>"#
+    );
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use hard_xml::{XmlBuilder, XmlRead, XmlResult, XmlSchemaInfo, XmlWrite};
+use std::borrow::Cow;
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "item")]
+struct Item<'a> {
+    #[xml(attr = "title")]
+    title: Cow<'a, str>,
+}
+
+#[derive(XmlWrite, XmlRead, XmlSchemaInfo, PartialEq, Debug)]
+#[xml(tag = "channel")]
+struct Channel<'a> {
+    #[xml(flatten_text = "title")]
+    title: Cow<'a, str>,
+    #[xml(child = "item", wrapper = "items")]
+    items: Vec<Item<'a>>,
+    #[xml(child = "item", wrapper = "featured")]
+    featured: Option<Item<'a>>,
+}
+
+#[derive(XmlWrite, XmlRead, PartialEq, Debug)]
+#[xml(tag = "outline")]
+struct Outline<'a> {
+    #[xml(child = "item", wrapper = "items")]
+    items: Vec<Item<'a>>,
+    #[xml(flatten_text = "note")]
+    notes: Vec<Cow<'a, str>>,
+    #[xml(order)]
+    order: Vec<&'static str>,
+}
+
+#[test]
+fn test() -> XmlResult<()> {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .format_timestamp(None)
+        .try_init();
+
+    let xml = r#"<channel><items><item title="a"/><item title="b"/></items><featured><item title="c"/></featured><title>news</title></channel>"#;
+
+    let channel = Channel::from_str(xml)?;
+
+    assert_eq!(
+        channel,
+        Channel {
+            title: "news".into(),
+            items: vec![Item { title: "a".into() }, Item { title: "b".into() }],
+            featured: Some(Item { title: "c".into() }),
+        }
+    );
+
+    assert_eq!(channel.to_string()?, xml);
+
+    // empty or missing wrappers are read as empty collections,
+    // and unknown elements inside wrappers are skipped
+    assert_eq!(
+        Channel::from_str(
+            r#"<channel><items count="0"/><featured><other/></featured><title>news</title></channel>"#
+        )?,
+        Channel {
+            title: "news".into(),
+            items: vec![],
+            featured: None,
+        }
+    );
+
+    // empty collections are written without their wrapper
+    assert_eq!(
+        Channel {
+            title: "news".into(),
+            items: vec![],
+            featured: None,
+        }
+        .to_string()?,
+        r#"<channel><title>news</title></channel>"#
+    );
+
+    assert!(
+        Channel::from_str(r#"<channel><title>news</title><items><item title="a"/></channel>"#)
+            .is_err()
+    );
+
+    let outline = Outline::from_str(
+        r#"<outline><note>1</note><items><item title="a"/></items><note>2</note></outline>"#,
+    )?;
+
+    assert_eq!(outline.order, vec!["notes", "items", "notes"]);
+    assert_eq!(
+        outline.to_string()?,
+        r#"<outline><note>1</note><items><item title="a"/></items><note>2</note></outline>"#
+    );
+
+    assert_eq!(
+        XmlBuilder::new::<Channel>("channel")?
+            .flatten_text("title", "news")?
+            .child("item", |item| item.attr("title", "a"))?
+            .child("item", |item| item.attr("title", "b"))?
+            .finish()?
+            .to_string()?,
+        r#"<channel><title>news</title><items><item title="a"/><item title="b"/></items></channel>"#
+    );
+
+    Ok(())
+}